@@ -0,0 +1,43 @@
+/// Signature active ability each class can use on a cooldown
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ability {
+    ShieldBash,
+    SmokeBomb,
+    Firebolt,
+}
+
+impl Ability {
+    pub fn get_name(&self) -> &str {
+        match self {
+            Ability::ShieldBash => "Shield Bash",
+            Ability::SmokeBomb => "Smoke Bomb",
+            Ability::Firebolt => "Firebolt",
+        }
+    }
+
+    pub fn get_description(&self) -> &str {
+        match self {
+            Ability::ShieldBash => "Slam an adjacent enemy with your shield, stunning it and knocking it back a tile. Needs a shield in your off hand.",
+            Ability::SmokeBomb => "Vanish in a cloud of smoke and reappear a short way off, leaving nearby enemies choking and lashing out at each other.",
+            Ability::Firebolt => "Hurl a bolt of fire at a creature you can see, setting the ground alight where it lands.",
+        }
+    }
+
+    /// Turns before the ability can be used again
+    pub fn cooldown(&self) -> u32 {
+        match self {
+            Ability::ShieldBash => 8,
+            Ability::SmokeBomb => 20,
+            Ability::Firebolt => 6,
+        }
+    }
+
+    /// How far away the ability can be aimed, or None if it isn't aimed at all
+    pub fn range(&self) -> Option<i32> {
+        match self {
+            Ability::ShieldBash => Some(1),
+            Ability::SmokeBomb => None,
+            Ability::Firebolt => Some(6),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashSet;
+
+use crate::geometry::{self, chebyshev_distance};
+use crate::npc::NPCState;
+use crate::state::{Entity, GameState, Visibility};
+
+/// Compass directions anticlockwise from east, in the order `geometry::angle` turns through them
+const COMPASS: [&str; 8] = ["east", "north-east", "north", "north-west", "west", "south-west", "south", "south-east"];
+/// The tiles around the player, read out clockwise from north
+const NEIGHBOURS: [(i32, i32); 8] = [(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)];
+
+/// The compass direction of an offset from the player, e.g. "north-east"
+pub fn direction_name(dx: i32, dy: i32) -> &'static str {
+    let octant = ((geometry::angle((0, 0), (dx, dy)) + 360.0 + 22.5) / 45.0) as usize % 8;
+    COMPASS[octant]
+}
+
+/// Where a tile is from the player, e.g. "3 tiles north-east"
+pub fn whereabouts(from: (i32, i32), to: (i32, i32)) -> String {
+    match chebyshev_distance(from, to) {
+        0 => "right here".to_string(),
+        1 => format!("1 tile {}", direction_name(to.0 - from.0, to.1 - from.1)),
+        distance => format!("{} tiles {}", distance, direction_name(to.0 - from.0, to.1 - from.1)),
+    }
+}
+
+/// A short name for something on a tile, or None for what can't be named
+fn entity_name(game_state: &GameState, entity: &Entity) -> Option<String> {
+    match entity {
+        Entity::Player => None,
+        Entity::Npc(npc, Visibility::Visible) => {
+            let mut name = npc.name.clone();
+            if game_state.is_hostile_to_player(npc) {
+                name.push_str(" (hostile)");
+            }
+            if npc.state == NPCState::Asleep {
+                name.push_str(" (asleep)");
+            }
+            Some(name)
+        }
+        Entity::Npc(npc, Visibility::Sensed) => Some(format!("{} (sensed)", npc.name)),
+        Entity::Npc(..) => Some("something unseen".to_string()),
+        Entity::Cart(merchant) => Some(format!("{}'s cart", merchant.name)),
+        Entity::Item(world_item) => Some(game_state.item_name(&world_item.item)),
+        Entity::Trap(trap) => Some(trap.get_name().to_string()),
+    }
+}
+
+/// Read off the tiles around the player and everything they can see further away, nearest first
+pub fn describe_surroundings(game_state: &GameState) -> String {
+    let (px, py) = game_state.player.position;
+    let mut sentences = Vec::new();
+    if let Some(tile) = game_state.world.get_tile(px, py) {
+        sentences.push(format!("You stand on {}.", tile.get_name()));
+    }
+
+    let neighbours: Vec<String> = NEIGHBOURS.iter()
+        .map(|&(dx, dy)| {
+            let (x, y) = (px + dx, py + dy);
+            let terrain = match game_state.world.get_tile(x, y) {
+                Some(tile) if game_state.can_examine(x, y) => tile.get_name().to_string(),
+                _ => "unknown".to_string(),
+            };
+            let things: Vec<String> = game_state.entities_at(x, y).iter()
+                .filter_map(|entity| entity_name(game_state, entity))
+                .collect();
+            if things.is_empty() {
+                format!("{} {}", direction_name(dx, dy), terrain)
+            } else {
+                format!("{} {} on {}", direction_name(dx, dy), things.join(" and "), terrain)
+            }
+        })
+        .collect();
+    sentences.push(format!("Around you: {}.", neighbours.join(", ")));
+
+    // Anything further off, as long as it's actually in view
+    let mut seen: Vec<((i32, i32), String)> = Vec::new();
+    for npc in &game_state.npcs {
+        if chebyshev_distance((px, py), npc.position(&game_state.world)) > 1
+            && game_state.npc_visibility(npc) == Visibility::Visible
+            && let Some(name) = entity_name(game_state, &Entity::Npc(npc, Visibility::Visible))
+        {
+            seen.push((npc.position(&game_state.world), name));
+        }
+    }
+    for world_item in &game_state.world.items {
+        let (x, y) = world_item.position;
+        if chebyshev_distance((px, py), (x, y)) > 1 && game_state.is_tile_visible(x, y) && game_state.is_item_perceived(world_item) {
+            seen.push(((x, y), game_state.item_name(&world_item.item)));
+        }
+    }
+    seen.sort_by_key(|(position, _)| chebyshev_distance((px, py), *position));
+    if seen.is_empty() {
+        sentences.push("Nothing else in view.".to_string());
+    } else {
+        let listed: Vec<String> = seen.iter()
+            .map(|(position, name)| format!("{} {}", name, whereabouts((px, py), *position)))
+            .collect();
+        sentences.push(format!("In view: {}.", listed.join(", ")));
+    }
+    sentences.join(" ")
+}
+
+/// Turns what the map shows into short sentences for a screen reader: every new line in the
+/// message log, plus creatures coming into view. The app hands the latest batch to egui's
+/// accesskit integration as a live region, which screen readers speak as it changes.
+#[derive(Debug, Default)]
+pub struct Announcer {
+    written: u64, // Log messages already announced, as counted by `MessageLog::written`
+    in_view: HashSet<u32>, // Ids of the creatures the player could see last time
+    latest: String, // The most recent announcement
+}
+
+impl Announcer {
+    /// Catch up with the game, replacing the latest announcement if anything happened
+    pub fn update(&mut self, game_state: &GameState) {
+        let mut announcements = game_state.log_messages.since(self.written);
+        self.written = game_state.log_messages.written();
+
+        let player = game_state.player.position;
+        let in_view: HashSet<u32> = game_state.npcs.iter()
+            .filter(|npc| game_state.npc_visibility(npc) == Visibility::Visible)
+            .map(|npc| npc.id)
+            .collect();
+        for npc in game_state.npcs.iter().filter(|npc| in_view.contains(&npc.id) && !self.in_view.contains(&npc.id)) {
+            announcements.push(format!("{} comes into view, {}.", npc.name, whereabouts(player, npc.position(&game_state.world))));
+        }
+        self.in_view = in_view;
+
+        if !announcements.is_empty() {
+            self.latest = announcements.join(" ");
+        }
+    }
+
+    pub fn latest(&self) -> &str {
+        &self.latest
+    }
+}
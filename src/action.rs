@@ -0,0 +1,249 @@
+use eframe::egui::{self, Key};
+
+use crate::geometry::is_adjacent;
+use crate::item::ItemType;
+use crate::state::{GameState, TileType, Visibility};
+
+/// A command the player triggers with a single key. The keybindings, the controls list
+/// and the context hints are all built from this one registry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    PickUp,
+    UseItem,
+    Interact,
+    Rest,
+    Equip,
+    MixPotions,
+    Ability,
+    DropItem,
+    CharacterSheet,
+    Encyclopedia,
+    CenterView,
+    LockCamera,
+    ExportMap,
+    DumpState,
+    ThreatOverlay,
+    DescribeSurroundings,
+    DebugOverlay,
+    WizardMode,
+    WizardDescend,
+    Pause,
+    Settings,
+    Quit,
+}
+
+/// Controller buttons an action can be bound to. South and East always confirm and cancel,
+/// and the d-pad and left stick always move, so those aren't offered here.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PadButton {
+    West,
+    North,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+}
+
+impl PadButton {
+    pub fn get_name(&self) -> &str {
+        match self {
+            PadButton::West => "Left face button",
+            PadButton::North => "Top face button",
+            PadButton::LeftBumper => "L1",
+            PadButton::RightBumper => "R1",
+            PadButton::LeftTrigger => "L2",
+            PadButton::RightTrigger => "R2",
+            PadButton::Select => "Select",
+            PadButton::Start => "Start",
+        }
+    }
+}
+
+impl Action {
+    pub const ALL: [Action; 22] = [
+        Action::PickUp,
+        Action::UseItem,
+        Action::Interact,
+        Action::Rest,
+        Action::Equip,
+        Action::MixPotions,
+        Action::Ability,
+        Action::DropItem,
+        Action::CharacterSheet,
+        Action::Encyclopedia,
+        Action::CenterView,
+        Action::LockCamera,
+        Action::ExportMap,
+        Action::DumpState,
+        Action::ThreatOverlay,
+        Action::DescribeSurroundings,
+        Action::DebugOverlay,
+        Action::WizardMode,
+        Action::WizardDescend,
+        Action::Pause,
+        Action::Settings,
+        Action::Quit,
+    ];
+
+    pub fn key(&self) -> Key {
+        match self {
+            Action::PickUp => Key::P,
+            Action::UseItem => Key::U,
+            Action::Interact => Key::E,
+            Action::Rest => Key::R,
+            Action::Equip => Key::G,
+            Action::MixPotions => Key::M,
+            Action::Ability => Key::F,
+            Action::DropItem => Key::X,
+            Action::CharacterSheet => Key::C,
+            Action::Encyclopedia => Key::B,
+            Action::CenterView => Key::V,
+            Action::LockCamera => Key::L,
+            Action::ExportMap => Key::F2,
+            Action::DumpState => Key::F12,
+            Action::ThreatOverlay => Key::T,
+            Action::DescribeSurroundings => Key::K,
+            Action::DebugOverlay => Key::F3,
+            Action::WizardMode => Key::F9,
+            Action::WizardDescend => Key::F10,
+            Action::Pause => Key::Space,
+            Action::Settings => Key::O,
+            Action::Quit => Key::Q,
+        }
+    }
+
+    /// The controller button that does the same as the key, if the action has one
+    pub fn pad_button(&self) -> Option<PadButton> {
+        match self {
+            Action::PickUp => Some(PadButton::West),
+            Action::UseItem => Some(PadButton::North),
+            Action::Equip => Some(PadButton::RightBumper),
+            Action::Ability => Some(PadButton::LeftBumper),
+            Action::DropItem => Some(PadButton::LeftTrigger),
+            Action::Rest => Some(PadButton::RightTrigger),
+            Action::CharacterSheet => Some(PadButton::Select),
+            Action::Settings => Some(PadButton::Start),
+            _ => None,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            Action::PickUp => "Pick up item",
+            Action::UseItem => "Use item",
+            Action::Interact => "Interact (talk, open, search)",
+            Action::Rest => "Rest",
+            Action::Equip => "Gear (equip)",
+            Action::MixPotions => "Mix potions at a cauldron",
+            Action::Ability => "Class ability",
+            Action::DropItem => "Drop item",
+            Action::CharacterSheet => "Character sheet",
+            Action::Encyclopedia => "Bestiary and item encyclopedia",
+            Action::CenterView => "Center view on player",
+            Action::LockCamera => "Lock/unlock camera",
+            Action::ExportMap => "Export map as text",
+            Action::DumpState => "Dump game state to JSON (debug)",
+            Action::ThreatOverlay => "Show where enemies can reach",
+            Action::DescribeSurroundings => "Describe surroundings",
+            Action::DebugOverlay => "AI overlay (debug)",
+            Action::WizardMode => "Wizard mode (testing, unscored)",
+            Action::WizardDescend => "Go down a floor (wizard mode)",
+            Action::Pause => "Pause (stops the clock)",
+            Action::Settings => "Options",
+            Action::Quit => "Quit",
+        }
+    }
+}
+
+/// Add a tap of a key to the frame's input, for controls that stand in for the keyboard
+/// (on-screen buttons, controllers) so they go through the same bindings
+pub fn press_key(raw_input: &mut egui::RawInput, key: Key) {
+    for pressed in [true, false] {
+        raw_input.events.push(egui::Event::Key { key, physical_key: None, pressed, repeat: false, modifiers: egui::Modifiers::NONE });
+    }
+}
+
+/// The tiles next to the player with something to interact with: someone to talk to,
+/// or a campfire, altar or workbench
+pub fn interaction_targets(game_state: &GameState) -> Vec<(i32, i32)> {
+    let (px, py) = game_state.player.position;
+    DIRECTIONS.iter()
+        .map(|(_, dx, dy)| (px + dx, py + dy))
+        .filter(|&target| is_interactable(game_state, target))
+        .collect()
+}
+
+/// Whether interacting with a tile does more than search it
+pub fn is_interactable(game_state: &GameState, target: (i32, i32)) -> bool {
+    let friendly = game_state.npcs.iter()
+        .any(|npc| npc.position(&game_state.world) == target && game_state.npc_visibility(npc) == Visibility::Visible && game_state.needs_bump_menu(npc));
+    friendly || matches!(game_state.world.get_tile(target.0, target.1), Some(TileType::Campfire | TileType::Altar | TileType::Workbench))
+}
+
+const DIRECTIONS: [(&str, i32, i32); 4] = [("Up", 0, -1), ("Down", 0, 1), ("Left", -1, 0), ("Right", 1, 0)];
+
+/// Short hints for what the player can do right where they stand, e.g. "P: pick up Gem".
+/// Attacking by walking into something has no key of its own, so those hints name the direction instead.
+pub fn context_hints(game_state: &GameState) -> Vec<String> {
+    let mut hints = Vec::new();
+    let hint = |action: Action, text: String| format!("{}: {}", action.key().name(), text);
+    let (px, py) = game_state.player.position;
+
+    match game_state.items_at((px, py)).as_slice() {
+        [] => {}
+        [index] => {
+            let item = &game_state.world.items[*index].item;
+            hints.push(hint(Action::PickUp, format!("pick up {}", game_state.item_name(item))));
+        }
+        pile => hints.push(hint(Action::PickUp, format!("pick up from a pile of {} items", pile.len()))),
+    }
+
+    let potion_count = game_state.player.inventory.iter()
+        .filter(|item| item.item_type == ItemType::Potion)
+        .count();
+    if game_state.is_next_to_cauldron() && potion_count >= 2 {
+        hints.push(hint(Action::MixPotions, "mix potions".to_string()));
+    }
+
+    let ability = game_state.player.class.ability();
+    let ability_target = game_state.npcs.iter().find(|npc| game_state.is_valid_ability_target(ability, npc.position(&game_state.world)));
+    if game_state.check_ability().is_ok() {
+        match (ability.range(), ability_target) {
+            (Some(_), Some(npc)) => hints.push(hint(Action::Ability, format!("{} {}", ability.get_name().to_lowercase(), npc.name))),
+            (None, _) if game_state.npcs.iter().any(|npc| npc.is_hostile() && is_adjacent((px, py), npc.position(&game_state.world))) => {
+                hints.push(hint(Action::Ability, format!("{} to escape", ability.get_name().to_lowercase())));
+            }
+            _ => {}
+        }
+    }
+
+    // Things the player can walk into or interact with
+    let interact = Action::Interact.key().name();
+    for (direction, dx, dy) in DIRECTIONS {
+        let target = (px + dx, py + dy);
+        if let Some(npc) = game_state.npcs.iter()
+            .find(|npc| npc.position(&game_state.world) == target && game_state.npc_visibility(npc) == Visibility::Visible)
+        {
+            if game_state.needs_bump_menu(npc) {
+                hints.push(format!("{} {}: talk to {}", interact, direction, npc.name));
+            } else if game_state.is_hostile_to_player(npc) {
+                hints.push(format!("{}: attack {}", direction, npc.name));
+            } else {
+                hints.push(format!("{}: bump {}", direction, npc.name));
+            }
+        }
+        if game_state.world.get_tile(target.0, target.1) == Some(&TileType::Campfire) {
+            hints.push(format!("{} {}: sit by the campfire", interact, direction));
+        }
+        if game_state.world.get_tile(target.0, target.1) == Some(&TileType::Altar) {
+            hints.push(format!("{} {}: make an offering at the altar", interact, direction));
+        }
+        if game_state.world.get_tile(target.0, target.1) == Some(&TileType::Workbench) {
+            hints.push(format!("{} {}: set gems at the workbench", interact, direction));
+        }
+    }
+
+    hints
+}
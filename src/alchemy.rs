@@ -0,0 +1,62 @@
+use rand::Rng;
+
+use crate::catalog;
+use crate::item::{Item, ItemEffect, ItemType};
+use crate::status::StatusKind;
+
+/// What comes out of the cauldron after mixing two potions
+#[derive(Debug)]
+pub enum MixOutcome {
+    Brewed(Item),
+    Exploded { damage: i32 }, // The ingredients reacted violently
+}
+
+/// Mix two potions together. Known combinations brew a new potion; anything else blows up.
+pub fn mix(first: &Item, second: &Item, rng: &mut impl Rng) -> MixOutcome {
+    let brewed = if first.item_type == ItemType::Potion && second.item_type == ItemType::Potion {
+        brew(first.effect.as_ref(), second.effect.as_ref())
+            .or_else(|| brew(second.effect.as_ref(), first.effect.as_ref()))
+    } else {
+        None
+    };
+
+    match brewed {
+        Some(potion) => MixOutcome::Brewed(potion),
+        None => MixOutcome::Exploded { damage: rng.gen_range(5..=15) },
+    }
+}
+
+/// The mixing table. Ingredients are identified by their effect; unidentified
+/// "Magic Potions" have none. Only checks one ordering of the pair.
+fn brew(first: Option<&ItemEffect>, second: Option<&ItemEffect>) -> Option<Item> {
+    use ItemEffect::{ApplyStatus, Heal};
+
+    match (first, second) {
+        (None, None) => Some(catalog::healing_potion()),
+        (Some(Heal(_)), Some(Heal(_))) => Some(potion(
+            "Potion of Greater Healing",
+            "A thick crimson draught that closes even deep wounds.",
+            Heal(40),
+        )),
+        (Some(Heal(_)), None) => Some(potion(
+            "Potion of Haste",
+            "A fizzing yellow potion. The bottle seems to tremble in your hand.",
+            ApplyStatus(StatusKind::Hasted, 10),
+        )),
+        (Some(ApplyStatus(StatusKind::Hasted, _)), Some(ApplyStatus(StatusKind::Hasted, _))) => Some(potion(
+            "Potion of Slowness",
+            "A sluggish grey sludge. Too much of a good thing.",
+            ApplyStatus(StatusKind::Slowed, 10),
+        )),
+        (Some(ApplyStatus(StatusKind::SeeInvisible, turns)), None) => Some(potion(
+            "Potion of Lasting Sight",
+            "A clear, cold liquid that sharpens the eyes for a long while.",
+            ApplyStatus(StatusKind::SeeInvisible, turns * 2),
+        )),
+        _ => None,
+    }
+}
+
+fn potion(label: &str, description: &str, effect: ItemEffect) -> Item {
+    Item::new(ItemType::Potion, label.to_string(), description.to_string()).with_effect(effect)
+}
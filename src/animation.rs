@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use eframe::egui;
+
+use crate::settings::Settings;
+use crate::state::GameState;
+
+/// Seconds the world view flashes red after the player is hurt
+const FLASH_SECONDS: f64 = 0.25;
+/// Seconds the world view shakes after a heavy blow
+const SHAKE_SECONDS: f64 = 0.3;
+/// Furthest a shake throws the world view, in points
+const SHAKE_AMPLITUDE: f32 = 4.0;
+/// Share of the player's max health one blow has to take to shake the view
+const SHAKE_THRESHOLD: f32 = 0.2;
+
+/// The kinds of visual effect, grouped by the setting that turns them off. Every effect asks
+/// `Settings::allows` for its kind, so none of them needs a toggle of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VisualEffect {
+    Shake, // The world view jolting after a heavy blow
+    Flash, // Bright bursts: the red of being hurt and the glare of a blast
+    Motion, // Anything else that moves: egui's transitions, scrolling and walking a tapped route
+}
+
+/// The short-lived effects played over the world view, started from what changed in the game
+/// since the last frame
+#[derive(Debug, Default)]
+pub struct Animations {
+    running: HashMap<VisualEffect, (f64, f64)>, // When each effect started and when it ends, in egui time
+    last_health: Option<i32>, // The player's health last frame, to notice them being hurt
+    motion: Option<bool>, // Whether egui's own animations were last left on or off
+}
+
+impl Animations {
+    /// Start effects for whatever happened since the last frame and drop the ones that are
+    /// over, or that the settings no longer allow. Returns whether any is still playing and
+    /// needs another frame.
+    pub fn update(&mut self, ctx: &egui::Context, game_state: Option<&GameState>, settings: &Settings) -> bool {
+        let motion = settings.allows(VisualEffect::Motion);
+        if self.motion != Some(motion) {
+            self.motion = Some(motion);
+            ctx.all_styles_mut(|style| {
+                let default = egui::Style::default();
+                style.animation_time = if motion { default.animation_time } else { 0.0 };
+                style.scroll_animation = if motion { default.scroll_animation } else { egui::style::ScrollAnimation::none() };
+            });
+        }
+
+        let now = ctx.input(|i| i.time);
+        let health = game_state.map(|game_state| (game_state.player.health, game_state.player.max_health));
+        if let (Some(before), Some((health, max_health))) = (self.last_health, health)
+            && health < before
+        {
+            self.start(settings, VisualEffect::Flash, now, FLASH_SECONDS);
+            if (before - health) as f32 >= max_health as f32 * SHAKE_THRESHOLD {
+                self.start(settings, VisualEffect::Shake, now, SHAKE_SECONDS);
+            }
+        }
+        self.last_health = health.map(|(health, _)| health);
+
+        self.running.retain(|effect, (_, end)| settings.allows(*effect) && *end > now);
+        !self.running.is_empty()
+    }
+
+    fn start(&mut self, settings: &Settings, effect: VisualEffect, now: f64, seconds: f64) {
+        if settings.allows(effect) {
+            self.running.insert(effect, (now, now + seconds));
+        }
+    }
+
+    /// How much of an effect is left to play, from 1 as it starts down to 0, or None when it isn't playing
+    fn remaining(&self, effect: VisualEffect, now: f64) -> Option<f32> {
+        let &(start, end) = self.running.get(&effect)?;
+        Some(((end - now) / (end - start)).clamp(0.0, 1.0) as f32)
+    }
+
+    /// How far to push the world view this frame, dying away as the shake ends. Never negative,
+    /// so it can be laid out as spacing.
+    pub fn shake_offset(&self, now: f64) -> egui::Vec2 {
+        let Some(remaining) = self.remaining(VisualEffect::Shake, now) else {
+            return egui::Vec2::ZERO;
+        };
+        let wobble = |frequency: f64| (1.0 + (now * frequency).sin() as f32) * SHAKE_AMPLITUDE * remaining;
+        egui::vec2(wobble(70.0), wobble(53.0))
+    }
+
+    /// The red laid over the world view after the player is hurt, fading as the flash ends
+    pub fn flash_color(&self, now: f64) -> Option<egui::Color32> {
+        let remaining = self.remaining(VisualEffect::Flash, now)?;
+        Some(egui::Color32::from_rgba_unmultiplied(200, 20, 20, (90.0 * remaining) as u8))
+    }
+}
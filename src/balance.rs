@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+/// Where the balance file is read from, next to the save files
+pub const BALANCE_PATH: &str = "balance.toml";
+
+/// The game's tunable numbers, read from `balance.toml` so they can be changed without a
+/// rebuild. Anything the file leaves out keeps the value below.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Balance {
+    pub orc_damage: (i32, i32), // Least and most an orc's blow deals, before elite bonuses
+    pub merchant_move_chance: u32, // Percent chance a merchant takes a step along the day's circuit each turn
+    pub cart_drop_chance: u32, // Percent chance a merchant's cart drops something with each step
+    pub experience_per_level: i32, // Experience for the next level is this times the current level
+    pub hunger_per_turn: i32, // Nutrition the player burns each turn
+    pub arena_wave_interval: u32, // Turns between each arena wave
+    pub survival_wave_interval: u32, // Turns between each orc joining the hunt in survival mode
+}
+
+impl Balance {
+    /// The numbers the game was designed around
+    pub const DEFAULT: Balance = Balance {
+        orc_damage: (5, 20),
+        merchant_move_chance: 24,
+        cart_drop_chance: 15,
+        experience_per_level: 50,
+        hunger_per_turn: 1,
+        arena_wave_interval: 15,
+        survival_wave_interval: 10,
+    };
+
+    /// Why these numbers can't be played with, if they can't
+    fn check(&self) -> Result<(), String> {
+        let (least, most) = self.orc_damage;
+        if least < 0 || most < least {
+            return Err(format!("orc_damage must be two amounts, least first, none negative (got [{}, {}])", least, most));
+        }
+        for (name, chance) in [("merchant_move_chance", self.merchant_move_chance), ("cart_drop_chance", self.cart_drop_chance)] {
+            if chance > 100 {
+                return Err(format!("{} is a percent chance, so can't be more than 100 (got {})", name, chance));
+            }
+        }
+        if self.experience_per_level < 1 {
+            // With no experience needed, every level-up would be followed by another
+            return Err(format!("experience_per_level must be at least 1 (got {})", self.experience_per_level));
+        }
+        if self.hunger_per_turn < 0 {
+            return Err(format!("hunger_per_turn can't be negative (got {})", self.hunger_per_turn));
+        }
+        Ok(())
+    }
+}
+
+impl Default for Balance {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// The numbers in play, shared by everything that needs one
+static CURRENT: RwLock<Balance> = RwLock::new(Balance::DEFAULT);
+
+/// The balance numbers in play
+pub fn current() -> Balance {
+    *CURRENT.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Read the balance file and put its numbers in play. A missing file leaves the defaults;
+/// a broken one, or one with numbers that make no sense, is reported and changes nothing.
+pub fn load(path: &str) -> Result<(), String> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(error) => return Err(format!("couldn't read {}: {}", path, error)),
+    };
+    let balance: Balance = toml::from_str(&text).map_err(|error| format!("couldn't parse {}: {}", path, error))?;
+    balance.check().map_err(|error| format!("{}: {}", path, error))?;
+    *CURRENT.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = balance;
+    Ok(())
+}
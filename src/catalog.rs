@@ -0,0 +1,186 @@
+use rand::seq::SliceRandom;
+
+use crate::item::{Element, Handedness, Item, ItemEffect, ItemStats, ItemType, PassiveEffect};
+use crate::movement::Movement;
+
+// The standard items found, sold and started with across every game mode.
+// Anything placed in more than one spot should be built here so its stats stay in sync.
+
+pub fn dagger() -> Item {
+    Item::new(
+        ItemType::Weapon,
+        "Dagger".to_string(),
+        "A slim, light blade. A rogue could fight with one in each hand.".to_string(),
+    ).with_stats(ItemStats { damage: Some((4, 10)), handedness: Handedness::Light, ..Default::default() })
+}
+
+pub fn short_sword() -> Item {
+    Item::new(
+        ItemType::Weapon,
+        "Short Sword".to_string(),
+        "A well-balanced blade, nicked from use.".to_string(),
+    ).with_stats(ItemStats { damage: Some((8, 18)), ..Default::default() }).with_sockets(1)
+}
+
+pub fn oak_staff() -> Item {
+    Item::new(
+        ItemType::Weapon,
+        "Oak Staff".to_string(),
+        "A plain walking staff, worn smooth where a hand grips it.".to_string(),
+    ).with_stats(ItemStats { damage: Some((3, 8)), ..Default::default() })
+}
+
+pub fn wooden_shield() -> Item {
+    Item::new(
+        ItemType::Shield,
+        "Wooden Shield".to_string(),
+        "A sturdy round shield of banded oak.".to_string(),
+    ).with_stats(ItemStats { block_chance: 25, ..Default::default() }).with_sockets(1)
+}
+
+pub fn healing_potion() -> Item {
+    Item::new(
+        ItemType::Potion,
+        "Potion of Healing".to_string(),
+        "A warm red potion that mends cuts and bruises.".to_string(),
+    ).with_effect(ItemEffect::Heal(20))
+}
+
+pub fn raw_meat() -> Item {
+    Item::new(
+        ItemType::RawFood,
+        "Raw Meat".to_string(),
+        "A slab of uncooked meat. Filling enough, but better cooked.".to_string(),
+    ).with_effect(ItemEffect::Nourish(100))
+}
+
+pub fn trail_rations() -> Item {
+    Item::new(
+        ItemType::Food,
+        "Trail Rations".to_string(),
+        "Hard biscuits and dried meat. Not tasty, but filling.".to_string(),
+    ).with_effect(ItemEffect::Nourish(300))
+}
+
+pub fn torch(fuel: u32) -> Item {
+    Item::new(
+        ItemType::Torch,
+        "Torch".to_string(),
+        "A pitch-soaked torch. Held in the off hand, it lights the way until it burns down.".to_string(),
+    ).with_stats(ItemStats { light_radius: 5, ..Default::default() }).with_fuel(fuel)
+}
+
+pub fn leather_backpack() -> Item {
+    Item::new(
+        ItemType::Backpack,
+        "Leather Backpack".to_string(),
+        "A roomy pack with plenty of straps. Carrying it lets you hold more.".to_string(),
+    ).with_stats(ItemStats { capacity: 5, ..Default::default() })
+}
+
+pub fn amulet_of_telepathy() -> Item {
+    Item::new(
+        ItemType::Amulet,
+        "Amulet of Telepathy".to_string(),
+        "A cold silver amulet. While worn you can sense the minds of every creature nearby.".to_string(),
+    ).with_stats(ItemStats { telepathy: true, ..Default::default() })
+}
+
+pub fn ring_of_regeneration() -> Item {
+    Item::new(
+        ItemType::Ring,
+        "Ring of Regeneration".to_string(),
+        "A warm band of braided copper. Slowly knits your wounds, but leaves you hungrier.".to_string(),
+    ).with_stats(ItemStats { passive: Some(PassiveEffect::Regeneration { interval: 5, hunger: 1 }), ..Default::default() })
+}
+
+pub fn ring_of_swimming() -> Item {
+    Item::new(
+        ItemType::Ring,
+        "Ring of Swimming".to_string(),
+        "A band of green-tinged bronze, cool and slick as a fish. The wearer swims as easily as they walk.".to_string(),
+    ).with_stats(ItemStats { movement: Movement::SWIM, ..Default::default() })
+}
+
+pub fn vampiric_dagger() -> Item {
+    Item::new(
+        ItemType::Weapon,
+        "Vampiric Dagger".to_string(),
+        "A thin black blade that drinks the blood it spills, feeding it back to its wielder.".to_string(),
+    ).with_stats(ItemStats {
+        damage: Some((3, 9)),
+        handedness: Handedness::Light,
+        passive: Some(PassiveEffect::Vampiric { percent: 50 }),
+        ..Default::default()
+    })
+}
+
+pub fn blink_scroll() -> Item {
+    Item::new(
+        ItemType::Scroll,
+        "Scroll of Blink".to_string(),
+        "Reading it whisks you to any spot you can see nearby.".to_string(),
+    ).with_effect(ItemEffect::Blink(6))
+}
+
+pub fn chest_key() -> Item {
+    Item::new(
+        ItemType::Key,
+        "Iron Key".to_string(),
+        "A heavy key on a chain, taken from the keeper of the treasure.".to_string(),
+    )
+}
+
+pub fn bone_key() -> Item {
+    Item::new(
+        ItemType::Key,
+        "Bone Key".to_string(),
+        "A key carved from ancient bone.".to_string(),
+    )
+}
+
+/// A gem that can be set into a socket at a workbench
+pub fn gem(element: Element) -> Item {
+    let description = match element {
+        Element::Fire => "A deep red gem, warm to the touch. Set into gear, it lends a burning edge.",
+        Element::Frost => "A pale blue gem that mists the air around it. Set into gear, it turns blows aside.",
+        Element::Lightning => "A golden gem that crackles faintly. Set into gear, it guides strikes to weak spots.",
+    };
+    let mut gem = Item::new(ItemType::Gem, element.gem_name().to_string(), description.to_string());
+    gem.stats.element = Some(element);
+    gem
+}
+
+/// One of the trinkets collectors are after: a gem, a scroll or a potion.
+/// Gems can be socketed; the scrolls and potions are a mystery, only good for collecting.
+pub fn collectible(item_type: ItemType) -> Item {
+    let (name, description) = match item_type {
+        ItemType::Gem => return gem(*Element::ALL.choose(&mut rand::thread_rng()).unwrap_or(&Element::Fire)),
+        ItemType::Scroll => ("Ancient Scroll", "A scroll covered in mysterious writing"),
+        ItemType::Potion => ("Magic Potion", "A bubbling potion with unknown effects"),
+        _ => ("Unknown Item", "A mysterious object"),
+    };
+    Item::new(item_type, name.to_string(), description.to_string())
+}
+
+/// Background flavour for the examine view, looked up by item name
+pub fn lore(label: &str) -> Option<&'static str> {
+    let lore = match label {
+        "Dagger" => "Cheap, quick and easy to hide. Every cutpurse in the city swears by one.",
+        "Vampiric Dagger" => "Forged by a cult that believed blood was only borrowed. The blade has never once been clean.",
+        "Short Sword" => "Standard issue for the old city watch, before the watch went below and never came back.",
+        "Greataxe" => "Made for felling trees, then for felling orcs. It hasn't been picky since.",
+        "Oak Staff" => "Apprentices are told the staff focuses the mind. Mostly it's for leaning on.",
+        "Wooden Shield" => "Banded oak, scarred by a dozen owners. None of the scars go all the way through.",
+        "Potion of Healing" => "Brewed from moss that grows only where blood has soaked into stone.",
+        "Torch" => "Rags, pitch and a stick. Down here it's worth more than gold.",
+        "Leather Backpack" => "Some previous adventurer's initials are burned into the flap.",
+        "Amulet of Telepathy" => "The silver is always cold, and sometimes it whispers in voices that aren't yours.",
+        "Ring of Regeneration" => "Troll blood was worked into the copper. The ring is always a little warm, and always hungry.",
+        "Scroll of Blink" => "The ink shifts when you aren't looking at it.",
+        "Treasure Chest" => "The dungeon's old lords buried their wealth deep, and the dead guard it jealously.",
+        "Bone Key" => "Carved from a knucklebone. The teeth of the key match no lock in the living world.",
+        _ => return None,
+    };
+    Some(lore)
+}
@@ -0,0 +1,27 @@
+/// Turns in a full day, the first half daylight and the second half night
+pub const DAY_LENGTH: u32 = 200;
+
+/// Whether it's day or night by the world clock. Townsfolk keep their routines by it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TimeOfDay {
+    Day,
+    Night,
+}
+
+impl TimeOfDay {
+    /// The time of day a number of turns into the game
+    pub fn at(turn: u32) -> Self {
+        if turn % DAY_LENGTH < DAY_LENGTH / 2 {
+            TimeOfDay::Day
+        } else {
+            TimeOfDay::Night
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            TimeOfDay::Day => "Day",
+            TimeOfDay::Night => "Night",
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+
+use crate::state::{GameWorld, TileType};
+
+/// Turns a floor holds before it starts to cave in, when collapsing floors are turned on
+pub const DEFAULT_DELAY: u32 = 150;
+/// Turns of warning the player gets before the first ring falls
+pub const WARNING_TURNS: u32 = 20;
+/// Turns between each ring of the floor falling in
+const RING_INTERVAL: u32 = 8;
+
+/// A floor caving in from its outer wall towards the middle, one ring of tiles at a time, to
+/// hurry the player to the stairs. Any game condition can give its floors one in `setup_world`
+/// or `setup_floor`; the stairs themselves always hold.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Collapse {
+    pub delay: u32, // Turns before the first ring falls
+    pub elapsed: u32, // Turns the floor has stood so far
+}
+
+impl Collapse {
+    pub fn new(delay: u32) -> Self {
+        Self { delay, elapsed: 0 }
+    }
+
+    /// Turns left before the floor starts to go, or None once it has
+    pub fn turns_until(&self) -> Option<u32> {
+        (self.elapsed < self.delay).then(|| self.delay - self.elapsed)
+    }
+
+    /// Rings of tiles fallen so far, counting in from the outer wall
+    fn rings_fallen(&self) -> i32 {
+        match self.elapsed.checked_sub(self.delay) {
+            Some(since) => (since / RING_INTERVAL + 1) as i32,
+            None => 0,
+        }
+    }
+
+    /// Let another turn go by, bringing down the next ring of the floor if it's due.
+    /// Returns the tiles that fell in, which are solid rock from then on.
+    pub fn advance(&mut self, world: &mut GameWorld) -> Vec<(i32, i32)> {
+        let before = self.rings_fallen();
+        self.elapsed += 1;
+        let ring = self.rings_fallen();
+        if ring == before {
+            return Vec::new();
+        }
+
+        let (width, height) = (world.size.0 as i32, world.size.1 as i32);
+        let mut fallen = Vec::new();
+        for x in 0..width {
+            for y in 0..height {
+                let distance_to_edge = x.min(y).min(width - 1 - x).min(height - 1 - y);
+                let holds = matches!(world.get_tile(x, y), Some(TileType::Wall | TileType::Stairs) | None);
+                if distance_to_edge == ring && !holds {
+                    world.tiles[(x as usize, y as usize)] = TileType::Wall;
+                    fallen.push((x, y));
+                }
+            }
+        }
+        fallen
+    }
+}
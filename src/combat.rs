@@ -0,0 +1,92 @@
+use crate::i18n::{Language, trf};
+use rand::Rng;
+
+/// The attacking side of a combat roll
+#[derive(Debug, Clone)]
+pub struct AttackProfile {
+    pub damage: (i32, i32), // min, max
+    pub crit_chance: u32,   // percent
+    pub life_steal: u32,    // percent of damage dealt healed back by the attacker
+}
+
+/// The defending side of a combat roll
+#[derive(Debug, Clone)]
+pub struct DefenseProfile {
+    pub dodge_chance: u32, // percent, from Dexterity
+    pub block_chance: u32, // percent, from an equipped shield
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttackOutcome {
+    Dodged,
+    Blocked,
+    Hit(i32),
+    Critical(i32),
+}
+
+impl AttackOutcome {
+    pub fn damage(&self) -> i32 {
+        match self {
+            AttackOutcome::Hit(damage) | AttackOutcome::Critical(damage) => *damage,
+            AttackOutcome::Dodged | AttackOutcome::Blocked => 0,
+        }
+    }
+
+    /// Short text and color floated over the defender's tile
+    pub fn floating_text(&self) -> (String, (u8, u8, u8)) {
+        match self {
+            AttackOutcome::Dodged => ("dodge".to_string(), (200, 200, 200)),
+            AttackOutcome::Blocked => ("block".to_string(), (150, 180, 255)),
+            AttackOutcome::Hit(damage) => (format!("-{}", damage), (255, 80, 80)),
+            AttackOutcome::Critical(damage) => (format!("CRIT -{}", damage), (255, 215, 0)),
+        }
+    }
+}
+
+/// Resolve a single attack. Every attack in the game, by the player or an NPC,
+/// goes through here: the defender may dodge, then block, and hits may be critical.
+pub fn resolve_attack(attacker: &AttackProfile, defender: &DefenseProfile, rng: &mut impl Rng) -> AttackOutcome {
+    if rng.gen_range(0..100) < defender.dodge_chance {
+        return AttackOutcome::Dodged;
+    }
+    if rng.gen_range(0..100) < defender.block_chance {
+        return AttackOutcome::Blocked;
+    }
+
+    let damage = rng.gen_range(attacker.damage.0..=attacker.damage.1);
+    if rng.gen_range(0..100) < attacker.crit_chance {
+        AttackOutcome::Critical(damage * 2)
+    } else {
+        AttackOutcome::Hit(damage)
+    }
+}
+
+/// Log message for an attack made by the player
+pub fn describe_player_attack(language: Language, outcome: &AttackOutcome, target: &str) -> String {
+    match outcome {
+        AttackOutcome::Dodged => trf(language, "attack.player_dodged", &[&target]),
+        AttackOutcome::Blocked => trf(language, "attack.player_blocked", &[&target]),
+        AttackOutcome::Hit(damage) => trf(language, "attack.player_hit", &[&target, damage]),
+        AttackOutcome::Critical(damage) => trf(language, "attack.player_critical", &[&target, damage]),
+    }
+}
+
+/// Log message for one NPC attacking another
+pub fn describe_npc_attack(language: Language, outcome: &AttackOutcome, attacker: &str, defender: &str) -> String {
+    match outcome {
+        AttackOutcome::Dodged => trf(language, "attack.npc_dodged", &[&defender, &attacker]),
+        AttackOutcome::Blocked => trf(language, "attack.npc_blocked", &[&defender, &attacker]),
+        AttackOutcome::Hit(damage) => trf(language, "attack.npc_hit", &[&attacker, &defender, damage]),
+        AttackOutcome::Critical(damage) => trf(language, "attack.npc_critical", &[&attacker, &defender, damage]),
+    }
+}
+
+/// Log message for an attack made against the player
+pub fn describe_attack_on_player(language: Language, outcome: &AttackOutcome, attacker: &str, verb: &str) -> String {
+    match outcome {
+        AttackOutcome::Dodged => trf(language, "attack.on_player_dodged", &[&attacker, &verb]),
+        AttackOutcome::Blocked => trf(language, "attack.on_player_blocked", &[&attacker]),
+        AttackOutcome::Hit(damage) => trf(language, "attack.on_player_hit", &[&attacker, &verb, damage]),
+        AttackOutcome::Critical(damage) => trf(language, "attack.on_player_critical", &[&attacker, &verb, damage]),
+    }
+}
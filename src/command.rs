@@ -0,0 +1,55 @@
+use serde::Serialize;
+
+use crate::item::{EquipSlot, ItemType};
+use crate::npc::NPCType;
+use crate::state::LevelUpChoice;
+
+/// Everything the player can do that changes the game. The UI turns input into commands and
+/// hands them to `GameState::apply_command`, so anything else that drives the game (replays,
+/// bots, undo) goes through the same single entry point. Keys are mapped in `action.rs`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Command {
+    Move { dx: i32, dy: i32 }, // Step, or attack whatever hostile is in the way
+    PickUp, // The first item underfoot
+    PickUpItems(Vec<usize>), // Chosen indices into `world.items`, all in one turn
+    Wait, // Rest in place for a turn
+    UseItem(usize), // Inventory index
+    ReadBlinkScroll { item_index: usize, target: (i32, i32) },
+    UseAbility(Option<(i32, i32)>), // Target tile, for abilities that need aiming
+    Equip { item_index: usize, slot: EquipSlot },
+    Unequip(EquipSlot),
+    DropLitTorch,
+    DropItem(usize),
+    MixPotions(usize, usize),
+    Cook(usize),
+    SocketGem { item_index: usize, gem_index: usize }, // Both inventory indices, at a workbench
+    RestByCampfire, // Several turns in a row, until interrupted
+    Talk((i32, i32)),
+    Search((i32, i32)), // Look an adjacent tile over for hidden traps
+    AttackFriendly((i32, i32)),
+    Buy { merchant: (i32, i32), stock_index: usize },
+    Sell { merchant: (i32, i32), item_index: usize },
+    Haggle((i32, i32)), // Try to talk the merchant at this position down
+    PayFine((i32, i32)), // Pay the guard at this position to clear the player's name
+    Donate(i32), // Gold to leave at an adjacent altar
+    ConsultOracle((i32, i32)), // Pay the Oracle at this position for a hint about the goal
+    PayShopkeeper, // Settle the shopkeeper's demand for goods taken
+    ReturnGoods, // Put the unpaid goods back instead
+    RefuseToPay, // Keep the goods and let the shopkeeper call the guards
+    ChooseLevelUp(LevelUpChoice),
+    TakeFromStash(usize), // Index into `GameState::stash`, before setting off
+}
+
+/// Something that happened while applying a command, in the order it happened
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum GameEvent {
+    Message(String), // A line added to the message log
+    TurnPassed, // The player acted and the world took its turn
+    TurnLost, // The player couldn't act, but the world took its turn anyway
+    Rejected, // The command couldn't be carried out and took no time
+    Slain { name: String, npc_type: NPCType }, // The player killed this creature
+    ItemUsed(String), // The player used up or applied the item with this name
+    PickedUp(ItemType), // The player put an item of this type in their pack
+    Hurt(i32), // Health the player lost while the command played out
+    Milestone(String), // A step towards the goal worth celebrating, from `GameCondition::on_event`
+}
@@ -0,0 +1,156 @@
+use eframe::egui;
+
+use crate::catalog;
+use crate::item::{Item, ItemEffect};
+use crate::names;
+use crate::npc::{NPC, NPCType};
+use crate::state::GameState;
+
+const HELP: &str = "Commands: spawn <creature> <x> <y>, give <item> [amount], teleport <x> <y>, reveal, heal, help";
+
+/// Drop-down developer console, opened with the ~ key in builds with the `console` feature
+#[derive(Debug, Default)]
+pub struct Console {
+    open: bool,
+    input: String,
+    history: Vec<String>, // Commands entered and what they did
+}
+
+impl Console {
+    /// Show the console if it's open and run any command entered.
+    /// Returns true while it's open, so the game ignores the keyboard.
+    pub fn update(&mut self, ctx: &egui::Context, game_state: Option<&mut GameState>) -> bool {
+        if ctx.input(|i| i.key_pressed(egui::Key::Backtick)) {
+            self.open = !self.open;
+            self.input.clear();
+        }
+        if !self.open {
+            return false;
+        }
+
+        let mut submitted = None;
+        egui::TopBottomPanel::top("console").show(ctx, |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(150.0)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for line in &self.history {
+                        ui.monospace(line);
+                    }
+                });
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.input)
+                    .font(egui::TextStyle::Monospace)
+                    .desired_width(f32::INFINITY)
+                    .hint_text(HELP),
+            );
+            response.request_focus();
+            if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                submitted = Some(std::mem::take(&mut self.input));
+            }
+        });
+
+        if let Some(line) = submitted.filter(|line| !line.trim().is_empty()) {
+            let result = match game_state {
+                Some(game_state) => run_command(game_state, &line),
+                None => Err("No game in progress".to_string()),
+            };
+            self.history.push(format!("> {}", line));
+            self.history.push(result.unwrap_or_else(|error| format!("Error: {}", error)));
+        }
+        true
+    }
+}
+
+/// Parse a console command and carry it out on the game state, describing what happened
+pub fn run_command(game_state: &mut GameState, line: &str) -> Result<String, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let number = |index: usize| -> Result<i32, String> {
+        let word = words.get(index).ok_or("Missing a number")?;
+        word.parse().map_err(|_| format!("'{}' isn't a number", word))
+    };
+    let position = |x: i32, y: i32| -> Result<(i32, i32), String> {
+        if !game_state.world.is_walkable(x, y) {
+            Err(format!("({}, {}) isn't open floor", x, y))
+        } else if game_state.is_occupied((x, y)) {
+            Err(format!("Something is already standing at ({}, {})", x, y))
+        } else {
+            Ok((x, y))
+        }
+    };
+
+    match words.first().copied() {
+        Some("spawn") => {
+            let name = words.get(1).ok_or("Spawn what?")?;
+            let npc_type = parse_npc_type(name)?;
+            let (x, y) = position(number(2)?, number(3)?)?;
+            let name = names::generate(&npc_type, &mut rand::thread_rng());
+            game_state.npcs.push(NPC::new(&mut game_state.world, x, y, npc_type, name.clone()));
+            Ok(format!("Spawned {} at ({}, {})", name, x, y))
+        }
+        Some("give") => {
+            let item = parse_item(words.get(1).ok_or("Give what?")?, number(2).unwrap_or(50))?;
+            let label = item.label.clone();
+            match item.effect {
+                Some(ItemEffect::Coins(amount)) => game_state.player.gold += amount, // Gold goes in the purse
+                _ => game_state.player.take_into_pack(item).map_err(|_| "Your pack is full".to_string())?,
+            }
+            Ok(format!("Gave you {}", label))
+        }
+        Some("teleport") => {
+            let destination = position(number(1)?, number(2)?)?;
+            game_state.teleport_player(destination);
+            Ok(format!("Teleported to ({}, {})", destination.0, destination.1))
+        }
+        Some("reveal") => {
+            game_state.reveal_map();
+            Ok("Revealed the whole floor".to_string())
+        }
+        Some("heal") => {
+            game_state.player.health = game_state.player.max_health;
+            Ok("Healed to full".to_string())
+        }
+        Some("help") => Ok(HELP.to_string()),
+        Some(command) => Err(format!("Unknown command '{}'. {}", command, HELP)),
+        None => Err(HELP.to_string()),
+    }
+}
+
+fn parse_npc_type(name: &str) -> Result<NPCType, String> {
+    match name.to_lowercase().as_str() {
+        "goblin" => Ok(NPCType::Goblin),
+        "orc" => Ok(NPCType::Orc),
+        "skeleton" => Ok(NPCType::Skeleton),
+        "archer" => Ok(NPCType::SkeletonArcher),
+        "necromancer" => Ok(NPCType::Necromancer),
+        "shaman" => Ok(NPCType::Shaman),
+        "stalker" => Ok(NPCType::Stalker),
+        "bat" => Ok(NPCType::Bat),
+        "ghost" => Ok(NPCType::Ghost),
+        "merchant" => Ok(NPCType::Merchant),
+        "guard" => Ok(NPCType::Guard),
+        "oracle" => Ok(NPCType::Oracle),
+        _ => Err(format!("Unknown creature '{}'", name)),
+    }
+}
+
+fn parse_item(name: &str, amount: i32) -> Result<Item, String> {
+    match name.to_lowercase().as_str() {
+        "potion" => Ok(catalog::healing_potion()),
+        "dagger" => Ok(catalog::dagger()),
+        "sword" => Ok(catalog::short_sword()),
+        "staff" => Ok(catalog::oak_staff()),
+        "shield" => Ok(catalog::wooden_shield()),
+        "torch" => Ok(catalog::torch(200)),
+        "backpack" => Ok(catalog::leather_backpack()),
+        "amulet" => Ok(catalog::amulet_of_telepathy()),
+        "ring" => Ok(catalog::ring_of_regeneration()),
+        "swimming" => Ok(catalog::ring_of_swimming()),
+        "vampiric" => Ok(catalog::vampiric_dagger()),
+        "blink" => Ok(catalog::blink_scroll()),
+        "rations" => Ok(catalog::trail_rations()),
+        "meat" => Ok(catalog::raw_meat()),
+        "gold" => Ok(Item::gold(amount)),
+        _ => Err(format!("Unknown item '{}'", name)),
+    }
+}
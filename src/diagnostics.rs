@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{self, Rotation};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::{fmt, reload, Registry};
+
+/// Directory the diagnostic log files are written to
+const LOG_DIRECTORY: &str = "logs";
+/// How many daily log files are kept before the oldest is deleted
+const MAX_LOG_FILES: usize = 7;
+
+/// How much detail goes into the diagnostic log file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Verbosity {
+    Off,
+    Warnings,
+    #[default]
+    Events, // Game events and messages
+    Turns, // Every turn and player action too
+    Everything,
+}
+
+impl Verbosity {
+    pub const ALL: [Verbosity; 5] = [Verbosity::Off, Verbosity::Warnings, Verbosity::Events, Verbosity::Turns, Verbosity::Everything];
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            Verbosity::Off => "Off",
+            Verbosity::Warnings => "Warnings only",
+            Verbosity::Events => "Game events",
+            Verbosity::Turns => "Turns and actions",
+            Verbosity::Everything => "Everything",
+        }
+    }
+
+    fn level_filter(&self) -> LevelFilter {
+        match self {
+            Verbosity::Off => LevelFilter::OFF,
+            Verbosity::Warnings => LevelFilter::WARN,
+            Verbosity::Events => LevelFilter::INFO,
+            Verbosity::Turns => LevelFilter::DEBUG,
+            Verbosity::Everything => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// Structured diagnostics written to a rotating file through `tracing`, separate from the in-game log.
+/// Dropping this flushes and closes the file.
+pub struct Diagnostics {
+    filter: reload::Handle<LevelFilter, Registry>,
+    _guard: Option<WorkerGuard>, // Keeps the background writer alive
+}
+
+impl Diagnostics {
+    pub fn init(verbosity: Verbosity) -> Self {
+        let (filter, handle) = reload::Layer::new(verbosity.level_filter());
+        let appender = rolling::Builder::new()
+            .rotation(Rotation::DAILY)
+            .filename_prefix("ai_rogue")
+            .filename_suffix("log")
+            .max_log_files(MAX_LOG_FILES)
+            .build(LOG_DIRECTORY);
+
+        let guard = match appender {
+            Ok(appender) => {
+                let (writer, guard) = tracing_appender::non_blocking(appender);
+                tracing_subscriber::registry()
+                    .with(filter)
+                    .with(fmt::layer().with_writer(writer).with_ansi(false))
+                    .init();
+                Some(guard)
+            }
+            Err(error) => {
+                // Diagnostics are a nice-to-have; fall back to the terminal and carry on
+                tracing_subscriber::registry()
+                    .with(filter)
+                    .with(fmt::layer().with_writer(std::io::stderr))
+                    .init();
+                tracing::warn!("Couldn't open the diagnostic log, logging to stderr instead: {}", error);
+                None
+            }
+        };
+
+        Self { filter: handle, _guard: guard }
+    }
+
+    pub fn set_verbosity(&self, verbosity: Verbosity) {
+        if let Err(error) = self.filter.reload(verbosity.level_filter()) {
+            tracing::warn!("Couldn't change the log verbosity: {}", error);
+        }
+    }
+}
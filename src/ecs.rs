@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A thing in the world, which is nothing but an id for components to hang off
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct EntityId(u32);
+
+/// Every value of one kind of component, keyed by the entity it belongs to. Kept in id order,
+/// so systems visit entities the same way every time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Components<T> {
+    values: BTreeMap<EntityId, T>,
+}
+
+impl<T> Default for Components<T> {
+    fn default() -> Self {
+        Self { values: BTreeMap::new() }
+    }
+}
+
+impl<T> Components<T> {
+    pub fn insert(&mut self, entity: EntityId, value: T) {
+        self.values.insert(entity, value);
+    }
+
+    pub fn get(&self, entity: EntityId) -> Option<&T> {
+        self.values.get(&entity)
+    }
+
+    pub fn get_mut(&mut self, entity: EntityId) -> Option<&mut T> {
+        self.values.get_mut(&entity)
+    }
+
+    pub fn remove(&mut self, entity: EntityId) -> Option<T> {
+        self.values.remove(&entity)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (EntityId, &T)> {
+        self.values.iter().map(|(&entity, value)| (entity, value))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (EntityId, &mut T)> {
+        self.values.iter_mut().map(|(&entity, value)| (entity, value))
+    }
+}
+
+/// How much harm an entity can take before it falls
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Health {
+    pub current: i32,
+    pub max: i32,
+}
+
+impl Health {
+    /// Full health out of `max`
+    pub fn new(max: i32) -> Self {
+        Self { current: max, max }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.current > 0
+    }
+}
+
+/// Light shining from an entity onto a dark floor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Light {
+    pub radius: i32,
+    pub turns_remaining: Option<u32>, // None for permanent lights such as campfires
+}
+
+/// The entities on a floor and their components. A new component is a new store here plus
+/// a line in `despawn`; a new system is a function over the stores it needs, added to `SYSTEMS`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Entities {
+    next_id: u32,
+    pub positions: Components<(i32, i32)>,
+    pub healths: Components<Health>,
+    pub lights: Components<Light>,
+}
+
+impl Entities {
+    /// A new entity with no components yet
+    pub fn spawn(&mut self) -> EntityId {
+        let entity = EntityId(self.next_id);
+        self.next_id += 1;
+        entity
+    }
+
+    /// Take an entity out of the world along with all its components
+    pub fn despawn(&mut self, entity: EntityId) {
+        self.positions.remove(entity);
+        self.healths.remove(entity);
+        self.lights.remove(entity);
+    }
+}
+
+/// The systems the scheduler runs over a floor's entities at the end of every turn, in order
+pub const SYSTEMS: [fn(&mut Entities); 1] = [burn_down_lights];
+
+/// Burn down temporary lights, taking away any that have gone out
+fn burn_down_lights(entities: &mut Entities) {
+    let mut gone_out = Vec::new();
+    for (entity, light) in entities.lights.iter_mut() {
+        if let Some(turns) = light.turns_remaining.as_mut() {
+            *turns = turns.saturating_sub(1);
+            if *turns == 0 {
+                gone_out.push(entity);
+            }
+        }
+    }
+    for entity in gone_out {
+        entities.despawn(entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn despawn_removes_every_component() {
+        let mut entities = Entities::default();
+        let gone = entities.spawn();
+        let kept = entities.spawn();
+        for entity in [gone, kept] {
+            entities.positions.insert(entity, (3, 4));
+            entities.healths.insert(entity, Health::new(10));
+            entities.lights.insert(entity, Light { radius: 2, turns_remaining: None });
+        }
+
+        entities.despawn(gone);
+        assert!(entities.positions.get(gone).is_none());
+        assert!(entities.healths.get(gone).is_none());
+        assert!(entities.lights.get(gone).is_none());
+        assert_eq!(entities.positions.get(kept), Some(&(3, 4)));
+        assert_eq!(entities.healths.get(kept), Some(&Health::new(10)));
+        assert!(entities.lights.get(kept).is_some());
+        // Ids aren't handed out again once used
+        assert_ne!(entities.spawn(), gone);
+    }
+
+    #[test]
+    fn temporary_lights_burn_down_and_go_out() {
+        let mut entities = Entities::default();
+        let torch = entities.spawn();
+        entities.positions.insert(torch, (1, 1));
+        entities.lights.insert(torch, Light { radius: 3, turns_remaining: Some(2) });
+        let campfire = entities.spawn();
+        entities.lights.insert(campfire, Light { radius: 4, turns_remaining: None });
+
+        burn_down_lights(&mut entities);
+        assert_eq!(entities.lights.get(torch).and_then(|light| light.turns_remaining), Some(1));
+
+        burn_down_lights(&mut entities);
+        assert!(entities.lights.get(torch).is_none());
+        assert!(entities.positions.get(torch).is_none());
+        assert_eq!(entities.lights.get(campfire).map(|light| light.turns_remaining), Some(None));
+    }
+}
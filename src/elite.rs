@@ -0,0 +1,82 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::catalog;
+use crate::item::{Element, Item};
+
+/// Percent chance a monster spawns as an elite on the first floor
+const BASE_ELITE_CHANCE: u32 = 5;
+/// Extra percent chance for every floor below the first
+const ELITE_CHANCE_PER_FLOOR: u32 = 3;
+/// The elite chance never climbs past this
+const MAX_ELITE_CHANCE: u32 = 35;
+
+/// What sets a named elite apart from the rest of its kind. Each brings bonus stats and an
+/// ability of its own, e.g. "Grob the Swift" or "Bonecrusher the Ancient".
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum EliteModifier {
+    Swift, // Acts twice as often
+    Ancient, // Far tougher, and knits its wounds back together every turn
+    Brutal, // Hits harder, and its blows can stun
+    Vampiric, // Drinks the health of whoever it hurts
+}
+
+impl EliteModifier {
+    pub const ALL: [EliteModifier; 4] = [EliteModifier::Swift, EliteModifier::Ancient, EliteModifier::Brutal, EliteModifier::Vampiric];
+
+    /// Added after the creature's given name
+    pub fn title(&self) -> &str {
+        match self {
+            EliteModifier::Swift => "the Swift",
+            EliteModifier::Ancient => "the Ancient",
+            EliteModifier::Brutal => "the Brutal",
+            EliteModifier::Vampiric => "the Bloodthirsty",
+        }
+    }
+
+    pub fn get_description(&self) -> &str {
+        match self {
+            EliteModifier::Swift => "Moves and strikes twice as fast as its kin.",
+            EliteModifier::Ancient => "Hardened by the ages; its wounds close as you watch.",
+            EliteModifier::Brutal => "Hits far harder than its kin, hard enough to stun.",
+            EliteModifier::Vampiric => "Heals itself on the blood it spills.",
+        }
+    }
+
+    /// Percent added to the creature's maximum health
+    pub fn health_bonus(&self) -> i32 {
+        match self {
+            EliteModifier::Swift => 0,
+            EliteModifier::Ancient => 100,
+            EliteModifier::Brutal => 50,
+            EliteModifier::Vampiric => 25,
+        }
+    }
+
+    /// Added to both ends of the creature's melee damage
+    pub fn damage_bonus(&self) -> i32 {
+        match self {
+            EliteModifier::Brutal => 3,
+            _ => 1,
+        }
+    }
+}
+
+/// Roll whether a monster spawned on this floor is an elite, and what kind. Deeper floors
+/// turn them up more often.
+pub fn roll(depth: i32, rng: &mut impl Rng) -> Option<EliteModifier> {
+    let floors_down = depth.max(1) as u32 - 1;
+    let chance = (BASE_ELITE_CHANCE + floors_down * ELITE_CHANCE_PER_FLOOR).min(MAX_ELITE_CHANCE);
+    (rng.gen_range(0..100) < chance).then(|| EliteModifier::ALL[rng.gen_range(0..EliteModifier::ALL.len())])
+}
+
+/// Something worth having, dropped by an elite when it dies
+pub fn loot(rng: &mut impl Rng) -> Item {
+    match rng.gen_range(0..5) {
+        0 => catalog::ring_of_regeneration(),
+        1 => catalog::vampiric_dagger(),
+        2 => catalog::gem(Element::Fire),
+        3 => catalog::gem(Element::Frost),
+        _ => Item::gold(rng.gen_range(25..=60)),
+    }
+}
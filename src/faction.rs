@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// Groups of NPCs that share an attitude towards the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Faction {
+    Town, // Merchants and guards
+    Goblins,
+    Monsters, // Always out for blood
+}
+
+impl Faction {
+    pub fn get_name(&self) -> &str {
+        match self {
+            Faction::Town => "Town",
+            Faction::Goblins => "Goblins",
+            Faction::Monsters => "Monsters",
+        }
+    }
+
+    /// Whether members of the two factions come to blows when they meet
+    pub fn is_rival(&self, other: Faction) -> bool {
+        matches!(
+            (self, other),
+            (Faction::Town, Faction::Monsters) | (Faction::Monsters, Faction::Town)
+                | (Faction::Goblins, Faction::Monsters) | (Faction::Monsters, Faction::Goblins)
+        )
+    }
+}
+
+/// Standing at or below which a faction turns on the player
+pub const HOSTILE_REPUTATION: i32 = -20;
+/// Reputation lost for attacking a member of a faction
+pub const ATTACK_PENALTY: i32 = 25;
+/// Reputation lost when a merchant takes offence at haggling
+pub const HAGGLE_PENALTY: i32 = 5;
+/// Reputation lost when the player refuses to pay a shopkeeper for goods they took
+pub const THEFT_PENALTY: i32 = 20;
+/// Turns between each point of reputation drifting back toward neutral
+pub const DECAY_INTERVAL: u32 = 20;
+/// Gold a guard asks for each point of lost reputation
+pub const FINE_PER_POINT: i32 = 2;
+/// Gold donated at an altar for each point of reputation restored
+pub const DONATION_PER_POINT: i32 = 2;
+
+/// How a faction feels about the player, worked out from their standing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Attitude {
+    Hostile,
+    Wary,
+    Neutral,
+    Friendly,
+}
+
+impl Attitude {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Attitude::Hostile => "Hostile",
+            Attitude::Wary => "Wary",
+            Attitude::Neutral => "Neutral",
+            Attitude::Friendly => "Friendly",
+        }
+    }
+}
+
+/// How each faction feels about the player. Everyone starts out neutral at zero.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Reputation {
+    standings: HashMap<Faction, i32>,
+}
+
+impl Reputation {
+    pub fn get(&self, faction: Faction) -> i32 {
+        self.standings.get(&faction).copied().unwrap_or(0)
+    }
+
+    pub fn adjust(&mut self, faction: Faction, amount: i32) {
+        *self.standings.entry(faction).or_insert(0) += amount;
+    }
+
+    /// Move every standing one point closer to neutral, so old grudges and favours fade
+    pub fn decay(&mut self) {
+        for standing in self.standings.values_mut() {
+            *standing -= standing.signum();
+        }
+    }
+
+    /// Gold that clears the player's bad standing with a faction, or nothing if there's none
+    pub fn fine(&self, faction: Faction) -> Option<i32> {
+        let standing = self.get(faction);
+        (standing < 0).then_some(-standing * FINE_PER_POINT)
+    }
+
+    /// Whether members of the faction attack the player on sight
+    pub fn is_hostile(&self, faction: Faction) -> bool {
+        faction == Faction::Monsters || self.get(faction) <= HOSTILE_REPUTATION
+    }
+
+    /// Percent off a merchant's prices because of how the faction feels about the player
+    pub fn price_modifier(&self, faction: Faction) -> i32 {
+        match self.attitude(faction) {
+            Attitude::Friendly => 10,
+            Attitude::Wary => -15,
+            Attitude::Hostile | Attitude::Neutral => 0,
+        }
+    }
+
+    pub fn attitude(&self, faction: Faction) -> Attitude {
+        match self.get(faction) {
+            _ if self.is_hostile(faction) => Attitude::Hostile,
+            standing if standing < 0 => Attitude::Wary,
+            standing if standing > 0 => Attitude::Friendly,
+            _ => Attitude::Neutral,
+        }
+    }
+
+    pub fn describe(&self, faction: Faction) -> &'static str {
+        self.attitude(faction).get_name()
+    }
+}
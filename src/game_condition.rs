@@ -1,44 +1,173 @@
-use crate::item::{Item, ItemType};
-use crate::npc::{NPC, NPCType};
-use crate::state::{GameState, WorldItem};
+use crate::accessibility;
+use crate::balance;
+use crate::catalog;
+use crate::elite::EliteModifier;
+use crate::geometry;
+use crate::i18n::{Language, tr, trf};
+use crate::command::GameEvent;
+use crate::item::{Element, EquipSlot, Handedness, Item, ItemEffect, ItemStats, ItemType};
+use crate::names;
+use crate::npc::{NPC, NPCState, NPCType};
+use crate::scores;
+use crate::state::{GameState, Shop, TileType, WorldItem};
+use crate::status::StatusKind;
+use crate::trap::{Trap, TrapType};
+use crate::weather::Weather;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum GameStatus {
     Playing,
     Won,
-    Lost,
+    Lost(LossReason),
+}
+
+/// Why a run was lost, shown on the Game Over screen
+#[derive(Debug, Clone, PartialEq)]
+pub enum LossReason {
+    Died, // Health reached zero
+    OutOfTurns, // A turn limit ran out
+    OutOfTime, // A real-time limit ran out
+    QuestItemLost(String), // Something the win needed is gone for good, such as the treasure chest falling into a collapse
 }
 
 /// Trait for determining win/loss conditions in the game
-pub trait GameCondition {
+pub trait GameCondition: std::fmt::Debug {
     /// Check the current game status based on game state
     fn check_status(&self, game_state: &GameState) -> GameStatus;
     
     /// Get a description of the win condition for this game type
-    fn win_description(&self) -> String;
+    fn win_description(&self, language: Language) -> String;
     
     /// Get a description of the loss condition for this game type
-    fn loss_description(&self) -> &str;
+    fn loss_description(&self, language: Language) -> &str;
     
     /// Get the victory message shown when the player wins
-    fn victory_message(&self) -> &str;
+    fn victory_message(&self, language: Language) -> &str;
+    
+    /// Live progress towards the win, shown next to the goal and updated every turn
+    fn progress(&self, game_state: &GameState) -> String;
+    
+    /// Where the player should head next, pointed at by the objective compass.
+    /// None when there is nowhere in particular to go.
+    fn objective_position(&self, game_state: &GameState) -> Option<(i32, i32)>;
+    
+    /// The condition as it's written into a save, to be rebuilt when the save is loaded
+    fn saved(&self) -> SavedCondition;
     
     /// Setup the world and NPCs for this game mode
     fn setup_world(&self, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player);
+    
+    /// Something the player needs to know about the run right now, shown in the goal banner
+    fn warning(&self, _game_state: &GameState) -> Option<String> {
+        None
+    }
+    
+    /// What a friendly NPC tells the player about the goal when they stop to talk, if it knows anything.
+    /// Hearing it sets `GameState::goal_revealed`.
+    fn npc_hint(&self, _language: Language, _npc: &NPC) -> Option<String> {
+        None
+    }
+    
+    /// What the Oracle sees in the smoke when the player pays for a reading: by default, where
+    /// the objective lies and how long is left
+    fn hint(&self, game_state: &GameState) -> String {
+        let language = game_state.settings.language;
+        let mut signs = Vec::new();
+        if let Some(target) = self.objective_position(game_state) {
+            signs.push(trf(language, "goal.hint_lies", &[&accessibility::whereabouts(game_state.player.position, target)]));
+        }
+        if let Some(turns) = self.turns_remaining(game_state) {
+            signs.push(trf(language, "goal.hint_turns", &[&turns]));
+        }
+        if let Some(seconds) = self.time_remaining(game_state) {
+            signs.push(trf(language, "goal.hint_seconds", &[&format!("{:.0}", seconds)]));
+        }
+        if signs.is_empty() {
+            return tr(language, "goal.hint_nothing").to_string();
+        }
+        signs.join(" ")
+    }
+    
+    /// Build the next floor down once the player takes the stairs. Only modes that place stairs need this.
+    fn setup_floor(&self, _depth: i32, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
+        self.setup_world(world, npcs, player);
+    }
+    
+    /// Real seconds left before the game is lost, for modes played against the clock
+    fn time_remaining(&self, _game_state: &GameState) -> Option<f64> {
+        None
+    }
+    
+    /// Turns left before the game is won, for modes played against the clock
+    fn turns_remaining(&self, _game_state: &GameState) -> Option<u32> {
+        None
+    }
+    
+    /// A milestone towards the win that this event just reached, such as the first gem picked
+    /// up, for the UI to celebrate. Called as each event happens, with the game as it stands then.
+    fn on_event(&self, _event: &GameEvent, _game_state: &GameState) -> Option<String> {
+        None
+    }
+    
+    /// Change the world as the game goes on, once the NPCs have acted each turn.
+    /// Returns messages for the log.
+    fn on_turn_end(&self, _language: Language, _world: &mut crate::state::GameWorld, _npcs: &mut Vec<crate::npc::NPC>, _player: &mut crate::state::Player, _turn: u32) -> Vec<String> {
+        Vec::new()
+    }
 }
 
-/// Default treasure hunt game condition
+/// Every mode's condition as it's written into a save. A save can't rebuild a trait object on
+/// its own, so it names the mode and carries whatever settings the mode was started with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SavedCondition {
+    TreasureHunt(TreasureHuntCondition),
+    Pacifist(PacifistCondition),
+    Speedrun(SpeedrunCondition),
+    Arena(ArenaCondition),
+    Endless(EndlessCondition),
+    Mystery(MysteryCondition),
+    Survival(SurvivalCondition),
+    Collection(CollectionCondition),
+}
+
+impl SavedCondition {
+    pub fn restore(self) -> Box<dyn GameCondition> {
+        match self {
+            SavedCondition::TreasureHunt(condition) => Box::new(condition),
+            SavedCondition::Pacifist(condition) => Box::new(condition),
+            SavedCondition::Speedrun(condition) => Box::new(condition),
+            SavedCondition::Arena(condition) => Box::new(condition),
+            SavedCondition::Endless(condition) => Box::new(condition),
+            SavedCondition::Mystery(condition) => Box::new(condition),
+            SavedCondition::Survival(condition) => Box::new(condition),
+            SavedCondition::Collection(condition) => Box::new(condition),
+        }
+    }
+}
+
+/// Where the chest sits on the first floor, or in the full hunt the stairs down
+const CHEST_SPOT: (i32, i32) = (35, 18);
+/// The floor where the keeper of the chest's key waits with the chest
+const KEEPER_FLOOR: i32 = 3;
+
+/// Default treasure hunt game condition: down the stairs to the third floor, where an elite orc
+/// keeps the chest and carries its key
 /// Win: Collect the treasure
-/// Lose: Player dies (health <= 0)
-#[derive(Debug)]
+/// Lose: Player dies (health <= 0), or the treasure is lost for good
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TreasureHuntCondition;
 
 impl GameCondition for TreasureHuntCondition {
+    fn saved(&self) -> SavedCondition {
+        SavedCondition::TreasureHunt(self.clone())
+    }
+
     fn check_status(&self, game_state: &GameState) -> GameStatus {
         // Check loss condition first
         if !game_state.player.is_alive() {
-            return GameStatus::Lost;
+            return GameStatus::Lost(LossReason::Died);
         }
         
         // Check win condition - player has treasure in inventory
@@ -46,46 +175,925 @@ impl GameCondition for TreasureHuntCondition {
             return GameStatus::Won;
         }
         
+        // The chest, the key and what's inside can be lost for good, burned or fallen through the
+        // floor; in a merchant's stock they can still be bought back, a creature carrying one
+        // still drops it, and while there are stairs down they may still be waiting below
+        let still_about = |item_type: ItemType| {
+            carried(game_state, &item_type) > 0
+                || game_state.world.items.iter().any(|world_item| world_item.item.item_type == item_type)
+                || game_state.npcs.iter().any(|npc| {
+                    npc.stock.iter().any(|item| item.item_type == item_type)
+                        || npc.loot.as_ref().is_some_and(|item| item.item_type == item_type)
+                })
+        };
+        let could_be_below = stairs_position(&game_state.world).is_some();
+        if !still_about(ItemType::Treasure) && !could_be_below {
+            // Opening the chest uses up the key, so they only matter until the treasure spills out
+            if !still_about(ItemType::TreasureChest) {
+                return GameStatus::Lost(LossReason::QuestItemLost("treasure".to_string()));
+            }
+            if !still_about(ItemType::Key) {
+                return GameStatus::Lost(LossReason::QuestItemLost("key".to_string()));
+            }
+        }
+        
         GameStatus::Playing
     }
     
-    fn win_description(&self) -> String {
-        "Find and collect the treasure!".to_string()
+    fn win_description(&self, language: Language) -> String {
+        trf(language, "goal.hunt_win", &[&KEEPER_FLOOR])
+    }
+    
+    fn progress(&self, game_state: &GameState) -> String {
+        let carrying = |item_type: ItemType| game_state.player.inventory.iter().any(|item| item.item_type == item_type);
+        let language = game_state.settings.language;
+        let mark = |done: bool| tr(language, if done { "goal.yes" } else { "goal.no" });
+        // Opening the chest uses up the key, so an opened chest counts as having both
+        let opened = !carrying(ItemType::TreasureChest)
+            && (carrying(ItemType::Treasure) || game_state.world.items.iter().any(|world_item| world_item.item.item_type == ItemType::Treasure));
+        trf(language, "goal.hunt_progress", &[
+            &mark(opened || carrying(ItemType::Key)),
+            &mark(opened || carrying(ItemType::TreasureChest)),
+            &mark(carrying(ItemType::Treasure)),
+        ])
+    }
+    
+    fn objective_position(&self, game_state: &GameState) -> Option<(i32, i32)> {
+        let on_ground = |item_type: ItemType| game_state.world.items.iter()
+            .find(|world_item| world_item.item.item_type == item_type)
+            .map(|world_item| world_item.position);
+        let carrying = |item_type: ItemType| game_state.player.inventory.iter().any(|item| item.item_type == item_type);
+
+        // Spilled treasure first, then the chest, then the key to open it, then the way down
+        if let Some(position) = on_ground(ItemType::Treasure) {
+            return Some(position);
+        }
+        if !carrying(ItemType::TreasureChest)
+            && let Some(position) = on_ground(ItemType::TreasureChest)
+        {
+            return Some(position);
+        }
+        let has_key = carrying(ItemType::Key);
+        if has_key && carrying(ItemType::TreasureChest) {
+            return None; // Everything needed is in hand
+        }
+        let key = on_ground(ItemType::Key).or_else(|| {
+            // Whoever carries the key guards it until they fall
+            game_state.npcs.iter()
+                .find(|npc| npc.loot.as_ref().is_some_and(|item| item.item_type == ItemType::Key))
+                .map(|npc| npc.position(&game_state.world))
+        });
+        if !has_key && key.is_some() {
+            return key;
+        }
+        stairs_position(&game_state.world)
     }
     
-    fn loss_description(&self) -> &str {
-        "Don't let your health reach zero!"
+    fn on_event(&self, event: &GameEvent, game_state: &GameState) -> Option<String> {
+        let GameEvent::PickedUp(item_type) = event else {
+            return None;
+        };
+        match item_type {
+            ItemType::Key if carried(game_state, item_type) == 1 => Some(tr(game_state.settings.language, "goal.hunt_key").to_string()),
+            ItemType::TreasureChest => Some(tr(game_state.settings.language, "goal.hunt_chest").to_string()),
+            _ => None,
+        }
     }
     
-    fn victory_message(&self) -> &str {
-        "Congratulations! You have found the treasure and escaped the dungeon!"
+    fn loss_description(&self, language: Language) -> &str {
+        tr(language, "goal.loss_died")
+    }
+    
+    fn victory_message(&self, language: Language) -> &str {
+        tr(language, "goal.hunt_victory")
     }
     
     fn setup_world(&self, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
+        Self::stock_first_floor(world, npcs, player);
+        // The skeleton no longer has the key; its keeper waits further down
+        for npc in npcs.iter_mut().filter(|npc| npc.npc_type == NPCType::Skeleton) {
+            npc.loot = None;
+        }
+        world.tiles[(CHEST_SPOT.0 as usize, CHEST_SPOT.1 as usize)] = TileType::Stairs;
+    }
+    
+    fn setup_floor(&self, depth: i32, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
+        stock_descent_floor(depth, depth < KEEPER_FLOOR, world, npcs, player);
+        if depth == KEEPER_FLOOR {
+            Self::stock_keepers_lair(world, npcs, player);
+        }
+    }
+}
+
+impl TreasureHuntCondition {
+    /// The floor the hunt starts on, which the modes built on it share: its cast, the shop,
+    /// the gear, traps and hazards. Everything but the chest.
+    fn stock_first_floor(world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
         // Default setup for treasure hunt - variety of NPCs
-        npcs.push(NPC::new(5, 5, NPCType::Goblin, "Grob".to_string()));
-        npcs.push(NPC::new(15, 8, NPCType::Merchant, "The Merchant".to_string()));
-        npcs.push(NPC::new(25, 12, NPCType::Skeleton, "Bonecrusher".to_string()));
-        npcs.push(NPC::new(8, 20, NPCType::Guard, "Guard Captain".to_string()));
-        npcs.push(NPC::new(30, 25, NPCType::Orc, "Orc Warrior".to_string()));
-
-        // Add treasure chest at a specific location
-        let treasure_chest = Item::new(
-            ItemType::TreasureChest,
-            "Treasure Chest".to_string(),
-            "A mysterious chest that might contain valuable items.".to_string(),
-        );
-        world.items.push(WorldItem::new(35, 18, treasure_chest));
+        let mut rng = rand::thread_rng();
+        let cast = [
+            (5, 5, NPCType::Goblin),
+            (15, 8, NPCType::Merchant),
+            (25, 12, NPCType::Skeleton),
+            (40, 10, NPCType::SkeletonArcher),
+            (8, 20, NPCType::Guard),
+            (30, 25, NPCType::Orc),
+            (42, 22, NPCType::Stalker),
+            (27, 23, NPCType::Bat), // Roosts over the pond
+            (3, 6, NPCType::Oracle),
+        ];
+        for (x, y, npc_type) in cast {
+            let name = names::generate(&npc_type, &mut rng);
+            let mut npc = NPC::new(world, x, y, npc_type, name);
+            npc.doze(&mut rng);
+            npc.maybe_promote(world.current_floor, world, &mut rng);
+            npcs.push(npc);
+        }
+
+        // A potion to reveal the stalker lurking somewhere on the floor
+        let see_invisible = Item::new(
+            ItemType::Potion,
+            "Potion of See Invisible".to_string(),
+            "A cloudy potion that lets you see what is hidden from the eye.".to_string(),
+        ).with_effect(ItemEffect::ApplyStatus(StatusKind::SeeInvisible, 50));
+        world.items.push(WorldItem::new(12, 10, see_invisible));
+
+        // Arms and armor for the fights ahead
+        world.items.push(WorldItem::new(7, 12, catalog::short_sword()));
+        world.items.push(WorldItem::new(13, 18, catalog::wooden_shield()));
+        let greataxe = Item::new(
+            ItemType::Weapon,
+            "Greataxe".to_string(),
+            "A brutal double-bitted axe. It takes both hands to swing.".to_string(),
+        ).with_stats(ItemStats { damage: Some((12, 26)), handedness: Handedness::TwoHanded, ..Default::default() }).with_sockets(2);
+        world.items.push(WorldItem::new(25, 8, greataxe));
+        for (x, y) in [(16, 20), (17, 21)] {
+            world.items.push(WorldItem::new(x, y, catalog::dagger()));
+        }
+
+        // Paralysis traps guarding the approach to the chest (or, in the full hunt, the stairs)
+        world.traps.push(Trap::new(33, 18, TrapType::Paralysis));
+        world.traps.push(Trap::new(35, 16, TrapType::Paralysis));
+
+        // Dry grass and a fire trap to set it alight
+        world.add_grass_patches(6);
+        world.traps.push(Trap::new(20, 14, TrapType::Fire));
+
+        // An alchemist's cauldron and a healing potion to experiment with
+        world.tiles[(12, 6)] = TileType::Cauldron;
+        world.items.push(WorldItem::new(18, 4, catalog::healing_potion()));
+
+        // Blinding hazards along the corridors to the chest
+        world.traps.push(Trap::new(28, 10, TrapType::BlindingDart));
+        for (x, y) in [(30, 20), (31, 20), (31, 21)] {
+            world.tiles[(x, y)] = TileType::Fungus;
+        }
+
+        // The amulet sits on an island in a ring of lava, reachable by levitating or blinking
+        world.items.push(WorldItem::new(44, 4, catalog::amulet_of_telepathy()));
+        world.items.push(WorldItem::new(33, 14, catalog::ring_of_regeneration()));
+        world.items.push(WorldItem::new(36, 24, catalog::ring_of_swimming()));
+        world.items.push(WorldItem::new(6, 18, catalog::leather_backpack()));
+        for (x, y, amount) in [(21, 14, 12), (38, 20, 20)] {
+            world.items.push(WorldItem::new(x, y, Item::gold(amount)));
+        }
+        world.items.push(WorldItem::new(10, 24, catalog::vampiric_dagger()));
+        for x in 42..=46 {
+            for y in 2..=6 {
+                if (x, y) != (44, 4) {
+                    world.tiles[(x, y)] = TileType::Lava;
+                }
+            }
+        }
+        for x in 26..=29 {
+            for y in 22..=24 {
+                world.tiles[(x, y)] = TileType::Water;
+            }
+        }
+        let levitation = Item::new(
+            ItemType::Potion,
+            "Potion of Levitation".to_string(),
+            "A pale blue potion that fizzes upwards out of the bottle.".to_string(),
+        ).with_effect(ItemEffect::ApplyStatus(StatusKind::Levitating, 12));
+        world.items.push(WorldItem::new(3, 12, levitation));
+        let haste = Item::new(
+            ItemType::Potion,
+            "Potion of Haste".to_string(),
+            "A fizzing yellow potion. The bottle seems to tremble in your hand.".to_string(),
+        ).with_effect(ItemEffect::ApplyStatus(StatusKind::Hasted, 10));
+        world.items.push(WorldItem::new(20, 26, haste));
+        let slowness = Item::new(
+            ItemType::Potion,
+            "Potion of Slowness".to_string(),
+            "A sluggish grey sludge. Best left undrunk.".to_string(),
+        ).with_effect(ItemEffect::ApplyStatus(StatusKind::Slowed, 10));
+        world.items.push(WorldItem::new(38, 24, slowness));
+
+        // Teleport runes scatter anyone careless, and a Blink scroll offers a controlled escape
+        world.traps.push(Trap::new(22, 18, TrapType::Teleport));
+        world.traps.push(Trap::new(38, 6, TrapType::Teleport));
+        world.items.push(WorldItem::new(5, 22, catalog::blink_scroll()));
+        let detect_treasure = Item::new(
+            ItemType::Scroll,
+            "Scroll of Detect Treasure".to_string(),
+            "Reveals the glint of every valuable on the floor for a while.".to_string(),
+        ).with_effect(ItemEffect::ApplyStatus(StatusKind::DetectingTreasure, 30));
+        world.items.push(WorldItem::new(14, 14, detect_treasure));
+        let amnesia = Item::new(
+            ItemType::Scroll,
+            "Scroll of Amnesia".to_string(),
+            "A scroll whose words slip out of your head as you read them.".to_string(),
+        ).with_effect(ItemEffect::Amnesia);
+        world.items.push(WorldItem::new(36, 26, amnesia));
+        world.traps.push(Trap::new(18, 12, TrapType::Amnesia));
+
+        // A campfire in a quiet corner, with something to cook on it
+        world.tiles[(6, 24)] = TileType::Campfire;
+        for (x, y) in [(9, 25), (22, 3)] {
+            world.items.push(WorldItem::new(x, y, catalog::raw_meat()));
+        }
+
+        // A checkpoint rune partway to the stairs, to come back to after a death
+        world.add_checkpoint((24, 16));
+
+        // The merchant's shop, with wares set out on the floor for the taking - at a price
+        if let Some(keeper) = npcs.iter().find(|npc| npc.npc_type == NPCType::Merchant) {
+            world.shops.push(Shop { area: ((14, 7), (18, 10)), keeper: keeper.id });
+            for (x, y, item) in [(17, 7, catalog::healing_potion()), (17, 9, catalog::torch(200)), (14, 10, catalog::trail_rations())] {
+                let price = item.value();
+                world.items.push(WorldItem::for_sale(x, y, item, price));
+            }
+        }
+
+        // An altar beside the guard, to make amends with the town
+        place_altar_by_guard(world, npcs, player);
+
+        // A workbench and a couple of gems to set into socketed gear
+        world.tiles[(14, 4)] = TileType::Workbench;
+        world.items.push(WorldItem::new(3, 26, catalog::gem(Element::Fire)));
+        world.items.push(WorldItem::new(40, 14, catalog::gem(Element::Frost)));
         
         // Set default player position
         player.position = (10, 15);
     }
+
+    /// The hunt all on one screen, for the modes that play it against a twist: the first
+    /// floor with the chest where the stairs would be, and the skeleton holding the key
+    pub fn setup_single_floor(world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
+        Self::stock_first_floor(world, npcs, player);
+        world.items.push(WorldItem::new(CHEST_SPOT.0, CHEST_SPOT.1, treasure_chest()));
+    }
+
+    /// The bottom of the hunt: the chest, far from the stairs the player came down, and the
+    /// elite orc who carries its key standing over it
+    fn stock_keepers_lair(world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
+        const LAIR_DISTANCE: i32 = 12;
+
+        let mut rng = rand::thread_rng();
+        let Some((x, y)) = spawn_position(world, npcs, player, LAIR_DISTANCE).or_else(|| spawn_position(world, npcs, player, 1)) else {
+            return;
+        };
+        world.items.push(WorldItem::new(x, y, treasure_chest()));
+        // The keeper stands guard beside the chest, or anywhere at all if it's boxed in, so the
+        // key is always somewhere on the floor
+        let beside = geometry::circle((x, y), 1)
+            .find(|&(bx, by)| world.is_walkable(bx, by) && (bx, by) != (x, y) && (bx, by) != player.position && !npcs.iter().any(|npc| npc.position(world) == (bx, by)))
+            .or_else(|| spawn_position(world, npcs, player, 1));
+        if let Some((bx, by)) = beside {
+            let mut keeper = NPC::new(world, bx, by, NPCType::Orc, String::new());
+            keeper.promote(EliteModifier::Brutal, world, &mut rng);
+            keeper.name = format!("{} the Keeper of the Key", names::given_name(&NPCType::Orc, &mut rng));
+            keeper.loot = Some(catalog::chest_key());
+            npcs.push(keeper);
+        }
+    }
+}
+
+/// Pacifist treasure hunt: the treasure hunt floor, won without hurting anyone
+/// Win: Collect the treasure without dealing any damage
+/// Lose: Player dies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PacifistCondition;
+
+impl GameCondition for PacifistCondition {
+    fn saved(&self) -> SavedCondition {
+        SavedCondition::Pacifist(self.clone())
+    }
+
+    fn check_status(&self, game_state: &GameState) -> GameStatus {
+        if !game_state.player.is_alive() {
+            return GameStatus::Lost(LossReason::Died);
+        }
+        // Once blood is spilled the run can't be won, but the player can keep exploring
+        if game_state.damage_dealt == 0 && TreasureHuntCondition.check_status(game_state) == GameStatus::Won {
+            return GameStatus::Won;
+        }
+        GameStatus::Playing
+    }
+    
+    fn win_description(&self, language: Language) -> String {
+        tr(language, "goal.pacifist_win").to_string()
+    }
+    
+    fn progress(&self, game_state: &GameState) -> String {
+        trf(game_state.settings.language, "goal.pacifist_progress", &[&TreasureHuntCondition.progress(game_state), &game_state.damage_dealt])
+    }
+    
+    fn warning(&self, game_state: &GameState) -> Option<String> {
+        (game_state.damage_dealt > 0)
+            .then(|| trf(game_state.settings.language, "goal.pacifist_warning", &[&game_state.damage_dealt]))
+    }
+    
+    fn objective_position(&self, game_state: &GameState) -> Option<(i32, i32)> {
+        TreasureHuntCondition.objective_position(game_state)
+    }
+    
+    fn on_event(&self, event: &GameEvent, game_state: &GameState) -> Option<String> {
+        TreasureHuntCondition.on_event(event, game_state)
+    }
+    
+    fn loss_description(&self, language: Language) -> &str {
+        tr(language, "goal.pacifist_loss")
+    }
+    
+    fn victory_message(&self, language: Language) -> &str {
+        tr(language, "goal.pacifist_victory")
+    }
+    
+    fn setup_world(&self, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
+        TreasureHuntCondition::setup_single_floor(world, npcs, player);
+        // The skeleton has dropped its key rather than guarding it, so it can be taken without a fight
+        world.items.push(WorldItem::new(27, 13, catalog::bone_key()));
+    }
+}
+
+/// Speedrun: the treasure hunt against a real-time clock that only stops while paused
+/// Win: Collect the treasure before time runs out
+/// Lose: Player dies or the clock runs out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeedrunCondition {
+    pub time_limit_secs: f64,
+}
+
+impl SpeedrunCondition {
+    pub fn new(time_limit_secs: f64) -> Self {
+        Self { time_limit_secs }
+    }
+}
+
+impl GameCondition for SpeedrunCondition {
+    fn saved(&self) -> SavedCondition {
+        SavedCondition::Speedrun(self.clone())
+    }
+
+    fn check_status(&self, game_state: &GameState) -> GameStatus {
+        if game_state.play_time >= self.time_limit_secs {
+            return GameStatus::Lost(LossReason::OutOfTime);
+        }
+        TreasureHuntCondition.check_status(game_state)
+    }
+    
+    fn win_description(&self, language: Language) -> String {
+        trf(language, "goal.speedrun_win", &[&scores::format_time(self.time_limit_secs)])
+    }
+    
+    fn progress(&self, game_state: &GameState) -> String {
+        TreasureHuntCondition.progress(game_state)
+    }
+    
+    fn time_remaining(&self, game_state: &GameState) -> Option<f64> {
+        Some((self.time_limit_secs - game_state.play_time).max(0.0))
+    }
+    
+    fn objective_position(&self, game_state: &GameState) -> Option<(i32, i32)> {
+        TreasureHuntCondition.objective_position(game_state)
+    }
+    
+    fn on_event(&self, event: &GameEvent, game_state: &GameState) -> Option<String> {
+        TreasureHuntCondition.on_event(event, game_state)
+    }
+    
+    fn loss_description(&self, language: Language) -> &str {
+        tr(language, "goal.speedrun_loss")
+    }
+    
+    fn victory_message(&self, language: Language) -> &str {
+        tr(language, "goal.speedrun_victory")
+    }
+    
+    fn setup_world(&self, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
+        TreasureHuntCondition::setup_single_floor(world, npcs, player);
+    }
+}
+
+/// Arena: a small walled pit where waves of monsters pour in
+/// Win: Survive wave 10 and clear the arena
+/// Lose: Player dies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArenaCondition;
+
+impl ArenaCondition {
+    /// Waves arrived so far
+    fn wave(turn: u32) -> u32 {
+        (turn / arena_wave_interval()).min(ARENA_WAVES)
+    }
+
+    /// The monsters sent in each wave, with tougher kinds joining as the waves go on
+    fn wave_monsters(wave: u32) -> Vec<NPCType> {
+        let mut monsters = vec![NPCType::Orc; 1 + wave as usize / 3];
+        if wave >= 3 {
+            monsters.push(NPCType::SkeletonArcher);
+        }
+        if wave >= 5 {
+            monsters.push(NPCType::Stalker);
+        }
+        if wave >= 7 {
+            monsters.push(NPCType::Shaman);
+        }
+        if wave == ARENA_WAVES {
+            monsters.push(NPCType::Necromancer);
+        }
+        monsters
+    }
+
+    /// Pit fighters go by their own names, but the final necromancer is the master of the pit
+    fn pit_name(npc_type: &NPCType) -> String {
+        let mut rng = rand::thread_rng();
+        match npc_type {
+            NPCType::Necromancer => format!("{} the Pit Master", names::given_name(npc_type, &mut rng)),
+            _ => names::generate(npc_type, &mut rng),
+        }
+    }
+}
+
+impl GameCondition for ArenaCondition {
+    fn saved(&self) -> SavedCondition {
+        SavedCondition::Arena(self.clone())
+    }
+
+    fn check_status(&self, game_state: &GameState) -> GameStatus {
+        if !game_state.player.is_alive() {
+            return GameStatus::Lost(LossReason::Died);
+        }
+        let final_wave_in = Self::wave(game_state.turn_counter) == ARENA_WAVES;
+        if final_wave_in && !game_state.npcs.iter().any(|npc| npc.is_hostile()) {
+            return GameStatus::Won;
+        }
+        GameStatus::Playing
+    }
+    
+    fn win_description(&self, language: Language) -> String {
+        trf(language, "goal.arena_win", &[&ARENA_WAVES])
+    }
+    
+    fn progress(&self, game_state: &GameState) -> String {
+        let wave = Self::wave(game_state.turn_counter);
+        let foes = game_state.npcs.iter().filter(|npc| npc.is_hostile()).count();
+        let language = game_state.settings.language;
+        if wave == ARENA_WAVES {
+            trf(language, "goal.arena_final_wave", &[&foes])
+        } else {
+            let interval = arena_wave_interval();
+            let next_wave = interval - game_state.turn_counter % interval;
+            trf(language, "goal.arena_progress", &[&wave, &ARENA_WAVES, &next_wave, &foes])
+        }
+    }
+    
+    fn objective_position(&self, game_state: &GameState) -> Option<(i32, i32)> {
+        let monsters = game_state.npcs.iter().filter(|npc| npc.is_hostile()).map(|npc| npc.position(&game_state.world));
+        nearest(game_state.player.position, monsters)
+    }
+    
+    fn loss_description(&self, language: Language) -> &str {
+        tr(language, "goal.arena_loss")
+    }
+    
+    fn victory_message(&self, language: Language) -> &str {
+        tr(language, "goal.arena_victory")
+    }
+    
+    fn setup_world(&self, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
+        // Everything outside the pit is solid rock
+        let (left, top, right, bottom) = (14, 8, 35, 21);
+        for x in 0..world.size.0 {
+            for y in 0..world.size.1 {
+                let inside = (left + 1..right).contains(&x) && (top + 1..bottom).contains(&y);
+                world.tiles[(x, y)] = if inside { TileType::Floor } else { TileType::Wall };
+            }
+        }
+        // A few pillars to duck behind
+        for (x, y) in [(19, 12), (30, 12), (19, 17), (30, 17)] {
+            world.tiles[(x, y)] = TileType::Wall;
+        }
+
+        player.position = (25, 15);
+        world.items.push(WorldItem::new(24, 15, catalog::healing_potion()));
+
+        // The first wave is waiting
+        for npc_type in Self::wave_monsters(0) {
+            if let Some((x, y)) = spawn_position(world, npcs, player, 6) {
+                let name = Self::pit_name(&npc_type);
+                npcs.push(NPC::new(world, x, y, npc_type, name));
+            }
+        }
+    }
+    
+    fn on_turn_end(&self, language: Language, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player, turn: u32) -> Vec<String> {
+        const HEALTH_PER_WAVE: i32 = 3;
+
+        let interval = arena_wave_interval();
+        let mut messages = Vec::new();
+        // A healing potion is thrown in halfway between waves
+        if turn % interval == interval / 2
+            && Self::wave(turn) < ARENA_WAVES
+            && let Some((x, y)) = spawn_position(world, npcs, player, 0)
+        {
+            world.items.push(WorldItem::new(x, y, catalog::healing_potion()));
+            messages.push(tr(language, "goal.arena_potion").to_string());
+        }
+
+        if turn == 0 || !turn.is_multiple_of(interval) || turn / interval > ARENA_WAVES {
+            return messages;
+        }
+        let wave = turn / interval;
+        for npc_type in Self::wave_monsters(wave) {
+            if let Some((x, y)) = spawn_position(world, npcs, player, 6) {
+                let name = Self::pit_name(&npc_type);
+                let mut npc = NPC::new(world, x, y, npc_type, name);
+                // Later waves are hardier, and come straight for the player
+                let max_health = npc.health(world).max + wave as i32 * HEALTH_PER_WAVE;
+                npc.set_max_health(world, max_health);
+                npc.state = NPCState::Investigating(player.position);
+                npcs.push(npc);
+            }
+        }
+        messages.push(trf(language, "goal.arena_wave", &[&wave, &ARENA_WAVES]));
+        messages
+    }
+}
+
+/// Endless descent: keep taking the stairs down to ever more dangerous floors
+/// Win: Never - the run is scored by how deep it got and the treasure carried
+/// Lose: Player dies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndlessCondition;
+
+impl GameCondition for EndlessCondition {
+    fn saved(&self) -> SavedCondition {
+        SavedCondition::Endless(self.clone())
+    }
+
+    fn check_status(&self, game_state: &GameState) -> GameStatus {
+        if !game_state.player.is_alive() {
+            return GameStatus::Lost(LossReason::Died);
+        }
+        GameStatus::Playing
+    }
+    
+    fn win_description(&self, language: Language) -> String {
+        tr(language, "goal.endless_win").to_string()
+    }
+    
+    fn progress(&self, game_state: &GameState) -> String {
+        trf(game_state.settings.language, "goal.endless_progress", &[
+            &game_state.world.current_floor,
+            &scores::treasure_value(&game_state.player),
+        ])
+    }
+    
+    fn objective_position(&self, game_state: &GameState) -> Option<(i32, i32)> {
+        stairs_position(&game_state.world)
+    }
+    
+    fn loss_description(&self, language: Language) -> &str {
+        tr(language, "goal.endless_loss")
+    }
+    
+    fn victory_message(&self, language: Language) -> &str {
+        tr(language, "goal.endless_victory")
+    }
+    
+    fn setup_world(&self, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
+        self.setup_floor(1, world, npcs, player);
+    }
+    
+    fn setup_floor(&self, depth: i32, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
+        stock_descent_floor(depth, true, world, npcs, player);
+        if depth % 3 == 0
+            && let Some((x, y)) = spawn_position(world, npcs, player, 0)
+        {
+            let treasure = Item::new(ItemType::Treasure, "Pile of Treasure".to_string(), "Glittering coins and gems left by someone who went no further.".to_string());
+            world.items.push(WorldItem::new(x, y, treasure));
+        }
+    }
+}
+
+/// Stock a freshly generated floor of a descent, tougher the deeper it is: obstacles, a
+/// spread of monsters from the depth's pool, loot worth diving for and, unless it's the last
+/// floor, stairs further down
+fn stock_descent_floor(depth: i32, stairs: bool, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
+    const HEALTH_PER_FLOOR: i32 = 4;
+    const STAIRS_DISTANCE: i32 = 15;
+    const CHECKPOINT_DISTANCE: i32 = 8;
+    const FROZEN_DEPTH: i32 = 3;
+
+    let mut rng = rand::thread_rng();
+    world.add_random_obstacles(15 + (depth as usize * 2).min(20));
+    world.add_grass_patches(5);
+    // The deeps are cold enough for the puddles to freeze
+    if depth >= FROZEN_DEPTH {
+        world.add_terrain_patches(TileType::Ice, 3);
+    }
+
+    if let Some(position) = spawn_position(world, npcs, player, 0) {
+        player.position = position;
+    }
+    if stairs
+        && let Some((x, y)) = spawn_position(world, npcs, player, STAIRS_DISTANCE).or_else(|| spawn_position(world, npcs, player, 1))
+    {
+        world.tiles[(x as usize, y as usize)] = TileType::Stairs;
+    }
+    if let Some(position) = spawn_position(world, npcs, player, CHECKPOINT_DISTANCE) {
+        world.add_checkpoint(position);
+    }
+
+    // Deeper floors have more, tougher and stranger monsters
+    let pool: &[NPCType] = match depth {
+        1..=2 => &[NPCType::Goblin, NPCType::Orc, NPCType::Bat],
+        3..=5 => &[NPCType::Orc, NPCType::SkeletonArcher, NPCType::Stalker, NPCType::Ghost],
+        _ => &[NPCType::Orc, NPCType::Stalker, NPCType::Ghost, NPCType::Shaman, NPCType::Necromancer],
+    };
+    for _ in 0..2 + depth {
+        let npc_type = &pool[rng.gen_range(0..pool.len())];
+        if let Some((x, y)) = spawn_position(world, npcs, player, 6) {
+            let mut npc = NPC::new(world, x, y, npc_type.clone(), names::generate(npc_type, &mut rng));
+            let max_health = npc.health(world).max + (depth - 1) * HEALTH_PER_FLOOR;
+            npc.set_max_health(world, max_health);
+            npc.doze(&mut rng);
+            npc.maybe_promote(depth, world, &mut rng);
+            npcs.push(npc);
+        }
+    }
+
+    // Loot worth diving for, plus enough to keep going
+    let mut loot = vec![catalog::collectible(ItemType::Gem), catalog::healing_potion(), Item::gold(5 + depth * 5)];
+    if rng.gen_bool(0.5) {
+        loot.push(catalog::trail_rations());
+    }
+    for item in loot {
+        if let Some((x, y)) = spawn_position(world, npcs, player, 0) {
+            world.items.push(WorldItem::new(x, y, item));
+        }
+    }
+}
+
+/// How a mystery run's floor is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MysteryMap {
+    Open,
+    Rubble, // Walls and pillars everywhere
+    Overgrown, // Grass and fungus choking the corridors
+    Dark, // Only a torch to see by
+}
+
+impl MysteryMap {
+    pub const ALL: [MysteryMap; 4] = [MysteryMap::Open, MysteryMap::Rubble, MysteryMap::Overgrown, MysteryMap::Dark];
+}
+
+/// Mystery: a collection run assembled at random from parts - what to collect, how long there is,
+/// and what the floor looks like. What to collect stays hidden until a guard lets it slip.
+/// Win: Collect the hidden items in time
+/// Lose: Player dies or runs out of turns
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MysteryCondition {
+    pub collection: CollectionCondition, // The hidden part of the goal
+    pub turn_limit: u32,
+    pub map: MysteryMap,
+}
+
+impl MysteryCondition {
+    /// Roll up a fresh mystery
+    pub fn random() -> Self {
+        let mut rng = rand::thread_rng();
+        let mut pool = vec![ItemType::Gem, ItemType::Scroll, ItemType::Potion];
+        let kinds = rng.gen_range(1..=pool.len());
+        let required_items = (0..kinds)
+            .map(|_| (pool.remove(rng.gen_range(0..pool.len())), rng.gen_range(1..=3)))
+            .collect();
+        Self {
+            collection: CollectionCondition::new(required_items),
+            turn_limit: rng.gen_range(15..=30) * 10,
+            map: MysteryMap::ALL[rng.gen_range(0..MysteryMap::ALL.len())],
+        }
+    }
+}
+
+impl GameCondition for MysteryCondition {
+    fn saved(&self) -> SavedCondition {
+        SavedCondition::Mystery(self.clone())
+    }
+
+    fn check_status(&self, game_state: &GameState) -> GameStatus {
+        if !game_state.player.is_alive() {
+            return GameStatus::Lost(LossReason::Died);
+        }
+        if game_state.turn_counter >= self.turn_limit {
+            return GameStatus::Lost(LossReason::OutOfTurns);
+        }
+        self.collection.check_status(game_state)
+    }
+    
+    fn win_description(&self, language: Language) -> String {
+        trf(language, "goal.mystery_win", &[&self.turn_limit])
+    }
+    
+    fn progress(&self, game_state: &GameState) -> String {
+        let goal = if game_state.goal_revealed {
+            self.collection.progress(game_state)
+        } else {
+            tr(game_state.settings.language, "goal.mystery_unknown").to_string()
+        };
+        trf(game_state.settings.language, "goal.mystery_progress", &[&goal, &game_state.turn_counter.min(self.turn_limit), &self.turn_limit])
+    }
+    
+    fn npc_hint(&self, language: Language, npc: &NPC) -> Option<String> {
+        if npc.npc_type != NPCType::Guard {
+            return None;
+        }
+        let wanted = self.collection.required_items.iter()
+            .map(|(item_type, count)| format!("{} {}", count, item_type.get_name().to_lowercase()))
+            .collect::<Vec<_>>()
+            .join(tr(language, "goal.and"));
+        Some(trf(language, "goal.mystery_hint", &[&wanted]))
+    }
+    
+    fn objective_position(&self, game_state: &GameState) -> Option<(i32, i32)> {
+        if game_state.goal_revealed {
+            return self.collection.objective_position(game_state);
+        }
+        game_state.npcs.iter()
+            .find(|npc| npc.npc_type == NPCType::Guard)
+            .map(|npc| npc.position(&game_state.world))
+    }
+    
+    fn on_event(&self, event: &GameEvent, game_state: &GameState) -> Option<String> {
+        if !game_state.goal_revealed {
+            return None; // Nothing to celebrate when the player doesn't know what counts
+        }
+        self.collection.on_event(event, game_state)
+    }
+    
+    fn loss_description(&self, language: Language) -> &str {
+        tr(language, "goal.mystery_loss")
+    }
+    
+    fn victory_message(&self, language: Language) -> &str {
+        tr(language, "goal.mystery_victory")
+    }
+    
+    fn setup_world(&self, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
+        const MIN_SPAWN_DISTANCE: i32 = 6;
+
+        let mut rng = rand::thread_rng();
+        match self.map {
+            MysteryMap::Open => {}
+            MysteryMap::Rubble => {
+                world.add_random_obstacles(rng.gen_range(35..50));
+                world.add_terrain_patches(TileType::Rubble, 6);
+            }
+            MysteryMap::Overgrown => {
+                world.add_grass_patches(12);
+                for _ in 0..6 {
+                    if let Some((x, y)) = spawn_position(world, npcs, player, 0) {
+                        world.tiles[(x as usize, y as usize)] = TileType::Fungus;
+                    }
+                }
+            }
+            MysteryMap::Dark => {
+                world.dark = true;
+                let displaced = player.equip(catalog::torch(self.turn_limit), EquipSlot::OffHand);
+                player.inventory.extend(displaced);
+            }
+        }
+        if let Some(position) = spawn_position(world, npcs, player, 0) {
+            player.position = position;
+        }
+
+        // The guard who knows what the dungeon wants, and the things standing in the way
+        let cast = [
+            (NPCType::Guard, 3),
+            (NPCType::Goblin, MIN_SPAWN_DISTANCE),
+            (NPCType::Orc, MIN_SPAWN_DISTANCE),
+            (NPCType::Orc, MIN_SPAWN_DISTANCE),
+            (NPCType::SkeletonArcher, MIN_SPAWN_DISTANCE),
+        ];
+        for (npc_type, distance) in cast {
+            if let Some((x, y)) = spawn_position(world, npcs, player, distance) {
+                let name = names::generate(&npc_type, &mut rng);
+                npcs.push(NPC::new(world, x, y, npc_type, name));
+            }
+        }
+
+        // Everything needed is on the floor somewhere, alongside a few decoys
+        for (item_type, count) in &self.collection.required_items {
+            for _ in 0..*count {
+                if let Some((x, y)) = spawn_position(world, npcs, player, MIN_SPAWN_DISTANCE) {
+                    world.items.push(WorldItem::new(x, y, catalog::collectible(item_type.clone())));
+                }
+            }
+        }
+        let decoys = [ItemType::Gem, ItemType::Scroll, ItemType::Potion].into_iter()
+            .filter(|item_type| !self.collection.required_items.iter().any(|(required_type, _)| required_type == item_type));
+        for item_type in decoys {
+            if let Some((x, y)) = spawn_position(world, npcs, player, MIN_SPAWN_DISTANCE) {
+                world.items.push(WorldItem::new(x, y, catalog::collectible(item_type)));
+            }
+        }
+        for item in [catalog::short_sword(), catalog::trail_rations()] {
+            if let Some((x, y)) = spawn_position(world, npcs, player, 0) {
+                world.items.push(WorldItem::new(x, y, item));
+            }
+        }
+    }
+}
+
+/// Turns between each arena wave
+fn arena_wave_interval() -> u32 {
+    balance::current().arena_wave_interval.max(1)
+}
+/// Waves to survive to win the arena
+const ARENA_WAVES: u32 = 10;
+
+/// Turns between each wave of reinforcements in survival mode
+fn survival_wave_interval() -> u32 {
+    balance::current().survival_wave_interval.max(1)
+}
+/// Health the player recovers for outlasting each wave
+const SURVIVAL_WAVE_HEALING: i32 = 5;
+
+/// A random open tile at least `min_distance` from the player with nobody standing on it
+fn spawn_position(world: &crate::state::GameWorld, npcs: &[NPC], player: &crate::state::Player, min_distance: i32) -> Option<(i32, i32)> {
+    let mut rng = rand::thread_rng();
+    (0..100)
+        .map(|_| (rng.gen_range(1..world.size.0 as i32 - 1), rng.gen_range(1..world.size.1 as i32 - 1)))
+        .find(|&(x, y)| {
+            world.is_walkable(x, y)
+                && (x - player.position.0).abs().max((y - player.position.1).abs()) >= min_distance
+                && (x, y) != player.position
+                && !npcs.iter().any(|npc| npc.position(world) == (x, y))
+                && !world.items.iter().any(|world_item| world_item.position == (x, y))
+        })
+}
+
+/// Set an altar on open floor next to the town guard, wherever the guard was placed
+fn place_altar_by_guard(world: &mut crate::state::GameWorld, npcs: &[NPC], player: &crate::state::Player) {
+    let Some(guard) = npcs.iter().find(|npc| npc.npc_type == NPCType::Guard) else {
+        return;
+    };
+    let (gx, gy) = guard.position(world);
+    let spot = [(0, 1), (1, 0), (0, -1), (-1, 0), (1, 1), (-1, 1), (1, -1), (-1, -1)].into_iter()
+        .map(|(dx, dy)| (gx + dx, gy + dy))
+        .find(|&(x, y)| {
+            world.get_tile(x, y) == Some(&TileType::Floor)
+                && (x, y) != player.position
+                && !npcs.iter().any(|npc| npc.position(world) == (x, y))
+                && !world.items.iter().any(|world_item| world_item.position == (x, y))
+                && !world.traps.iter().any(|trap| trap.position == (x, y))
+        });
+    if let Some((x, y)) = spot {
+        world.tiles[(x as usize, y as usize)] = TileType::Altar;
+    }
+}
+
+/// Where the stairs down are on a floor, if it has any
+fn stairs_position(world: &crate::state::GameWorld) -> Option<(i32, i32)> {
+    (0..world.size.0 as i32)
+        .flat_map(|x| (0..world.size.1 as i32).map(move |y| (x, y)))
+        .find(|&(x, y)| world.get_tile(x, y) == Some(&TileType::Stairs))
+}
+
+fn treasure_chest() -> Item {
+    Item::new(
+        ItemType::TreasureChest,
+        "Treasure Chest".to_string(),
+        "A mysterious chest that might contain valuable items.".to_string(),
+    )
+}
+
+/// How many items of a type the player is carrying
+fn carried(game_state: &GameState, item_type: &ItemType) -> u32 {
+    game_state.player.inventory.iter().filter(|item| item.item_type == *item_type).count() as u32
+}
+
+/// The position closest to `from`, if there are any
+fn nearest(from: (i32, i32), positions: impl Iterator<Item = (i32, i32)>) -> Option<(i32, i32)> {
+    positions.min_by_key(|&(x, y)| (x - from.0).pow(2) + (y - from.1).pow(2))
 }
 
 /// Survival game condition
 /// Win: Survive for a certain number of turns
 /// Lose: Player dies
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SurvivalCondition {
     pub target_turns: u32,
 }
@@ -97,10 +1105,14 @@ impl SurvivalCondition {
 }
 
 impl GameCondition for SurvivalCondition {
+    fn saved(&self) -> SavedCondition {
+        SavedCondition::Survival(self.clone())
+    }
+
     fn check_status(&self, game_state: &GameState) -> GameStatus {
         // Check loss condition first
         if !game_state.player.is_alive() {
-            return GameStatus::Lost;
+            return GameStatus::Lost(LossReason::Died);
         }
         
         // Check win condition - survived enough turns
@@ -111,29 +1123,91 @@ impl GameCondition for SurvivalCondition {
         GameStatus::Playing
     }
     
-    fn win_description(&self) -> String {
-        format!("Survive for {} turns!", self.target_turns)
+    fn win_description(&self, language: Language) -> String {
+        trf(language, "goal.survival_win", &[&self.target_turns])
+    }
+    
+    fn progress(&self, game_state: &GameState) -> String {
+        trf(game_state.settings.language, "goal.survival_progress", &[&game_state.turn_counter.min(self.target_turns), &self.target_turns])
     }
     
-    fn loss_description(&self) -> &str {
-        "Don't let your health reach zero!"
+    fn turns_remaining(&self, game_state: &GameState) -> Option<u32> {
+        Some(self.target_turns.saturating_sub(game_state.turn_counter))
     }
     
-    fn victory_message(&self) -> &str {
-        "Amazing! You have survived the required number of turns and proven your resilience!"
+    fn on_event(&self, event: &GameEvent, game_state: &GameState) -> Option<String> {
+        let halfway = self.target_turns / 2;
+        let turn_ended = matches!(event, GameEvent::TurnPassed | GameEvent::TurnLost);
+        (turn_ended && halfway > 0 && game_state.turn_counter == halfway)
+            .then(|| trf(game_state.settings.language, "goal.survival_halfway", &[&(self.target_turns - halfway)]))
+    }
+    
+    fn hint(&self, game_state: &GameState) -> String {
+        let turns = self.target_turns.saturating_sub(game_state.turn_counter);
+        let language = game_state.settings.language;
+        let mut hint = trf(language, "goal.survival_hint", &[&turns]);
+        if let Some(necromancer) = self.objective_position(game_state) {
+            hint.push_str(&trf(language, "goal.survival_hint_necromancer", &[&accessibility::whereabouts(game_state.player.position, necromancer)]));
+        }
+        hint
+    }
+    
+    fn on_turn_end(&self, language: Language, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player, turn: u32) -> Vec<String> {
+        const MIN_SPAWN_DISTANCE: i32 = 8;
+
+        let interval = survival_wave_interval();
+        if turn == 0 || !turn.is_multiple_of(interval) || turn >= self.target_turns {
+            return Vec::new();
+        }
+        let mut messages = Vec::new();
+
+        // Another orc joins the hunt somewhere out of sight
+        if let Some((x, y)) = spawn_position(world, npcs, player, MIN_SPAWN_DISTANCE) {
+            let wave = turn / interval;
+            let name = names::generate(&NPCType::Orc, &mut rand::thread_rng());
+            npcs.push(NPC::new(world, x, y, NPCType::Orc, format!("{} (wave {})", name, wave)));
+            messages.push(tr(language, "goal.survival_drums").to_string());
+        }
+
+        // Every wave survived steels the player a little
+        if player.is_alive() && player.health < player.max_health {
+            player.heal(SURVIVAL_WAVE_HEALING);
+            messages.push(trf(language, "goal.survival_heal", &[&SURVIVAL_WAVE_HEALING]));
+        }
+        messages
+    }
+    
+    fn objective_position(&self, game_state: &GameState) -> Option<(i32, i32)> {
+        // Nothing to reach, but the necromancer is worth hunting down
+        game_state.npcs.iter()
+            .find(|npc| npc.npc_type == NPCType::Necromancer)
+            .map(|npc| npc.position(&game_state.world))
+    }
+    
+    fn loss_description(&self, language: Language) -> &str {
+        tr(language, "goal.loss_died")
+    }
+    
+    fn victory_message(&self, language: Language) -> &str {
+        tr(language, "goal.survival_victory")
     }
     
     fn setup_world(&self, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
         let mut rng = rand::thread_rng();
         
-        // Add random obstacles to make the map more interesting
-        let obstacle_count = rng.gen_range(15..30);
-        world.add_random_obstacles(obstacle_count);
+        // The hunt plays out in winding caves rather than the open rooms of the other modes
+        world.generate_caves();
+        world.add_grass_patches(8);
+        world.dark = true;
+
+        // The player starts with a lit torch; spares are scattered around for when it burns out
+        let displaced = player.equip(catalog::torch(120), EquipSlot::OffHand);
+        player.inventory.extend(displaced);
         
         let mut occupied_positions = Vec::new();
         
         // Helper function to find a random valid position
-        let mut find_random_position = || {
+        let mut find_random_position = |world: &crate::state::GameWorld| {
             for _ in 0..100 { // Try up to 100 times to find a valid position
                 let x = rng.gen_range(1..world.size.0 as i32 - 1);
                 let y = rng.gen_range(1..world.size.1 as i32 - 1);
@@ -147,33 +1221,81 @@ impl GameCondition for SurvivalCondition {
         };
         
         // Place player randomly
-        if let Some(pos) = find_random_position() {
+        if let Some(pos) = find_random_position(world) {
             player.position = pos;
         } else {
             player.position = (10, 15); // Fallback position
         }
         
         // Survival mode - 5 aggressive orcs at random positions
-        let orc_names = [
-            "Urg the Destroyer",
-            "Grok the Fierce", 
-            "Morg the Brutal",
-            "Thok the Savage",
-            "Vrak the Terrible"
-        ];
+        for _ in 0..5 {
+            if let Some(pos) = find_random_position(world) {
+                npcs.push(NPC::new(world, pos.0, pos.1, NPCType::Orc, names::generate(&NPCType::Orc, &mut rand::thread_rng())));
+            }
+        }
         
-        for name in orc_names.iter() {
-            if let Some(pos) = find_random_position() {
-                npcs.push(NPC::new(pos.0, pos.1, NPCType::Orc, name.to_string()));
+        // A few hidden paralysis traps make running around carelessly dangerous
+        let mut traps = Vec::new();
+        for trap_type in [TrapType::Paralysis, TrapType::Paralysis, TrapType::Fire, TrapType::Fire, TrapType::Teleport, TrapType::Amnesia] {
+            if let Some(pos) = find_random_position(world) {
+                traps.push(Trap::new(pos.0, pos.1, trap_type));
             }
         }
+        
+        // A shaman supports the orc pack from behind
+        if let Some(pos) = find_random_position(world) {
+            npcs.push(NPC::new(world, pos.0, pos.1, NPCType::Shaman, names::generate(&NPCType::Shaman, &mut rand::thread_rng())));
+        }
+        
+        // A necromancer keeps raising skeletons until the player hunts it down
+        if let Some(pos) = find_random_position(world) {
+            npcs.push(NPC::new(world, pos.0, pos.1, NPCType::Necromancer, names::generate(&NPCType::Necromancer, &mut rand::thread_rng())));
+        }
+        
+        // An oracle who will say how long is left and where the necromancer is, for a fee
+        if let Some(pos) = find_random_position(world) {
+            npcs.push(NPC::new(world, pos.0, pos.1, NPCType::Oracle, names::generate(&NPCType::Oracle, &mut rand::thread_rng())));
+        }
+        
+        // A campfire to fall back to, and some food to cook on it
+        let campfire = find_random_position(world);
+        let meat_positions: Vec<_> = (0..3).filter_map(|_| find_random_position(world)).collect();
+        let torch_positions: Vec<_> = (0..2).filter_map(|_| find_random_position(world)).collect();
+        let amulet_position = find_random_position(world);
+        let checkpoint = find_random_position(world);
+        let scroll_position = find_random_position(world);
+        let ring_position = find_random_position(world);
+        
+        world.traps.extend(traps);
+        if let Some((x, y)) = campfire {
+            world.tiles[(x as usize, y as usize)] = TileType::Campfire;
+            world.add_light((x, y), 3, None);
+        }
+        for (x, y) in torch_positions {
+            world.items.push(WorldItem::new(x, y, catalog::torch(80)));
+        }
+        if let Some((x, y)) = amulet_position {
+            world.items.push(WorldItem::new(x, y, catalog::amulet_of_telepathy()));
+        }
+        if let Some(position) = checkpoint {
+            world.add_checkpoint(position);
+        }
+        if let Some((x, y)) = scroll_position {
+            world.items.push(WorldItem::new(x, y, catalog::blink_scroll()));
+        }
+        if let Some((x, y)) = ring_position {
+            world.items.push(WorldItem::new(x, y, catalog::ring_of_regeneration()));
+        }
+        for (x, y) in meat_positions {
+            world.items.push(WorldItem::new(x, y, catalog::raw_meat()));
+        }
     }
 }
 
 /// Collection game condition
 /// Win: Collect a certain number of items of specific types
 /// Lose: Player dies
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CollectionCondition {
     pub required_items: Vec<(ItemType, u32)>, // (item_type, quantity)
 }
@@ -185,10 +1307,14 @@ impl CollectionCondition {
 }
 
 impl GameCondition for CollectionCondition {
+    fn saved(&self) -> SavedCondition {
+        SavedCondition::Collection(self.clone())
+    }
+
     fn check_status(&self, game_state: &GameState) -> GameStatus {
         // Check loss condition first
         if !game_state.player.is_alive() {
-            return GameStatus::Lost;
+            return GameStatus::Lost(LossReason::Died);
         }
         
         // Check win condition - collected all required items
@@ -205,28 +1331,136 @@ impl GameCondition for CollectionCondition {
         GameStatus::Won
     }
     
-    fn win_description(&self) -> String {
-        "Collect all required items!".to_string()
+    fn win_description(&self, language: Language) -> String {
+        tr(language, "goal.collection_win").to_string()
+    }
+    
+    fn progress(&self, game_state: &GameState) -> String {
+        let language = game_state.settings.language;
+        self.required_items.iter()
+            .map(|(required_type, required_count)| {
+                let collected = game_state.player.inventory.iter().filter(|item| item.item_type == *required_type).count() as u32;
+                trf(language, "goal.collection_progress", &[&required_type.get_name(), &collected.min(*required_count), required_count])
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+    
+    fn on_event(&self, event: &GameEvent, game_state: &GameState) -> Option<String> {
+        let GameEvent::PickedUp(item_type) = event else {
+            return None;
+        };
+        let (_, required_count) = self.required_items.iter().find(|(required_type, _)| required_type == item_type)?;
+        let name = item_type.get_name().to_lowercase();
+        let language = game_state.settings.language;
+        match carried(game_state, item_type) {
+            count if count == *required_count => Some(trf(language, "goal.collection_every", &[&name])),
+            1 => Some(trf(language, "goal.collection_first", &[&name])),
+            _ => None,
+        }
+    }
+    
+    fn hint(&self, game_state: &GameState) -> String {
+        let language = game_state.settings.language;
+        let missing: Vec<String> = self.required_items.iter()
+            .filter_map(|(required_type, required_count)| {
+                let name = required_type.get_name().to_lowercase();
+                match required_count.saturating_sub(carried(game_state, required_type)) {
+                    0 => None,
+                    1 => Some(trf(language, "goal.collection_one", &[&name])),
+                    count => Some(trf(language, "goal.collection_many", &[&count, &name])),
+                }
+            })
+            .collect();
+        if missing.is_empty() {
+            return tr(language, "goal.collection_complete").to_string();
+        }
+        let mut hint = trf(language, "goal.collection_lacking", &[&missing.join(tr(language, "goal.and"))]);
+        if let Some(target) = self.objective_position(game_state) {
+            hint.push_str(&trf(language, "goal.collection_look", &[&accessibility::whereabouts(game_state.player.position, target)]));
+        }
+        hint
     }
     
-    fn loss_description(&self) -> &str {
-        "Don't let your health reach zero!"
+    fn objective_position(&self, game_state: &GameState) -> Option<(i32, i32)> {
+        let still_needed = |item_type: &ItemType| self.required_items.iter().any(|(required_type, required_count)| {
+            required_type == item_type
+                && (game_state.player.inventory.iter().filter(|item| item.item_type == *required_type).count() as u32) < *required_count
+        });
+        let needed_items = game_state.world.items.iter()
+            .filter(|world_item| still_needed(&world_item.item.item_type))
+            .map(|world_item| world_item.position);
+
+        // Head for the nearest item still needed, or follow the merchant until they drop one
+        nearest(game_state.player.position, needed_items).or_else(|| {
+            game_state.npcs.iter()
+                .find(|npc| npc.npc_type == NPCType::Merchant)
+                .map(|npc| npc.position(&game_state.world))
+        })
     }
     
-    fn victory_message(&self) -> &str {
-        "Excellent! You have collected all the required items and completed your quest!"
+    fn loss_description(&self, language: Language) -> &str {
+        tr(language, "goal.loss_died")
     }
     
-    fn setup_world(&self, _world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
+    fn victory_message(&self, language: Language) -> &str {
+        tr(language, "goal.collection_victory")
+    }
+    
+    fn on_turn_end(&self, language: Language, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player, turn: u32) -> Vec<String> {
+        const SUPPLY_GRACE_TURNS: u32 = 50;
+        const MIN_SPAWN_DISTANCE: i32 = 6;
+
+        if turn < SUPPLY_GRACE_TURNS {
+            return Vec::new();
+        }
+        // If the merchant hasn't turned up something still needed, one washes up somewhere so the run can't stall
+        let mut messages = Vec::new();
+        for (item_type, count) in &self.required_items {
+            let collected = player.inventory.iter().filter(|item| item.item_type == *item_type).count() as u32;
+            let on_floor = world.items.iter().any(|world_item| world_item.item.item_type == *item_type);
+            if collected >= *count || on_floor {
+                continue;
+            }
+            if let Some((x, y)) = spawn_position(world, npcs, player, MIN_SPAWN_DISTANCE) {
+                let item = catalog::collectible(item_type.clone());
+                messages.push(trf(language, "goal.collection_clatter", &[&item.display_name(language)]));
+                world.items.push(WorldItem::new(x, y, item));
+            }
+        }
+        messages
+    }
+    
+    fn setup_world(&self, world: &mut crate::state::GameWorld, npcs: &mut Vec<crate::npc::NPC>, player: &mut crate::state::Player) {
         // Collection mode - merchant who provides items plus some other NPCs
-        npcs.push(NPC::new(25, 15, NPCType::Merchant, "The Wandering Merchant".to_string()));
-        npcs.push(NPC::new(5, 5, NPCType::Goblin, "Snitch".to_string()));
-        npcs.push(NPC::new(40, 20, NPCType::Guard, "Tower Guard".to_string()));
-        npcs.push(NPC::new(15, 25, NPCType::Orc, "Grum the Collector".to_string()));
+        let mut rng = rand::thread_rng();
+        for (x, y, npc_type) in [(25, 15, NPCType::Merchant), (5, 5, NPCType::Goblin), (40, 20, NPCType::Guard), (15, 25, NPCType::Orc)] {
+            let name = names::generate(&npc_type, &mut rng);
+            npcs.push(NPC::new(world, x, y, npc_type, name));
+        }
         
         // Set default player position
         player.position = (10, 15);
         
-        // No initial items - the merchant will drop them
+        // The market is held outdoors, under whatever the sky brings
+        world.weather = Some(Weather::at(0));
+        world.add_grass_patches(6);
+        world.tiles[(20, 12)] = TileType::Cauldron;
+        place_altar_by_guard(world, npcs, player);
+        world.tiles[(22, 12)] = TileType::Workbench;
+
+        // About half of each required item is lying around from the start; the merchant drops the rest
+        for (item_type, count) in &self.required_items {
+            for _ in 0..count.div_ceil(2) {
+                if let Some((x, y)) = spawn_position(world, npcs, player, 0) {
+                    world.items.push(WorldItem::new(x, y, catalog::collectible(item_type.clone())));
+                }
+            }
+        }
+
+        // A little spending money for the merchant's wares
+        for (x, y, amount) in [(30, 5, 15), (8, 24, 15)] {
+            world.items.push(WorldItem::new(x, y, Item::gold(amount)));
+        }
     }
 }
\ No newline at end of file
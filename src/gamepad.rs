@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use eframe::egui::{self, Key};
+use gilrs::{Axis, Button, EventType, Gilrs};
+
+use crate::action::{self, Action, PadButton};
+
+/// How far a stick has to be pushed to count as a step
+const STICK_THRESHOLD: f32 = 0.5;
+/// How often to check the controller while nothing else is waking the UI
+const POLL_INTERVAL: Duration = Duration::from_millis(30);
+
+/// Controller input, turned into the same key presses the keyboard makes. Everything bound to
+/// a key through `Action`, plus movement and menu navigation, works from a controller for free.
+pub struct Gamepad {
+    gilrs: Option<Gilrs>, // None if the platform's controller support couldn't start
+    stick: (i32, i32), // Direction the left stick is held in, so each push is one step
+}
+
+impl Gamepad {
+    pub fn new() -> Self {
+        let gilrs = match Gilrs::new() {
+            Ok(gilrs) => Some(gilrs),
+            Err(error) => {
+                tracing::warn!("controller support unavailable: {}", error);
+                None
+            }
+        };
+        Self { gilrs, stick: (0, 0) }
+    }
+
+    /// Add key presses for everything the controller did since the last frame
+    pub fn feed(&mut self, ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        let Some(ref mut gilrs) = self.gilrs else {
+            return;
+        };
+        let mut keys = Vec::new();
+        while let Some(event) = gilrs.next_event() {
+            match event.event {
+                EventType::ButtonPressed(button, _) => keys.extend(key_for_button(button)),
+                EventType::AxisChanged(Axis::LeftStickX, value, _) => {
+                    let direction = stick_direction(value);
+                    if direction != self.stick.0 && direction != 0 {
+                        keys.push(if direction < 0 { Key::ArrowLeft } else { Key::ArrowRight });
+                    }
+                    self.stick.0 = direction;
+                }
+                EventType::AxisChanged(Axis::LeftStickY, value, _) => {
+                    let direction = stick_direction(value);
+                    if direction != self.stick.1 && direction != 0 {
+                        keys.push(if direction > 0 { Key::ArrowUp } else { Key::ArrowDown });
+                    }
+                    self.stick.1 = direction;
+                }
+                _ => {}
+            }
+        }
+        for key in keys {
+            action::press_key(raw_input, key);
+        }
+        if gilrs.gamepads().next().is_some() {
+            ctx.request_repaint_after(POLL_INTERVAL);
+        }
+    }
+}
+
+fn stick_direction(value: f32) -> i32 {
+    if value > STICK_THRESHOLD {
+        1
+    } else if value < -STICK_THRESHOLD {
+        -1
+    } else {
+        0
+    }
+}
+
+/// The key a controller button stands in for
+fn key_for_button(button: Button) -> Option<Key> {
+    let pad_button = match button {
+        Button::DPadUp => return Some(Key::ArrowUp),
+        Button::DPadDown => return Some(Key::ArrowDown),
+        Button::DPadLeft => return Some(Key::ArrowLeft),
+        Button::DPadRight => return Some(Key::ArrowRight),
+        Button::South => return Some(Key::Enter),
+        Button::East => return Some(Key::Escape),
+        Button::West => PadButton::West,
+        Button::North => PadButton::North,
+        Button::LeftTrigger => PadButton::LeftBumper,
+        Button::RightTrigger => PadButton::RightBumper,
+        Button::LeftTrigger2 => PadButton::LeftTrigger,
+        Button::RightTrigger2 => PadButton::RightTrigger,
+        Button::Select => PadButton::Select,
+        Button::Start => PadButton::Start,
+        _ => return None,
+    };
+    Action::ALL.into_iter().find(|action| action.pad_button() == Some(pad_button)).map(|action| action.key())
+}
@@ -0,0 +1,121 @@
+/// Squared straight-line distance between two tiles, for comparing distances without floating point
+pub fn distance_squared(a: (i32, i32), b: (i32, i32)) -> i32 {
+    let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+    dx * dx + dy * dy
+}
+
+/// Whether a tile lies within `radius` of `center`, measured in a straight line
+pub fn within_radius(center: (i32, i32), position: (i32, i32), radius: i32) -> bool {
+    distance_squared(center, position) <= radius * radius
+}
+
+/// Whether a tile is strictly closer than `distance` to another, measured in a straight line
+pub fn closer_than(a: (i32, i32), b: (i32, i32), distance: i32) -> bool {
+    distance_squared(a, b) < distance * distance
+}
+
+/// Steps between two tiles when diagonal steps count as one
+pub fn chebyshev_distance(a: (i32, i32), b: (i32, i32)) -> i32 {
+    (a.0 - b.0).abs().max((a.1 - b.1).abs())
+}
+
+/// Steps between two tiles moving only in the four cardinal directions
+pub fn manhattan_distance(a: (i32, i32), b: (i32, i32)) -> i32 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+/// Whether two different tiles touch, diagonals included
+pub fn is_adjacent(a: (i32, i32), b: (i32, i32)) -> bool {
+    a != b && chebyshev_distance(a, b) <= 1
+}
+
+/// The tiles on a straight line from `from` to `to` by Bresenham's algorithm, leaving out
+/// `from` and ending on `to`
+pub fn line(from: (i32, i32), to: (i32, i32)) -> Line {
+    let dx = (to.0 - from.0).abs();
+    let dy = -(to.1 - from.1).abs();
+    Line {
+        position: from,
+        to,
+        step: ((to.0 - from.0).signum(), (to.1 - from.1).signum()),
+        delta: (dx, dy),
+        error: dx + dy,
+    }
+}
+
+/// Iterator over the tiles of a Bresenham line, made by `line`
+pub struct Line {
+    position: (i32, i32),
+    to: (i32, i32),
+    step: (i32, i32),
+    delta: (i32, i32),
+    error: i32,
+}
+
+impl Iterator for Line {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<(i32, i32)> {
+        if self.position == self.to {
+            return None;
+        }
+        let doubled_error = 2 * self.error;
+        if doubled_error >= self.delta.1 {
+            self.error += self.delta.1;
+            self.position.0 += self.step.0;
+        }
+        if doubled_error <= self.delta.0 {
+            self.error += self.delta.0;
+            self.position.1 += self.step.1;
+        }
+        Some(self.position)
+    }
+}
+
+/// Every tile within `radius` of `center`, the center included
+pub fn circle(center: (i32, i32), radius: i32) -> impl Iterator<Item = (i32, i32)> {
+    (center.0 - radius..=center.0 + radius)
+        .flat_map(move |x| (center.1 - radius..=center.1 + radius).map(move |y| (x, y)))
+        .filter(move |&position| within_radius(center, position, radius))
+}
+
+/// Direction from one tile to another in degrees, counter-clockwise from east with north at 90
+pub fn angle(from: (i32, i32), to: (i32, i32)) -> f32 {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    (-(dy as f32)).atan2(dx as f32).to_degrees()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_leaves_out_the_start_and_ends_on_the_target() {
+        assert_eq!(line((0, 0), (3, 0)).collect::<Vec<_>>(), vec![(1, 0), (2, 0), (3, 0)]);
+        assert_eq!(line((2, 2), (0, 0)).collect::<Vec<_>>(), vec![(1, 1), (0, 0)]);
+        assert_eq!(line((5, 5), (5, 5)).count(), 0);
+    }
+
+    #[test]
+    fn line_steps_one_tile_at_a_time() {
+        for to in [(7, 3), (-4, 9), (-6, -2), (1, -8)] {
+            let mut previous = (0, 0);
+            for position in line((0, 0), to) {
+                assert!(is_adjacent(previous, position));
+                previous = position;
+            }
+            assert_eq!(previous, to);
+            assert_eq!(line((0, 0), to).count() as i32, chebyshev_distance((0, 0), to));
+        }
+    }
+
+    #[test]
+    fn circle_holds_every_tile_within_the_radius() {
+        assert_eq!(circle((3, 3), 0).collect::<Vec<_>>(), vec![(3, 3)]);
+        assert_eq!(circle((0, 0), 1).count(), 5); // The center and its four neighbours; diagonals are too far
+        let tiles: Vec<_> = circle((10, -4), 3).collect();
+        assert_eq!(tiles.len(), 29);
+        assert!(tiles.iter().all(|&tile| within_radius((10, -4), tile, 3)));
+        assert!(tiles.contains(&(13, -4)) && !tiles.contains(&(13, -3)));
+    }
+}
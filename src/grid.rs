@@ -0,0 +1,153 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::ops::{Index, IndexMut};
+use std::sync::Arc;
+
+/// Cells along each side of a chunk
+const CHUNK_SIZE: usize = 16;
+
+/// A 2D grid of per-tile data, indexed by `(x, y)`, stored as square chunks behind `Arc`s.
+/// Cloning it only copies the chunk pointers, so snapshotting a floor (for undo, replays or
+/// AI lookahead) costs next to nothing; a chunk is copied the first time a cell in it changes,
+/// in whichever copy changes it. Nothing takes snapshots yet: this is groundwork for them, and
+/// until then it behaves like the nested `Vec` it replaced.
+#[derive(Debug, Clone)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    chunks_across: usize,
+    chunks: Vec<Arc<Vec<T>>>, // Row by row of chunks, each holding its cells row by row
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        let chunks_across = width.div_ceil(CHUNK_SIZE);
+        let chunks_down = height.div_ceil(CHUNK_SIZE);
+        let chunk = Arc::new(vec![fill; CHUNK_SIZE * CHUNK_SIZE]);
+        Self {
+            width,
+            height,
+            chunks_across,
+            // Every chunk starts out as the same one, copied apart as they're written to
+            chunks: vec![chunk; chunks_across * chunks_down],
+        }
+    }
+
+    /// Which chunk a cell is in, and where in that chunk
+    fn locate(&self, (x, y): (usize, usize)) -> (usize, usize) {
+        assert!(x < self.width && y < self.height, "cell ({}, {}) is outside a {}x{} grid", x, y, self.width, self.height);
+        let chunk = (y / CHUNK_SIZE) * self.chunks_across + x / CHUNK_SIZE;
+        let cell = (y % CHUNK_SIZE) * CHUNK_SIZE + x % CHUNK_SIZE;
+        (chunk, cell)
+    }
+}
+
+impl<T: Clone> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &T {
+        let (chunk, cell) = self.locate(position);
+        &self.chunks[chunk][cell]
+    }
+}
+
+impl<T: Clone> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        let (chunk, cell) = self.locate(position);
+        &mut Arc::make_mut(&mut self.chunks[chunk])[cell]
+    }
+}
+
+/// Written out column by column, the same as a `Vec<Vec<T>>` indexed `[x][y]`
+impl<T: Clone + Serialize> Serialize for Grid<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq((0..self.width).map(|x| (0..self.height).map(|y| &self[(x, y)]).collect::<Vec<_>>()))
+    }
+}
+
+/// Read back from the column-by-column form it's written in
+impl<'de, T: Clone + Deserialize<'de>> Deserialize<'de> for Grid<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let columns = Vec::<Vec<T>>::deserialize(deserializer)?;
+        let height = columns.first().map_or(0, Vec::len);
+        if columns.iter().any(|column| column.len() != height) {
+            return Err(serde::de::Error::custom("grid columns aren't all the same height"));
+        }
+        let Some(fill) = columns.first().and_then(|column| column.first()).cloned() else {
+            return Err(serde::de::Error::custom("a grid needs at least one cell"));
+        };
+        let mut grid = Self::new(columns.len(), height, fill);
+        for (x, column) in columns.into_iter().enumerate() {
+            for (y, cell) in column.into_iter().enumerate() {
+                grid[(x, y)] = cell;
+            }
+        }
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn locate_gives_every_cell_its_own_slot() {
+        for (width, height) in [(16, 16), (50, 30), (1, 1), (17, 33)] {
+            let grid = Grid::new(width, height, 0);
+            let mut slots = HashSet::new();
+            for x in 0..width {
+                for y in 0..height {
+                    let (chunk, cell) = grid.locate((x, y));
+                    assert!(chunk < grid.chunks.len() && cell < CHUNK_SIZE * CHUNK_SIZE);
+                    assert!(slots.insert((chunk, cell)), "({}, {}) shares a slot in a {}x{} grid", x, y, width, height);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn cells_keep_what_is_written_to_them() {
+        let mut grid = Grid::new(50, 30, 0);
+        for x in 0..50 {
+            for y in 0..30 {
+                grid[(x, y)] = x * 100 + y;
+            }
+        }
+        assert_eq!(grid[(0, 0)], 0);
+        assert_eq!(grid[(15, 16)], 1516);
+        assert_eq!(grid[(49, 29)], 4929);
+    }
+
+    #[test]
+    #[should_panic]
+    fn cells_past_the_edge_are_refused() {
+        // 50 wide still has room in its last chunk, but those cells aren't part of the grid
+        let grid = Grid::new(50, 30, 0);
+        let _ = grid[(50, 0)];
+    }
+
+    #[test]
+    fn copies_share_chunks_until_written() {
+        let original = Grid::new(50, 30, 0);
+        let mut copy = original.clone();
+        copy[(20, 5)] = 7;
+        assert_eq!(original[(20, 5)], 0);
+        assert_eq!(copy[(20, 5)], 7);
+        let (changed, _) = copy.locate((20, 5));
+        for (index, (a, b)) in original.chunks.iter().zip(&copy.chunks).enumerate() {
+            assert_eq!(Arc::ptr_eq(a, b), index != changed);
+        }
+    }
+
+    #[test]
+    fn serializes_as_columns_and_reads_back() {
+        let mut grid = Grid::new(3, 2, 0);
+        grid[(2, 1)] = 5;
+        let json = serde_json::to_string(&grid).unwrap();
+        assert_eq!(json, "[[0,0],[0,0],[0,5]]");
+        let read: Grid<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(read[(2, 1)], 5);
+        assert!(serde_json::from_str::<Grid<i32>>("[[0,0],[0]]").is_err());
+        assert!(serde_json::from_str::<Grid<i32>>("[]").is_err());
+    }
+}
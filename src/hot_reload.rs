@@ -0,0 +1,63 @@
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, channel};
+
+use eframe::egui;
+use notify::{RecursiveMode, Watcher};
+
+use crate::balance;
+
+/// Reads a data file and puts what's in it in play
+type Loader = fn(&str) -> Result<(), String>;
+
+/// The data files read at startup that can be loaded again while the game runs, each with
+/// what loads it. Loaders put the new data where the game reads it from, so the floor in
+/// play carries on with it. Items, prefabs and dialogue are still written in the code; a data
+/// file for any of them joins this list.
+const DATA_FILES: [(&str, Loader); 1] = [(balance::BALANCE_PATH, balance::load)];
+
+/// Watches the data files and reloads any that change, so content can be tuned without
+/// restarting and losing the current floor. Only debug builds have one.
+pub struct DataWatcher {
+    _watcher: Option<notify::RecommendedWatcher>, // Kept alive for as long as events are wanted
+    changes: Receiver<PathBuf>, // Paths the watcher has seen change, waiting to be looked at
+}
+
+impl DataWatcher {
+    /// Start watching, waking the UI whenever something changes so the reload shows straight away
+    pub fn new(ctx: &egui::Context) -> Self {
+        let (sender, changes) = channel();
+        let ctx = ctx.clone();
+        let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else { return };
+            if event.kind.is_access() {
+                return;
+            }
+            for path in event.paths {
+                let _ = sender.send(path);
+            }
+            ctx.request_repaint();
+        });
+        // Editors often save by replacing the file, which ends a watch on the file itself, so
+        // watch the folder it's in instead
+        let watcher = watcher.and_then(|mut watcher| {
+            watcher.watch(Path::new("."), RecursiveMode::NonRecursive)?;
+            Ok(watcher)
+        });
+        if let Err(ref error) = watcher {
+            tracing::warn!("Couldn't watch the data files, so they won't reload: {}", error);
+        }
+        Self { _watcher: watcher.ok(), changes }
+    }
+
+    /// Reload every data file that changed since the last look, returning a message for each
+    pub fn poll(&mut self) -> Vec<String> {
+        let changed: Vec<PathBuf> = self.changes.try_iter().collect();
+        DATA_FILES.iter()
+            .filter(|(path, _)| changed.iter().any(|changed| changed.file_name() == Path::new(path).file_name()))
+            .map(|(path, load)| match load(path) {
+                Ok(()) => format!("Reloaded {}.", path),
+                Err(error) => format!("Kept the old data: {}", error),
+            })
+            .collect()
+    }
+}
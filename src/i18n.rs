@@ -0,0 +1,1318 @@
+use serde::{Deserialize, Serialize};
+
+/// Language the interface is shown in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Spanish];
+
+    /// The language's name in that language, so it can be found whatever is currently selected
+    pub fn get_name(&self) -> &str {
+        match self {
+            Language::English => "English",
+            Language::Spanish => "Español",
+        }
+    }
+}
+
+/// Look up a piece of interface text by key. Text missing from a language falls back to
+/// English, and a key missing altogether shows up as itself so it's easy to spot.
+pub fn tr(language: Language, key: &'static str) -> &'static str {
+    let text = match language {
+        Language::English => english(key),
+        Language::Spanish => spanish(key),
+    };
+    text.or_else(|| english(key)).unwrap_or_else(|| {
+        tracing::warn!(key, "missing interface text");
+        key
+    })
+}
+
+/// Look up a template and fill its `{}` placeholders in order
+pub fn trf(language: Language, key: &'static str, args: &[&dyn std::fmt::Display]) -> String {
+    let mut parts = tr(language, key).split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+    for (index, part) in parts.enumerate() {
+        if let Some(arg) = args.get(index) {
+            text.push_str(&arg.to_string());
+        }
+        text.push_str(part);
+    }
+    text
+}
+
+/// A stat or detail as "Label: value", e.g. "Health: 12/20"
+pub fn labeled(language: Language, key: &'static str, value: impl std::fmt::Display) -> String {
+    format!("{}: {}", tr(language, key), value)
+}
+
+/// An item's name in the given language. Items are known by their English label, which doubles
+/// as the key, so an item without a translation keeps its label.
+pub fn item_name(language: Language, label: &str) -> &str {
+    let name = match language {
+        Language::English => None,
+        Language::Spanish => spanish_item(label),
+    };
+    name.unwrap_or(label)
+}
+
+fn english(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "common.yes" => "Yes",
+        "common.no" => "No",
+        "common.ok" => "Ok",
+        "common.cancel" => "Cancel",
+        "common.close" => "Close",
+        "common.done" => "Done",
+        "common.empty" => "Empty",
+
+        "stat.class" => "Class",
+        "stat.level" => "Level",
+        "stat.health" => "Health",
+        "stat.experience" => "Experience",
+        "stat.strength" => "Strength",
+        "stat.dexterity" => "Dexterity",
+        "stat.charisma" => "Charisma",
+        "stat.skill" => "Skill",
+        "stat.hunger" => "Hunger",
+        "stat.gold" => "Gold",
+        "stat.town_standing" => "Town standing",
+        "stat.floor" => "Floor",
+        "stat.respawns" => "Respawns",
+        "stat.no_checkpoint" => "no checkpoint yet",
+        "stat.time" => "Time",
+        "stat.status" => "Status",
+        "stat.speed" => "Speed",
+        "stat.ability" => "Ability",
+        "stat.type" => "Type",
+        "stat.value" => "Value: {} gold",
+        "stat.weight" => "Weight: {} lb",
+        "stat.load" => "Load: {}/{} lb",
+        "stat.encumbered" => "Encumbered: each step takes longer",
+        "stat.turns" => "{} ({} turns)",
+
+        "select.title" => "Select Game Type",
+        "select.class" => "Choose your class:",
+        "select.collapsing" => "Collapsing floors: each floor caves in after a while",
+        "select.hardcore" => "Hardcore: no respawning at checkpoint runes",
+        "select.resume_crash" => "Resume crashed run",
+        "select.resume_checkpoint" => "Resume from last checkpoint",
+        "select.adventure" => "Choose your adventure:",
+        "select.play" => "Play this mode",
+        "scores.title" => "High Scores",
+        "scores.endless_title" => "Deepest Descents",
+        "scores.online_title" => "Online Leaderboard",
+        "scores.none" => "No runs recorded yet.",
+        "scores.online_none" => "No runs on the leaderboard yet.",
+        "scores.retry" => "Retry",
+
+        "stats.title" => "Statistics",
+        "stats.runs" => "Runs played",
+        "stats.wins" => "Runs won",
+        "stats.turns" => "Turns played",
+        "stats.kills" => "Creatures slain",
+        "stats.favorite_item" => "Favorite item",
+        "stats.times_used" => "{} (used {} times)",
+        "stats.wins_by_mode" => "Wins by mode",
+        "stats.kills_by_type" => "Kills by creature",
+
+        "encyclopedia.title" => "Encyclopedia",
+        "encyclopedia.bestiary" => "Bestiary ({}/{})",
+        "encyclopedia.unknown" => "??? (not yet encountered)",
+        "encyclopedia.items" => "Items ({} identified)",
+        "encyclopedia.no_items" => "Items you identify will be recorded here.",
+
+        "quit.title" => "Quit Game",
+        "quit.confirm" => "Are you sure you want to quit?",
+        "respawn.title" => "You Have Fallen",
+        "respawn.prompt" => "The last rune you touched still glows. Respawns left: {}.",
+        "respawn.rise" => "Rise again at the rune",
+        "respawn.give_up" => "Give up",
+        "game_over.title" => "Game Over",
+        "game_over.met_end" => "Your character has met its end!",
+        "game_over.out_of_turns" => "You ran out of turns!",
+        "game_over.out_of_time" => "You ran out of time!",
+        "game_over.item_lost" => "The {} is lost for good, and with it any hope of winning!",
+        "game_over.floor" => "You made it to floor {}.",
+        "victory.title" => "Victory!",
+        "victory.congratulations" => "Congratulations!",
+        "victory.wizard" => "Wizard mode was used, so this run doesn't count for high scores.",
+        "victory.unknown" => "Congratulations, you are surrounded by adoring masses chanting your name and cheering your victory! If only you knew how you won!",
+
+        "use_item.title" => "Use Item",
+        "use_item.prompt" => "Choose an item to use:",
+        "equip.title" => "Equipment",
+        "equip.unequip" => "Unequip",
+        "equip.drop_lit" => "Drop lit",
+        "equip.from_inventory" => "Equip from inventory:",
+        "equip.two_handed" => "(two-handed)",
+        "equip.main_hand" => "Main hand",
+        "equip.off_hand" => "Off hand",
+        "equip.neck" => "Neck",
+        "equip.finger" => "Finger",
+        "mix.title" => "Mix Potions",
+        "mix.prompt" => "Choose two potions to pour into the cauldron:",
+        "drop.title" => "Drop Item",
+        "drop.prompt" => "Choose an item to drop:",
+        "pick_up.title" => "Pick Up",
+        "pick_up.prompt" => "There are several things here:",
+        "pick_up.all" => "Pick up all",
+        "campfire.title" => "Campfire",
+        "campfire.crackles" => "The fire crackles warmly.",
+        "campfire.cook" => "Cook {}",
+        "campfire.rest" => "Rest by the fire",
+        "campfire.leave" => "Leave",
+        "level_up.title" => "Level Up!",
+        "bump.talk" => "Talk",
+        "bump.trade" => "Trade",
+        "bump.step_back" => "Step Back",
+        "bump.pay_fine" => "Pay fine ({} gold)",
+        "bump.consult" => "Consult ({} gold)",
+        "shop.title" => "Unpaid Goods",
+        "shop.demand" => "{} wants {} gold for what you picked up.",
+        "shop.pay" => "Pay {} gold",
+        "shop.put_back" => "Put it back",
+        "shop.refuse" => "Refuse to pay",
+        "altar.title" => "Altar",
+        "altar.prompt" => "Offerings left here are said to smooth over old grudges.",
+        "altar.donate" => "Donate {} gold",
+        "stash.title" => "Your Stash",
+        "stash.prompt" => "Items banked after your last victory wait here. Take what you want for this run.",
+        "stash.take" => "Take {}",
+        "stash.set_off" => "Set off",
+        "stash.bank_title" => "Bank items for your next run ({}/{} in the stash)",
+        "stash.bank" => "Bank {}",
+        "workbench.title" => "Workbench",
+        "workbench.prompt" => "Choose gear with an open socket, then a gem to set into it. Set gems can't be removed.",
+        "workbench.no_gear" => "You carry nothing with an open socket.",
+        "workbench.no_gems" => "You have no gems to set.",
+        "workbench.open_sockets" => "{} ({} open)",
+        "workbench.set" => "Set {} ({})",
+        "trade.your_gold" => "Your gold: {}",
+        "trade.for_sale" => "For sale",
+        "trade.sold_out" => "Sold out",
+        "trade.your_items" => "Your items",
+        "trade.sell" => "Sell ({} gold)",
+        "trade.buy" => "Buy ({} gold)",
+        "trade.haggle" => "Haggle",
+        "trade.haggle_hint" => "Takes a turn. Charisma and goodwill help. Fail and prices go up, and the town thinks less of you.",
+        "trade.haggled" => "Haggled: {}% off",
+        "options.title" => "Options",
+        "options.language" => "Language",
+        "options.auto_pickup" => "Auto-pickup",
+        "options.auto_pickup_hint" => "Walking over these is enough to pick them up:",
+        "options.touch" => "Touch controls",
+        "options.touch_hint" => "On-screen buttons, tap to walk, long-press to examine",
+        "options.screen_reader" => "Screen reader",
+        "options.screen_reader_hint" => "Announce events and new sightings as text",
+        "options.display" => "Map display",
+        "options.comfort" => "Motion and flashes",
+        "options.reduced_motion" => "Reduce motion (no screen shake or animations)",
+        "options.no_flashes" => "No flashes (damage and blasts)",
+        "options.power" => "Power saving",
+        "options.power_hint" => "Only redraw on input (countdowns still tick), for laptop play",
+        "options.diagnostics" => "Diagnostic log",
+        "options.diagnostics_hint" => "Written to the logs folder, for bug reports:",
+        "examine.unidentified" => "Unidentified. You can't recall what these do until you use one.",
+        "character.title" => "Character Sheet",
+        "character.skills" => "Skills",
+        "character.none_yet" => "None yet",
+
+        "world.title" => "World View",
+        "world.paused" => "PAUSED",
+        "panel.player_stats" => "Player Stats",
+        "panel.equipment" => "Equipment",
+        "panel.message_log" => "Message Log",
+        "panel.controls" => "Controls",
+        "panel.touch_controls" => "Touch Controls",
+        "panel.location" => "Location Details",
+        "controls.move" => "Arrow Keys / WASD: Move",
+        "controls.campfire" => "Walk into a campfire to cook or rest",
+        "controls.examine" => "Click an item to examine it, or a creature to pin its details",
+        "controls.menus" => "In menus: arrow keys and Enter, or 1-9",
+        "controls.touch" => "Tap the map to walk there, hold to examine",
+        "controls.more" => "More controls coming...",
+        "location.nothing" => "Nothing of interest here.",
+        "location.examine" => "Examine",
+
+        "stat.known_abilities" => "Known abilities:",
+        "stat.effect" => "Effect",
+        "stat.position" => "Position",
+        "stat.kill_streak" => "Kill streak: {} (x{})",
+        "stat.best_streak" => "Best streak",
+        "stat.turn_weather" => "Turn: {} ({}, {})",
+        "stat.turn_time" => "Turn: {} ({})",
+
+        "mode.treasure_hunt" => "Find and collect the treasure while avoiding dangers.",
+        "mode.survival" => "Survive for 200 turns in the dark. Keep your torch lit and slay the necromancer to stop the undead.",
+        "mode.collection" => "Collect 3 gems, 2 scrolls, and 1 potion.",
+        "mode.pacifist" => "The treasure hunt, but you may not deal a single point of damage. Sneak, dodge and flee.",
+        "mode.speedrun" => "The treasure hunt against a real-time clock: 5 minutes. Space pauses.",
+        "mode.arena" => "Hold out in a small pit against 10 waves of ever tougher monsters.",
+        "mode.endless" => "Take the stairs down, floor after floor, as deep as you dare. Scored by floors cleared plus treasure carried.",
+        "mode.mystery" => "A randomly assembled run: unknown items to collect, a turn limit and a surprise map. Talk to the guard to learn the goal.",
+
+        "scores.score" => "Score",
+        "scores.mode" => "Mode",
+        "scores.class" => "Class",
+        "scores.floor" => "Floor",
+        "scores.result" => "Result",
+        "scores.turns" => "Turns",
+        "scores.time" => "Time",
+        "scores.won" => "Won",
+        "scores.lost" => "Lost",
+        "scores.share" => "Share my runs on the online leaderboard",
+        "scores.sent_to" => "Finished runs will be sent to {}",
+        "scores.offline" => "{} - your scores are still kept locally.",
+
+        "targeting.blink" => "Blink: pick a destination with the arrow keys or mouse. Enter to confirm, Esc to cancel.",
+        "targeting.ability" => "{}: pick a target with the arrow keys or mouse. Enter to confirm, Esc to cancel.",
+        "mix.mix" => "Mix",
+        "pick_up.selected" => "Pick up",
+        "level_up.prompt" => "You have reached level {}. Choose a reward:",
+        "bump.attack" => "Attack",
+        "bump.attack_hint" => "Your reputation with the town will suffer",
+        "trade.title" => "Trading with {}",
+        "character.heading" => "Level {} {}",
+        "character.ability" => "Ability ({}): {}",
+        "character.cooldown" => "Cooldown: {} turns",
+        "ability.ready" => "{} (ready)",
+        "ability.cooling_down" => "{} ({} turns)",
+
+        "equip.bare_hands" => "Bare hands",
+        "equip.two_handed_grip" => "(two-handed grip)",
+        "equip.torch_fuel" => "Torch fuel: {} turns",
+        "item.damage" => "Damage: {}-{} ({})",
+        "item.one_handed" => "one-handed",
+        "item.two_handed" => "two-handed",
+        "item.light" => "light",
+        "item.block_chance" => "Block chance: {}%",
+        "item.light_radius" => "Light radius",
+        "item.telepathy" => "Telepathy while worn",
+        "item.passive" => "Passive",
+        "item.capacity" => "Capacity: +{} slots",
+        "item.sockets" => "Sockets",
+        "item.empty_socket" => "empty",
+        "item.socketed" => "Socketed",
+        "item.charges" => "Charges: {} turns of fuel",
+        "item.gold" => "{} Gold",
+        "item.unidentified" => "Unidentified {}",
+        "item.stolen" => "{} (stolen)",
+
+        "world.resume" => "Press {} to resume",
+        "world.wizard" => "WIZARD MODE - invincible, map revealed, not scored",
+        "world.time_left" => "TIME LEFT {}",
+        "world.turns_to_survive" => "{} TURNS TO SURVIVE",
+        "world.collapse_soon" => "The floor collapses in {} turns",
+        "world.collapsing" => "THE FLOOR IS COLLAPSING - get to the stairs!",
+        "world.goal" => "GOAL",
+        "world.distance" => "  {} {} tiles",
+        "world.size" => "World Size",
+        "world.player_position" => "Player Position",
+        "world.mouse_over" => "Mouse Over",
+        "world.path" => "Path",
+        "world.path_steps" => "{} steps (~{} turns)",
+        "world.no_route" => "no known route",
+        "world.camera_follows" => "Camera: following you ({} to unlock)",
+        "world.camera_free" => "Camera: free - scroll to look around ({} to recenter, {} to lock)",
+        "panel.inventory" => "Inventory ({}/{})",
+        "panel.click_to_examine" => "Click to examine",
+        "panel.unpin" => "Unpin",
+
+        "npc.hostile" => "Hostile",
+        "npc.peaceful" => "Peaceful",
+        "npc.asleep" => "asleep",
+        "npc.alert" => "alert",
+        "npc.searching" => "searching",
+        "npc.disposition" => "Disposition: {}, {}",
+        "npc.sensed" => "Sensed through telepathy",
+        "npc.out_of_sight" => "Out of sight",
+        "location.blind" => "You are blind and can't make anything out.",
+        "location.too_dark" => "It's too dark to see anything there.",
+        "location.player" => "Player (@) is here",
+        "location.unseen" => "Something unseen (?) - You can't make out what it is",
+        "location.elite" => "Elite",
+        "location.hungry" => "Looks hungry - it might go for food on the floor",
+        "location.cart" => "{}'s cart (¤) - Loaded with {} wares for sale",
+        "location.forgotten" => "You can't remember what this does.",
+        "location.price" => "Price: {} gold",
+        "location.terrain" => "Terrain: {} ({})",
+        "location.burning" => "The ground here is on fire!",
+        "location.firelight" => "Firelight flickers here.",
+
+        "tile.wall" => "Solid stone wall",
+        "tile.floor" => "Stone floor",
+        "tile.door" => "Wooden door",
+        "tile.stairs" => "Stone stairs",
+        "tile.empty" => "Empty space",
+        "tile.grass" => "Dry grass",
+        "tile.cauldron" => "An alchemist's cauldron",
+        "tile.campfire" => "A crackling campfire",
+        "tile.altar" => "A quiet altar",
+        "tile.workbench" => "A jeweller's workbench",
+        "tile.fungus" => "A cluster of swollen fungus",
+        "tile.water" => "Deep water",
+        "tile.lava" => "Molten lava",
+        "tile.rubble" => "Loose rubble, slow going",
+        "tile.ice" => "Slick ice",
+        "tile.checkpoint" => "A glowing checkpoint rune",
+
+        "refuse.cant_equip" => "The {} can't be equipped.",
+        "refuse.wrong_slot" => "The {} doesn't go there.",
+        "refuse.dual_wield_rogue" => "Only a rogue can fight with a weapon in each hand.",
+        "refuse.too_heavy_off_hand" => "The {} is too heavy for your off hand.",
+        "refuse.dual_wield_dagger" => "You need a dagger in your main hand to dual-wield.",
+        "refuse.needs_both_hands" => "Your {} needs both hands.",
+        "refuse.not_carried" => "You don't have that.",
+        "refuse.pack_overflow" => "Your other things won't fit without the {}.",
+        "refuse.mix_two_potions" => "You need two different potions to mix.",
+        "refuse.no_cauldron" => "You need a cauldron to mix potions.",
+        "refuse.no_campfire_to_cook" => "You need a campfire to cook over.",
+        "refuse.no_campfire_to_rest" => "There's no campfire here to rest by.",
+        "refuse.not_in_stash" => "That isn't in your stash.",
+        "refuse.pack_full" => "Your pack is full.",
+        "refuse.no_workbench" => "You need a workbench to set gems.",
+        "refuse.no_open_socket" => "That has no open socket.",
+        "refuse.not_a_gem" => "That can't be set into a socket.",
+        "refuse.npc_in_the_way" => "{} is in your way.",
+        "refuse.cart_in_the_way" => "{}'s cart is in the way.",
+        "refuse.item_gone" => "That isn't here any more.",
+        "refuse.cant_blink_there" => "You can't blink there.",
+        "refuse.cant_use_ability_there" => "You can't use {} there.",
+        "refuse.too_far" => "That's too far away.",
+        "refuse.wont_deal_with_thief" => "{} won't deal with a thief.",
+        "refuse.no_fine" => "{} has no fine to collect.",
+        "refuse.cant_afford_fine" => "You can't afford the {} gold fine.",
+        "refuse.nobody_there" => "There's nobody there.",
+        "refuse.no_visions" => "{} has no visions to sell.",
+        "refuse.oracle_hostile" => "{} won't look into the smoke for you.",
+        "refuse.cant_afford_gold" => "You can't afford the {} gold.",
+        "refuse.no_payment_due" => "Nobody is asking you for payment.",
+        "refuse.no_altar" => "There's no altar here.",
+        "refuse.cant_afford_offering" => "You can't afford that offering.",
+        "refuse.nothing_to_atone" => "You have nothing to atone for.",
+        "refuse.reward_not_offered" => "That reward isn't on offer.",
+        "refuse.ability_not_ready" => "{} isn't ready yet ({} turns).",
+        "refuse.bash_needs_shield" => "You need a shield in your off hand to bash with.",
+
+        "log.welcome" => "Welcome to the dungeon!",
+        "log.welcome_controls" => "Press arrow keys to move.",
+        "log.welcome_explore" => "Explore carefully...",
+        "log.wizard_on" => "Wizard mode on: you are invincible, know every floor and can go down from anywhere.",
+        "log.wizard_off" => "Wizard mode off.",
+        "log.no_floor_below" => "There is no floor below this one.",
+        "log.bestiary_entry" => "New bestiary entry: {}.",
+        "log.amnesia" => "Your mind goes blank - you can't remember where you've been!",
+        "log.forget_item_type" => "You no longer recognize your {}s.",
+        "log.remember_item_type" => "You remember what {}s do now.",
+        "log.torch_out" => "Your {} sputters and goes out.",
+        "log.torch_low" => "Your torch is burning low.",
+        "log.drop_lit_torch" => "You drop the {}. It keeps burning on the floor.",
+        "log.spot_trap" => "You spot a {}!",
+        "log.search_found" => "You search carefully and find a {}!",
+        "log.search_nothing" => "You search but find nothing.",
+        "log.level_up_ready" => "You feel more experienced! Choose how to grow stronger.",
+        "log.level_up" => "You reach level {}: {}.",
+        "log.getting_hungry" => "You are getting hungry.",
+        "log.starving" => "You are starving!",
+        "log.item_burns" => "The {} burns up!",
+        "log.burned" => "You are burned for {} damage!",
+        "log.npc_burns_to_death" => "{} burns to death!",
+        "log.status_expired" => "You are no longer {}.",
+        "log.unable_to_act" => "You are unable to act!",
+        "log.levitation_sinking" => "You are sinking towards the surface below - get to solid ground!",
+        "log.levitation_fading" => "You feel your levitation wearing off.",
+        "log.torch_doused" => "Your torch hisses out in the water.",
+        "log.fall_into_water" => "You splash down into the water and struggle to the shore.",
+        "log.fall_into_lava" => "You drop into the lava and scramble out, horribly burned!",
+        "log.cant_move" => "Can't move there!",
+        "log.slide" => "You slide across the ice!",
+        "log.moved" => "Moved to ({}, {})",
+        "log.checkpoint" => "The rune flares beneath your feet. Your progress is saved.",
+        "log.respawn" => "You rise again on the rune. Respawns left: {}.",
+        "log.collapse_warning" => "Dust trickles from the ceiling. This floor won't hold much longer - find the stairs!",
+        "log.collapse" => "With a roar, the edge of the floor caves in!",
+        "log.npc_crushed" => "{} is crushed under the falling rock!",
+        "log.crushed" => "Falling rock batters you for {} damage!",
+        "log.descend" => "You descend the stairs to floor {}.",
+        "log.trap_paralysis" => "A hidden needle jabs your foot - you are paralyzed!",
+        "log.trap_fire" => "A gout of flame erupts beneath you!",
+        "log.trap_blinding_dart" => "A dart stings your neck and your vision goes dark - you are blinded!",
+        "log.trap_amnesia" => "A faded glyph flashes beneath your feet.",
+        "log.trap_teleport" => "The rune flares and the world lurches - you find yourself somewhere else!",
+        "log.shield_bash" => "You slam {} with your shield, stunning them!",
+        "log.knocked_onto_ice" => "{} is knocked back and skids across the ice!",
+        "log.knocked_into_rubble" => "{} is knocked sprawling into the rubble!",
+        "log.knocked_back" => "{} is knocked back.",
+        "log.nowhere_to_escape" => "There's nowhere to escape to!",
+        "log.smoke_bomb" => "You hurl down a smoke bomb and slip away in the haze!",
+        "log.firebolt" => "You hurl a bolt of fire!",
+        "log.firebolt_hit" => "The firebolt strikes {}!",
+        "log.caught_in_blast" => "You are caught in the {} for {} damage!",
+        "log.blast_hits" => "The {} engulfs {}!",
+        "log.item_shatters" => "The {} shatters!",
+        "log.fungus" => "The fungus bursts into a cloud of spores - you are blinded!",
+        "log.goblin_tweaks_nose" => "Goblin cackles and tweaks your nose",
+        "log.pay_fine" => "You pay {} a fine of {} gold. \"Mind yourself from now on.\"",
+        "log.oracle" => "You pay {} {} gold. Peering into the smoke, they murmur: \"{}\"",
+        "log.socket_gem" => "You set the {} into the {}: {}.",
+        "log.donation_unneeded" => "The town holds nothing against you; you keep your gold.",
+        "log.donation_too_small" => "{} gold is too little to be noticed; you keep it.",
+        "log.donation" => "You leave {} gold at the altar. The town's mood softens towards you.",
+        "log.donation_change" => "That is all it takes; you keep the other {}.",
+        "log.npc_hint" => "{} leans in: \"{}\"",
+        "log.goal_revealed" => "Your goal has been revealed!",
+        "log.npc_says" => "{} says: \"{}\"",
+        "log.faction_turns_hostile" => "The {} will remember this!",
+        "log.cant_afford" => "You can't afford that.",
+        "log.buy" => "You buy the {} for {} gold.",
+        "log.sell" => "You sell the {} for {} gold.",
+        "log.merchant_not_interested" => "The merchant isn't interested in that.",
+        "log.haggle_limit" => "{} won't go any lower.",
+        "log.haggle_success" => "{} grumbles, but knocks {}% off.",
+        "log.haggle_failure" => "{} takes offence and raises their prices!",
+        "log.ambush" => "You catch {} asleep!",
+        "log.drain" => "You drain {} health from {}.",
+        "log.slay" => "You slay {}!",
+        "log.elite_drops" => "{} drops {}.",
+        "log.npc_drops" => "{} drops the {}.",
+        "log.equip" => "You ready the {}.",
+        "log.unequip" => "You put away the {}.",
+        "log.nothing_to_pick_up" => "There is nothing here to pick up.",
+        "log.drop" => "You drop the {}.",
+        "log.stow_full" => "Your pack is full - you set the {} down.",
+        "log.take_from_stash" => "You take the {} from your stash.",
+        "log.pick_up_gold" => "You pick up {} gold.",
+        "log.pick_up_full" => "Your pack is full - you can't carry the {}. Drop something first.",
+        "log.pick_up" => "You picked up {}.",
+        "log.shop_demand" => "{} calls out: \"That comes to {} gold, friend.\"",
+        "log.pay_shopkeeper" => "You pay {} {} gold. \"Pleasure doing business.\"",
+        "log.return_goods" => "You put the goods back.",
+        "log.refuse_to_pay" => "You refuse to pay.",
+        "log.thief" => "{} shouts: \"Thief! Guards, guards!\"",
+        "log.guard_arrives" => "{} comes running.",
+        "log.open_chest" => "When the key clicks in the lock the treasure chest spills open, dropping a pile of treasure on the ground",
+        "log.key_needs_chest" => "You need a treasure chest to use {}.",
+        "log.drink_status" => "You drink the {}. You are now {}.",
+        "log.drink_heal" => "You drink the {}. You feel better.",
+        "log.read" => "You read the {}.",
+        "log.read_status" => "You read the {}. You are now {}.",
+        "log.read_blink" => "You read the {} and blink across the room.",
+        "log.blink_needs_target" => "The {} needs a destination.",
+        "log.eat" => "You eat the {}. You are {}.",
+        "log.cant_use" => "You don't know how to use {}.",
+        "log.brew" => "You stir the {} and the {} together and brew a {}.",
+        "log.brew_explodes" => "The {} and the {} react violently - the cauldron explodes!",
+        "log.rest" => "You rest for a moment.",
+        "log.cook" => "You cook the {} into {}.",
+        "log.campfire_rest" => "You settle down by the fire.",
+        "log.rest_interrupted" => "Your rest is interrupted!",
+        "log.ambushed" => "Something stirs in the darkness - you are ambushed!",
+        "log.npc_falls" => "{} falls to {}.",
+        "log.npc_status_expired" => "{} is no longer {}.",
+        "log.npc_slides" => "{} slides across the ice.",
+        "log.npc_teleported" => "{} steps on a rune and vanishes in a flash!",
+        "log.npc_eats" => "{} wolfs down the {}.",
+        "log.npc_wakes" => "{} wakes up!",
+        "log.npc_stirs" => "{} stirs and wakes!",
+        "log.npc_loads_cart" => "{} loads the {} onto his cart.",
+        "log.cart_drop" => "The merchant dropped a {} from his cart!",
+        "log.npc_curses" => "{} points a bony finger at you - your limbs grow heavy!",
+        "log.npc_raises_skeleton" => "{} raises a skeleton from the ground!",
+        "log.npc_heals" => "{} chants, and {}'s wounds close.",
+        "log.npc_hastes" => "{} howls, and {} speeds up!",
+        "log.npc_stuns" => "{}'s blow leaves you reeling!",
+        "log.something_unseen" => "Something unseen",
+
+        "blast.flames" => "flames",
+        "blast.explosion" => "explosion",
+
+        "talk.merchant_robbed" => "You've got some nerve coming back here, thief.",
+        "talk.attacked" => "I haven't forgotten what you did.",
+        "talk.merchant_wary" => "Coin first, then we talk.",
+        "talk.merchant" => "Finest wares this side of the necropolis! Have a look.",
+        "talk.guard_wary" => "I've got my eye on you.",
+        "talk.guard" => "Stay out of trouble down here, traveller.",
+        "talk.oracle" => "Cross my palm with gold and I will tell you what the smoke shows.",
+        "talk.nothing" => "...",
+
+        "hunger.starving" => "Starving",
+        "hunger.hungry" => "Hungry",
+        "hunger.satiated" => "Satiated",
+        "hunger.fed" => "Fed",
+
+        "goal.yes" => "yes",
+        "goal.no" => "no",
+        "goal.and" => " and ",
+        "goal.loss_died" => "Don't let your health reach zero!",
+        "goal.hint_lies" => "What you seek lies {}.",
+        "goal.hint_turns" => "{} turns remain.",
+        "goal.hint_seconds" => "The sands run out in {} seconds.",
+        "goal.hint_nothing" => "The smoke shows nothing you don't already know.",
+        "goal.hunt_win" => "Find the treasure on floor {}, and take the key from its keeper!",
+        "goal.hunt_progress" => "Key: {}, Chest: {}, Treasure: {}",
+        "goal.hunt_key" => "You have a key! Now for the chest.",
+        "goal.hunt_chest" => "The treasure chest is yours! Now to get it open.",
+        "goal.hunt_victory" => "Congratulations! You have found the treasure and escaped the dungeon!",
+        "goal.pacifist_win" => "Collect the treasure without hurting a soul!",
+        "goal.pacifist_progress" => "{}, Damage dealt: {}",
+        "goal.pacifist_warning" => "RUN INVALIDATED - you have dealt {} damage and can no longer win",
+        "goal.pacifist_loss" => "Don't let your health reach zero, and don't harm anyone!",
+        "goal.pacifist_victory" => "Remarkable! You slipped past every creature in the dungeon and took the treasure without spilling a drop of blood.",
+        "goal.speedrun_win" => "Grab the treasure within {}!",
+        "goal.speedrun_loss" => "Don't run out of time, or health!",
+        "goal.speedrun_victory" => "Blistering! You snatched the treasure with time to spare.",
+        "goal.arena_win" => "Survive {} waves in the arena!",
+        "goal.arena_final_wave" => "Final wave, foes left: {}",
+        "goal.arena_progress" => "Wave {}/{}, next in {} turns, foes left: {}",
+        "goal.arena_loss" => "Don't fall in the arena!",
+        "goal.arena_victory" => "The crowd roars! You have outlasted every wave the arena could throw at you.",
+        "goal.arena_potion" => "Someone in the crowd tosses a potion into the arena.",
+        "goal.arena_wave" => "The gates grind open - wave {} of {}!",
+        "goal.endless_win" => "Descend as deep as you can!",
+        "goal.endless_progress" => "Floor {}, Treasure: {} gold",
+        "goal.endless_loss" => "There is no way out but down. Your run ends when you die.",
+        "goal.endless_victory" => "There is no bottom to this dungeon.",
+        "goal.mystery_win" => "Collect... something, within {} turns. The guard may know what.",
+        "goal.mystery_unknown" => "Goal: ???",
+        "goal.mystery_progress" => "{}, Turn {}/{}",
+        "goal.mystery_hint" => "They say whoever brings back {} walks out of here free.",
+        "goal.mystery_loss" => "Don't let your health reach zero, and don't dawdle!",
+        "goal.mystery_victory" => "Mystery solved! You worked out what the dungeon wanted and brought it back in time.",
+        "goal.survival_win" => "Survive for {} turns!",
+        "goal.survival_progress" => "Turns survived: {}/{}",
+        "goal.survival_halfway" => "Halfway there! {} turns to go.",
+        "goal.survival_hint" => "Hold out {} more turns and the hunt is over.",
+        "goal.survival_hint_necromancer" => " The one raising the dead is {}.",
+        "goal.survival_drums" => "War drums echo through the dark. Another orc has joined the hunt!",
+        "goal.survival_heal" => "Surviving another wave steadies you (+{} health).",
+        "goal.survival_victory" => "Amazing! You have survived the required number of turns and proven your resilience!",
+        "goal.collection_win" => "Collect all required items!",
+        "goal.collection_progress" => "{}s {}/{}",
+        "goal.collection_every" => "That's every {} you need!",
+        "goal.collection_first" => "Your first {}!",
+        "goal.collection_one" => "1 {}",
+        "goal.collection_many" => "{} {}s",
+        "goal.collection_complete" => "You have everything you came for.",
+        "goal.collection_lacking" => "You still lack {}.",
+        "goal.collection_look" => " Look {}.",
+        "goal.collection_victory" => "Excellent! You have collected all the required items and completed your quest!",
+        "goal.collection_clatter" => "You hear something clatter to the floor. Perhaps a {}?",
+
+        "creature.goblin.description" => "A mischievous goblin",
+        "creature.orc.description" => "A fierce orc warrior",
+        "creature.skeleton.description" => "Ancient bones animated by dark magic",
+        "creature.skeleton_archer.description" => "A skeleton clutching a bow of bone",
+        "creature.necromancer.description" => "A robed necromancer who raises the dead",
+        "creature.shaman.description" => "An orc shaman who mends and spurs on its allies",
+        "creature.stalker.description" => "A shimmering stalker, invisible to the naked eye",
+        "creature.bat.description" => "A squeaking cave bat",
+        "creature.ghost.description" => "A restless spirit of the dungeon's dead",
+        "creature.merchant.description" => "A traveling merchant",
+        "creature.guard.description" => "A stalwart guard",
+        "creature.oracle.description" => "A blind seer wreathed in incense smoke",
+        "creature.ability.dodges_nimbly" => "Dodges nimbly",
+        "creature.ability.hits_hard" => "Hits hard in melee",
+        "creature.ability.lies_dormant" => "Lies dormant until disturbed",
+        "creature.ability.ignores_hazards" => "Walks straight through fire and traps",
+        "creature.ability.shoots_arrows" => "Shoots arrows from a distance",
+        "creature.ability.keeps_bow_range" => "Backs away to keep you at bow range",
+        "creature.ability.raises_skeletons" => "Raises skeletons from the ground",
+        "creature.ability.curses_slowness" => "Curses you with slowness",
+        "creature.ability.keeps_its_distance" => "Keeps its distance",
+        "creature.ability.heals_wounded_allies" => "Heals wounded allies",
+        "creature.ability.hastes_its_allies" => "Hastes its allies",
+        "creature.ability.invisible" => "Invisible without magical sight",
+        "creature.ability.flies" => "Flies over water and lava",
+        "creature.ability.drifts_through_walls" => "Drifts through walls",
+        "creature.ability.floats" => "Floats over water and lava",
+        "creature.ability.trades" => "Buys and sells goods",
+        "creature.ability.flees_if_attacked" => "Flees if attacked",
+        "creature.ability.tower_shield" => "Blocks with a tower shield",
+        "creature.ability.defends_town" => "Hunts down anyone who attacks the town",
+        "creature.ability.reads_smoke" => "Reads the smoke for a hint about your goal, for a fee",
+        "creature.ability.stays_seated" => "Never leaves their seat",
+        "creature.goblin.lore" => "Goblins breed in the upper halls and fight over everything, each other included. Alone they are cowards; in a pack, a nuisance.",
+        "creature.orc.lore" => "Orcs came down from the hills for the dungeon's iron and stayed for the fighting.",
+        "creature.skeleton.lore" => "The old lords' soldiers, still standing their last watch. Most have been asleep for centuries, and wake in a foul mood.",
+        "creature.skeleton_archer.lore" => "Bowmen of the same dead garrison. Their aim has not suffered for the lack of eyes.",
+        "creature.necromancer.lore" => "Scholars who learned that the dead make patient servants, and forgot what the living are for.",
+        "creature.shaman.lore" => "Orc elders who speak with the spirits of the deep. The spirits mostly tell them to hit things.",
+        "creature.stalker.lore" => "Nobody has seen one and lived to describe it. The scratches they leave on the walls are enough.",
+        "creature.bat.lore" => "They roost in their thousands above the flooded halls. One alone is a nuisance; the cloud is worse.",
+        "creature.ghost.lore" => "Not everyone who died down here has noticed. The walls they walk through were built after their time.",
+        "creature.merchant.lore" => "Where there's treasure there's someone selling shovels. Merchants follow adventurers down and buy what they drag back up.",
+        "creature.guard.lore" => "The town pays them to keep the peace below ground. They take that very seriously.",
+        "creature.oracle.lore" => "Nobody knows how long the Oracle has sat in the same smoky corner. The smoke knows where everything in the dungeon is, and the Oracle will tell you, for a price.",
+
+        "attack.player_dodged" => "{} dodges your attack!",
+        "attack.player_blocked" => "{} blocks your attack!",
+        "attack.player_hit" => "You hit {} for {} damage!",
+        "attack.player_critical" => "Critical hit! You hit {} for {} damage!",
+        "attack.npc_dodged" => "{} dodges {}'s attack.",
+        "attack.npc_blocked" => "{} blocks {}'s attack.",
+        "attack.npc_hit" => "{} hits {} for {} damage.",
+        "attack.npc_critical" => "{} lands a critical hit on {} for {} damage!",
+        "attack.on_player_dodged" => "You dodge as {} {} you!",
+        "attack.on_player_blocked" => "You block {}'s attack with your shield!",
+        "attack.on_player_hit" => "{} {} you for {} damage!",
+        "attack.on_player_critical" => "Critical hit! {} {} you for {} damage!",
+        "attack.verb_attacks" => "attacks",
+        "attack.verb_lashes_out" => "lashes out at",
+        "attack.verb_bone_shard" => "fires a bone shard at",
+        _ => return None,
+    })
+}
+
+fn spanish(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "common.yes" => "Sí",
+        "common.no" => "No",
+        "common.ok" => "Aceptar",
+        "common.cancel" => "Cancelar",
+        "common.close" => "Cerrar",
+        "common.done" => "Hecho",
+        "common.empty" => "Vacío",
+
+        "stat.class" => "Clase",
+        "stat.level" => "Nivel",
+        "stat.health" => "Salud",
+        "stat.experience" => "Experiencia",
+        "stat.strength" => "Fuerza",
+        "stat.dexterity" => "Destreza",
+        "stat.charisma" => "Carisma",
+        "stat.skill" => "Habilidad",
+        "stat.hunger" => "Hambre",
+        "stat.gold" => "Oro",
+        "stat.town_standing" => "Reputación en el pueblo",
+        "stat.floor" => "Piso",
+        "stat.respawns" => "Reapariciones",
+        "stat.no_checkpoint" => "aún sin punto de control",
+        "stat.time" => "Tiempo",
+        "stat.status" => "Estado",
+        "stat.speed" => "Velocidad",
+        "stat.ability" => "Habilidad de clase",
+        "stat.type" => "Tipo",
+        "stat.value" => "Valor: {} de oro",
+        "stat.weight" => "Peso: {} lb",
+        "stat.load" => "Carga: {}/{} lb",
+        "stat.encumbered" => "Sobrecargado: cada paso tarda más",
+        "stat.turns" => "{} ({} turnos)",
+
+        "select.title" => "Elige el modo de juego",
+        "select.class" => "Elige tu clase:",
+        "select.collapsing" => "Suelos que se derrumban: cada piso se hunde al cabo de un rato",
+        "select.hardcore" => "Extremo: sin reaparecer en las runas de control",
+        "select.resume_crash" => "Reanudar la partida interrumpida",
+        "select.resume_checkpoint" => "Reanudar desde el último punto de control",
+        "select.adventure" => "Elige tu aventura:",
+        "select.play" => "Jugar este modo",
+        "scores.title" => "Mejores puntuaciones",
+        "scores.endless_title" => "Descensos más profundos",
+        "scores.online_title" => "Clasificación en línea",
+        "scores.none" => "Aún no hay partidas registradas.",
+        "scores.online_none" => "Aún no hay partidas en la clasificación.",
+        "scores.retry" => "Reintentar",
+
+        "stats.title" => "Estadísticas",
+        "stats.runs" => "Partidas jugadas",
+        "stats.wins" => "Partidas ganadas",
+        "stats.turns" => "Turnos jugados",
+        "stats.kills" => "Criaturas abatidas",
+        "stats.favorite_item" => "Objeto favorito",
+        "stats.times_used" => "{} (usado {} veces)",
+        "stats.wins_by_mode" => "Victorias por modo",
+        "stats.kills_by_type" => "Bajas por criatura",
+
+        "encyclopedia.title" => "Enciclopedia",
+        "encyclopedia.bestiary" => "Bestiario ({}/{})",
+        "encyclopedia.unknown" => "??? (aún no encontrado)",
+        "encyclopedia.items" => "Objetos ({} identificados)",
+        "encyclopedia.no_items" => "Los objetos que identifiques quedarán registrados aquí.",
+
+        "quit.title" => "Salir del juego",
+        "quit.confirm" => "¿Seguro que quieres salir?",
+        "respawn.title" => "Has caído",
+        "respawn.prompt" => "La última runa que tocaste aún brilla. Reapariciones restantes: {}.",
+        "respawn.rise" => "Alzarse de nuevo en la runa",
+        "respawn.give_up" => "Rendirse",
+        "game_over.title" => "Fin de la partida",
+        "game_over.met_end" => "¡Tu personaje ha encontrado su final!",
+        "game_over.out_of_turns" => "¡Te quedaste sin turnos!",
+        "game_over.out_of_time" => "¡Se te acabó el tiempo!",
+        "game_over.item_lost" => "¡El objeto ({}) se ha perdido para siempre, y con él toda esperanza de ganar!",
+        "game_over.floor" => "Llegaste hasta el piso {}.",
+        "victory.title" => "¡Victoria!",
+        "victory.congratulations" => "¡Enhorabuena!",
+        "victory.wizard" => "Se usó el modo mago, así que esta partida no cuenta para las puntuaciones.",
+        "victory.unknown" => "¡Enhorabuena! Te rodean multitudes que corean tu nombre y celebran tu victoria. ¡Si al menos supieras cómo has ganado!",
+
+        "use_item.title" => "Usar objeto",
+        "use_item.prompt" => "Elige un objeto para usar:",
+        "equip.title" => "Equipo",
+        "equip.unequip" => "Quitar",
+        "equip.drop_lit" => "Soltar encendida",
+        "equip.from_inventory" => "Equipar desde el inventario:",
+        "equip.two_handed" => "(a dos manos)",
+        "equip.main_hand" => "Mano principal",
+        "equip.off_hand" => "Mano secundaria",
+        "equip.neck" => "Cuello",
+        "equip.finger" => "Dedo",
+        "mix.title" => "Mezclar pociones",
+        "mix.prompt" => "Elige dos pociones para verter en el caldero:",
+        "drop.title" => "Soltar objeto",
+        "drop.prompt" => "Elige un objeto para soltar:",
+        "pick_up.title" => "Recoger",
+        "pick_up.prompt" => "Aquí hay varias cosas:",
+        "pick_up.all" => "Recoger todo",
+        "campfire.title" => "Hoguera",
+        "campfire.crackles" => "El fuego crepita con calidez.",
+        "campfire.cook" => "Cocinar {}",
+        "campfire.rest" => "Descansar junto al fuego",
+        "campfire.leave" => "Marcharse",
+        "level_up.title" => "¡Subes de nivel!",
+        "bump.talk" => "Hablar",
+        "bump.trade" => "Comerciar",
+        "bump.step_back" => "Retroceder",
+        "bump.pay_fine" => "Pagar la multa ({} de oro)",
+        "bump.consult" => "Consultar ({} de oro)",
+        "shop.title" => "Mercancía sin pagar",
+        "shop.demand" => "{} quiere {} de oro por lo que has cogido.",
+        "shop.pay" => "Pagar {} de oro",
+        "shop.put_back" => "Devolverlo",
+        "shop.refuse" => "Negarse a pagar",
+        "altar.title" => "Altar",
+        "altar.prompt" => "Dicen que las ofrendas dejadas aquí suavizan viejos rencores.",
+        "altar.donate" => "Donar {} de oro",
+        "stash.title" => "Tu alijo",
+        "stash.prompt" => "Aquí esperan los objetos guardados tras tu última victoria. Llévate lo que quieras para esta partida.",
+        "stash.take" => "Coger {}",
+        "stash.set_off" => "Partir",
+        "stash.bank_title" => "Guarda objetos para tu próxima partida ({}/{} en el alijo)",
+        "stash.bank" => "Guardar {}",
+        "workbench.title" => "Banco de trabajo",
+        "workbench.prompt" => "Elige un equipo con un engaste libre y luego una gema para engastar. Las gemas engastadas no se pueden quitar.",
+        "workbench.no_gear" => "No llevas nada con un engaste libre.",
+        "workbench.no_gems" => "No tienes gemas que engastar.",
+        "workbench.open_sockets" => "{} ({} libres)",
+        "workbench.set" => "Engastar {} ({})",
+        "trade.your_gold" => "Tu oro: {}",
+        "trade.for_sale" => "En venta",
+        "trade.sold_out" => "Agotado",
+        "trade.your_items" => "Tus objetos",
+        "trade.sell" => "Vender ({} de oro)",
+        "trade.buy" => "Comprar ({} de oro)",
+        "trade.haggle" => "Regatear",
+        "trade.haggle_hint" => "Gasta un turno. El carisma y la buena voluntad ayudan. Si fallas, los precios suben y el pueblo te aprecia menos.",
+        "trade.haggled" => "Regateado: {}% de descuento",
+        "options.title" => "Opciones",
+        "options.language" => "Idioma",
+        "options.auto_pickup" => "Recogida automática",
+        "options.auto_pickup_hint" => "Basta con pasar por encima de estos para recogerlos:",
+        "options.touch" => "Controles táctiles",
+        "options.touch_hint" => "Botones en pantalla, toca para caminar, mantén pulsado para examinar",
+        "options.screen_reader" => "Lector de pantalla",
+        "options.screen_reader_hint" => "Anunciar sucesos y criaturas a la vista como texto",
+        "options.display" => "Visualización del mapa",
+        "options.comfort" => "Movimiento y destellos",
+        "options.reduced_motion" => "Reducir movimiento (sin sacudidas ni animaciones)",
+        "options.no_flashes" => "Sin destellos (daño y explosiones)",
+        "options.power" => "Ahorro de energía",
+        "options.power_hint" => "Redibujar solo al pulsar (las cuentas atrás siguen), para portátiles",
+        "options.diagnostics" => "Registro de diagnóstico",
+        "options.diagnostics_hint" => "Se escribe en la carpeta logs, para informar de errores:",
+        "examine.unidentified" => "Sin identificar. No recuerdas para qué sirven hasta que uses uno.",
+        "character.title" => "Hoja de personaje",
+        "character.skills" => "Habilidades",
+        "character.none_yet" => "Ninguna todavía",
+
+        "world.title" => "Vista del mundo",
+        "world.paused" => "EN PAUSA",
+        "panel.player_stats" => "Estadísticas del jugador",
+        "panel.equipment" => "Equipo",
+        "panel.message_log" => "Registro de mensajes",
+        "panel.controls" => "Controles",
+        "panel.touch_controls" => "Controles táctiles",
+        "panel.location" => "Detalles del lugar",
+        "controls.move" => "Flechas / WASD: Moverse",
+        "controls.campfire" => "Camina hacia una hoguera para cocinar o descansar",
+        "controls.examine" => "Haz clic en un objeto para examinarlo, o en una criatura para fijar sus detalles",
+        "controls.menus" => "En los menús: flechas y Enter, o 1-9",
+        "controls.touch" => "Toca el mapa para caminar, mantén pulsado para examinar",
+        "controls.more" => "Más controles próximamente...",
+        "location.nothing" => "Aquí no hay nada de interés.",
+        "location.examine" => "Examinar",
+
+        "stat.known_abilities" => "Habilidades conocidas:",
+        "stat.effect" => "Efecto",
+        "stat.position" => "Posición",
+        "stat.kill_streak" => "Racha de muertes: {} (x{})",
+        "stat.best_streak" => "Mejor racha",
+        "stat.turn_weather" => "Turno: {} ({}, {})",
+        "stat.turn_time" => "Turno: {} ({})",
+
+        "mode.treasure_hunt" => "Encuentra y recoge el tesoro evitando los peligros.",
+        "mode.survival" => "Sobrevive 200 turnos en la oscuridad. Mantén la antorcha encendida y mata al nigromante para detener a los no muertos.",
+        "mode.collection" => "Reúne 3 gemas, 2 pergaminos y 1 poción.",
+        "mode.pacifist" => "La búsqueda del tesoro, pero sin causar ni un punto de daño. Escóndete, esquiva y huye.",
+        "mode.speedrun" => "La búsqueda del tesoro contra un reloj en tiempo real: 5 minutos. Espacio pausa.",
+        "mode.arena" => "Resiste en un pequeño foso contra 10 oleadas de monstruos cada vez más duros.",
+        "mode.endless" => "Baja las escaleras, piso tras piso, tan hondo como te atrevas. Puntúan los pisos superados y el tesoro que lleves.",
+        "mode.mystery" => "Una partida montada al azar: objetos desconocidos que reunir, un límite de turnos y un mapa sorpresa. Habla con el guardia para saber el objetivo.",
+
+        "scores.score" => "Puntos",
+        "scores.mode" => "Modo",
+        "scores.class" => "Clase",
+        "scores.floor" => "Piso",
+        "scores.result" => "Resultado",
+        "scores.turns" => "Turnos",
+        "scores.time" => "Tiempo",
+        "scores.won" => "Victoria",
+        "scores.lost" => "Derrota",
+        "scores.share" => "Compartir mis partidas en la clasificación en línea",
+        "scores.sent_to" => "Las partidas terminadas se enviarán a {}",
+        "scores.offline" => "{}: tus puntuaciones se siguen guardando en local.",
+
+        "targeting.blink" => "Parpadeo: elige un destino con las flechas o el ratón. Intro para confirmar, Esc para cancelar.",
+        "targeting.ability" => "{}: elige un objetivo con las flechas o el ratón. Intro para confirmar, Esc para cancelar.",
+        "mix.mix" => "Mezclar",
+        "pick_up.selected" => "Recoger",
+        "level_up.prompt" => "Has alcanzado el nivel {}. Elige una recompensa:",
+        "bump.attack" => "Atacar",
+        "bump.attack_hint" => "Tu reputación en el pueblo se resentirá",
+        "trade.title" => "Comerciando con {}",
+        "character.heading" => "Nivel {}, {}",
+        "character.ability" => "Habilidad ({}): {}",
+        "character.cooldown" => "Recarga: {} turnos",
+        "ability.ready" => "{} (lista)",
+        "ability.cooling_down" => "{} ({} turnos)",
+
+        "equip.bare_hands" => "Manos desnudas",
+        "equip.two_handed_grip" => "(empuñadura a dos manos)",
+        "equip.torch_fuel" => "Combustible de la antorcha: {} turnos",
+        "item.damage" => "Daño: {}-{} ({})",
+        "item.one_handed" => "a una mano",
+        "item.two_handed" => "a dos manos",
+        "item.light" => "ligera",
+        "item.block_chance" => "Probabilidad de bloqueo: {}%",
+        "item.light_radius" => "Radio de luz",
+        "item.telepathy" => "Telepatía mientras se lleva puesto",
+        "item.passive" => "Pasiva",
+        "item.capacity" => "Capacidad: +{} espacios",
+        "item.sockets" => "Engastes",
+        "item.empty_socket" => "vacío",
+        "item.socketed" => "Engastado",
+        "item.charges" => "Cargas: {} turnos de combustible",
+        "item.gold" => "{} de oro",
+        "item.unidentified" => "{} sin identificar",
+        "item.stolen" => "{} (robado)",
+
+        "world.resume" => "Pulsa {} para continuar",
+        "world.wizard" => "MODO MAGO - invencible, mapa revelado, sin puntuación",
+        "world.time_left" => "TIEMPO RESTANTE {}",
+        "world.turns_to_survive" => "{} TURNOS PARA SOBREVIVIR",
+        "world.collapse_soon" => "El suelo se derrumba en {} turnos",
+        "world.collapsing" => "¡EL SUELO SE DERRUMBA! ¡Corre a las escaleras!",
+        "world.goal" => "OBJETIVO",
+        "world.distance" => "  {} {} casillas",
+        "world.size" => "Tamaño del mundo",
+        "world.player_position" => "Posición del jugador",
+        "world.mouse_over" => "Bajo el ratón",
+        "world.path" => "Ruta",
+        "world.path_steps" => "{} pasos (~{} turnos)",
+        "world.no_route" => "ningún camino conocido",
+        "world.camera_follows" => "Cámara: te sigue ({} para soltarla)",
+        "world.camera_free" => "Cámara: libre - desplázate para mirar ({} para centrar, {} para fijar)",
+        "panel.inventory" => "Inventario ({}/{})",
+        "panel.click_to_examine" => "Haz clic para examinar",
+        "panel.unpin" => "Desfijar",
+
+        "npc.hostile" => "Hostil",
+        "npc.peaceful" => "Pacífico",
+        "npc.asleep" => "dormido",
+        "npc.alert" => "alerta",
+        "npc.searching" => "buscando",
+        "npc.disposition" => "Actitud: {}, {}",
+        "npc.sensed" => "Percibido por telepatía",
+        "npc.out_of_sight" => "Fuera de la vista",
+        "location.blind" => "Estás ciego y no distingues nada.",
+        "location.too_dark" => "Está demasiado oscuro para ver nada ahí.",
+        "location.player" => "El jugador (@) está aquí",
+        "location.unseen" => "Algo invisible (?) - No distingues qué es",
+        "location.elite" => "Élite",
+        "location.hungry" => "Parece hambriento: podría ir a por comida del suelo",
+        "location.cart" => "Carro de {} (¤) - Cargado con {} artículos a la venta",
+        "location.forgotten" => "No recuerdas qué hace esto.",
+        "location.price" => "Precio: {} de oro",
+        "location.terrain" => "Terreno: {} ({})",
+        "location.burning" => "¡El suelo aquí está ardiendo!",
+        "location.firelight" => "Aquí parpadea la luz del fuego.",
+
+        "tile.wall" => "Muro de piedra maciza",
+        "tile.floor" => "Suelo de piedra",
+        "tile.door" => "Puerta de madera",
+        "tile.stairs" => "Escaleras de piedra",
+        "tile.empty" => "Espacio vacío",
+        "tile.grass" => "Hierba seca",
+        "tile.cauldron" => "Un caldero de alquimista",
+        "tile.campfire" => "Una hoguera crepitante",
+        "tile.altar" => "Un altar silencioso",
+        "tile.workbench" => "Un banco de joyero",
+        "tile.fungus" => "Un racimo de hongos hinchados",
+        "tile.water" => "Agua profunda",
+        "tile.lava" => "Lava fundida",
+        "tile.rubble" => "Escombros sueltos, se avanza despacio",
+        "tile.ice" => "Hielo resbaladizo",
+        "tile.checkpoint" => "Una runa de control resplandeciente",
+
+        "refuse.cant_equip" => "No puedes equiparte {}.",
+        "refuse.wrong_slot" => "{} no va ahí.",
+        "refuse.dual_wield_rogue" => "Solo un pícaro puede luchar con un arma en cada mano.",
+        "refuse.too_heavy_off_hand" => "{} pesa demasiado para tu mano secundaria.",
+        "refuse.dual_wield_dagger" => "Necesitas una daga en la mano principal para luchar con dos armas.",
+        "refuse.needs_both_hands" => "{} necesita las dos manos.",
+        "refuse.not_carried" => "No tienes eso.",
+        "refuse.pack_overflow" => "El resto de tus cosas no cabrá sin {}.",
+        "refuse.mix_two_potions" => "Necesitas dos pociones distintas para mezclar.",
+        "refuse.no_cauldron" => "Necesitas un caldero para mezclar pociones.",
+        "refuse.no_campfire_to_cook" => "Necesitas una hoguera para cocinar.",
+        "refuse.no_campfire_to_rest" => "Aquí no hay ninguna hoguera junto a la que descansar.",
+        "refuse.not_in_stash" => "Eso no está en tu alijo.",
+        "refuse.pack_full" => "Tu mochila está llena.",
+        "refuse.no_workbench" => "Necesitas un banco de trabajo para engastar gemas.",
+        "refuse.no_open_socket" => "Eso no tiene ningún engarce libre.",
+        "refuse.not_a_gem" => "Eso no se puede engastar.",
+        "refuse.npc_in_the_way" => "{} te cierra el paso.",
+        "refuse.cart_in_the_way" => "El carro de {} te cierra el paso.",
+        "refuse.item_gone" => "Eso ya no está aquí.",
+        "refuse.cant_blink_there" => "No puedes parpadear hasta ahí.",
+        "refuse.cant_use_ability_there" => "No puedes usar {} ahí.",
+        "refuse.too_far" => "Eso está demasiado lejos.",
+        "refuse.wont_deal_with_thief" => "{} no hace tratos con ladrones.",
+        "refuse.no_fine" => "{} no tiene ninguna multa que cobrar.",
+        "refuse.cant_afford_fine" => "No puedes pagar la multa de {} de oro.",
+        "refuse.nobody_there" => "Ahí no hay nadie.",
+        "refuse.no_visions" => "{} no tiene visiones que vender.",
+        "refuse.oracle_hostile" => "{} no mirará el humo por ti.",
+        "refuse.cant_afford_gold" => "No puedes pagar los {} de oro.",
+        "refuse.no_payment_due" => "Nadie te está pidiendo que pagues.",
+        "refuse.no_altar" => "Aquí no hay ningún altar.",
+        "refuse.cant_afford_offering" => "No puedes permitirte esa ofrenda.",
+        "refuse.nothing_to_atone" => "No tienes nada que expiar.",
+        "refuse.reward_not_offered" => "Esa recompensa no está entre las opciones.",
+        "refuse.ability_not_ready" => "{} aún no está listo ({} turnos).",
+        "refuse.bash_needs_shield" => "Necesitas un escudo en la mano secundaria para golpear con él.",
+
+        "log.welcome" => "¡Bienvenido a la mazmorra!",
+        "log.welcome_controls" => "Pulsa las flechas para moverte.",
+        "log.welcome_explore" => "Explora con cuidado...",
+        "log.wizard_on" => "Modo mago activado: eres invencible, conoces cada piso y puedes bajar desde cualquier lugar.",
+        "log.wizard_off" => "Modo mago desactivado.",
+        "log.no_floor_below" => "No hay ningún piso por debajo de este.",
+        "log.bestiary_entry" => "Nueva entrada en el bestiario: {}.",
+        "log.amnesia" => "Tu mente se queda en blanco: ¡no recuerdas dónde has estado!",
+        "log.forget_item_type" => "Ya no reconoces tus objetos de tipo {}.",
+        "log.remember_item_type" => "Ahora recuerdas para qué sirven los objetos de tipo {}.",
+        "log.torch_out" => "{} chisporrotea y se apaga.",
+        "log.torch_low" => "Tu antorcha se está consumiendo.",
+        "log.drop_lit_torch" => "Sueltas {}. Sigue ardiendo en el suelo.",
+        "log.spot_trap" => "¡Descubres una trampa: {}!",
+        "log.search_found" => "¡Buscas con cuidado y encuentras una trampa: {}!",
+        "log.search_nothing" => "Buscas, pero no encuentras nada.",
+        "log.level_up_ready" => "¡Te sientes más experimentado! Elige cómo hacerte más fuerte.",
+        "log.level_up" => "Alcanzas el nivel {}: {}.",
+        "log.getting_hungry" => "Empiezas a tener hambre.",
+        "log.starving" => "¡Te mueres de hambre!",
+        "log.item_burns" => "¡{} arde hasta consumirse!",
+        "log.burned" => "¡Te quemas y sufres {} de daño!",
+        "log.npc_burns_to_death" => "¡{} muere abrasado!",
+        "log.status_expired" => "Ya no estás {}.",
+        "log.unable_to_act" => "¡No puedes actuar!",
+        "log.levitation_sinking" => "Estás descendiendo hacia la superficie de abajo: ¡busca terreno firme!",
+        "log.levitation_fading" => "Notas que tu levitación se desvanece.",
+        "log.torch_doused" => "Tu antorcha se apaga con un siseo en el agua.",
+        "log.fall_into_water" => "Caes al agua de un chapuzón y llegas a duras penas a la orilla.",
+        "log.fall_into_lava" => "¡Caes en la lava y sales a rastras, horriblemente quemado!",
+        "log.cant_move" => "¡No puedes moverte ahí!",
+        "log.slide" => "¡Resbalas por el hielo!",
+        "log.moved" => "Te mueves a ({}, {})",
+        "log.checkpoint" => "La runa destella bajo tus pies. Tu progreso queda guardado.",
+        "log.respawn" => "Te alzas de nuevo sobre la runa. Reapariciones restantes: {}.",
+        "log.collapse_warning" => "Cae polvo del techo. Este piso no aguantará mucho más: ¡encuentra las escaleras!",
+        "log.collapse" => "¡Con un estruendo, el borde del piso se derrumba!",
+        "log.npc_crushed" => "¡{} queda aplastado bajo las rocas!",
+        "log.crushed" => "¡Las rocas te golpean y sufres {} de daño!",
+        "log.descend" => "Bajas las escaleras hasta el piso {}.",
+        "log.trap_paralysis" => "Una aguja oculta se te clava en el pie: ¡quedas paralizado!",
+        "log.trap_fire" => "¡Una llamarada brota bajo tus pies!",
+        "log.trap_blinding_dart" => "Un dardo te pica en el cuello y se te nubla la vista: ¡quedas cegado!",
+        "log.trap_amnesia" => "Un glifo desvaído destella bajo tus pies.",
+        "log.trap_teleport" => "La runa destella y el mundo da un vuelco: ¡apareces en otro lugar!",
+        "log.shield_bash" => "¡Golpeas a {} con tu escudo y lo aturdes!",
+        "log.knocked_onto_ice" => "¡{} sale despedido y resbala por el hielo!",
+        "log.knocked_into_rubble" => "¡{} sale despedido y cae de bruces entre los escombros!",
+        "log.knocked_back" => "{} sale despedido hacia atrás.",
+        "log.nowhere_to_escape" => "¡No hay adónde escapar!",
+        "log.smoke_bomb" => "¡Arrojas una bomba de humo y te escabulles entre la niebla!",
+        "log.firebolt" => "¡Lanzas un rayo de fuego!",
+        "log.firebolt_hit" => "¡El rayo de fuego alcanza a {}!",
+        "log.caught_in_blast" => "¡Quedas atrapado en {} y sufres {} de daño!",
+        "log.blast_hits" => "¡Alcanzado por {}: {}!",
+        "log.item_shatters" => "¡{} se hace añicos!",
+        "log.fungus" => "El hongo revienta en una nube de esporas: ¡quedas cegado!",
+        "log.goblin_tweaks_nose" => "El goblin se ríe y te pellizca la nariz",
+        "log.pay_fine" => "Pagas a {} una multa de {} de oro. \"Cuidadito a partir de ahora.\"",
+        "log.oracle" => "Pagas a {} {} de oro. Escrutando el humo, murmura: \"{}\"",
+        "log.socket_gem" => "Engastas {} en {}: {}.",
+        "log.donation_unneeded" => "El pueblo no te guarda rencor; te quedas con tu oro.",
+        "log.donation_too_small" => "{} de oro es demasiado poco para que se note; te lo quedas.",
+        "log.donation" => "Dejas {} de oro en el altar. El pueblo se ablanda contigo.",
+        "log.donation_change" => "Con eso basta; te quedas con los otros {}.",
+        "log.npc_hint" => "{} se inclina hacia ti: \"{}\"",
+        "log.goal_revealed" => "¡Se ha revelado tu objetivo!",
+        "log.npc_says" => "{} dice: \"{}\"",
+        "log.faction_turns_hostile" => "¡Esto no lo olvidará: {}!",
+        "log.cant_afford" => "No te lo puedes permitir.",
+        "log.buy" => "Compras {} por {} de oro.",
+        "log.sell" => "Vendes {} por {} de oro.",
+        "log.merchant_not_interested" => "Al mercader no le interesa eso.",
+        "log.haggle_limit" => "{} no bajará más el precio.",
+        "log.haggle_success" => "{} refunfuña, pero rebaja un {}%.",
+        "log.haggle_failure" => "¡{} se ofende y sube sus precios!",
+        "log.ambush" => "¡Pillas a {} dormido!",
+        "log.drain" => "Absorbes {} de salud de {}.",
+        "log.slay" => "¡Matas a {}!",
+        "log.elite_drops" => "{} suelta {}.",
+        "log.npc_drops" => "{} suelta {}.",
+        "log.equip" => "Preparas {}.",
+        "log.unequip" => "Guardas {}.",
+        "log.nothing_to_pick_up" => "Aquí no hay nada que recoger.",
+        "log.drop" => "Sueltas {}.",
+        "log.stow_full" => "Tu mochila está llena: dejas {} en el suelo.",
+        "log.take_from_stash" => "Sacas {} de tu alijo.",
+        "log.pick_up_gold" => "Recoges {} de oro.",
+        "log.pick_up_full" => "Tu mochila está llena: no puedes llevar {}. Suelta algo primero.",
+        "log.pick_up" => "Has recogido {}.",
+        "log.shop_demand" => "{} te llama: \"Son {} de oro, amigo.\"",
+        "log.pay_shopkeeper" => "Pagas a {} {} de oro. \"Un placer hacer negocios.\"",
+        "log.return_goods" => "Devuelves la mercancía.",
+        "log.refuse_to_pay" => "Te niegas a pagar.",
+        "log.thief" => "{} grita: \"¡Al ladrón! ¡Guardias, guardias!\"",
+        "log.guard_arrives" => "{} llega corriendo.",
+        "log.open_chest" => "Al girar la llave en la cerradura, el cofre se abre de golpe y derrama un montón de tesoro por el suelo",
+        "log.key_needs_chest" => "Necesitas un cofre del tesoro para usar {}.",
+        "log.drink_status" => "Bebes {}. Ahora estás {}.",
+        "log.drink_heal" => "Bebes {}. Te sientes mejor.",
+        "log.read" => "Lees {}.",
+        "log.read_status" => "Lees {}. Ahora estás {}.",
+        "log.read_blink" => "Lees {} y parpadeas al otro lado de la sala.",
+        "log.blink_needs_target" => "{} necesita un destino.",
+        "log.eat" => "Comes {}. Estás {}.",
+        "log.cant_use" => "No sabes cómo usar {}.",
+        "log.brew" => "Remueves {} y {} juntos y preparas {}.",
+        "log.brew_explodes" => "{} y {} reaccionan con violencia: ¡el caldero explota!",
+        "log.rest" => "Descansas un momento.",
+        "log.cook" => "Cocinas {} y obtienes {}.",
+        "log.campfire_rest" => "Te acomodas junto al fuego.",
+        "log.rest_interrupted" => "¡Tu descanso se interrumpe!",
+        "log.ambushed" => "Algo se mueve en la oscuridad: ¡te tienden una emboscada!",
+        "log.npc_falls" => "{} cae ante {}.",
+        "log.npc_status_expired" => "{} ya no está {}.",
+        "log.npc_slides" => "{} resbala por el hielo.",
+        "log.npc_teleported" => "¡{} pisa una runa y desaparece en un destello!",
+        "log.npc_eats" => "{} engulle: {}.",
+        "log.npc_wakes" => "¡{} se despierta!",
+        "log.npc_stirs" => "¡{} se revuelve y se despierta!",
+        "log.npc_loads_cart" => "{} carga en su carro: {}.",
+        "log.cart_drop" => "¡Al mercader se le ha caído algo del carro: {}!",
+        "log.npc_curses" => "{} te señala con un dedo huesudo: ¡tus miembros se vuelven pesados!",
+        "log.npc_raises_skeleton" => "¡{} alza un esqueleto del suelo!",
+        "log.npc_heals" => "{} entona un cántico y las heridas de {} se cierran.",
+        "log.npc_hastes" => "¡{} aúlla y {} se acelera!",
+        "log.npc_stuns" => "¡El golpe de {} te deja aturdido!",
+        "log.something_unseen" => "Algo invisible",
+
+        "blast.flames" => "las llamas",
+        "blast.explosion" => "la explosión",
+
+        "talk.merchant_robbed" => "Hay que tener valor para volver aquí, ladrón.",
+        "talk.attacked" => "No he olvidado lo que hiciste.",
+        "talk.merchant_wary" => "Primero las monedas, luego hablamos.",
+        "talk.merchant" => "¡La mejor mercancía a este lado de la necrópolis! Echa un vistazo.",
+        "talk.guard_wary" => "Te tengo echado el ojo.",
+        "talk.guard" => "No te metas en líos aquí abajo, viajero.",
+        "talk.oracle" => "Cruza mi palma con oro y te diré lo que muestra el humo.",
+        "talk.nothing" => "...",
+
+        "hunger.starving" => "Famélico",
+        "hunger.hungry" => "Hambriento",
+        "hunger.satiated" => "Saciado",
+        "hunger.fed" => "Alimentado",
+
+        "goal.yes" => "sí",
+        "goal.no" => "no",
+        "goal.and" => " y ",
+        "goal.loss_died" => "¡No dejes que tu salud llegue a cero!",
+        "goal.hint_lies" => "Lo que buscas está {}.",
+        "goal.hint_turns" => "Quedan {} turnos.",
+        "goal.hint_seconds" => "La arena se agota en {} segundos.",
+        "goal.hint_nothing" => "El humo no muestra nada que no sepas ya.",
+        "goal.hunt_win" => "¡Encuentra el tesoro en el piso {} y arrebátale la llave a su guardián!",
+        "goal.hunt_progress" => "Llave: {}, Cofre: {}, Tesoro: {}",
+        "goal.hunt_key" => "¡Tienes una llave! Ahora, a por el cofre.",
+        "goal.hunt_chest" => "¡El cofre del tesoro es tuyo! Ahora hay que abrirlo.",
+        "goal.hunt_victory" => "¡Enhorabuena! ¡Has encontrado el tesoro y escapado de la mazmorra!",
+        "goal.pacifist_win" => "¡Consigue el tesoro sin herir a nadie!",
+        "goal.pacifist_progress" => "{}, Daño infligido: {}",
+        "goal.pacifist_warning" => "PARTIDA INVALIDADA - has infligido {} de daño y ya no puedes ganar",
+        "goal.pacifist_loss" => "¡No dejes que tu salud llegue a cero y no hagas daño a nadie!",
+        "goal.pacifist_victory" => "¡Extraordinario! Esquivaste a todas las criaturas de la mazmorra y te llevaste el tesoro sin derramar una gota de sangre.",
+        "goal.speedrun_win" => "¡Hazte con el tesoro en menos de {}!",
+        "goal.speedrun_loss" => "¡Que no se te acabe el tiempo ni la salud!",
+        "goal.speedrun_victory" => "¡Fulgurante! Te hiciste con el tesoro con tiempo de sobra.",
+        "goal.arena_win" => "¡Sobrevive a {} oleadas en la arena!",
+        "goal.arena_final_wave" => "Última oleada, enemigos restantes: {}",
+        "goal.arena_progress" => "Oleada {}/{}, siguiente en {} turnos, enemigos restantes: {}",
+        "goal.arena_loss" => "¡No caigas en la arena!",
+        "goal.arena_victory" => "¡El público ruge! Has resistido todas las oleadas que la arena podía lanzarte.",
+        "goal.arena_potion" => "Alguien del público lanza una poción a la arena.",
+        "goal.arena_wave" => "¡Las puertas se abren chirriando: oleada {} de {}!",
+        "goal.endless_win" => "¡Desciende tan hondo como puedas!",
+        "goal.endless_progress" => "Piso {}, Tesoro: {} de oro",
+        "goal.endless_loss" => "No hay más salida que bajar. Tu partida termina cuando mueras.",
+        "goal.endless_victory" => "Esta mazmorra no tiene fondo.",
+        "goal.mystery_win" => "Reúne... algo, en {} turnos. Puede que el guardia sepa qué.",
+        "goal.mystery_unknown" => "Objetivo: ???",
+        "goal.mystery_progress" => "{}, Turno {}/{}",
+        "goal.mystery_hint" => "Dicen que quien traiga {} saldrá de aquí libre.",
+        "goal.mystery_loss" => "¡No dejes que tu salud llegue a cero y no te entretengas!",
+        "goal.mystery_victory" => "¡Misterio resuelto! Descubriste lo que quería la mazmorra y lo trajiste a tiempo.",
+        "goal.survival_win" => "¡Sobrevive durante {} turnos!",
+        "goal.survival_progress" => "Turnos sobrevividos: {}/{}",
+        "goal.survival_halfway" => "¡A mitad de camino! Quedan {} turnos.",
+        "goal.survival_hint" => "Aguanta {} turnos más y la cacería habrá terminado.",
+        "goal.survival_hint_necromancer" => " Quien alza a los muertos está {}.",
+        "goal.survival_drums" => "Los tambores de guerra resuenan en la oscuridad. ¡Otro orco se ha unido a la cacería!",
+        "goal.survival_heal" => "Sobrevivir a otra oleada te da ánimos (+{} de salud).",
+        "goal.survival_victory" => "¡Asombroso! ¡Has sobrevivido los turnos necesarios y demostrado tu resistencia!",
+        "goal.collection_win" => "¡Reúne todos los objetos necesarios!",
+        "goal.collection_progress" => "{}: {}/{}",
+        "goal.collection_every" => "¡Ya tienes todos los objetos de tipo {} que necesitas!",
+        "goal.collection_first" => "¡Tu primer objeto de tipo {}!",
+        "goal.collection_one" => "1 de tipo {}",
+        "goal.collection_many" => "{} de tipo {}",
+        "goal.collection_complete" => "Tienes todo lo que viniste a buscar.",
+        "goal.collection_lacking" => "Aún te falta: {}.",
+        "goal.collection_look" => " Busca {}.",
+        "goal.collection_victory" => "¡Excelente! ¡Has reunido todos los objetos necesarios y completado tu misión!",
+        "goal.collection_clatter" => "Oyes algo caer al suelo con estrépito. ¿Será {}?",
+
+        "creature.goblin.description" => "Un trasgo travieso",
+        "creature.orc.description" => "Un fiero guerrero orco",
+        "creature.skeleton.description" => "Huesos antiguos animados por magia oscura",
+        "creature.skeleton_archer.description" => "Un esqueleto que empuña un arco de hueso",
+        "creature.necromancer.description" => "Un nigromante con túnica que alza a los muertos",
+        "creature.shaman.description" => "Un chamán orco que cura y espolea a sus aliados",
+        "creature.stalker.description" => "Un acechador reluciente, invisible a simple vista",
+        "creature.bat.description" => "Un murciélago chillón de las cuevas",
+        "creature.ghost.description" => "Un espíritu inquieto de los muertos de la mazmorra",
+        "creature.merchant.description" => "Un mercader ambulante",
+        "creature.guard.description" => "Un guardia firme",
+        "creature.oracle.description" => "Una vidente ciega envuelta en humo de incienso",
+        "creature.ability.dodges_nimbly" => "Esquiva con agilidad",
+        "creature.ability.hits_hard" => "Golpea fuerte cuerpo a cuerpo",
+        "creature.ability.lies_dormant" => "Permanece inerte hasta que lo molestan",
+        "creature.ability.ignores_hazards" => "Atraviesa fuego y trampas sin inmutarse",
+        "creature.ability.shoots_arrows" => "Dispara flechas a distancia",
+        "creature.ability.keeps_bow_range" => "Retrocede para mantenerte a tiro de arco",
+        "creature.ability.raises_skeletons" => "Alza esqueletos del suelo",
+        "creature.ability.curses_slowness" => "Te maldice con lentitud",
+        "creature.ability.keeps_its_distance" => "Mantiene las distancias",
+        "creature.ability.heals_wounded_allies" => "Cura a los aliados heridos",
+        "creature.ability.hastes_its_allies" => "Acelera a sus aliados",
+        "creature.ability.invisible" => "Invisible sin visión mágica",
+        "creature.ability.flies" => "Vuela sobre el agua y la lava",
+        "creature.ability.drifts_through_walls" => "Atraviesa las paredes",
+        "creature.ability.floats" => "Flota sobre el agua y la lava",
+        "creature.ability.trades" => "Compra y vende mercancías",
+        "creature.ability.flees_if_attacked" => "Huye si lo atacan",
+        "creature.ability.tower_shield" => "Bloquea con un escudo pavés",
+        "creature.ability.defends_town" => "Persigue a cualquiera que ataque el pueblo",
+        "creature.ability.reads_smoke" => "Lee el humo para darte una pista sobre tu objetivo, a cambio de una tarifa",
+        "creature.ability.stays_seated" => "Nunca se levanta de su asiento",
+        "creature.goblin.lore" => "Los trasgos se crían en las salas superiores y se pelean por todo, incluso entre ellos. Solos son unos cobardes; en manada, una molestia.",
+        "creature.orc.lore" => "Los orcos bajaron de las colinas por el hierro de la mazmorra y se quedaron por las peleas.",
+        "creature.skeleton.lore" => "Los soldados de los antiguos señores, aún montando su última guardia. La mayoría llevan siglos dormidos y despiertan de muy mal humor.",
+        "creature.skeleton_archer.lore" => "Arqueros de la misma guarnición muerta. Su puntería no se ha resentido por la falta de ojos.",
+        "creature.necromancer.lore" => "Eruditos que aprendieron que los muertos son sirvientes pacientes y olvidaron para qué sirven los vivos.",
+        "creature.shaman.lore" => "Ancianos orcos que hablan con los espíritus de las profundidades. Los espíritus casi siempre les dicen que golpeen cosas.",
+        "creature.stalker.lore" => "Nadie ha visto uno y ha vivido para describirlo. Los arañazos que dejan en las paredes bastan.",
+        "creature.bat.lore" => "Anidan por millares sobre las salas inundadas. Uno solo es una molestia; la nube es peor.",
+        "creature.ghost.lore" => "No todos los que murieron aquí abajo se han dado cuenta. Las paredes que atraviesan se construyeron después de su época.",
+        "creature.merchant.lore" => "Donde hay tesoros hay alguien vendiendo palas. Los mercaderes siguen a los aventureros hacia abajo y compran lo que suben a rastras.",
+        "creature.guard.lore" => "El pueblo les paga para mantener la paz bajo tierra. Se lo toman muy en serio.",
+        "creature.oracle.lore" => "Nadie sabe cuánto tiempo lleva la Oráculo sentada en el mismo rincón humeante. El humo sabe dónde está todo en la mazmorra, y la Oráculo te lo dirá, por un precio.",
+
+        "attack.player_dodged" => "¡{} esquiva tu ataque!",
+        "attack.player_blocked" => "¡{} bloquea tu ataque!",
+        "attack.player_hit" => "¡Golpeas a {} y le haces {} de daño!",
+        "attack.player_critical" => "¡Golpe crítico! ¡Golpeas a {} y le haces {} de daño!",
+        "attack.npc_dodged" => "{} esquiva el ataque de {}.",
+        "attack.npc_blocked" => "{} bloquea el ataque de {}.",
+        "attack.npc_hit" => "{} golpea a {} y le hace {} de daño.",
+        "attack.npc_critical" => "¡{} asesta un golpe crítico a {} y le hace {} de daño!",
+        "attack.on_player_dodged" => "¡Esquivas cuando {} te {}!",
+        "attack.on_player_blocked" => "¡Bloqueas con tu escudo el ataque de {}!",
+        "attack.on_player_hit" => "¡{} te {} y te hace {} de daño!",
+        "attack.on_player_critical" => "¡Golpe crítico! ¡{} te {} y te hace {} de daño!",
+        "attack.verb_attacks" => "ataca",
+        "attack.verb_lashes_out" => "golpea a ciegas",
+        "attack.verb_bone_shard" => "dispara una esquirla de hueso",
+        _ => return None,
+    })
+}
+
+fn spanish_item(label: &str) -> Option<&'static str> {
+    Some(match label {
+        "Dagger" => "Daga",
+        "Short Sword" => "Espada corta",
+        "Oak Staff" => "Bastón de roble",
+        "Greataxe" => "Gran hacha",
+        "Vampiric Dagger" => "Daga vampírica",
+        "Wooden Shield" => "Escudo de madera",
+        "Torch" => "Antorcha",
+        "Leather Backpack" => "Mochila de cuero",
+        "Amulet of Telepathy" => "Amuleto de telepatía",
+        "Ring of Regeneration" => "Anillo de regeneración",
+        "Ring of Swimming" => "Anillo de natación",
+        "Raw Meat" => "Carne cruda",
+        "Cooked Meat" => "Carne asada",
+        "Trail Rations" => "Raciones de viaje",
+        "Potion of Healing" => "Poción de curación",
+        "Potion of Greater Healing" => "Poción de curación mayor",
+        "Potion of Haste" => "Poción de celeridad",
+        "Potion of Slowness" => "Poción de lentitud",
+        "Potion of See Invisible" => "Poción de ver lo invisible",
+        "Potion of Lasting Sight" => "Poción de vista duradera",
+        "Potion of Levitation" => "Poción de levitación",
+        "Magic Potion" => "Poción mágica",
+        "Scroll of Blink" => "Pergamino de parpadeo",
+        "Scroll of Detect Treasure" => "Pergamino de detectar tesoros",
+        "Scroll of Amnesia" => "Pergamino de amnesia",
+        "Ancient Scroll" => "Pergamino antiguo",
+        "Ruby" => "Rubí",
+        "Sapphire" => "Zafiro",
+        "Topaz" => "Topacio",
+        "Iron Key" => "Llave de hierro",
+        "Bone Key" => "Llave de hueso",
+        "Treasure Chest" => "Cofre del tesoro",
+        "Pile of Treasure" => "Montón de tesoro",
+        "Unknown Item" => "Objeto desconocido",
+        _ => return None,
+    })
+}
@@ -1,11 +1,21 @@
-#[derive(Debug, Clone)]
+use crate::i18n::{self, Language, trf};
+use crate::movement::Movement;
+use crate::status::StatusKind;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Item {
     pub item_type: ItemType,
     pub label: String,
     pub description: String,
+    pub effect: Option<ItemEffect>,
+    pub stats: ItemStats,
+    pub fuel: Option<u32>, // Turns left to burn, for items that burn while equipped
+    #[serde(default)]
+    pub stolen_from: Option<u32>, // Id of the shopkeeper it was taken from without paying
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ItemType {
     Key,
     TreasureChest,
@@ -13,12 +23,228 @@ pub enum ItemType {
     Gem,
     Scroll,
     Potion,
+    Weapon,
+    Shield,
+    RawFood, // Edible, but much better cooked over a campfire
+    Food,
+    Torch,
+    Amulet,
+    Ring,
+    Gold, // Coins go straight into the purse when picked up
+    Backpack, // Carried for extra inventory space
+}
+
+impl ItemType {
+    pub fn get_name(&self) -> &str {
+        match self {
+            ItemType::Key => "Key",
+            ItemType::TreasureChest => "Treasure Chest",
+            ItemType::Treasure => "Treasure",
+            ItemType::Gem => "Gem",
+            ItemType::Scroll => "Scroll",
+            ItemType::Potion => "Potion",
+            ItemType::Weapon => "Weapon",
+            ItemType::Shield => "Shield",
+            ItemType::RawFood => "Raw Food",
+            ItemType::Food => "Food",
+            ItemType::Torch => "Torch",
+            ItemType::Amulet => "Amulet",
+            ItemType::Ring => "Ring",
+            ItemType::Gold => "Gold",
+            ItemType::Backpack => "Backpack",
+        }
+    }
+
+    pub const ALL: [ItemType; 15] = [
+        ItemType::Key,
+        ItemType::TreasureChest,
+        ItemType::Treasure,
+        ItemType::Gem,
+        ItemType::Scroll,
+        ItemType::Potion,
+        ItemType::Weapon,
+        ItemType::Shield,
+        ItemType::RawFood,
+        ItemType::Food,
+        ItemType::Torch,
+        ItemType::Amulet,
+        ItemType::Ring,
+        ItemType::Gold,
+        ItemType::Backpack,
+    ];
+
+    /// Heavy gear and anything that might carry a curse always has to be picked up by hand
+    pub fn allows_auto_pickup(&self) -> bool {
+        !matches!(
+            self,
+            ItemType::TreasureChest | ItemType::Weapon | ItemType::Shield | ItemType::Amulet | ItemType::Ring | ItemType::Backpack
+        )
+    }
+}
+
+/// Where an item is worn or held when equipped
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum EquipSlot {
+    MainHand,
+    OffHand,
+    Neck,
+    Finger,
+}
+
+impl EquipSlot {
+    pub const ALL: [EquipSlot; 4] = [EquipSlot::MainHand, EquipSlot::OffHand, EquipSlot::Neck, EquipSlot::Finger];
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            EquipSlot::MainHand => "Main hand",
+            EquipSlot::OffHand => "Off hand",
+            EquipSlot::Neck => "Neck",
+            EquipSlot::Finger => "Finger",
+        }
+    }
+}
+
+/// How many hands a weapon needs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Handedness {
+    #[default]
+    OneHanded,
+    TwoHanded, // Leaves no hand free for a shield
+    Light, // Small enough for a rogue to wield one in each hand
+}
+
+/// The power a gem lends to whatever gear it's socketed into
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Element {
+    Fire, // Extra damage on every attack
+    Frost, // Better chance to block
+    Lightning, // Better chance to land a critical hit
+}
+
+impl Element {
+    pub const ALL: [Element; 3] = [Element::Fire, Element::Frost, Element::Lightning];
+
+    pub const FIRE_DAMAGE: i32 = 3;
+    pub const FROST_BLOCK: u32 = 10;
+    pub const LIGHTNING_CRIT: u32 = 5;
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            Element::Fire => "Fire",
+            Element::Frost => "Frost",
+            Element::Lightning => "Lightning",
+        }
+    }
+
+    /// The gem that carries this element
+    pub fn gem_name(&self) -> &str {
+        match self {
+            Element::Fire => "Ruby",
+            Element::Frost => "Sapphire",
+            Element::Lightning => "Topaz",
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Element::Fire => format!("+{} damage on every attack", Element::FIRE_DAMAGE),
+            Element::Frost => format!("+{}% block chance", Element::FROST_BLOCK),
+            Element::Lightning => format!("+{}% critical chance", Element::LIGHTNING_CRIT),
+        }
+    }
+}
+
+/// Numbers and passive powers an item contributes while equipped
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ItemStats {
+    pub damage: Option<(i32, i32)>, // Weapon damage range, replacing bare-handed damage
+    pub block_chance: u32, // Percent chance to block an attack
+    pub handedness: Handedness,
+    pub light_radius: i32, // How far the item lights up dark floors while equipped
+    pub telepathy: bool, // Lets the wearer sense every creature on the floor
+    pub movement: Movement, // Ways of getting around the wearer gains, such as swimming
+    pub passive: Option<PassiveEffect>,
+    pub capacity: usize, // Extra inventory slots while carried
+    pub sockets: Vec<Option<Element>>, // Gem slots, each holding the element of the gem set into it
+    pub element: Option<Element>, // For gems, what they lend to gear once socketed
 }
 
-#[derive(Debug)]
+/// An ongoing power an equipped item has, checked each turn or whenever its wielder lands a hit
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PassiveEffect {
+    Regeneration { interval: u32, hunger: i32 }, // Heal 1 HP every `interval` turns, burning extra nutrition each turn
+    Vampiric { percent: u32 }, // Heal this share of the damage dealt on each hit
+}
+
+/// What happens to the player when an item is used up
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ItemEffect {
+    ApplyStatus(StatusKind, u32), // Grants a status effect for a number of turns
+    Heal(i32), // Restores health
+    Nourish(i32), // Restores nutrition when eaten
+    Blink(i32), // Teleports the reader to a chosen visible tile within this range
+    Amnesia, // Wipes the reader's memory of the map
+    Coins(i32), // Gold added to the purse when picked up
+}
+
+impl ItemEffect {
+    pub fn describe(&self) -> String {
+        match self {
+            ItemEffect::ApplyStatus(kind, turns) => format!("{} for {} turns", kind.get_name(), turns),
+            ItemEffect::Heal(amount) => format!("Restores {} health", amount),
+            ItemEffect::Nourish(amount) => format!("Restores {} nutrition", amount),
+            ItemEffect::Blink(range) => format!("Teleports you up to {} tiles", range),
+            ItemEffect::Amnesia => "Wipes your memory of the map".to_string(),
+            ItemEffect::Coins(amount) => format!("Worth {} gold", amount),
+        }
+    }
+}
+
+impl PassiveEffect {
+    pub fn describe(&self) -> String {
+        match self {
+            PassiveEffect::Regeneration { interval, hunger } => {
+                format!("Heals 1 health every {} turns, costing {} extra nutrition a turn", interval, hunger)
+            }
+            PassiveEffect::Vampiric { percent } => format!("Drains {}% of the damage dealt on each hit", percent),
+        }
+    }
+}
+
+/// What using an item came to: where the item and anything it produced end up, what the
+/// player is told, and the changes to the player and world it calls for, applied by the game
+/// state afterwards
+#[derive(Debug, Default)]
 pub struct ItemUseResult {
     pub returned_to_inventory: Option<Item>,
     pub dropped_on_ground: Vec<Item>,
+    pub messages: Vec<String>, // Logged before the mutations are applied
+    pub mutations: Vec<WorldMutation>,
+}
+
+impl ItemUseResult {
+    /// The item was used up, bringing about these changes
+    pub fn consumed(message: String, mutations: Vec<WorldMutation>) -> Self {
+        Self { messages: vec![message], mutations, ..Self::default() }
+    }
+
+    /// The item couldn't be used and goes back in the pack
+    pub fn unused(message: String, item: Item) -> Self {
+        Self { returned_to_inventory: Some(item), messages: vec![message], ..Self::default() }
+    }
+}
+
+/// A change to the player or world brought about by using an item
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorldMutation {
+    Heal(i32), // Health restored to the player
+    Nourish(i32), // Nutrition restored to the player
+    ApplyStatus(StatusKind, u32), // A status effect on the player for a number of turns
+    Teleport((i32, i32)), // The player is moved to this tile
+    ForgetMap, // The player's memory of the map is wiped
+    OpenChest(usize), // The treasure chest at this inventory index is opened and used up
+    Equip(Item, EquipSlot), // The item goes into the slot, putting away whatever was there
 }
 
 impl Item {
@@ -27,9 +253,175 @@ impl Item {
             item_type,
             label,
             description,
+            effect: None,
+            stats: ItemStats::default(),
+            fuel: None,
+            stolen_from: None,
         }
     }
 
+    /// A pile of coins worth the given amount
+    pub fn gold(amount: i32) -> Self {
+        Item::new(ItemType::Gold, format!("{} Gold", amount), "A scattering of tarnished coins.".to_string())
+            .with_effect(ItemEffect::Coins(amount))
+    }
+
+    pub fn with_stats(mut self, stats: ItemStats) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    pub fn with_fuel(mut self, fuel: u32) -> Self {
+        self.fuel = Some(fuel);
+        self
+    }
+
+    pub fn is_two_handed(&self) -> bool {
+        self.item_type == ItemType::Weapon && self.stats.handedness == Handedness::TwoHanded
+    }
+
+    pub fn is_light_weapon(&self) -> bool {
+        self.item_type == ItemType::Weapon && self.stats.handedness == Handedness::Light
+    }
+
+    /// The slot this item normally goes into, if it can be equipped at all
+    pub fn equip_slot(&self) -> Option<EquipSlot> {
+        match self.item_type {
+            ItemType::Weapon => Some(EquipSlot::MainHand),
+            ItemType::Shield | ItemType::Torch => Some(EquipSlot::OffHand),
+            ItemType::Amulet => Some(EquipSlot::Neck),
+            ItemType::Ring => Some(EquipSlot::Finger),
+            _ => None,
+        }
+    }
+
+    /// Every slot this item could be equipped in, whether or not it fits there right now
+    pub fn possible_slots(&self) -> Vec<EquipSlot> {
+        match self.item_type {
+            ItemType::Weapon => vec![EquipSlot::MainHand, EquipSlot::OffHand],
+            _ => self.equip_slot().into_iter().collect(),
+        }
+    }
+
+    pub fn with_sockets(mut self, count: usize) -> Self {
+        self.stats.sockets = vec![None; count];
+        self
+    }
+
+    pub fn has_open_socket(&self) -> bool {
+        self.stats.sockets.contains(&None)
+    }
+
+    /// Elements of the gems set into this item
+    pub fn socketed(&self) -> impl Iterator<Item = Element> + '_ {
+        self.stats.sockets.iter().flatten().copied()
+    }
+
+    pub fn with_effect(mut self, effect: ItemEffect) -> Self {
+        self.effect = Some(effect);
+        self
+    }
+
+    /// The meal this item turns into when cooked over a campfire, if it can be cooked
+    pub fn cooked(&self) -> Option<Item> {
+        if self.item_type != ItemType::RawFood {
+            return None;
+        }
+        let nutrition = match self.effect {
+            Some(ItemEffect::Nourish(nutrition)) => nutrition,
+            _ => 0,
+        };
+        Some(Item::new(
+            ItemType::Food,
+            format!("Cooked {}", self.label.trim_start_matches("Raw ")),
+            format!("{}, cooked through over an open fire.", self.label),
+        ).with_effect(ItemEffect::Nourish(nutrition * 3)))
+    }
+
+    /// The item's name in the given language
+    pub fn display_name(&self, language: Language) -> String {
+        match self.effect {
+            Some(ItemEffect::Coins(amount)) => trf(language, "item.gold", &[&amount]),
+            _ => i18n::item_name(language, &self.label).to_string(),
+        }
+    }
+
+    /// The name the player knows the item by, given the item types they've forgotten. Forgotten
+    /// types show up unidentified.
+    pub fn known_name(&self, language: Language, forgotten: &[ItemType]) -> String {
+        let name = if forgotten.contains(&self.item_type) {
+            trf(language, "item.unidentified", &[&self.item_type.get_name()])
+        } else {
+            self.display_name(language)
+        };
+        if self.stolen_from.is_some() {
+            trf(language, "item.stolen", &[&name])
+        } else {
+            name
+        }
+    }
+
+    /// What a merchant charges for the item, in gold. They buy it back for half.
+    /// Socketed gems keep their worth.
+    pub fn value(&self) -> i32 {
+        const GEM_VALUE: i32 = 30;
+        let base = match self.item_type {
+            ItemType::Key | ItemType::TreasureChest => 0, // Quest items, not for sale
+            ItemType::Treasure => 50,
+            ItemType::Gem => GEM_VALUE,
+            ItemType::Scroll => 20,
+            ItemType::Potion => 15,
+            ItemType::Weapon => 25,
+            ItemType::Shield => 20,
+            ItemType::RawFood => 3,
+            ItemType::Food => 8,
+            ItemType::Torch => 5,
+            ItemType::Amulet | ItemType::Ring => 60,
+            ItemType::Gold => 0, // Already money
+            ItemType::Backpack => 30,
+        };
+        base + self.socketed().count() as i32 * GEM_VALUE
+    }
+
+    /// How heavy the item is, in pounds
+    pub fn weight(&self) -> u32 {
+        match self.item_type {
+            ItemType::Gem | ItemType::Ring | ItemType::Amulet | ItemType::Gold | ItemType::Key | ItemType::Scroll => 0,
+            ItemType::Potion | ItemType::RawFood | ItemType::Food | ItemType::Torch => 1,
+            ItemType::Treasure | ItemType::Backpack => 2,
+            ItemType::Weapon if self.is_light_weapon() => 1,
+            ItemType::Weapon if self.is_two_handed() => 12,
+            ItemType::Weapon => 4,
+            ItemType::Shield => 6,
+            ItemType::TreasureChest => 25,
+        }
+    }
+
+    /// Valuables that a Detect Treasure scroll reveals
+    pub fn is_treasure(&self) -> bool {
+        matches!(self.item_type, ItemType::Treasure | ItemType::TreasureChest | ItemType::Gem | ItemType::Key)
+    }
+
+    /// Items a quest hangs on, which must never be destroyed outright
+    pub fn is_quest_item(&self) -> bool {
+        matches!(self.item_type, ItemType::Key | ItemType::TreasureChest | ItemType::Treasure)
+    }
+
+    /// Whether the item can be eaten, cooked or not
+    pub fn is_food(&self) -> bool {
+        matches!(self.item_type, ItemType::RawFood | ItemType::Food)
+    }
+
+    /// Whether the item shatters when caught in an explosion
+    pub fn is_fragile(&self) -> bool {
+        self.item_type == ItemType::Potion
+    }
+
+    /// Whether the item burns up when caught in a fire
+    pub fn is_flammable(&self) -> bool {
+        self.item_type == ItemType::Scroll
+    }
+
     pub fn get_display_char(&self) -> char {
         match self.item_type {
             ItemType::Key => '-',
@@ -38,6 +430,14 @@ impl Item {
             ItemType::Gem => '*',
             ItemType::Scroll => '?',
             ItemType::Potion => '!',
+            ItemType::Weapon => ')',
+            ItemType::Shield => '[',
+            ItemType::RawFood | ItemType::Food => '%',
+            ItemType::Torch => '(',
+            ItemType::Amulet => '"',
+            ItemType::Ring => 'o',
+            ItemType::Gold => '$',
+            ItemType::Backpack => '{',
         }
     }
 
@@ -50,6 +450,15 @@ impl Item {
             ItemType::Gem => (255, 20, 147), // Deep pink
             ItemType::Scroll => (245, 245, 220), // Beige
             ItemType::Potion => (138, 43, 226), // Blue violet
+            ItemType::Weapon => (192, 192, 192), // Silver
+            ItemType::Shield => (160, 120, 60), // Oak brown
+            ItemType::RawFood => (205, 92, 92), // Raw red
+            ItemType::Food => (205, 133, 63), // Roasted brown
+            ItemType::Torch => (255, 180, 60), // Flame yellow
+            ItemType::Amulet => (64, 224, 208), // Turquoise
+            ItemType::Ring => (218, 165, 32), // Goldenrod
+            ItemType::Gold => (255, 200, 60), // Coin yellow
+            ItemType::Backpack => (150, 110, 70), // Worn leather
         };
         (char, color)
     }
@@ -0,0 +1,117 @@
+use std::sync::{Arc, Mutex};
+
+use eframe::egui;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::scores::ScoreEntry;
+
+/// Where the leaderboard server lives, e.g. "https://example.com/ai_rogue/scores"
+const URL_VARIABLE: &str = "AI_ROGUE_LEADERBOARD_URL";
+/// Secret shared with the server, used to sign submitted runs
+const KEY_VARIABLE: &str = "AI_ROGUE_LEADERBOARD_KEY";
+/// Header carrying the hex HMAC-SHA256 of the request body
+const SIGNATURE_HEADER: &str = "X-Signature";
+
+/// The online table as last fetched
+#[derive(Debug, Clone, Default)]
+pub enum Standings {
+    #[default]
+    NotFetched,
+    Loading,
+    Loaded(Vec<ScoreEntry>),
+    Offline(String), // Why the server couldn't be reached; local scores still work
+}
+
+/// An online leaderboard that finished runs are posted to, signed so the server can tell them
+/// from forgeries. Requests run in the background and never hold up the game.
+pub struct OnlineLeaderboard {
+    url: String,
+    key: String,
+    standings: Arc<Mutex<Standings>>,
+}
+
+impl OnlineLeaderboard {
+    /// The leaderboard configured through the environment, if there is one. Without a signing
+    /// key the server would take every run for a forgery, so there's no leaderboard then either.
+    pub fn from_env() -> Option<Self> {
+        let url = std::env::var(URL_VARIABLE).ok().filter(|url| !url.is_empty())?;
+        let Some(key) = std::env::var(KEY_VARIABLE).ok().filter(|key| !key.is_empty()) else {
+            tracing::warn!("{} is set but {} isn't, so runs won't be submitted", URL_VARIABLE, KEY_VARIABLE);
+            return None;
+        };
+        Some(Self { url, key, standings: Arc::default() })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn standings(&self) -> Standings {
+        self.standings.lock().map(|standings| standings.clone()).unwrap_or_default()
+    }
+
+    /// Fetch the top entries again
+    pub fn refresh(&self, ctx: &egui::Context) {
+        fetch_standings(self.url.clone(), self.standings.clone(), ctx.clone());
+    }
+
+    /// Post a finished run, then fetch the table it landed in
+    pub fn submit(&self, entry: &ScoreEntry, ctx: &egui::Context) {
+        let body = match serde_json::to_vec(entry) {
+            Ok(body) => body,
+            Err(error) => {
+                tracing::warn!("couldn't encode the run for the leaderboard: {}", error);
+                return;
+            }
+        };
+        let mut request = ehttp::Request::post(&self.url, body.clone());
+        request.headers.insert("Content-Type", "application/json");
+        request.headers.insert(SIGNATURE_HEADER, sign(&self.key, &body));
+
+        let (url, standings, ctx) = (self.url.clone(), self.standings.clone(), ctx.clone());
+        set(&standings, Standings::Loading);
+        ehttp::fetch(request, move |result| match result {
+            Ok(response) if response.ok => fetch_standings(url, standings, ctx),
+            Ok(response) => {
+                tracing::warn!(status = response.status, "leaderboard refused the run");
+                set(&standings, Standings::Offline(format!("The leaderboard refused the run ({})", response.status_text)));
+                ctx.request_repaint();
+            }
+            Err(error) => {
+                tracing::warn!("couldn't reach the leaderboard: {}", error);
+                set(&standings, Standings::Offline("Couldn't reach the leaderboard".to_string()));
+                ctx.request_repaint();
+            }
+        });
+    }
+}
+
+fn fetch_standings(url: String, standings: Arc<Mutex<Standings>>, ctx: egui::Context) {
+    set(&standings, Standings::Loading);
+    ehttp::fetch(ehttp::Request::get(url), move |result| {
+        let fetched = result
+            .and_then(|response| serde_json::from_slice::<Vec<ScoreEntry>>(&response.bytes).map_err(|error| error.to_string()));
+        match fetched {
+            Ok(entries) => set(&standings, Standings::Loaded(entries)),
+            Err(error) => {
+                tracing::warn!("couldn't fetch the leaderboard: {}", error);
+                set(&standings, Standings::Offline("Couldn't reach the leaderboard".to_string()));
+            }
+        }
+        ctx.request_repaint();
+    });
+}
+
+fn set(standings: &Mutex<Standings>, value: Standings) {
+    if let Ok(mut standings) = standings.lock() {
+        *standings = value;
+    }
+}
+
+/// Hex HMAC-SHA256 of a request body
+fn sign(key: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    mac.finalize().into_bytes().iter().map(|byte| format!("{:02x}", byte)).collect()
+}
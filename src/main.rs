@@ -1,36 +1,257 @@
 use eframe::egui;
 
+mod ability;
+mod accessibility;
+mod action;
+mod alchemy;
+mod animation;
+mod balance;
+mod catalog;
+mod clock;
+mod collapse;
+mod combat;
+mod command;
+#[cfg(feature = "console")]
+mod console;
+mod diagnostics;
+mod ecs;
+mod elite;
+mod faction;
+#[cfg(feature = "gamepad")]
+mod gamepad;
 mod game_condition;
+mod geometry;
+mod grid;
+#[cfg(debug_assertions)]
+mod hot_reload;
+mod i18n;
 mod item;
+mod leaderboard;
+mod message_log;
+mod movement;
+mod names;
+mod noise;
 mod npc;
+mod pathfinding;
+mod profile;
+mod projectile;
+mod render;
+mod repaint;
+mod scheduler;
+mod scores;
+mod settings;
 mod state;
-use game_condition::{GameStatus, TreasureHuntCondition, SurvivalCondition, CollectionCondition};
-use item::ItemType;
-use npc::NPCType;
-use state::{GameState, TileType, WorldItem};
+mod status;
+mod toast;
+mod trap;
+mod weather;
+use i18n::{tr, trf, Language};
+use game_condition::{GameStatus, LossReason, TreasureHuntCondition, SurvivalCondition, CollectionCondition, PacifistCondition, SpeedrunCondition, ArenaCondition, EndlessCondition, MysteryCondition};
+use ability::Ability;
+use action::Action;
+use animation::VisualEffect;
+use repaint::RepaintReason;
+use scores::{HighScores, ScoreEntry};
+use command::{Command, GameEvent};
+use item::{EquipSlot, Handedness, Item, ItemEffect, ItemType};
+use npc::{NPCState, NPCType};
+use state::{Entity, GameState, LevelUpChoice, PlayerClass, TileType, Visibility};
+use status::StatusKind;
 
-#[derive(Default, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub enum DialogState {
     #[default]
     GameTypeSelection,
     NoDialog,
     QuitConfirmation,
     UseItem,
+    Equip,
+    MixPotions,
+    Campfire,
+    Altar,
+    Workbench,
+    Stash, // Withdrawing banked items at the start of a run
+    Statistics, // Lifetime totals, opened from the game type selection
+    Encyclopedia, // Unlocked bestiary and item entries, from the game type selection or in play
+    Targeting, // Picking a tile on the map rather than showing a window
+    LevelUp,
+    ShopDemand, // A shopkeeper wants paying for goods the player picked up
+    CharacterSheet,
+    BumpMenu,
+    Trade,
+    Settings,
+    PickUp,
+    DropItem,
+    Examine,
+    Respawn, // Died with a checkpoint to rise again at
     GameOver,
     Victory,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// What a tile is being picked on the map for
+#[derive(Debug, Clone)]
+pub enum TargetingPurpose {
+    Blink { item_index: usize, range: i32 }, // The scroll in the player's inventory
+    Ability(Ability),
+}
+
+/// A tile being picked on the map for a Blink scroll or an aimed ability
+#[derive(Debug, Clone)]
+pub struct Targeting {
+    pub purpose: TargetingPurpose,
+    pub cursor: (i32, i32),
+}
+
+impl Targeting {
+    /// Whether confirming now would succeed, for colouring the cursor
+    fn is_valid(&self, game_state: &GameState) -> bool {
+        match self.purpose {
+            TargetingPurpose::Blink { range, .. } => game_state.is_valid_blink_target(self.cursor, range),
+            TargetingPurpose::Ability(ability) => game_state.is_valid_ability_target(ability, self.cursor),
+        }
+    }
+
+    fn hint(&self, lang: Language) -> String {
+        match self.purpose {
+            TargetingPurpose::Blink { .. } => tr(lang, "targeting.blink").to_string(),
+            TargetingPurpose::Ability(ability) => trf(lang, "targeting.ability", &[&ability.get_name()]),
+        }
+    }
+}
+
+/// Keyboard control for the entries of whichever dialog is open. Up/Down (or Left/Right)
+/// move a highlight and Enter presses it; the number keys press the first nine directly.
+#[derive(Debug, Default)]
+pub struct MenuNavigation {
+    cursor: usize, // Highlighted entry
+    pressed: Option<usize>, // Entry chosen from the keyboard this frame
+    entries: usize, // Entries drawn so far this frame
+    last_entries: usize, // Entries the dialog had last frame, which bound the cursor
+}
+
+impl MenuNavigation {
+    const NUMBER_KEYS: [egui::Key; 9] = [
+        egui::Key::Num1, egui::Key::Num2, egui::Key::Num3,
+        egui::Key::Num4, egui::Key::Num5, egui::Key::Num6,
+        egui::Key::Num7, egui::Key::Num8, egui::Key::Num9,
+    ];
+
+    fn handle_keys(&mut self, i: &egui::InputState) {
+        let count = self.last_entries;
+        if count == 0 {
+            return;
+        }
+        if i.key_pressed(egui::Key::ArrowUp) || i.key_pressed(egui::Key::ArrowLeft) {
+            self.cursor = (self.cursor + count - 1) % count;
+        }
+        if i.key_pressed(egui::Key::ArrowDown) || i.key_pressed(egui::Key::ArrowRight) {
+            self.cursor = (self.cursor + 1) % count;
+        }
+        if i.key_pressed(egui::Key::Enter) {
+            self.pressed = Some(self.cursor);
+        }
+        for (index, key) in Self::NUMBER_KEYS.into_iter().enumerate() {
+            if index < count && i.key_pressed(key) {
+                self.cursor = index;
+                self.pressed = Some(index);
+            }
+        }
+    }
+
+    /// Draw the next entry of the dialog, numbering it and highlighting it if it has the cursor.
+    /// Returns the widget's response and whether the keyboard pressed it this frame.
+    fn add(&mut self, ui: &mut egui::Ui, enabled: bool, widget: impl FnOnce(&mut egui::Ui, String) -> egui::Response, label: impl std::fmt::Display) -> (egui::Response, bool) {
+        let index = self.entries;
+        self.entries += 1;
+        let text = if index < Self::NUMBER_KEYS.len() {
+            format!("{}. {}", index + 1, label)
+        } else {
+            label.to_string()
+        };
+        let mut response = ui.add_enabled_ui(enabled, |ui| widget(ui, text)).inner;
+        if index == self.cursor {
+            response = response.highlight();
+        }
+        (response, enabled && self.pressed == Some(index))
+    }
+
+    fn button(&mut self, ui: &mut egui::Ui, label: impl std::fmt::Display) -> bool {
+        let (response, pressed) = self.add(ui, true, |ui, text| ui.button(text), label);
+        response.clicked() || pressed
+    }
+
+    /// A button that may be greyed out, for dialogs that want its response for hover text
+    fn enabled_button(&mut self, ui: &mut egui::Ui, enabled: bool, label: impl std::fmt::Display) -> (egui::Response, bool) {
+        self.add(ui, enabled, |ui, text| ui.button(text), label)
+    }
+
+    fn selectable(&mut self, ui: &mut egui::Ui, selected: bool, label: impl std::fmt::Display) -> bool {
+        let (response, pressed) = self.add(ui, true, |ui, text| ui.selectable_label(selected, text), label);
+        response.clicked() || pressed
+    }
+
+    /// Returns true when the box was toggled
+    fn checkbox(&mut self, ui: &mut egui::Ui, checked: &mut bool, label: impl std::fmt::Display) -> bool {
+        let (response, pressed) = self.add(ui, true, |ui, text| ui.checkbox(checked, text), label);
+        if pressed {
+            *checked = !*checked;
+        }
+        response.changed() || pressed
+    }
+
+    /// Called once the dialog has been drawn, ready for the next frame
+    fn finish_frame(&mut self) {
+        self.last_entries = self.entries;
+        self.entries = 0;
+        self.pressed = None;
+        if self.cursor >= self.last_entries {
+            self.cursor = self.last_entries.saturating_sub(1);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AvailableGameType {
     TreasureHunt,
     Survival,
     Collection,
+    Pacifist,
+    Speedrun,
+    Arena,
+    Endless,
+    Mystery,
 }
 
 #[derive(Debug, Default)]
 pub struct WorldViewInteraction {
     pub mouse_position: Option<(i32, i32)>,
     pub clicked_position: Option<(i32, i32)>,
+    pub long_pressed_position: Option<(i32, i32)>, // Held down on a touch screen
+}
+
+/// What the player clicked in the information panel this frame
+#[derive(Debug, Default)]
+pub struct InfoPanelInteraction {
+    pub examined_item: Option<Item>, // Inventory item to show in the examine dialog
+    pub unpin_npc: bool, // The pinned NPC's details were dismissed
+    pub pinned_npc: Option<u32>, // Creature picked to pin from the location details
+    pub unpin_location: bool, // The pinned location's details were dismissed
+    pub pressed_keys: Vec<egui::Key>, // Keys of the on-screen controls tapped
+}
+
+/// What an examine button in the location details opens
+#[derive(Debug, Clone)]
+pub enum LocationExamine {
+    Npc(u32), // Pins the creature's details
+    Item(Item), // Opens the examine dialog
+}
+
+/// Walking to a tapped tile one step per frame, until something needs the player's attention
+#[derive(Debug, Clone, Copy)]
+pub struct TapWalk {
+    target: (i32, i32),
+    health: i32, // Health when the walk began; getting hurt stops it
+    steps: u32,
 }
 
 impl WorldViewInteraction {
@@ -55,15 +276,30 @@ impl AvailableGameType {
             AvailableGameType::TreasureHunt => "Treasure Hunt",
             AvailableGameType::Survival => "Survival Challenge",
             AvailableGameType::Collection => "Item Collection",
+            AvailableGameType::Pacifist => "Pacifist",
+            AvailableGameType::Speedrun => "Speedrun",
+            AvailableGameType::Arena => "Arena",
+            AvailableGameType::Endless => "Endless Descent",
+            AvailableGameType::Mystery => "Mystery",
         }
     }
 
-    pub fn get_description(&self) -> &str {
-        match self {
-            AvailableGameType::TreasureHunt => "Find and collect the treasure while avoiding dangers.",
-            AvailableGameType::Survival => "Survive for 200 turns without dying.",
-            AvailableGameType::Collection => "Collect 3 gems, 2 scrolls, and 1 potion.",
-        }
+    pub fn get_description(&self, lang: Language) -> &'static str {
+        tr(lang, match self {
+            AvailableGameType::TreasureHunt => "mode.treasure_hunt",
+            AvailableGameType::Survival => "mode.survival",
+            AvailableGameType::Collection => "mode.collection",
+            AvailableGameType::Pacifist => "mode.pacifist",
+            AvailableGameType::Speedrun => "mode.speedrun",
+            AvailableGameType::Arena => "mode.arena",
+            AvailableGameType::Endless => "mode.endless",
+            AvailableGameType::Mystery => "mode.mystery",
+        })
+    }
+
+    /// Whether the mode leads down floor after floor, so collapsing floors make sense for it
+    pub fn has_stairs(&self) -> bool {
+        !matches!(self, AvailableGameType::Survival | AvailableGameType::Collection | AvailableGameType::Arena)
     }
 }
 
@@ -71,36 +307,207 @@ pub struct RoguelikeApp {
     game_state: Option<GameState>,
     dialog_state: DialogState,
     mouse_world_pos: Option<(i32, i32)>,
+    selected_class: PlayerClass, // Class the next game will start with
+    collapsing_floors: bool, // Run modifier for the next game: floors cave in after a while
+    hardcore: bool, // Run modifier for the next game: no respawning at checkpoints
+    mix_selection: Vec<usize>, // Inventory indices of the potions chosen for mixing
+    socket_target: Option<usize>, // Inventory index of the gear chosen at a workbench
+    pickup_selection: Vec<usize>, // World item indices chosen from the pile underfoot
+    targeting: Option<Targeting>,
+    interaction_target: Option<(i32, i32)>, // The friendly NPC being talked to or traded with
+    examined_item: Option<Item>, // Shown in full by the examine dialog
+    menu: MenuNavigation, // Keyboard highlight in the open dialog
+    pinned_npc: Option<u32>, // Id of the NPC whose details stay in the side panel
+    pinned_location: Option<(i32, i32)>, // Crowded tile whose contents stay listed in the side panel
+    camera_follows: bool, // Keep the world view scrolled to the player
+    recenter_camera: bool, // Scroll back to the player once, even when the camera is unlocked
+    threat_overlay: bool, // Shade the tiles visible hostiles could move onto or attack next turn
+    debug_overlay: bool, // Draw NPC AI state, targets and paths plus the edge of the player's sight
+    game_type: AvailableGameType, // Mode of the current run, for the high-score table
+    loss_reason: Option<LossReason>, // How the run was lost, for the Game Over screen
+    high_scores: HighScores,
+    endless_scores: HighScores, // Endless descents are scored differently, so they get a table of their own
+    online: Option<leaderboard::OnlineLeaderboard>, // Configured through the environment; only used once the player opts in
+    score_place: Option<usize>, // Where the finished run landed in the high-score table
+    paused: bool, // Stops the run's clock and hides the map
+    clock_time: f64, // egui's time at the last frame, to measure how long the run has been played
+    #[cfg(feature = "console")]
+    console: console::Console,
+    #[cfg(feature = "gamepad")]
+    gamepad: gamepad::Gamepad,
+    settings: settings::Settings, // Kept here so they carry over into the next game
+    pending_keys: Vec<egui::Key>, // Presses from the on-screen controls, fed in with the next frame's input
+    choosing_interaction: bool, // The interact key was pressed and a direction is awaited
+    tap_walk: Option<TapWalk>,
+    profile: profile::Profile, // The stash and anything else kept on disk between runs
+    crash_save: Option<std::path::PathBuf>, // The newest emergency save, offered for resuming on the game type selection
+    checkpoint_save: Option<std::path::PathBuf>, // The last checkpoint autosave of an unfinished run, offered for resuming too
+    diagnostics: diagnostics::Diagnostics,
+    announcer: accessibility::Announcer, // Mirrors the log and new sightings for screen readers
+    toasts: toast::Toasts, // Celebrations of milestones towards the goal
+    animations: animation::Animations, // Screen shake and damage flashes over the world view
+    repaint: repaint::RepaintScheduler, // When the next frame is drawn, if nothing else wakes the UI first
+    #[cfg(debug_assertions)]
+    data_watcher: hot_reload::DataWatcher, // Reloads data files when they're saved, for tuning while playing
 }
 
 impl RoguelikeApp {
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+    pub fn new(#[cfg_attr(not(debug_assertions), allow(unused_variables))] cc: &eframe::CreationContext<'_>) -> Self {
         // Customize egui here with cc.egui_ctx.set_fonts and cc.egui_ctx.set_style
+        let settings = settings::Settings::default();
+        let diagnostics = diagnostics::Diagnostics::init(settings.log_verbosity);
+        if let Err(error) = balance::load(balance::BALANCE_PATH) {
+            tracing::warn!("{}; playing with the default balance", error);
+        }
         Self {
             game_state: None,
             dialog_state: DialogState::GameTypeSelection,
             mouse_world_pos: None,
+            selected_class: PlayerClass::default(),
+            collapsing_floors: false,
+            hardcore: false,
+            mix_selection: Vec::new(),
+            socket_target: None,
+            pickup_selection: Vec::new(),
+            targeting: None,
+            interaction_target: None,
+            examined_item: None,
+            menu: MenuNavigation::default(),
+            pinned_npc: None,
+            pinned_location: None,
+            camera_follows: true,
+            recenter_camera: false,
+            threat_overlay: false,
+            debug_overlay: false,
+            game_type: AvailableGameType::TreasureHunt,
+            loss_reason: None,
+            high_scores: HighScores::load(scores::SCORES_PATH),
+            endless_scores: HighScores::load(scores::ENDLESS_SCORES_PATH),
+            online: leaderboard::OnlineLeaderboard::from_env(),
+            score_place: None,
+            paused: false,
+            clock_time: 0.0,
+            #[cfg(feature = "console")]
+            console: console::Console::default(),
+            #[cfg(feature = "gamepad")]
+            gamepad: gamepad::Gamepad::new(),
+            diagnostics,
+            settings,
+            pending_keys: Vec::new(),
+            choosing_interaction: false,
+            tap_walk: None,
+            profile: profile::Profile::load(),
+            crash_save: latest_emergency_save(),
+            checkpoint_save: existing_checkpoint_save(),
+            announcer: accessibility::Announcer::default(),
+            toasts: toast::Toasts::default(),
+            animations: animation::Animations::default(),
+            repaint: repaint::RepaintScheduler::default(),
+            #[cfg(debug_assertions)]
+            data_watcher: hot_reload::DataWatcher::new(&cc.egui_ctx),
         }
     }
 }
 
 impl eframe::App for RoguelikeApp {
+    fn raw_input_hook(&mut self, _ctx: &egui::Context, raw_input: &mut egui::RawInput) {
+        #[cfg(feature = "gamepad")]
+        self.gamepad.feed(_ctx, raw_input);
+        for key in self.pending_keys.drain(..) {
+            action::press_key(raw_input, key);
+        }
+    }
+
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        // A panic anywhere in the frame still gets the run saved before the process dies
+        let frame_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| self.update_frame(ctx, frame)));
+        if let Err(panic) = frame_result {
+            if let Some(ref game_state) = self.game_state {
+                // The state may be half-updated, so saving it could panic too
+                let saved = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| write_emergency_save(game_state)));
+                match saved {
+                    Ok(Ok(path)) => tracing::error!("The game crashed; emergency save written to {}", path.display()),
+                    Ok(Err(error)) => tracing::error!("The game crashed and the emergency save couldn't be written: {}", error),
+                    Err(_) => tracing::error!("The game crashed and the game state is too broken to save"),
+                }
+            }
+            std::panic::resume_unwind(panic);
+        }
+        self.repaint.finish_frame(ctx, self.settings.power_saving);
+    }
+}
+
+impl RoguelikeApp {
+    fn update_frame(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
         // Handle input
-        self.handle_input(ctx);
+        let dialog_before = self.dialog_state;
+        // The developer console takes over the keyboard while it's open
+        #[cfg(feature = "console")]
+        let console_open = self.console.update(ctx, self.game_state.as_mut());
+        #[cfg(not(feature = "console"))]
+        let console_open = false;
+        if !console_open {
+            self.handle_input(ctx);
+        }
+        if let Some(ref game_state) = self.game_state {
+            self.announcer.update(game_state);
+        }
+        let now = ctx.input(|i| i.time);
+        if let Some(ref mut game_state) = self.game_state {
+            for milestone in game_state.take_milestones() {
+                self.toasts.push(milestone, now);
+            }
+        }
+        if let Some(until_next) = self.toasts.show(ctx, now) {
+            self.repaint.request(RepaintReason::Toast, until_next);
+        }
+        if self.animations.update(ctx, self.game_state.as_ref(), &self.settings) {
+            self.repaint.request(RepaintReason::Animation, std::time::Duration::ZERO);
+        }
+
+        self.advance_clock(ctx);
+        self.save_encyclopedia();
+        self.save_stash();
+        self.autosave();
+        #[cfg(debug_assertions)]
+        for message in self.data_watcher.poll() {
+            tracing::info!("{}", message);
+            if let Some(ref mut game_state) = self.game_state {
+                game_state.add_log_message(message);
+            }
+        }
+        if ctx.input(|i| i.any_touches()) {
+            self.settings.touch_controls = true;
+        }
 
         // Check game status using the new condition system
-        if self.dialog_state == DialogState::NoDialog {
-            if let Some(ref game_state) = self.game_state {
-                match game_state.check_game_status() {
-                    GameStatus::Lost => {
-                        self.dialog_state = DialogState::GameOver;
-                    }
-                    GameStatus::Won => {
-                        self.dialog_state = DialogState::Victory;
-                    }
-                    GameStatus::Playing => {
-                        // Continue playing
+        if self.dialog_state == DialogState::NoDialog
+            && let Some(ref game_state) = self.game_state
+        {
+            match game_state.check_game_status() {
+                GameStatus::Lost(LossReason::Died) if game_state.can_respawn() => {
+                    tracing::info!(turn = game_state.turn_counter, respawns = game_state.respawns_left, "died with a checkpoint");
+                    self.dialog_state = DialogState::Respawn;
+                }
+                GameStatus::Lost(reason) => {
+                    tracing::info!(turn = game_state.turn_counter, ?reason, "game lost");
+                    self.loss_reason = Some(reason);
+                    self.dialog_state = DialogState::GameOver;
+                    self.record_score(ctx, false);
+                    self.discard_checkpoint_save();
+                }
+                GameStatus::Won => {
+                    tracing::info!(turn = game_state.turn_counter, wizard = game_state.wizard_used, "game won");
+                    self.dialog_state = DialogState::Victory;
+                    self.record_score(ctx, true);
+                    self.discard_checkpoint_save();
+                }
+                GameStatus::Playing => {
+                    if game_state.pending_level_up.is_some() {
+                        self.dialog_state = DialogState::LevelUp;
+                    } else if game_state.shop_demand.is_some() {
+                        self.dialog_state = DialogState::ShopDemand;
                     }
                 }
             }
@@ -110,14 +517,27 @@ impl eframe::App for RoguelikeApp {
         match self.dialog_state {
             DialogState::GameTypeSelection => {
                 self.show_game_type_selection_dialog(ctx, frame);
+                self.finish_menu_frame(dialog_before);
                 return; // Don't process anything else until game type is selected
             }
+            DialogState::Statistics => {
+                self.show_statistics_dialog(ctx, frame);
+                self.finish_menu_frame(dialog_before);
+                return;
+            }
+            DialogState::Respawn => {
+                self.show_respawn_dialog(ctx);
+                self.finish_menu_frame(dialog_before);
+                return; // The run is on hold until the player chooses
+            }
             DialogState::GameOver => {
                 self.show_game_over_dialog(ctx, frame);
+                self.finish_menu_frame(dialog_before);
                 return; // Don't process anything else if game is over
             }
             DialogState::Victory => {
                 self.show_victory_dialog_window(ctx, frame);
+                self.finish_menu_frame(dialog_before);
                 return; // Don't process anything else if player won
             }
             DialogState::QuitConfirmation => {
@@ -126,14 +546,68 @@ impl eframe::App for RoguelikeApp {
             DialogState::UseItem => {
                 self.show_use_item_dialog_window(ctx, frame);
             }
+            DialogState::Equip => {
+                self.show_equip_dialog_window(ctx, frame);
+            }
+            DialogState::MixPotions => {
+                self.show_mix_potions_dialog_window(ctx, frame);
+            }
+            DialogState::Campfire => {
+                self.show_campfire_dialog_window(ctx, frame);
+            }
+            DialogState::Altar => {
+                self.show_altar_dialog_window(ctx, frame);
+            }
+            DialogState::Workbench => {
+                self.show_workbench_dialog_window(ctx, frame);
+            }
+            DialogState::Stash => {
+                self.show_stash_dialog_window(ctx, frame);
+            }
+            DialogState::LevelUp => {
+                self.show_level_up_dialog_window(ctx, frame);
+            }
+            DialogState::ShopDemand => {
+                self.show_shop_demand_dialog_window(ctx, frame);
+            }
+            DialogState::CharacterSheet => {
+                self.show_character_sheet_dialog_window(ctx, frame);
+            }
+            DialogState::BumpMenu => {
+                self.show_bump_menu_dialog_window(ctx, frame);
+            }
+            DialogState::Trade => {
+                self.show_trade_dialog_window(ctx, frame);
+            }
+            DialogState::Settings => {
+                self.show_settings_dialog_window(ctx, frame);
+            }
+            DialogState::PickUp => {
+                self.show_pickup_dialog_window(ctx, frame);
+            }
+            DialogState::DropItem => {
+                self.show_drop_item_dialog_window(ctx, frame);
+            }
+            DialogState::Examine => {
+                self.show_examine_dialog_window(ctx, frame);
+            }
+            DialogState::Encyclopedia => {
+                self.show_encyclopedia_dialog_window(ctx, frame);
+            }
+            DialogState::Targeting => {
+                // The cursor is drawn in the world view and driven by handle_input
+            }
             DialogState::NoDialog => {
                 // Continue with normal game processing
             }
         }
+        self.finish_menu_frame(dialog_before);
+        self.continue_tap_walk();
 
         // Main UI layout - only show if game is initialized
         if let Some(ref game_state) = self.game_state {
             let mut world_interaction = WorldViewInteraction::new();
+            let mut panel_interaction = InfoPanelInteraction::default();
             
             egui::CentralPanel::default().show(ctx, |ui| {
                 let desired_height = ui.available_height();
@@ -145,7 +619,7 @@ impl eframe::App for RoguelikeApp {
                         |ui| {
                             ui.group(|ui| {
                                 ui.set_height(ui.available_height());
-                                ui.label("World View");
+                                ui.label(tr(lang, "world.title"));
                                 ui.separator();
                                 world_interaction = self.draw_world_view(ui, game_state);
                             });
@@ -160,7 +634,7 @@ impl eframe::App for RoguelikeApp {
                         egui::Layout::top_down(egui::Align::Min),
                         |ui| {
                             ui.set_height(ui.available_height());
-                            self.draw_info_panel(ui, game_state);
+                            panel_interaction = self.draw_info_panel(ui, game_state);
                         },
                     );
                 });
@@ -168,11 +642,137 @@ impl eframe::App for RoguelikeApp {
             
             // Update mouse position based on interaction
             self.mouse_world_pos = world_interaction.mouse_position;
+            self.recenter_camera = false;
+
+            // Taps on the on-screen controls are handled as key presses next frame
+            if !panel_interaction.pressed_keys.is_empty() {
+                self.pending_keys.extend(panel_interaction.pressed_keys);
+                self.repaint.request(RepaintReason::Input, std::time::Duration::ZERO);
+            }
+
+            // Clicking a creature on the map pins its details and clicking an item examines it;
+            // clicking a crowded tile pins a list of everything on it to examine one by one. With touch controls a tap walks there and a long press examines.
+            let touch = self.settings.touch_controls;
+            let examine_at = if touch { world_interaction.long_pressed_position } else { world_interaction.clicked_position };
+            if touch
+                && self.dialog_state == DialogState::NoDialog
+                && let Some(target) = world_interaction.clicked_position
+            {
+                self.tap_walk = Some(TapWalk { target, health: game_state.player.health, steps: 0 });
+            }
+            let mut examined_item = panel_interaction.examined_item;
+            if let Some(id) = panel_interaction.pinned_npc {
+                self.pinned_npc = Some(id);
+            }
+            if self.dialog_state == DialogState::NoDialog
+                && let Some(clicked) = examine_at
+            {
+                if game_state.is_crowded(clicked.0, clicked.1) {
+                    self.pinned_location = Some(clicked);
+                } else if let Some((npc, Visibility::Visible | Visibility::Sensed)) = game_state.perceived_npc_at(clicked.0, clicked.1) {
+                    self.pinned_npc = Some(npc.id);
+                } else {
+                    examined_item = examined_item.or_else(|| {
+                        game_state.world.items.iter().rev()
+                            .find(|world_item| world_item.position == clicked && game_state.is_item_perceived(world_item))
+                            .map(|world_item| world_item.item.clone())
+                    });
+                }
+            }
+            // Slain creatures drop out of the panel, and a pinned tile once it's no longer crowded
+            if panel_interaction.unpin_npc || !game_state.npcs.iter().any(|npc| Some(npc.id) == self.pinned_npc) {
+                self.pinned_npc = None;
+            }
+            if panel_interaction.unpin_location || self.pinned_location.is_some_and(|(x, y)| !game_state.is_crowded(x, y)) {
+                self.pinned_location = None;
+            }
+            if self.dialog_state == DialogState::NoDialog && examined_item.is_some() {
+                self.examined_item = examined_item;
+                self.dialog_state = DialogState::Examine;
+            }
+
+            // Clicking a tile while targeting picks it
+            if self.dialog_state == DialogState::Targeting
+                && let Some(clicked) = world_interaction.clicked_position
+            {
+                if let Some(ref mut targeting) = self.targeting {
+                    targeting.cursor = clicked;
+                }
+                self.confirm_target();
+            }
         }
     }
 }
 
 impl RoguelikeApp {
+    /// Count the real time spent playing towards the run's clock. It stops while paused, while
+    /// the window is in the background and once the game is over.
+    fn advance_clock(&mut self, ctx: &egui::Context) {
+        const MAX_FRAME_GAP: f64 = 1.5; // Longer gaps mean the app was asleep, not being played
+        const MAX_IDLE_GAP: f64 = 60.0; // The same when power saving only draws frames on input
+
+        let (now, focused) = ctx.input(|i| (i.time, i.focused));
+        let max_gap = if self.settings.power_saving { MAX_IDLE_GAP } else { MAX_FRAME_GAP };
+        let elapsed = (now - self.clock_time).min(max_gap);
+        self.clock_time = now;
+
+        let running = !self.paused
+            && focused
+            && !matches!(self.dialog_state, DialogState::GameTypeSelection | DialogState::Respawn | DialogState::GameOver | DialogState::Victory);
+        if let Some(ref mut game_state) = self.game_state
+            && running
+        {
+            game_state.play_time += elapsed;
+            // Keep frames coming so the clock ticks without any input
+            let (reason, refresh) = if game_state.time_remaining().is_some() {
+                (RepaintReason::Countdown, 0.25)
+            } else {
+                (RepaintReason::Clock, 1.0)
+            };
+            self.repaint.request(reason, std::time::Duration::from_secs_f64(refresh));
+        }
+    }
+
+    /// Put the finished run on the high-score table, unless wizard mode was used
+    fn record_score(&mut self, ctx: &egui::Context, won: bool) {
+        let Some(ref game_state) = self.game_state else {
+            return;
+        };
+        if game_state.wizard_used {
+            return;
+        }
+        let mode = self.game_type.get_name();
+        self.profile.stats.record_run(mode, won, game_state.turn_counter, &game_state.run_stats);
+        self.save_profile();
+        let entry = if self.game_type == AvailableGameType::Endless {
+            ScoreEntry::from_descent(game_state, mode)
+        } else {
+            ScoreEntry::from_run(game_state, mode, won)
+        };
+        if self.settings.online_scores
+            && let Some(ref online) = self.online
+        {
+            online.submit(&entry, ctx);
+        }
+        let recorded = if self.game_type == AvailableGameType::Endless {
+            self.endless_scores.record(entry)
+        } else {
+            self.high_scores.record(entry)
+        };
+        match recorded {
+            Ok(place) => self.score_place = place,
+            Err(error) => tracing::warn!("couldn't save the high scores: {}", error),
+        }
+    }
+
+    /// Moving on to a different dialog starts its highlight back at the top
+    fn finish_menu_frame(&mut self, dialog_before: DialogState) {
+        self.menu.finish_frame();
+        if self.dialog_state != dialog_before {
+            self.menu = MenuNavigation::default();
+        }
+    }
+
     fn handle_input(&mut self, ctx: &egui::Context) {
         // Only handle input if game is initialized
         if let Some(ref mut game_state) = self.game_state {
@@ -183,215 +783,1652 @@ impl RoguelikeApp {
         }
 
         // Handle keyboard input for movement and quit
+        let mut interact_at = None;
         ctx.input(|i| {
             // Check for quit key first
-            if i.key_pressed(egui::Key::Q) {
+            if i.key_pressed(Action::Quit.key()) {
                 self.dialog_state = DialogState::QuitConfirmation;
                 return;
             }
 
-            // Only handle movement and commands if no dialog is shown and game is initialized
-            if self.dialog_state == DialogState::NoDialog {
-                if let Some(ref mut game_state) = self.game_state {
-                    let mut dx = 0;
-                    let mut dy = 0;
+            // Nothing happens in the game while it's paused
+            if self.dialog_state == DialogState::NoDialog
+                && self.game_state.is_some()
+                && i.key_pressed(Action::Pause.key())
+            {
+                self.paused = !self.paused;
+                return;
+            }
+            if self.paused {
+                return;
+            }
 
+            // While targeting, the movement keys steer the cursor instead of the player
+            if self.dialog_state == DialogState::Targeting {
+                if let Some(ref mut targeting) = self.targeting {
                     if i.key_pressed(egui::Key::ArrowUp) || i.key_pressed(egui::Key::W) {
-                        dy = -1;
+                        targeting.cursor.1 -= 1;
                     }
                     if i.key_pressed(egui::Key::ArrowDown) || i.key_pressed(egui::Key::S) {
-                        dy = 1;
+                        targeting.cursor.1 += 1;
                     }
                     if i.key_pressed(egui::Key::ArrowLeft) || i.key_pressed(egui::Key::A) {
-                        dx = -1;
+                        targeting.cursor.0 -= 1;
                     }
                     if i.key_pressed(egui::Key::ArrowRight) || i.key_pressed(egui::Key::D) {
-                        dx = 1;
+                        targeting.cursor.0 += 1;
                     }
+                }
+                if i.key_pressed(egui::Key::Enter) {
+                    self.confirm_target();
+                } else if i.key_pressed(egui::Key::Escape) {
+                    self.targeting = None;
+                    self.dialog_state = DialogState::NoDialog;
+                }
+                return;
+            }
+
+            // Every other dialog can be driven from the keyboard too
+            if self.dialog_state != DialogState::NoDialog {
+                self.menu.handle_keys(i);
+                return;
+            }
+
+            // Only handle movement and commands if no dialog is shown and game is initialized
+            if self.dialog_state == DialogState::NoDialog
+                && let Some(ref mut game_state) = self.game_state
+            {
+                let mut dx = 0;
+                let mut dy = 0;
 
-                    let mut player_acted = false;
+                if i.key_pressed(egui::Key::ArrowUp) || i.key_pressed(egui::Key::W) {
+                    dy = -1;
+                }
+                if i.key_pressed(egui::Key::ArrowDown) || i.key_pressed(egui::Key::S) {
+                    dy = 1;
+                }
+                if i.key_pressed(egui::Key::ArrowLeft) || i.key_pressed(egui::Key::A) {
+                    dx = -1;
+                }
+                if i.key_pressed(egui::Key::ArrowRight) || i.key_pressed(egui::Key::D) {
+                    dx = 1;
+                }
 
-                    // Try to move the player
+                // Interacting asks which way, unless there's only one thing within reach.
+                // Walking is only ever moving or attacking, so friendly folk are never hit by accident.
+                let (px, py) = game_state.player.position;
+                if self.choosing_interaction {
                     if dx != 0 || dy != 0 {
-                        game_state.try_move_player(dx, dy);
-                        player_acted = true;
+                        self.choosing_interaction = false;
+                        interact_at = Some((px + dx, py + dy));
+                    } else if i.key_pressed(egui::Key::Escape) {
+                        self.choosing_interaction = false;
+                        game_state.add_log_message("Never mind.".to_string());
+                    }
+                    return;
+                }
+                if i.key_pressed(Action::Interact.key()) || i.key_pressed(egui::Key::Enter) {
+                    match action::interaction_targets(game_state).as_slice() {
+                        [target] => interact_at = Some(*target),
+                        _ => {
+                            self.choosing_interaction = true;
+                            game_state.add_log_message("Interact in which direction?".to_string());
+                        }
+                    }
+                    return;
+                }
+
+                // Looking over equipment is free; changing it takes a turn
+                if i.key_pressed(Action::Equip.key()) {
+                    self.dialog_state = DialogState::Equip;
+                    return;
+                }
+
+                // Looking at the character sheet is free
+                if i.key_pressed(Action::CharacterSheet.key()) {
+                    self.dialog_state = DialogState::CharacterSheet;
+                    return;
+                }
+
+                if i.key_pressed(Action::Encyclopedia.key()) {
+                    self.dialog_state = DialogState::Encyclopedia;
+                    return;
+                }
+
+                if i.key_pressed(Action::Settings.key()) {
+                    self.dialog_state = DialogState::Settings;
+                    return;
+                }
+
+                // Moving the camera is free
+                if i.key_pressed(Action::CenterView.key()) {
+                    self.recenter_camera = true;
+                    return;
+                }
+                if i.key_pressed(Action::LockCamera.key()) {
+                    self.camera_follows = !self.camera_follows;
+                    return;
+                }
+
+                if i.key_pressed(Action::WizardMode.key()) {
+                    game_state.toggle_wizard_mode();
+                    return;
+                }
+
+                if i.key_pressed(Action::WizardDescend.key()) {
+                    game_state.wizard_descend();
+                    return;
+                }
+
+                if i.key_pressed(Action::ThreatOverlay.key()) {
+                    self.threat_overlay = !self.threat_overlay;
+                    return;
+                }
+
+                // Looking around is free
+                if i.key_pressed(Action::DescribeSurroundings.key()) {
+                    game_state.add_log_message(accessibility::describe_surroundings(game_state));
+                    return;
+                }
+
+                if i.key_pressed(Action::DebugOverlay.key()) {
+                    self.debug_overlay = !self.debug_overlay;
+                    return;
+                }
+
+                #[cfg(debug_assertions)]
+                if i.key_pressed(Action::DumpState.key()) {
+                    let message = match dump_game_state(game_state) {
+                        Ok(path) => format!("Game state dumped to {}", path.display()),
+                        Err(error) => format!("Couldn't dump the game state: {}", error),
+                    };
+                    game_state.add_log_message(message);
+                    return;
+                }
+
+                if i.key_pressed(Action::ExportMap.key()) {
+                    let message = match export_map(game_state) {
+                        Ok(path) => format!("Map saved to {}", path.display()),
+                        Err(error) => format!("Couldn't save the map: {}", error),
+                    };
+                    game_state.add_log_message(message);
+                    return;
+                }
+
+                // Aimed abilities need a target picked first; the rest are used straight away below
+                let ability = game_state.player.class.ability();
+                if i.key_pressed(Action::Ability.key()) {
+                    if let Err(reason) = game_state.check_ability() {
+                        game_state.add_log_message(reason);
+                        return;
                     }
+                    if ability.range().is_some() {
+                        // Start on the closest creature that can be targeted, if there is one
+                        let (px, py) = game_state.player.position;
+                        let cursor = game_state.npcs.iter()
+                            .map(|npc| npc.position(&game_state.world))
+                            .filter(|position| game_state.is_valid_ability_target(ability, *position))
+                            .min_by_key(|position| (position.0 - px).abs().max((position.1 - py).abs()))
+                            .unwrap_or((px, py));
+                        self.targeting = Some(Targeting { purpose: TargetingPurpose::Ability(ability), cursor });
+                        self.dialog_state = DialogState::Targeting;
+                        return;
+                    }
+                }
 
-                    // Check for pickup command
-                    if i.key_pressed(egui::Key::P) {
-                        game_state.try_pickup_item();
-                        player_acted = true;
+                // Choosing potions is free; the brewing itself takes a turn
+                if i.key_pressed(Action::MixPotions.key()) {
+                    let potion_count = game_state.player.inventory.iter()
+                        .filter(|item| item.item_type == ItemType::Potion)
+                        .count();
+                    if !game_state.is_next_to_cauldron() {
+                        game_state.add_log_message("You need a cauldron to mix potions.".to_string());
+                    } else if potion_count < 2 {
+                        game_state.add_log_message("You need two potions to mix.".to_string());
+                    } else {
+                        self.mix_selection.clear();
+                        self.dialog_state = DialogState::MixPotions;
                     }
+                    return;
+                }
 
-                    // Check for use item command
-                    if i.key_pressed(egui::Key::U) {
-                        if !game_state.player.inventory.is_empty() {
-                            self.dialog_state = DialogState::UseItem;
-                        } else {
-                            game_state.add_log_message("You have no items to use.".to_string());
-                        }
-                        player_acted = true;
+                // Choosing what to drop is free; dropping it takes a turn
+                if i.key_pressed(Action::DropItem.key()) {
+                    if game_state.player.inventory.is_empty() {
+                        game_state.add_log_message("You have nothing to drop.".to_string());
+                    } else {
+                        self.dialog_state = DialogState::DropItem;
                     }
+                    return;
+                }
+
+                // Picking from a pile of items opens a list to choose from first
+                if i.key_pressed(Action::PickUp.key()) && game_state.items_at(game_state.player.position).len() > 1 {
+                    self.pickup_selection.clear();
+                    self.dialog_state = DialogState::PickUp;
+                    return;
+                }
 
-                    // Process NPC actions after player acts
-                    if player_acted {
-                        game_state.increment_turn();
-                        game_state.process_npc_actions();
+                // Choosing what to use is free; using it takes a turn
+                if i.key_pressed(Action::UseItem.key()) {
+                    if game_state.player.inventory.is_empty() {
+                        game_state.add_log_message("You have no items to use.".to_string());
+                    } else {
+                        self.dialog_state = DialogState::UseItem;
                     }
+                    return;
                 }
+
+                let command = if dx != 0 || dy != 0 {
+                    Command::Move { dx, dy }
+                } else if i.key_pressed(Action::PickUp.key()) {
+                    Command::PickUp
+                } else if i.key_pressed(Action::Rest.key()) {
+                    Command::Wait
+                } else if i.key_pressed(Action::Ability.key()) {
+                    Command::UseAbility(None) // Abilities that don't need aiming
+                } else {
+                    return;
+                };
+                game_state.apply_command(command);
             }
         });
+        if let Some(target) = interact_at {
+            self.interact_toward(target);
+        }
+    }
+
+    /// Talk to, sit at or use whatever is on an adjacent tile, or search it if there's nothing there
+    fn interact_toward(&mut self, target: (i32, i32)) {
+        let Some(ref mut game_state) = self.game_state else {
+            return;
+        };
+        if let Some(npc) = game_state.npcs.iter()
+            .find(|npc| npc.position(&game_state.world) == target && game_state.npc_visibility(npc) == Visibility::Visible)
+        {
+            if game_state.needs_bump_menu(npc) {
+                self.interaction_target = Some(target);
+                self.dialog_state = DialogState::BumpMenu;
+            } else {
+                let message = format!("{} has nothing to say to you.", npc.name);
+                game_state.add_log_message(message);
+            }
+            return;
+        }
+        match game_state.world.get_tile(target.0, target.1) {
+            Some(TileType::Campfire) => self.dialog_state = DialogState::Campfire,
+            Some(TileType::Altar) => self.dialog_state = DialogState::Altar,
+            Some(TileType::Workbench) => {
+                self.socket_target = None;
+                self.dialog_state = DialogState::Workbench;
+            }
+            _ => {
+                game_state.apply_command(Command::Search(target));
+            }
+        }
+    }
+
+    /// Read the Blink scroll or use the ability being targeted at the cursor's tile.
+    /// An invalid tile keeps targeting open.
+    fn confirm_target(&mut self) {
+        let (Some(game_state), Some(targeting)) = (self.game_state.as_mut(), self.targeting.as_ref()) else {
+            return;
+        };
+        let command = match targeting.purpose {
+            TargetingPurpose::Blink { item_index, .. } => Command::ReadBlinkScroll { item_index, target: targeting.cursor },
+            TargetingPurpose::Ability(_) => Command::UseAbility(Some(targeting.cursor)),
+        };
+        if !game_state.apply_command(command).contains(&GameEvent::Rejected) {
+            self.targeting = None;
+            self.dialog_state = DialogState::NoDialog;
+        }
     }
 
     fn show_game_type_selection_dialog(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::Window::new("Select Game Type")
+        let lang = self.settings.language;
+        egui::Window::new(tr(lang, "select.title"))
             .collapsible(false)
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
             .show(ctx, |ui| {
                 ui.vertical_centered(|ui| {
                     ui.add_space(10.0);
-                    ui.label("Choose your adventure:");
+                    ui.label(tr(lang, "select.class"));
+                    ui.horizontal(|ui| {
+                        for class in PlayerClass::ALL {
+                            if self.menu.selectable(ui, self.selected_class == class, class.get_name()) {
+                                self.selected_class = class;
+                            }
+                        }
+                    });
+                    ui.label(self.selected_class.get_description());
+                    ui.add_space(10.0);
+                    self.menu.checkbox(ui, &mut self.hardcore, tr(lang, "select.hardcore"));
+                    ui.add_space(20.0);
+
+                    ui.label(tr(lang, "select.adventure"));
                     ui.add_space(20.0);
 
                     let game_types = vec![
                         AvailableGameType::TreasureHunt,
                         AvailableGameType::Survival,
                         AvailableGameType::Collection,
+                        AvailableGameType::Pacifist,
+                        AvailableGameType::Speedrun,
+                        AvailableGameType::Arena,
+                        AvailableGameType::Endless,
+                        AvailableGameType::Mystery,
                     ];
 
                     for game_type in game_types {
                         ui.group(|ui| {
                             ui.vertical(|ui| {
                                 ui.strong(game_type.get_name());
-                                ui.label(game_type.get_description());
+                                ui.label(game_type.get_description(lang));
                                 ui.add_space(5.0);
-                                if ui.button("Play this mode").clicked() {
+                                if game_type.has_stairs() {
+                                    self.menu.checkbox(ui, &mut self.collapsing_floors, tr(lang, "select.collapsing"));
+                                }
+                                if self.menu.button(ui, tr(lang, "select.play")) {
                                     self.start_game_with_type(game_type);
                                 }
                             });
                         });
                         ui.add_space(10.0);
                     }
+
+                    if self.checkpoint_save.is_some() && self.menu.button(ui, tr(lang, "select.resume_checkpoint")) {
+                        self.resume_checkpoint_run();
+                    }
+                    if self.crash_save.is_some() && self.menu.button(ui, tr(lang, "select.resume_crash")) {
+                        self.resume_crashed_run();
+                    }
+                    if self.menu.button(ui, tr(lang, "stats.title")) {
+                        self.dialog_state = DialogState::Statistics;
+                    }
+                    if self.menu.button(ui, tr(lang, "encyclopedia.title")) {
+                        self.dialog_state = DialogState::Encyclopedia;
+                    }
+                    ui.add_space(10.0);
+
+                    egui::CollapsingHeader::new(tr(lang, "scores.title")).show(ui, |ui| self.draw_high_scores(ui, false));
+                    egui::CollapsingHeader::new(tr(lang, "scores.endless_title")).show(ui, |ui| self.draw_high_scores(ui, true));
+                    if self.online.is_some() {
+                        egui::CollapsingHeader::new(tr(lang, "scores.online_title")).show(ui, |ui| self.draw_online_scores(ui));
+                    }
                     
                     ui.add_space(10.0);
                 });
             });
     }
 
-    fn start_game_with_type(&mut self, game_type: AvailableGameType) {
-        let game_condition: Box<dyn game_condition::GameCondition> = match game_type {
-            AvailableGameType::TreasureHunt => Box::new(TreasureHuntCondition),
-            AvailableGameType::Survival => Box::new(SurvivalCondition::new(200)),
-            AvailableGameType::Collection => Box::new(CollectionCondition::new(vec![
-                (ItemType::Gem, 3),
-                (ItemType::Scroll, 2),
-                (ItemType::Potion, 1),
-            ])),
-        };
-
-        self.game_state = Some(GameState::with_condition(game_condition));
-        self.dialog_state = DialogState::NoDialog;
-    }
-
-    fn show_quit_confirmation_dialog(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::Window::new("Quit Game")
+    fn show_statistics_dialog(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        let stats = &self.profile.stats;
+        egui::Window::new(tr(lang, "stats.title"))
             .collapsible(false)
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
             .show(ctx, |ui| {
-                ui.vertical_centered(|ui| {
+                ui.vertical(|ui| {
                     ui.add_space(10.0);
-                    ui.label("Are you sure you want to quit?");
-                    ui.add_space(20.0);
-                    
-                    ui.horizontal(|ui| {
-                        ui.add_space(20.0);
-                        if ui.button("Yes").clicked() {
-                            ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
+                    ui.label(i18n::labeled(lang, "stats.runs", stats.runs));
+                    ui.label(i18n::labeled(lang, "stats.wins", stats.total_wins()));
+                    ui.label(i18n::labeled(lang, "stats.turns", stats.turns));
+                    ui.label(i18n::labeled(lang, "stats.kills", stats.total_kills()));
+                    match stats.favorite_item() {
+                        Some((label, count)) => ui.label(i18n::labeled(lang, "stats.favorite_item", trf(lang, "stats.times_used", &[&label, &count]))),
+                        None => ui.label(i18n::labeled(lang, "stats.favorite_item", tr(lang, "character.none_yet"))),
+                    };
+                    ui.add_space(10.0);
+
+                    ui.columns(2, |columns| {
+                        columns[0].strong(tr(lang, "stats.wins_by_mode"));
+                        for (mode, wins) in &stats.wins {
+                            columns[0].label(format!("{}: {}", mode, wins));
                         }
-                        ui.add_space(20.0);
-                        if ui.button("No").clicked() {
-                            self.dialog_state = DialogState::NoDialog;
+                        columns[1].strong(tr(lang, "stats.kills_by_type"));
+                        for (kind, kills) in &stats.kills {
+                            columns[1].label(format!("{}: {}", kind, kills));
                         }
-                        ui.add_space(20.0);
                     });
+
                     ui.add_space(10.0);
-                });
+                    if self.menu.button(ui, tr(lang, "common.close")) {
+                        self.dialog_state = DialogState::GameTypeSelection;
+                    }
+                    ui.add_space(10.0);
+                });
+            });
+    }
+
+    /// Every creature and item the player has come across, across all their runs
+    fn show_encyclopedia_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        let encyclopedia = &self.profile.encyclopedia;
+        egui::Window::new(tr(lang, "encyclopedia.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.vertical(|ui| {
+                    ui.add_space(10.0);
+                    egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                        ui.strong(trf(lang, "encyclopedia.bestiary", &[&encyclopedia.creatures.len(), &NPCType::ALL.len()]));
+                        for npc_type in NPCType::ALL {
+                            if !encyclopedia.creatures.contains(&npc_type) {
+                                ui.weak(tr(lang, "encyclopedia.unknown"));
+                                continue;
+                            }
+                            egui::CollapsingHeader::new(npc_type.get_name()).show(ui, |ui| {
+                                ui.label(npc_type.get_description(lang));
+                                ui.label(i18n::labeled(lang, "stat.health", npc_type.max_health()));
+                                ui.label(tr(lang, "stat.known_abilities"));
+                                for ability in npc_type.abilities(lang) {
+                                    ui.label(format!("  - {}", ability));
+                                }
+                                ui.label(egui::RichText::new(npc_type.lore(lang)).italics());
+                            });
+                        }
+
+                        ui.add_space(10.0);
+                        ui.strong(trf(lang, "encyclopedia.items", &[&encyclopedia.items.len()]));
+                        if encyclopedia.items.is_empty() {
+                            ui.weak(tr(lang, "encyclopedia.no_items"));
+                        }
+                        for (label, item) in &encyclopedia.items {
+                            egui::CollapsingHeader::new(i18n::item_name(lang, label)).show(ui, |ui| {
+                                ui.label(i18n::labeled(lang, "stat.type", item.item_type.get_name()));
+                                ui.label(&item.description);
+                                draw_item_stats(ui, lang, item);
+                                if let Some(effect) = &item.effect {
+                                    ui.label(i18n::labeled(lang, "stat.effect", effect.describe()));
+                                }
+                                ui.label(trf(lang, "stat.value", &[&item.value()]));
+                                if let Some(lore) = catalog::lore(&item.label) {
+                                    ui.label(egui::RichText::new(lore).italics());
+                                }
+                            });
+                        }
+                    });
+
+                    ui.add_space(10.0);
+                    if self.menu.button(ui, tr(lang, "common.close")) {
+                        // Opened from the game type selection if there's no run under way
+                        self.dialog_state = if self.game_state.is_some() { DialogState::NoDialog } else { DialogState::GameTypeSelection };
+                    }
+                    ui.add_space(10.0);
+                });
+            });
+    }
+
+    fn start_game_with_type(&mut self, game_type: AvailableGameType) {
+        let game_condition: Box<dyn game_condition::GameCondition> = match game_type {
+            AvailableGameType::TreasureHunt => Box::new(TreasureHuntCondition),
+            AvailableGameType::Survival => Box::new(SurvivalCondition::new(200)),
+            AvailableGameType::Collection => Box::new(CollectionCondition::new(vec![
+                (ItemType::Gem, 3),
+                (ItemType::Scroll, 2),
+                (ItemType::Potion, 1),
+            ])),
+            AvailableGameType::Pacifist => Box::new(PacifistCondition),
+            AvailableGameType::Speedrun => Box::new(SpeedrunCondition::new(300.0)),
+            AvailableGameType::Arena => Box::new(ArenaCondition),
+            AvailableGameType::Endless => Box::new(EndlessCondition),
+            AvailableGameType::Mystery => Box::new(MysteryCondition::random()),
+        };
+
+        tracing::info!(mode = game_type.get_name(), class = self.selected_class.get_name(), "new game");
+        let mut game_state = GameState::with_condition(game_condition, self.selected_class, self.settings.clone());
+        game_state.encyclopedia = self.profile.encyclopedia.clone();
+        game_state.stash = self.profile.stash.clone();
+        if self.collapsing_floors && game_type.has_stairs() {
+            game_state.enable_collapsing_floors();
+        }
+        if self.hardcore {
+            game_state.enable_hardcore();
+        }
+        self.game_state = Some(game_state);
+        self.game_type = game_type;
+        self.announcer = accessibility::Announcer::default();
+        self.toasts = toast::Toasts::default();
+        self.score_place = None;
+        self.paused = false;
+        // Anything banked last run can be taken along before setting off
+        self.dialog_state = if self.profile.stash.is_empty() { DialogState::NoDialog } else { DialogState::Stash };
+    }
+
+    /// Pick up a run from the newest emergency save. The save is deleted once it's loaded,
+    /// so the same crash can't be resumed twice.
+    fn resume_crashed_run(&mut self) {
+        let Some(path) = self.crash_save.take() else {
+            return;
+        };
+        if !self.load_run(&path, "You pick up where you left off.") {
+            return;
+        }
+        if let Err(error) = std::fs::remove_file(&path) {
+            tracing::warn!("Couldn't remove the emergency save {}: {}", path.display(), error);
+        }
+        tracing::info!(path = %path.display(), "resumed crashed run");
+        // Anything else left over from earlier crashes is next in line
+        self.crash_save = latest_emergency_save();
+    }
+
+    /// Pick up an unfinished run from the last checkpoint rune it touched. The save stays until
+    /// the run is over, and the next rune touched replaces it.
+    fn resume_checkpoint_run(&mut self) {
+        let Some(path) = self.checkpoint_save.clone() else {
+            return;
+        };
+        if self.load_run(&path, "You pick up where you left off, at the last rune you touched.") {
+            tracing::info!(path = %path.display(), "resumed run from its checkpoint");
+        } else {
+            self.checkpoint_save = None;
+        }
+    }
+
+    /// Put a saved run in play with a fresh interface. Returns false if the save couldn't be read.
+    fn load_run(&mut self, path: &std::path::Path, message: &str) -> bool {
+        let mut game_state = match read_game_state(path) {
+            Ok(game_state) => game_state,
+            Err(error) => {
+                tracing::error!("Couldn't resume the run saved in {}: {}", path.display(), error);
+                return false;
+            }
+        };
+        game_state.settings = self.settings.clone();
+        game_state.add_log_message(message.to_string());
+        self.game_type = game_type_of(&game_state.game_condition.saved());
+        self.game_state = Some(game_state);
+        self.announcer = accessibility::Announcer::default();
+        self.toasts = toast::Toasts::default();
+        self.score_place = None;
+        self.paused = false;
+        self.dialog_state = DialogState::NoDialog;
+        true
+    }
+
+    /// Throw away the checkpoint autosave once its run is over, so a finished run can't be resumed
+    fn discard_checkpoint_save(&mut self) {
+        let Some(path) = self.checkpoint_save.take() else {
+            return;
+        };
+        if let Err(error) = std::fs::remove_file(&path) {
+            tracing::warn!("Couldn't remove the checkpoint save {}: {}", path.display(), error);
+        }
+    }
+
+    /// Take the next step of a walk to a tapped tile. It stops at the destination, when a bump
+    /// or anything else opens a dialog, when the player is hurt, or once a hostile comes into view.
+    fn continue_tap_walk(&mut self) {
+        const STEP_DELAY: std::time::Duration = std::time::Duration::from_millis(80);
+
+        let (Some(walk), Some(game_state)) = (self.tap_walk, &self.game_state) else {
+            return;
+        };
+        if !self.pending_keys.is_empty() {
+            return; // The last step hasn't been taken yet
+        }
+        // Tapping something next to the player interacts with it
+        let (px, py) = game_state.player.position;
+        if geometry::manhattan_distance(walk.target, (px, py)) == 1 && action::is_interactable(game_state, walk.target) {
+            self.tap_walk = None;
+            self.interact_toward(walk.target);
+            return;
+        }
+        let hostile_in_view = game_state.npcs.iter()
+            .any(|npc| game_state.is_hostile_to_player(npc) && game_state.npc_visibility(npc) == Visibility::Visible);
+        let blocked = |step: (i32, i32)| game_state.npcs.iter().any(|npc| npc.position(&game_state.world) == step && game_state.needs_bump_menu(npc));
+        let interrupted = self.dialog_state != DialogState::NoDialog
+            || self.paused
+            || game_state.player.health < walk.health
+            || (walk.steps > 0 && hostile_in_view);
+        let next_step = game_state.player_path_to(walk.target).and_then(|path| path.first().copied());
+        let Some((x, y)) = next_step.filter(|&step| !interrupted && !blocked(step)) else {
+            self.tap_walk = None;
+            return;
+        };
+        let key = match (x - px, y - py) {
+            (0, -1) => egui::Key::ArrowUp,
+            (0, 1) => egui::Key::ArrowDown,
+            (-1, 0) => egui::Key::ArrowLeft,
+            _ => egui::Key::ArrowRight,
+        };
+        self.pending_keys.push(key);
+        self.tap_walk = Some(TapWalk { steps: walk.steps + 1, ..walk });
+        let delay = if self.settings.allows(VisualEffect::Motion) { STEP_DELAY } else { std::time::Duration::ZERO };
+        self.repaint.request(RepaintReason::Walk, delay);
+    }
+
+    /// Write newly unlocked encyclopedia entries from the run into the profile
+    fn save_encyclopedia(&mut self) {
+        let Some(ref mut game_state) = self.game_state else {
+            return;
+        };
+        if game_state.encyclopedia_changed {
+            game_state.encyclopedia_changed = false;
+            self.profile.encyclopedia = game_state.encyclopedia.clone();
+            self.save_profile();
+        }
+    }
+
+    /// Write what's left in the stash back into the profile once items are taken out of it
+    fn save_stash(&mut self) {
+        let Some(ref mut game_state) = self.game_state else {
+            return;
+        };
+        if game_state.stash_changed {
+            game_state.stash_changed = false;
+            self.profile.stash = game_state.stash.clone();
+            self.save_profile();
+        }
+    }
+
+    /// Write the run under `saves/` whenever a checkpoint rune saves it, ready to resume from
+    /// the game type selection
+    fn autosave(&mut self) {
+        let Some(ref mut game_state) = self.game_state else {
+            return;
+        };
+        if game_state.checkpoint_reached {
+            game_state.checkpoint_reached = false;
+            match write_autosave(game_state) {
+                Ok(path) => self.checkpoint_save = Some(path),
+                Err(error) => tracing::warn!("couldn't autosave at the checkpoint: {}", error),
+            }
+        }
+    }
+
+    /// Bring the player back at their checkpoint. Anything the UI was in the middle of when
+    /// they died is cleared away, so play picks up as if from the start of a fresh turn.
+    fn respawn(&mut self) {
+        if let Some(ref mut game_state) = self.game_state {
+            game_state.respawn();
+        }
+        self.dialog_state = DialogState::NoDialog;
+        self.targeting = None;
+        self.interaction_target = None;
+        self.examined_item = None;
+        self.mix_selection.clear();
+        self.socket_target = None;
+        self.pickup_selection.clear();
+        self.choosing_interaction = false;
+        self.tap_walk = None;
+        self.pending_keys.clear();
+        self.recenter_camera = true;
+    }
+
+    fn save_profile(&self) {
+        if let Err(error) = self.profile.save() {
+            tracing::warn!("couldn't save the profile: {}", error);
+        }
+    }
+
+    fn show_quit_confirmation_dialog(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        egui::Window::new(tr(lang, "quit.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(10.0);
+                    ui.label(tr(lang, "quit.confirm"));
+                    ui.add_space(20.0);
+                    
+                    ui.horizontal(|ui| {
+                        ui.add_space(20.0);
+                        if self.menu.button(ui, tr(lang, "common.yes")) {
+                            ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
+                        }
+                        ui.add_space(20.0);
+                        if self.menu.button(ui, tr(lang, "common.no")) {
+                            self.dialog_state = DialogState::NoDialog;
+                        }
+                        ui.add_space(20.0);
+                    });
+                    ui.add_space(10.0);
+                });
+            });
+    }
+
+    fn show_respawn_dialog(&mut self, ctx: &egui::Context) {
+        let lang = self.settings.language;
+        let respawns_left = self.game_state.as_ref().map_or(0, |game_state| game_state.respawns_left);
+        let mut rise = None;
+        egui::Window::new(tr(lang, "respawn.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(10.0);
+                    ui.label(trf(lang, "respawn.prompt", &[&respawns_left]));
+                    ui.add_space(20.0);
+                    if self.menu.button(ui, tr(lang, "respawn.rise")) {
+                        rise = Some(true);
+                    }
+                    if self.menu.button(ui, tr(lang, "respawn.give_up")) {
+                        rise = Some(false);
+                    }
+                    ui.add_space(10.0);
+                });
+            });
+        match rise {
+            Some(true) => self.respawn(),
+            Some(false) => {
+                self.loss_reason = Some(LossReason::Died);
+                self.dialog_state = DialogState::GameOver;
+                self.record_score(ctx, false);
+                self.discard_checkpoint_save();
+            }
+            None => {}
+        }
+    }
+
+    fn show_game_over_dialog(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        egui::Window::new(tr(lang, "game_over.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(10.0);
+                    let reason = match &self.loss_reason {
+                        Some(LossReason::Died) | None => tr(lang, "game_over.met_end").to_string(),
+                        Some(LossReason::OutOfTurns) => tr(lang, "game_over.out_of_turns").to_string(),
+                        Some(LossReason::OutOfTime) => tr(lang, "game_over.out_of_time").to_string(),
+                        Some(LossReason::QuestItemLost(item)) => trf(lang, "game_over.item_lost", &[item]),
+                    };
+                    ui.strong(reason);
+                    if let Some(ref game_state) = self.game_state {
+                        ui.label(game_state.get_loss_description());
+                    }
+                    ui.label(tr(lang, "game_over.title"));
+                    if let Some(ref game_state) = self.game_state
+                        && self.game_type == AvailableGameType::Endless
+                    {
+                        ui.label(trf(lang, "game_over.floor", &[&game_state.world.current_floor]));
+                    }
+                    ui.add_space(10.0);
+                    self.draw_high_scores(ui, self.game_type == AvailableGameType::Endless);
+                    if self.online.is_some() {
+                        self.draw_online_scores(ui);
+                    }
+                    ui.add_space(20.0);
+                    
+                    if self.menu.button(ui, tr(lang, "common.ok")) {
+                        ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
+                    }
+                    
+                    ui.add_space(10.0);
+                });
+            });
+    }
+
+    fn show_use_item_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        if let Some(ref mut game_state) = self.game_state {
+            egui::Window::new(tr(lang, "use_item.title"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .show(ctx, |ui| {
+                    ui.vertical(|ui| {
+                        ui.add_space(10.0);
+                        ui.label(tr(lang, "use_item.prompt"));
+                        ui.add_space(10.0);
+
+                        let mut item_to_use: Option<usize> = None;
+
+                        // Show each item in inventory as a button
+                        for (index, item) in game_state.player.inventory.iter().enumerate() {
+                            if self.menu.button(ui, game_state.item_name(item)) {
+                                item_to_use = Some(index);
+                            }
+                        }
+
+                        ui.add_space(10.0);
+
+                        // Cancel button
+                        if self.menu.button(ui, tr(lang, "common.cancel")) {
+                            self.dialog_state = DialogState::NoDialog;
+                        }
+
+                        // Blink scrolls need a destination picked on the map before they can be read
+                        if let Some(index) = item_to_use
+                            && let Some(ItemEffect::Blink(range)) = game_state.player.inventory[index].effect
+                        {
+                            self.targeting = Some(Targeting {
+                                purpose: TargetingPurpose::Blink { item_index: index, range },
+                                cursor: game_state.player.position,
+                            });
+                            self.dialog_state = DialogState::Targeting;
+                            return;
+                        }
+
+                        // Handle item usage
+                        if let Some(index) = item_to_use {
+                            game_state.apply_command(Command::UseItem(index));
+                            self.dialog_state = DialogState::NoDialog;
+                        }
+
+                        ui.add_space(10.0);
+                    });
+                });
+        }
+    }
+
+    fn show_equip_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        if let Some(ref mut game_state) = self.game_state {
+            egui::Window::new(tr(lang, "equip.title"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .show(ctx, |ui| {
+                    ui.vertical(|ui| {
+                        ui.add_space(10.0);
+
+                        let mut slot_to_clear: Option<EquipSlot> = None;
+                        let mut drop_torch = false;
+                        let mut item_to_equip: Option<(usize, EquipSlot)> = None;
+
+                        // Currently equipped items
+                        for slot in EquipSlot::ALL {
+                            ui.horizontal(|ui| {
+                                match game_state.player.equipment.get(slot) {
+                                    Some(item) => {
+                                        ui.label(format!("{}: {}", slot.get_name(), item.label));
+                                        if self.menu.button(ui, tr(lang, "equip.unequip")) {
+                                            slot_to_clear = Some(slot);
+                                        }
+                                        if item.item_type == ItemType::Torch && self.menu.button(ui, tr(lang, "equip.drop_lit")) {
+                                            drop_torch = true;
+                                        }
+                                    }
+                                    None => {
+                                        ui.label(format!("{}: {}", slot.get_name(), tr(lang, "common.empty")));
+                                    }
+                                }
+                            });
+                        }
+
+                        ui.add_space(10.0);
+                        ui.label(tr(lang, "equip.from_inventory"));
+
+                        // Every equippable item, with a button per slot. Slots that would conflict
+                        // with what's already held are disabled and explain why on hover.
+                        let player = &game_state.player;
+                        for (index, item) in player.inventory.iter().enumerate() {
+                            if item.equip_slot().is_none() {
+                                continue;
+                            }
+                            ui.horizontal(|ui| {
+                                ui.label(item.display_name(lang));
+                                if item.is_two_handed() {
+                                    ui.weak(tr(lang, "equip.two_handed"));
+                                }
+                                for slot in item.possible_slots() {
+                                    let allowed = player.check_equip(lang, item, slot);
+                                    let (button, pressed) = self.menu.enabled_button(ui, allowed.is_ok(), slot.get_name());
+                                    if let Err(reason) = allowed {
+                                        button.on_disabled_hover_text(reason);
+                                    } else if button.clicked() || pressed {
+                                        item_to_equip = Some((index, slot));
+                                    }
+                                }
+                            });
+                        }
+
+                        ui.add_space(10.0);
+
+                        if self.menu.button(ui, tr(lang, "common.close")) {
+                            self.dialog_state = DialogState::NoDialog;
+                        }
+
+                        // Changing equipment takes a turn
+                        if drop_torch {
+                            game_state.apply_command(Command::DropLitTorch);
+                        }
+                        if let Some(slot) = slot_to_clear {
+                            game_state.apply_command(Command::Unequip(slot));
+                        }
+                        if let Some((item_index, slot)) = item_to_equip {
+                            game_state.apply_command(Command::Equip { item_index, slot });
+                        }
+
+                        ui.add_space(10.0);
+                    });
+                });
+        }
+    }
+
+    fn show_mix_potions_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        if let Some(ref mut game_state) = self.game_state {
+            egui::Window::new(tr(lang, "mix.title"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .show(ctx, |ui| {
+                    ui.vertical(|ui| {
+                        ui.add_space(10.0);
+                        ui.label(tr(lang, "mix.prompt"));
+                        ui.add_space(10.0);
+
+                        // Toggle potions in and out of the selection, two at most
+                        for (index, item) in game_state.player.inventory.iter().enumerate() {
+                            if item.item_type != ItemType::Potion {
+                                continue;
+                            }
+                            let selected = self.mix_selection.contains(&index);
+                            if self.menu.selectable(ui, selected, game_state.item_name(item)) {
+                                if selected {
+                                    self.mix_selection.retain(|&chosen| chosen != index);
+                                } else if self.mix_selection.len() < 2 {
+                                    self.mix_selection.push(index);
+                                }
+                            }
+                        }
+
+                        ui.add_space(10.0);
+
+                        ui.horizontal(|ui| {
+                            let (mix, pressed) = self.menu.enabled_button(ui, self.mix_selection.len() == 2, tr(lang, "mix.mix"));
+                            if mix.clicked() || pressed {
+                                game_state.apply_command(Command::MixPotions(self.mix_selection[0], self.mix_selection[1]));
+                                self.mix_selection.clear();
+                                self.dialog_state = DialogState::NoDialog;
+                            }
+                            if self.menu.button(ui, tr(lang, "common.cancel")) {
+                                self.mix_selection.clear();
+                                self.dialog_state = DialogState::NoDialog;
+                            }
+                        });
+
+                        ui.add_space(10.0);
+                    });
+                });
+        }
+    }
+
+    fn show_drop_item_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        if let Some(ref mut game_state) = self.game_state {
+            egui::Window::new(tr(lang, "drop.title"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .show(ctx, |ui| {
+                    ui.vertical(|ui| {
+                        ui.add_space(10.0);
+                        ui.label(tr(lang, "drop.prompt"));
+                        ui.add_space(10.0);
+
+                        let mut item_to_drop: Option<usize> = None;
+                        for (index, item) in game_state.player.inventory.iter().enumerate() {
+                            if self.menu.button(ui, game_state.item_name(item)) {
+                                item_to_drop = Some(index);
+                            }
+                        }
+
+                        ui.add_space(10.0);
+
+                        if self.menu.button(ui, tr(lang, "common.cancel")) {
+                            self.dialog_state = DialogState::NoDialog;
+                        }
+
+                        if let Some(index) = item_to_drop {
+                            game_state.apply_command(Command::DropItem(index));
+                            self.dialog_state = DialogState::NoDialog;
+                        }
+
+                        ui.add_space(10.0);
+                    });
+                });
+        }
+    }
+
+    fn show_pickup_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        if let Some(ref mut game_state) = self.game_state {
+            egui::Window::new(tr(lang, "pick_up.title"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .show(ctx, |ui| {
+                    ui.vertical(|ui| {
+                        ui.add_space(10.0);
+                        ui.label(tr(lang, "pick_up.prompt"));
+                        ui.add_space(10.0);
+
+                        let pile = game_state.items_at(game_state.player.position);
+                        for &index in &pile {
+                            let selected = self.pickup_selection.contains(&index);
+                            let label = game_state.item_name(&game_state.world.items[index].item);
+                            if self.menu.selectable(ui, selected, label) {
+                                if selected {
+                                    self.pickup_selection.retain(|&chosen| chosen != index);
+                                } else {
+                                    self.pickup_selection.push(index);
+                                }
+                            }
+                        }
+
+                        ui.add_space(10.0);
+
+                        // Picking up any number of items from the pile takes a single turn
+                        let mut to_pick_up: Option<Vec<usize>> = None;
+                        ui.horizontal(|ui| {
+                            let (pick_up, pressed) = self.menu.enabled_button(ui, !self.pickup_selection.is_empty(), tr(lang, "pick_up.selected"));
+                            if pick_up.clicked() || pressed {
+                                to_pick_up = Some(self.pickup_selection.clone());
+                            }
+                            if self.menu.button(ui, tr(lang, "pick_up.all")) {
+                                to_pick_up = Some(pile.clone());
+                            }
+                            if self.menu.button(ui, tr(lang, "common.cancel")) {
+                                self.pickup_selection.clear();
+                                self.dialog_state = DialogState::NoDialog;
+                            }
+                        });
+                        if let Some(indices) = to_pick_up {
+                            game_state.apply_command(Command::PickUpItems(indices));
+                            self.pickup_selection.clear();
+                            self.dialog_state = DialogState::NoDialog;
+                        }
+
+                        ui.add_space(10.0);
+                    });
+                });
+        }
+    }
+
+    fn show_campfire_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        if let Some(ref mut game_state) = self.game_state {
+            egui::Window::new(tr(lang, "campfire.title"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .show(ctx, |ui| {
+                    ui.vertical(|ui| {
+                        ui.add_space(10.0);
+                        ui.label(tr(lang, "campfire.crackles"));
+                        ui.add_space(10.0);
+
+                        let mut item_to_cook: Option<usize> = None;
+
+                        // Each raw food item can be cooked into a more filling meal
+                        for (index, item) in game_state.player.inventory.iter().enumerate() {
+                            if item.item_type == ItemType::RawFood && self.menu.button(ui, trf(lang, "campfire.cook", &[&item.display_name(lang)])) {
+                                item_to_cook = Some(index);
+                            }
+                        }
+
+                        if self.menu.button(ui, tr(lang, "campfire.rest")) {
+                            game_state.apply_command(Command::RestByCampfire);
+                            self.dialog_state = DialogState::NoDialog;
+                        }
+
+                        ui.add_space(10.0);
+
+                        if self.menu.button(ui, tr(lang, "campfire.leave")) {
+                            self.dialog_state = DialogState::NoDialog;
+                        }
+
+                        // Cooking takes a turn, but the player stays by the fire
+                        if let Some(index) = item_to_cook {
+                            game_state.apply_command(Command::Cook(index));
+                        }
+
+                        ui.add_space(10.0);
+                    });
+                });
+        }
+    }
+
+    fn show_altar_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        const OFFERINGS: [i32; 3] = [10, 25, 50];
+
+        let lang = self.settings.language;
+        if let Some(ref mut game_state) = self.game_state {
+            egui::Window::new(tr(lang, "altar.title"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .show(ctx, |ui| {
+                    ui.vertical(|ui| {
+                        ui.add_space(10.0);
+                        ui.label(tr(lang, "altar.prompt"));
+                        ui.label(i18n::labeled(lang, "stat.town_standing", game_state.reputation.describe(faction::Faction::Town)));
+                        ui.label(trf(lang, "trade.your_gold", &[&game_state.player.gold]));
+                        ui.add_space(10.0);
+
+                        for amount in OFFERINGS {
+                            let affordable = game_state.player.gold >= amount;
+                            let (offer, pressed) = self.menu.enabled_button(ui, affordable, trf(lang, "altar.donate", &[&amount]));
+                            if offer.clicked() || pressed {
+                                game_state.apply_command(Command::Donate(amount));
+                                self.dialog_state = DialogState::NoDialog;
+                            }
+                        }
+
+                        ui.add_space(10.0);
+
+                        if self.menu.button(ui, tr(lang, "campfire.leave")) {
+                            self.dialog_state = DialogState::NoDialog;
+                        }
+
+                        ui.add_space(10.0);
+                    });
+                });
+        }
+    }
+
+    fn show_workbench_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        if let Some(ref mut game_state) = self.game_state {
+            egui::Window::new(tr(lang, "workbench.title"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .show(ctx, |ui| {
+                    ui.vertical(|ui| {
+                        ui.add_space(10.0);
+                        ui.label(tr(lang, "workbench.prompt"));
+                        ui.add_space(10.0);
+
+                        let mut socket: Option<(usize, usize)> = None;
+                        let inventory = &game_state.player.inventory;
+                        let gear: Vec<usize> = (0..inventory.len()).filter(|&index| inventory[index].has_open_socket()).collect();
+                        let gems: Vec<usize> = (0..inventory.len()).filter(|&index| inventory[index].stats.element.is_some()).collect();
+
+                        if gear.is_empty() {
+                            ui.label(tr(lang, "workbench.no_gear"));
+                        }
+                        for &index in &gear {
+                            let item = &inventory[index];
+                            let open = item.stats.sockets.iter().filter(|socket| socket.is_none()).count();
+                            let label = trf(lang, "workbench.open_sockets", &[&item.display_name(lang), &open]);
+                            if self.menu.selectable(ui, self.socket_target == Some(index), label) {
+                                self.socket_target = Some(index);
+                            }
+                        }
+
+                        ui.add_space(10.0);
+
+                        if gems.is_empty() {
+                            ui.label(tr(lang, "workbench.no_gems"));
+                        }
+                        for &index in &gems {
+                            let gem = &inventory[index];
+                            let bonus = gem.stats.element.map(|element| element.describe()).unwrap_or_default();
+                            let label = trf(lang, "workbench.set", &[&gem.display_name(lang), &bonus]);
+                            let (set, pressed) = self.menu.enabled_button(ui, self.socket_target.is_some(), label);
+                            if let Some(target) = self.socket_target
+                                && (set.clicked() || pressed)
+                            {
+                                socket = Some((target, index));
+                            }
+                        }
+
+                        ui.add_space(10.0);
+
+                        if self.menu.button(ui, tr(lang, "campfire.leave")) {
+                            self.socket_target = None;
+                            self.dialog_state = DialogState::NoDialog;
+                        }
+
+                        // Setting a gem takes a turn, and shifts the inventory so the choice is cleared
+                        if let Some((item_index, gem_index)) = socket {
+                            game_state.apply_command(Command::SocketGem { item_index, gem_index });
+                            self.socket_target = None;
+                        }
+
+                        ui.add_space(10.0);
+                    });
+                });
+        }
+    }
+
+    fn show_stash_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        let Some(ref mut game_state) = self.game_state else {
+            return;
+        };
+        let mut withdrawn = None;
+        egui::Window::new(tr(lang, "stash.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.vertical(|ui| {
+                    ui.add_space(10.0);
+                    ui.label(tr(lang, "stash.prompt"));
+                    ui.add_space(10.0);
+
+                    let can_take = !game_state.player.is_inventory_full();
+                    for (index, item) in game_state.stash.iter().enumerate() {
+                        let (take, pressed) = self.menu.enabled_button(ui, can_take, trf(lang, "stash.take", &[&item.display_name(lang)]));
+                        if take.clicked() || pressed {
+                            withdrawn = Some(index);
+                        }
+                    }
+
+                    ui.add_space(10.0);
+
+                    if self.menu.button(ui, tr(lang, "stash.set_off")) {
+                        self.dialog_state = DialogState::NoDialog;
+                    }
+
+                    ui.add_space(10.0);
+                });
+            });
+
+        if let Some(index) = withdrawn {
+            game_state.apply_command(Command::TakeFromStash(index));
+            if game_state.stash.is_empty() {
+                self.dialog_state = DialogState::NoDialog;
+            }
+        }
+    }
+
+    fn show_level_up_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        if let Some(ref mut game_state) = self.game_state {
+            egui::Window::new(tr(lang, "level_up.title"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .show(ctx, |ui| {
+                    ui.vertical(|ui| {
+                        ui.add_space(10.0);
+                        ui.label(trf(lang, "level_up.prompt", &[&(game_state.player.level + 1)]));
+                        ui.add_space(10.0);
+
+                        let mut chosen: Option<LevelUpChoice> = None;
+                        for choice in game_state.pending_level_up.iter().flatten() {
+                            ui.horizontal(|ui| {
+                                if self.menu.button(ui, choice.get_name()) {
+                                    chosen = Some(choice.clone());
+                                }
+                                ui.weak(choice.get_description());
+                            });
+                        }
+
+                        // Picking a reward is free; another level may be waiting straight after
+                        if let Some(choice) = chosen {
+                            game_state.apply_command(Command::ChooseLevelUp(choice));
+                            if game_state.pending_level_up.is_none() {
+                                self.dialog_state = DialogState::NoDialog;
+                            }
+                        }
+
+                        ui.add_space(10.0);
+                    });
+                });
+        }
+    }
+
+    fn show_shop_demand_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        let Some(ref mut game_state) = self.game_state else {
+            return;
+        };
+        let Some(demand) = game_state.shop_demand.clone() else {
+            self.dialog_state = DialogState::NoDialog;
+            return;
+        };
+        let keeper = game_state.npcs.iter().find(|npc| npc.id == demand.keeper).map_or_else(String::new, |npc| npc.name.clone());
+        egui::Window::new(tr(lang, "shop.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.vertical(|ui| {
+                    ui.add_space(10.0);
+                    ui.label(trf(lang, "shop.demand", &[&keeper, &demand.amount]));
+                    ui.label(trf(lang, "trade.your_gold", &[&game_state.player.gold]));
+                    ui.add_space(10.0);
+
+                    // Settling up is free, but the dialog stays until the player picks one
+                    let affordable = game_state.player.gold >= demand.amount;
+                    let (pay, pressed) = self.menu.enabled_button(ui, affordable, trf(lang, "shop.pay", &[&demand.amount]));
+                    let command = if pay.clicked() || pressed {
+                        Some(Command::PayShopkeeper)
+                    } else if self.menu.button(ui, tr(lang, "shop.put_back")) {
+                        Some(Command::ReturnGoods)
+                    } else if self.menu.button(ui, tr(lang, "shop.refuse")) {
+                        Some(Command::RefuseToPay)
+                    } else {
+                        None
+                    };
+                    if let Some(command) = command {
+                        game_state.apply_command(command);
+                        self.dialog_state = DialogState::NoDialog;
+                    }
+
+                    ui.add_space(10.0);
+                });
+            });
+    }
+
+    fn show_bump_menu_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        let Some(ref mut game_state) = self.game_state else {
+            return;
+        };
+        let Some(npc) = self.interaction_target.and_then(|target| game_state.npcs.iter().find(|npc| npc.position(&game_state.world) == target)) else {
+            self.dialog_state = DialogState::NoDialog; // They wandered off
+            return;
+        };
+        let position = npc.position(&game_state.world);
+        let is_merchant = npc.npc_type == NPCType::Merchant;
+        let is_oracle = npc.npc_type == NPCType::Oracle && !game_state.is_hostile_to_player(npc);
+        let fine = game_state.can_pay_fine(npc).then(|| game_state.reputation.fine(faction::Faction::Town)).flatten();
+
+        egui::Window::new(npc.name.clone())
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.vertical(|ui| {
+                    ui.add_space(10.0);
+                    ui.label(i18n::labeled(lang, "stat.town_standing", game_state.reputation.describe(faction::Faction::Town)));
+                    ui.add_space(10.0);
+
+                    if self.menu.button(ui, tr(lang, "bump.talk")) {
+                        game_state.apply_command(Command::Talk(position));
+                        self.dialog_state = DialogState::NoDialog;
+                    }
+                    if is_merchant && self.menu.button(ui, tr(lang, "bump.trade")) {
+                        self.dialog_state = DialogState::Trade;
+                    }
+                    if is_oracle && self.menu.button(ui, trf(lang, "bump.consult", &[&npc::ORACLE_FEE])) {
+                        game_state.apply_command(Command::ConsultOracle(position));
+                        self.dialog_state = DialogState::NoDialog;
+                    }
+                    if let Some(fine) = fine
+                        && self.menu.button(ui, trf(lang, "bump.pay_fine", &[&fine]))
+                    {
+                        game_state.apply_command(Command::PayFine(position));
+                        self.dialog_state = DialogState::NoDialog;
+                    }
+                    let (attack, pressed) = self.menu.enabled_button(ui, true, tr(lang, "bump.attack"));
+                    if attack.on_hover_text(tr(lang, "bump.attack_hint")).clicked() || pressed {
+                        game_state.apply_command(Command::AttackFriendly(position));
+                        self.dialog_state = DialogState::NoDialog;
+                    }
+                    if self.menu.button(ui, tr(lang, "bump.step_back")) {
+                        self.dialog_state = DialogState::NoDialog;
+                    }
+
+                    ui.add_space(10.0);
+                });
+            });
+    }
+
+    fn show_trade_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        let Some(ref mut game_state) = self.game_state else {
+            return;
+        };
+        let Some(merchant) = self.interaction_target.and_then(|target| game_state.npcs.iter().find(|npc| npc.position(&game_state.world) == target)) else {
+            self.dialog_state = DialogState::NoDialog;
+            return;
+        };
+        let (position, stock) = (merchant.position(&game_state.world), merchant.stock.clone());
+        let buy_prices: Vec<i32> = stock.iter().map(|item| game_state.buy_price(merchant, item)).collect();
+        let sell_prices: Vec<i32> = game_state.player.inventory.iter().map(|item| game_state.sell_price(merchant, item)).collect();
+        let haggled = merchant.haggled;
+
+        egui::Window::new(trf(lang, "trade.title", &[&merchant.name]))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.vertical(|ui| {
+                    ui.add_space(10.0);
+                    ui.label(trf(lang, "trade.your_gold", &[&game_state.player.gold]));
+                    ui.label(i18n::labeled(lang, "stat.charisma", game_state.player.charisma));
+                    if haggled != 0 {
+                        ui.weak(trf(lang, "trade.haggled", &[&haggled]));
+                    }
+                    ui.add_space(10.0);
+
+                    let mut to_buy: Option<usize> = None;
+                    let mut to_sell: Option<usize> = None;
+
+                    ui.strong(tr(lang, "trade.for_sale"));
+                    if stock.is_empty() {
+                        ui.label(tr(lang, "trade.sold_out"));
+                    }
+                    for (index, item) in stock.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            ui.label(item.display_name(lang));
+                            let affordable = game_state.player.gold >= buy_prices[index];
+                            let (buy, pressed) = self.menu.enabled_button(ui, affordable, trf(lang, "trade.buy", &[&buy_prices[index]]));
+                            if buy.clicked() || pressed {
+                                to_buy = Some(index);
+                            }
+                        });
+                    }
+                    ui.add_space(10.0);
+
+                    ui.strong(tr(lang, "trade.your_items"));
+                    for (index, item) in game_state.player.inventory.iter().enumerate() {
+                        if sell_prices[index] == 0 {
+                            continue;
+                        }
+                        ui.horizontal(|ui| {
+                            ui.label(game_state.item_name(item));
+                            if self.menu.button(ui, trf(lang, "trade.sell", &[&sell_prices[index]])) {
+                                to_sell = Some(index);
+                            }
+                        });
+                    }
+                    ui.add_space(10.0);
+                    let haggle = self.menu.button(ui, tr(lang, "trade.haggle"));
+                    ui.weak(tr(lang, "trade.haggle_hint"));
+                    ui.add_space(10.0);
+
+                    // Haggling doesn't take any time
+                    if haggle {
+                        game_state.apply_command(Command::Haggle(position));
+                    }
+                    if let Some(index) = to_buy {
+                        game_state.apply_command(Command::Buy { merchant: position, stock_index: index });
+                    }
+                    if let Some(index) = to_sell {
+                        game_state.apply_command(Command::Sell { merchant: position, item_index: index });
+                    }
+
+                    if self.menu.button(ui, tr(lang, "common.done")) {
+                        self.dialog_state = DialogState::NoDialog;
+                    }
+
+                    ui.add_space(10.0);
+                });
+            });
+    }
+
+    fn show_settings_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        egui::Window::new(tr(lang, "options.title"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(ctx, |ui| {
+                ui.vertical(|ui| {
+                    ui.add_space(10.0);
+                    ui.strong(tr(lang, "options.language"));
+                    ui.horizontal(|ui| {
+                        for language in i18n::Language::ALL {
+                            if self.menu.selectable(ui, self.settings.language == language, language.get_name()) {
+                                self.settings.language = language;
+                            }
+                        }
+                    });
+                    ui.add_space(10.0);
+
+                    ui.strong(tr(lang, "options.auto_pickup"));
+                    ui.label(tr(lang, "options.auto_pickup_hint"));
+                    ui.add_space(5.0);
+
+                    // Heavy gear and possibly cursed items are never picked up automatically
+                    for item_type in ItemType::ALL.into_iter().filter(|item_type| item_type.allows_auto_pickup()) {
+                        let mut enabled = self.settings.auto_pickup.contains(&item_type);
+                        if self.menu.checkbox(ui, &mut enabled, item_type.get_name()) {
+                            self.settings.set_auto_pickup(item_type, enabled);
+                        }
+                    }
+                    ui.add_space(10.0);
+
+                    ui.strong(tr(lang, "options.touch"));
+                    self.menu.checkbox(ui, &mut self.settings.touch_controls, tr(lang, "options.touch_hint"));
+                    ui.add_space(10.0);
+
+                    ui.strong(tr(lang, "options.display"));
+                    for theme in render::Theme::ALL {
+                        if self.menu.selectable(ui, self.settings.theme == theme, theme.get_name()) {
+                            self.settings.theme = theme;
+                        }
+                    }
+                    ui.add_space(10.0);
+
+                    ui.strong(tr(lang, "options.comfort"));
+                    self.menu.checkbox(ui, &mut self.settings.reduced_motion, tr(lang, "options.reduced_motion"));
+                    self.menu.checkbox(ui, &mut self.settings.no_flashes, tr(lang, "options.no_flashes"));
+                    ui.add_space(10.0);
+
+                    ui.strong(tr(lang, "options.power"));
+                    self.menu.checkbox(ui, &mut self.settings.power_saving, tr(lang, "options.power_hint"));
+                    ui.add_space(10.0);
+
+                    ui.strong(tr(lang, "options.screen_reader"));
+                    self.menu.checkbox(ui, &mut self.settings.screen_reader, tr(lang, "options.screen_reader_hint"));
+                    ui.add_space(10.0);
+
+                    ui.strong(tr(lang, "options.diagnostics"));
+                    ui.label(tr(lang, "options.diagnostics_hint"));
+                    for verbosity in diagnostics::Verbosity::ALL {
+                        if self.menu.selectable(ui, self.settings.log_verbosity == verbosity, verbosity.get_name()) {
+                            self.settings.log_verbosity = verbosity;
+                            self.diagnostics.set_verbosity(verbosity);
+                        }
+                    }
+                    ui.add_space(10.0);
+
+                    if self.menu.button(ui, tr(lang, "common.close")) {
+                        if let Some(ref mut game_state) = self.game_state {
+                            game_state.settings = self.settings.clone();
+                        }
+                        self.dialog_state = DialogState::NoDialog;
+                    }
+
+                    ui.add_space(10.0);
+                });
             });
     }
 
-    fn show_game_over_dialog(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::Window::new("Game Over")
+    fn show_examine_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        let (Some(game_state), Some(item)) = (&self.game_state, &self.examined_item) else {
+            self.dialog_state = DialogState::NoDialog;
+            return;
+        };
+        let identified = !game_state.forgotten_item_types.contains(&item.item_type);
+        let mut close = false;
+
+        egui::Window::new(game_state.item_name(item))
             .collapsible(false)
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
             .show(ctx, |ui| {
-                ui.vertical_centered(|ui| {
+                ui.vertical(|ui| {
                     ui.add_space(10.0);
-                    ui.label("Your character has met its end!");
-                    ui.label("Game Over");
-                    ui.add_space(20.0);
-                    
-                    if ui.button("Ok").clicked() {
-                        ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
+                    ui.label(i18n::labeled(lang, "stat.type", item.item_type.get_name()));
+                    if identified {
+                        ui.label(&item.description);
+                    } else {
+                        ui.label(tr(lang, "examine.unidentified"));
+                    }
+                    ui.add_space(10.0);
+
+                    draw_item_stats(ui, lang, item);
+                    if identified && let Some(effect) = &item.effect {
+                        ui.label(i18n::labeled(lang, "stat.effect", effect.describe()));
+                    }
+                    ui.label(trf(lang, "stat.value", &[&item.value()]));
+                    ui.label(trf(lang, "stat.weight", &[&item.weight()]));
+
+                    if identified && let Some(lore) = catalog::lore(&item.label) {
+                        ui.add_space(10.0);
+                        ui.label(egui::RichText::new(lore).italics());
+                    }
+
+                    ui.add_space(10.0);
+                    if self.menu.button(ui, tr(lang, "common.close")) {
+                        close = true;
                     }
-                    
                     ui.add_space(10.0);
                 });
             });
+
+        if close {
+            self.examined_item = None;
+            self.dialog_state = DialogState::NoDialog;
+        }
     }
 
-    fn show_use_item_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        if let Some(ref mut game_state) = self.game_state {
-            egui::Window::new("Use Item")
+    fn show_character_sheet_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let lang = self.settings.language;
+        if let Some(ref game_state) = self.game_state {
+            let player = &game_state.player;
+            egui::Window::new(tr(lang, "character.title"))
                 .collapsible(false)
                 .resizable(false)
                 .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
                 .show(ctx, |ui| {
                     ui.vertical(|ui| {
                         ui.add_space(10.0);
-                        ui.label("Choose an item to use:");
+                        ui.strong(trf(lang, "character.heading", &[&player.level, &player.class.get_name()]));
+                        ui.label(player.class.get_description());
                         ui.add_space(10.0);
 
-                        let mut item_to_use: Option<usize> = None;
+                        ui.label(i18n::labeled(lang, "stat.health", format!("{}/{}", player.health, player.max_health)));
+                        ui.label(i18n::labeled(lang, "stat.strength", player.strength));
+                        ui.label(i18n::labeled(lang, "stat.dexterity", player.dexterity));
+                        ui.label(i18n::labeled(lang, "stat.charisma", player.charisma));
+                        ui.label(i18n::labeled(lang, "stat.experience", format!("{}/{}", player.experience, player.experience_to_level())));
+                        ui.add_space(10.0);
 
-                        // Show each item in inventory as a button
-                        for (index, item) in game_state.player.inventory.iter().enumerate() {
-                            if ui.button(&item.label).clicked() {
-                                item_to_use = Some(index);
-                            }
-                        }
+                        let ability = player.class.ability();
+                        ui.strong(trf(lang, "character.ability", &[&Action::Ability.key().name(), &ability_status(lang, player)]));
+                        ui.label(ability.get_description());
+                        ui.weak(trf(lang, "character.cooldown", &[&ability.cooldown()]));
+                        ui.add_space(10.0);
 
+                        ui.strong(tr(lang, "character.skills"));
+                        if player.skills.is_empty() {
+                            ui.label(tr(lang, "character.none_yet"));
+                        }
+                        for skill in &player.skills {
+                            ui.label(format!("{}: {}", skill.get_name(), skill.get_description()));
+                        }
                         ui.add_space(10.0);
 
-                        // Cancel button
-                        if ui.button("Cancel").clicked() {
-                            self.dialog_state = DialogState::NoDialog;
+                        ui.strong(tr(lang, "equip.title"));
+                        for slot in EquipSlot::ALL {
+                            ui.label(format!("{}: {}", slot.get_name(), player.equipment.get(slot).map_or(tr(lang, "common.empty"), |item| item.label.as_str())));
                         }
+                        ui.add_space(10.0);
 
-                        // Handle item usage
-                        if let Some(index) = item_to_use {
-                            let item = game_state.player.inventory.remove(index);
-                            let result = game_state.use_item(item);
-                            
-                            // Handle the result
-                            if let Some(returned_item) = result.returned_to_inventory {
-                                game_state.player.inventory.push(returned_item);
-                            }
-                            
-                            for dropped_item in result.dropped_on_ground {
-                                game_state.world.items.push(WorldItem::new(
-                                    game_state.player.position.0,
-                                    game_state.player.position.1,
-                                    dropped_item
-                                ));
-                            }
-                            
-                            // Process NPC actions after item use
-                            game_state.increment_turn();
-                            game_state.process_npc_actions();
-                            
+                        if self.menu.button(ui, tr(lang, "common.close")) {
                             self.dialog_state = DialogState::NoDialog;
                         }
 
@@ -402,24 +2439,35 @@ impl RoguelikeApp {
     }
 
     fn show_victory_dialog_window(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::Window::new("Victory!")
+        let lang = self.settings.language;
+        egui::Window::new(tr(lang, "victory.title"))
             .collapsible(false)
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
             .show(ctx, |ui| {
                 ui.vertical_centered(|ui| {
                     ui.add_space(10.0);
-                    ui.label("Congratulations!");
+                    ui.label(tr(lang, "victory.congratulations"));
                     
                     let victory_message = if let Some(ref game_state) = self.game_state {
                         game_state.get_victory_message()
                     } else {
-                        "Congratulations, you are surrounded by adoring masses chanting your name and cheering your victory! If only you knew how you won!"
+                        tr(lang, "victory.unknown")
                     };
                     ui.label(victory_message);
+                    if self.game_state.as_ref().is_some_and(|game_state| game_state.wizard_used) {
+                        ui.weak(tr(lang, "victory.wizard"));
+                    }
+                    ui.add_space(10.0);
+                    self.draw_high_scores(ui, false);
+                    if self.online.is_some() {
+                        self.draw_online_scores(ui);
+                    }
+                    ui.add_space(10.0);
+                    self.draw_bank_to_stash(ui);
                     ui.add_space(20.0);
                     
-                    if ui.button("Ok").clicked() {
+                    if self.menu.button(ui, tr(lang, "common.ok")) {
                         ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
                     }
                     
@@ -428,114 +2476,603 @@ impl RoguelikeApp {
             });
     }
 
+    /// Buttons to bank what the winner is carrying into the stash for their next run.
+    /// Wizard runs can't bank anything.
+    fn draw_bank_to_stash(&mut self, ui: &mut egui::Ui) {
+        let lang = self.settings.language;
+        let Some(ref mut game_state) = self.game_state else {
+            return;
+        };
+        if game_state.wizard_used {
+            return;
+        }
+        ui.strong(trf(lang, "stash.bank_title", &[&self.profile.stash.len(), &profile::STASH_CAPACITY]));
+        let can_bank = !self.profile.stash_is_full();
+        let (mut bank_slot, mut bank_index) = (None, None);
+        for slot in EquipSlot::ALL {
+            if let Some(item) = game_state.player.equipment.get(slot) {
+                let (bank, pressed) = self.menu.enabled_button(ui, can_bank, trf(lang, "stash.bank", &[&item.display_name(lang)]));
+                if bank.clicked() || pressed {
+                    bank_slot = Some(slot);
+                }
+            }
+        }
+        for (index, item) in game_state.player.inventory.iter().enumerate() {
+            let (bank, pressed) = self.menu.enabled_button(ui, can_bank, trf(lang, "stash.bank", &[&item.display_name(lang)]));
+            if bank.clicked() || pressed {
+                bank_index = Some(index);
+            }
+        }
+        let banked = match (bank_slot, bank_index) {
+            (Some(slot), _) => game_state.player.equipment.take(slot),
+            (None, Some(index)) => Some(game_state.player.inventory.remove(index)),
+            (None, None) => None,
+        };
+        if let Some(item) = banked {
+            self.profile.stash.push(item);
+            self.save_profile();
+        }
+    }
+
+    /// The local high-score table, or the endless descent one, with the run that just finished highlighted
+    fn draw_high_scores(&self, ui: &mut egui::Ui, endless: bool) {
+        let lang = self.settings.language;
+        const SHOWN_ENTRIES: usize = 10;
+
+        let table = if endless { &self.endless_scores } else { &self.high_scores };
+        let entries = table.entries();
+        if entries.is_empty() {
+            ui.weak(tr(lang, "scores.none"));
+            return;
+        }
+        egui::Grid::new(("high_scores", endless)).striped(true).show(ui, |ui| {
+            ui.strong("#");
+            for heading in ["scores.score", "scores.mode", "scores.class", if endless { "scores.floor" } else { "scores.result" }, "scores.turns", "scores.time"] {
+                ui.strong(tr(lang, heading));
+            }
+            ui.end_row();
+            for (place, entry) in entries.iter().enumerate().take(SHOWN_ENTRIES) {
+                let color = if Some(place) == self.score_place { egui::Color32::from_rgb(255, 215, 0) } else { ui.visuals().text_color() };
+                let result = if endless {
+                    entry.depth.to_string()
+                } else if entry.won {
+                    tr(lang, "scores.won").to_string()
+                } else {
+                    tr(lang, "scores.lost").to_string()
+                };
+                let cells = [
+                    (place + 1).to_string(),
+                    entry.score.to_string(),
+                    entry.mode.clone(),
+                    entry.class.clone(),
+                    result,
+                    entry.turns.to_string(),
+                    scores::format_time(entry.elapsed_secs),
+                ];
+                for cell in cells {
+                    ui.colored_label(color, cell);
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    /// The opt-in for the online leaderboard, and its top entries once opted in
+    fn draw_online_scores(&mut self, ui: &mut egui::Ui) {
+        let lang = self.settings.language;
+        const SHOWN_ENTRIES: usize = 10;
+
+        let Some(ref online) = self.online else {
+            return;
+        };
+        let mut enabled = self.settings.online_scores;
+        if self.menu.checkbox(ui, &mut enabled, tr(lang, "scores.share")) {
+            self.settings.online_scores = enabled;
+            if let Some(ref mut game_state) = self.game_state {
+                game_state.settings.online_scores = enabled;
+            }
+        }
+        if !self.settings.online_scores {
+            ui.weak(trf(lang, "scores.sent_to", &[&online.url()]));
+            return;
+        }
+        match online.standings() {
+            leaderboard::Standings::NotFetched => online.refresh(ui.ctx()),
+            leaderboard::Standings::Loading => {
+                ui.spinner();
+            }
+            leaderboard::Standings::Offline(reason) => {
+                ui.colored_label(egui::Color32::from_rgb(255, 165, 0), trf(lang, "scores.offline", &[&reason]));
+                if self.menu.button(ui, tr(lang, "scores.retry")) {
+                    online.refresh(ui.ctx());
+                }
+            }
+            leaderboard::Standings::Loaded(entries) if entries.is_empty() => {
+                ui.weak(tr(lang, "scores.online_none"));
+            }
+            leaderboard::Standings::Loaded(entries) => {
+                egui::Grid::new("online_scores").striped(true).show(ui, |ui| {
+                    ui.strong("#");
+                    for heading in ["scores.score", "scores.mode", "scores.class", "scores.turns"] {
+                        ui.strong(tr(lang, heading));
+                    }
+                    ui.end_row();
+                    for (place, entry) in entries.iter().enumerate().take(SHOWN_ENTRIES) {
+                        ui.label((place + 1).to_string());
+                        ui.label(entry.score.to_string());
+                        ui.label(&entry.mode);
+                        ui.label(&entry.class);
+                        ui.label(entry.turns.to_string());
+                        ui.end_row();
+                    }
+                });
+            }
+        }
+    }
+
     fn draw_world_view(&self, ui: &mut egui::Ui, game_state: &GameState) -> WorldViewInteraction {
+        let lang = self.settings.language;
         let mut interaction = WorldViewInteraction::new();
         let available_size = ui.available_size();
 
+        // The map stays hidden while paused, so a timed run can't be planned with the clock stopped
+        if self.paused {
+            ui.vertical_centered(|ui| {
+                ui.add_space(available_size.y / 3.0);
+                ui.heading(tr(lang, "world.paused"));
+                ui.label(trf(lang, "world.resume", &[&Action::Pause.key().name()]));
+            });
+            return interaction;
+        }
+
         // Draw the game world
         ui.allocate_ui_with_layout(
             available_size,
             egui::Layout::top_down(egui::Align::Min),
             |ui| {
-                ui.label(format!("GOAL: {}", game_state.get_win_description()));
+                if game_state.wizard_mode {
+                    ui.colored_label(egui::Color32::from_rgb(200, 80, 255), tr(lang, "world.wizard"));
+                }
+                if let Some(remaining) = game_state.time_remaining() {
+                    let color = if remaining <= 30.0 { egui::Color32::from_rgb(255, 80, 80) } else { egui::Color32::from_rgb(255, 215, 0) };
+                    ui.label(egui::RichText::new(trf(lang, "world.time_left", &[&scores::format_time(remaining)])).heading().strong().color(color));
+                }
+                if let Some(remaining) = game_state.turns_remaining() {
+                    // The final stretch is shown in red
+                    let color = if remaining <= 10 { egui::Color32::from_rgb(255, 80, 80) } else { egui::Color32::from_rgb(255, 215, 0) };
+                    ui.label(egui::RichText::new(trf(lang, "world.turns_to_survive", &[&remaining])).heading().strong().color(color));
+                }
+                if let Some(floor_collapse) = &game_state.world.collapse {
+                    match floor_collapse.turns_until() {
+                        Some(turns) if turns <= collapse::WARNING_TURNS => {
+                            ui.colored_label(egui::Color32::from_rgb(255, 165, 0), trf(lang, "world.collapse_soon", &[&turns]));
+                        }
+                        Some(_) => {}
+                        None => {
+                            ui.colored_label(egui::Color32::from_rgb(255, 80, 80), tr(lang, "world.collapsing"));
+                        }
+                    }
+                }
+                if let Some(warning) = game_state.get_condition_warning() {
+                    ui.colored_label(egui::Color32::from_rgb(255, 80, 80), warning);
+                }
+                ui.horizontal(|ui| {
+                    ui.label(i18n::labeled(lang, "world.goal", game_state.get_win_description()));
+                    ui.strong(game_state.get_progress());
+                    if let Some(objective) = game_state.objective_position() {
+                        let (dx, dy) = (objective.0 - game_state.player.position.0, objective.1 - game_state.player.position.1);
+                        let distance = dx.abs().max(dy.abs());
+                        ui.strong(trf(lang, "world.distance", &[&compass_arrow(dx, dy), &distance]));
+                    }
+                });
+                if let Some(targeting) = &self.targeting {
+                    ui.strong(targeting.hint(lang));
+                } else {
+                    // Context-sensitive hints for what can be done right here
+                    let hints = action::context_hints(game_state);
+                    if !hints.is_empty() {
+                        ui.colored_label(egui::Color32::from_rgb(255, 215, 0), hints.join("   |   "));
+                    }
+                }
                 ui.separator();
-                ui.label(format!("World Size: {}x{}", game_state.world.size.0, game_state.world.size.1));
-                ui.label(format!("Player Position: ({}, {})", game_state.player.position.0, game_state.player.position.1));
-                ui.label(format!("Floor: {}", game_state.world.current_floor));
+                ui.label(i18n::labeled(lang, "world.size", format!("{}x{}", game_state.world.size.0, game_state.world.size.1)));
+                ui.label(i18n::labeled(lang, "world.player_position", format!("({}, {})", game_state.player.position.0, game_state.player.position.1)));
+                ui.label(i18n::labeled(lang, "stat.floor", game_state.world.current_floor));
+                let respawns = if game_state.checkpoint.is_some() {
+                    game_state.respawns_left.to_string()
+                } else {
+                    format!("{} ({})", game_state.respawns_left, tr(lang, "stat.no_checkpoint"))
+                };
+                ui.label(i18n::labeled(lang, "stat.respawns", respawns));
                 if let Some((x, y)) = self.mouse_world_pos {
-                    ui.label(format!("Mouse Over: ({}, {})", x, y));
+                    ui.label(i18n::labeled(lang, "world.mouse_over", format!("({}, {})", x, y)));
+                } else {
+                    ui.label(i18n::labeled(lang, "world.mouse_over", "--"));
+                }
+
+                // Preview the route to the hovered tile, or flag it red if there's no known way there
+                let path_preview = self.mouse_world_pos
+                    .filter(|&target| self.targeting.is_none() && target != game_state.player.position)
+                    .map(|target| (target, game_state.player_path_to(target)));
+                match &path_preview {
+                    Some((_, Some(path))) => {
+                        let speed = scheduler::speed(&game_state.player.status_effects);
+                        let cost = path.iter().map(|&step| game_state.step_cost(step)).sum();
+                        let turns = scheduler::turns_for_actions(1, cost, speed);
+                        ui.label(i18n::labeled(lang, "world.path", trf(lang, "world.path_steps", &[&path.len(), &turns])));
+                    }
+                    Some((_, None)) => {
+                        ui.colored_label(egui::Color32::from_rgb(200, 60, 60), i18n::labeled(lang, "world.path", tr(lang, "world.no_route")));
+                    }
+                    None => {
+                        ui.label(i18n::labeled(lang, "world.path", "--"));
+                    }
+                }
+
+                if self.camera_follows {
+                    ui.label(trf(lang, "world.camera_follows", &[&Action::LockCamera.key().name()]));
                 } else {
-                    ui.label("Mouse Over: --");
+                    ui.label(trf(lang, "world.camera_free", &[&Action::CenterView.key().name(), &Action::LockCamera.key().name()]));
+                }
+
+                let threatened = if self.threat_overlay { game_state.threatened_tiles() } else { Default::default() };
+
+                // Every NPC's plans, seen or not, when the debug overlay is on
+                let mut debug_paths = std::collections::HashSet::new();
+                let mut debug_targets = std::collections::HashSet::new();
+                let mut debug_labels = std::collections::HashMap::new();
+                if self.debug_overlay {
+                    for npc in &game_state.npcs {
+                        let target = npc.ai_target(&game_state.world, &game_state.player);
+                        let activity = match (&npc.state, target) {
+                            (NPCState::Asleep, _) => "asleep",
+                            (_, Some(target)) if target == game_state.player.position => "hunting",
+                            (NPCState::Investigating(_), _) => "investigating",
+                            _ => "idle",
+                        };
+                        debug_labels.insert(npc.position(&game_state.world), format!("{} e{}", activity, npc.energy));
+                        if let Some(target) = target {
+                            debug_paths.extend(npc.planned_path(target, &game_state.world));
+                            debug_targets.insert(target);
+                        }
+                    }
                 }
 
-                // World representation that takes remaining space
-                let visible_width = game_state.world.size.0.min(60);
-                let visible_height = game_state.world.size.1.min(30);
+                // World representation that takes remaining space. The scroll area is the camera.
+                let (visible_width, visible_height) = game_state.world.size;
+                let now = ui.input(|i| i.time);
+                let shake = self.animations.shake_offset(now);
+                let theme = self.settings.theme;
+                let spacing = egui::Vec2::splat(theme.tile_spacing());
+                let background = |color: (u8, u8, u8)| {
+                    let (r, g, b) = theme.background_color(color);
+                    egui::Color32::from_rgb(r, g, b)
+                };
                 
-                egui::ScrollArea::both()
+                let map = egui::ScrollArea::both()
                     .max_height(ui.available_height())
                     .show(ui, |ui| {
-                        ui.style_mut().override_font_id = Some(egui::FontId::monospace(12.0));
-                        ui.style_mut().spacing.item_spacing = egui::Vec2::new(0.0, 0.0);
+                        ui.style_mut().override_font_id = Some(egui::FontId::monospace(theme.glyph_size()));
+                        ui.style_mut().spacing.item_spacing = spacing;
+                        ui.add_space(shake.y);
+                        let mut floating_text_rects = Vec::new();
+                        let mut debug_label_rects = Vec::new();
+                        let mut sight_edge_rects = Vec::new();
 
                         for y in 0..visible_height {
                             ui.horizontal(|ui| {
-                                ui.style_mut().spacing.item_spacing = egui::Vec2::new(0.0, 0.0);
+                                ui.style_mut().spacing.item_spacing = spacing;
+                                ui.add_space(shake.x);
                                 
                                 for x in 0..visible_width {
-                                    let (tile_char, color) = if x == game_state.player.position.0 as usize &&
-                                        y == game_state.player.position.1 as usize {
-                                        ('@', (255, 255, 0)) // Player - bright yellow
-                                    } else if let Some(npc) = game_state.npcs.iter().find(|npc| 
-                                        npc.position.0 == x as i32 && npc.position.1 == y as i32) {
-                                        npc.display_info()
-                                    } else if let Some(world_item) = game_state.world.items.iter().find(|item| 
-                                        item.position.0 == x as i32 && item.position.1 == y as i32) {
-                                        world_item.item.display_info()
-                                    } else {
-                                        match game_state.world.get_tile(x as i32, y as i32) {
-                                            Some(tile) => tile.display_info(),
-                                            None => (' ', (0, 0, 0)),
-                                        }
-                                    };
+                                    let visible = game_state.is_tile_visible(x as i32, y as i32);
+                                    let appearance = game_state.tile_appearance(x as i32, y as i32);
+                                    let (tile_char, color) = (appearance.glyph.symbol, theme.glyph_color(appearance.glyph.color));
+                                    
+                                    let mut text = egui::RichText::new(tile_char.to_string())
+                                        .color(egui::Color32::from_rgb(color.0, color.1, color.2));
+                                    if theme.bold() {
+                                        text = text.strong();
+                                    }
+                                    
+                                    // Tile effects such as fire are drawn as a background overlay,
+                                    // otherwise highlight actors that are losing their turns
+                                    if let Some(targeting) = self.targeting.as_ref().filter(|targeting| targeting.cursor == (x as i32, y as i32)) {
+                                        // Targeting cursor, green if confirming would work
+                                        let cursor_color = if targeting.is_valid(game_state) {
+                                            (40, 140, 60)
+                                        } else {
+                                            (150, 40, 40)
+                                        };
+                                        text = text.background_color(background(cursor_color));
+                                    } else if let Some((target, route)) = &path_preview
+                                        && (*target == (x as i32, y as i32)
+                                            || route.as_ref().is_some_and(|path| path.contains(&(x as i32, y as i32))))
+                                    {
+                                        let path_color = if route.is_some() {
+                                            (40, 60, 110)
+                                        } else {
+                                            (150, 40, 40)
+                                        };
+                                        text = text.background_color(background(path_color));
+                                    } else if self.debug_overlay && debug_targets.contains(&(x as i32, y as i32)) {
+                                        text = text.background_color(background((140, 90, 0)));
+                                    } else if self.debug_overlay && debug_paths.contains(&(x as i32, y as i32)) {
+                                        text = text.background_color(background((70, 40, 90)));
+                                    } else if let Some(overlay) = appearance.effect {
+                                        text = text.background_color(background(overlay));
+                                    } else if !visible {
+                                        // Nothing shows through the dark
+                                    } else if threatened.contains(&(x as i32, y as i32)) {
+                                        text = text.background_color(background((90, 25, 25))); // In reach of an enemy
+                                    } else if game_state.is_incapacitated_at(x as i32, y as i32) {
+                                        text = text.background_color(background((70, 70, 140)));
+                                    } else if game_state.world.shops.iter().any(|shop| shop.contains((x as i32, y as i32))) {
+                                        text = text.background_color(background((50, 40, 20))); // Shop floor
+                                    }
                                     
-                                    let label = egui::Label::new(
-                                        egui::RichText::new(tile_char.to_string())
-                                            .color(egui::Color32::from_rgb(color.0, color.1, color.2))
-                                    ).sense(egui::Sense::hover());
+                                    let label = egui::Label::new(text).sense(egui::Sense::click());
                                     let response = ui.add(label);
                                     
                                     if response.hovered() {
                                         interaction.mouse_position = Some((x as i32, y as i32));
                                     }
+                                    if (self.camera_follows || self.recenter_camera)
+                                        && (x as i32, y as i32) == game_state.player.position
+                                    {
+                                        response.scroll_to_me(Some(egui::Align::Center));
+                                    }
+                                    if response.long_touched() {
+                                        interaction.long_pressed_position = Some((x as i32, y as i32));
+                                    } else if response.clicked() {
+                                        interaction.clicked_position = Some((x as i32, y as i32));
+                                    }
+                                    
+                                    if self.debug_overlay {
+                                        if let Some(label) = debug_labels.get(&(x as i32, y as i32)) {
+                                            debug_label_rects.push((response.rect, label));
+                                        }
+                                        let (tx, ty) = (x as i32, y as i32);
+                                        if visible && [(0, 1), (0, -1), (1, 0), (-1, 0)].iter()
+                                            .any(|(dx, dy)| !game_state.is_tile_visible(tx + dx, ty + dy))
+                                        {
+                                            sight_edge_rects.push(response.rect);
+                                        }
+                                    }
+
+                                    for floating_text in game_state.world.floating_texts.iter()
+                                        .filter(|text| visible && text.position == (x as i32, y as i32)) {
+                                        floating_text_rects.push((response.rect, floating_text));
+                                    }
                                 }
                             });
                         }
+                        
+                        // Float combat feedback above the tiles once the whole grid is drawn
+                        for (rect, floating_text) in floating_text_rects {
+                            let color = theme.glyph_color(floating_text.color);
+                            ui.painter().text(
+                                rect.center_top(),
+                                egui::Align2::CENTER_BOTTOM,
+                                &floating_text.text,
+                                egui::FontId::monospace(theme.glyph_size() - 1.0),
+                                egui::Color32::from_rgb(color.0, color.1, color.2),
+                            );
+                        }
+
+                        for rect in sight_edge_rects {
+                            ui.painter().rect_stroke(rect, 0.0, egui::Stroke::new(1.0, egui::Color32::from_rgb(60, 160, 60)), egui::StrokeKind::Inside);
+                        }
+                        for (rect, label) in debug_label_rects {
+                            ui.painter().text(
+                                rect.center_bottom(),
+                                egui::Align2::CENTER_TOP,
+                                label,
+                                egui::FontId::monospace(9.0),
+                                egui::Color32::from_rgb(255, 170, 60),
+                            );
+                        }
                     });
+                if let Some(flash) = self.animations.flash_color(now) {
+                    ui.painter().rect_filled(map.inner_rect, 0.0, flash);
+                }
             },
         );
         
         interaction
     }
 
-    fn draw_info_panel(&self, ui: &mut egui::Ui, game_state: &GameState) {
+    /// On-screen movement and action buttons, returning the keys of any that were tapped
+    fn draw_touch_controls(&self, ui: &mut egui::Ui) -> Vec<egui::Key> {
+        const BUTTON_SIZE: egui::Vec2 = egui::vec2(44.0, 44.0);
+        const ACTIONS: [Action; 7] = [Action::Interact, Action::PickUp, Action::UseItem, Action::Equip, Action::Ability, Action::CharacterSheet, Action::Settings];
+
+        let lang = self.settings.language;
+        let mut pressed = Vec::new();
+        ui.group(|ui| {
+            ui.label(tr(lang, "panel.touch_controls"));
+            ui.separator();
+            ui.horizontal(|ui| {
+                egui::Grid::new("touch_pad").spacing(egui::vec2(4.0, 4.0)).show(ui, |ui| {
+                    let pad = [
+                        [None, Some(("↑", egui::Key::ArrowUp)), None],
+                        [Some(("←", egui::Key::ArrowLeft)), Some(("·", Action::Rest.key())), Some(("→", egui::Key::ArrowRight))],
+                        [None, Some(("↓", egui::Key::ArrowDown)), None],
+                    ];
+                    for row in pad {
+                        for cell in row {
+                            match cell {
+                                Some((label, key)) => {
+                                    if ui.add_sized(BUTTON_SIZE, egui::Button::new(label)).clicked() {
+                                        pressed.push(key);
+                                    }
+                                }
+                                None => {
+                                    ui.label("");
+                                }
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+                ui.vertical(|ui| {
+                    for action in ACTIONS {
+                        if ui.button(action.get_name()).clicked() {
+                            pressed.push(action.key());
+                        }
+                    }
+                });
+            });
+            ui.weak(tr(lang, "controls.touch"));
+        });
+        pressed
+    }
+
+    fn draw_info_panel(&self, ui: &mut egui::Ui, game_state: &GameState) -> InfoPanelInteraction {
+        let lang = self.settings.language;
+        let mut interaction = InfoPanelInteraction::default();
+
+        // A live region, so screen readers speak each announcement as it replaces the last
+        if self.settings.screen_reader {
+            let response = ui.label(self.announcer.latest());
+            ui.ctx().accesskit_node_builder(response.id, |node| node.set_live(egui::accesskit::Live::Polite));
+            ui.add_space(10.0);
+        }
+
+        if self.settings.touch_controls {
+            interaction.pressed_keys = self.draw_touch_controls(ui);
+            ui.add_space(10.0);
+        }
+
         ui.group(|ui| {
-            ui.label("Player Stats");
+            ui.label(tr(lang, "panel.player_stats"));
             ui.separator();
 
-            ui.label(format!("Level: {}", game_state.player.level));
-            ui.label(format!("Health: {}/{}", game_state.player.health, game_state.player.max_health));
-            ui.label(format!("Experience: {}", game_state.player.experience));
-            ui.label(format!("Floor: {}", game_state.world.current_floor));
-            ui.label(format!("Position: ({}, {})", game_state.player.position.0, game_state.player.position.1));
+            ui.label(i18n::labeled(lang, "stat.class", game_state.player.class.get_name()));
+            ui.label(i18n::labeled(lang, "stat.level", game_state.player.level));
+            ui.label(i18n::labeled(lang, "stat.health", format!("{}/{}", game_state.player.health, game_state.player.max_health)));
+            ui.label(i18n::labeled(lang, "stat.experience", format!("{}/{}", game_state.player.experience, game_state.player.experience_to_level())));
+            ui.label(i18n::labeled(lang, "stat.strength", game_state.player.strength));
+            ui.label(i18n::labeled(lang, "stat.dexterity", game_state.player.dexterity));
+            ui.label(i18n::labeled(lang, "stat.charisma", game_state.player.charisma));
+            for skill in &game_state.player.skills {
+                ui.label(i18n::labeled(lang, "stat.skill", skill.get_name()));
+            }
+            ui.label(i18n::labeled(lang, "stat.hunger", format!("{} ({})", tr(lang, game_state.player.hunger_status()), game_state.player.nutrition)));
+            ui.label(i18n::labeled(lang, "stat.gold", game_state.player.gold));
+            ui.label(i18n::labeled(lang, "stat.town_standing", game_state.reputation.describe(faction::Faction::Town)));
+            ui.label(i18n::labeled(lang, "stat.floor", game_state.world.current_floor));
+            ui.label(i18n::labeled(lang, "stat.position", format!("({}, {})", game_state.player.position.0, game_state.player.position.1)));
             ui.label(game_state.get_turn_info());
+            ui.label(i18n::labeled(lang, "stat.time", scores::format_time(game_state.play_time)));
+            if game_state.tally.streak > 1 {
+                ui.colored_label(egui::Color32::from_rgb(255, 165, 0), trf(lang, "stat.kill_streak", &[&game_state.tally.streak, &game_state.tally.multiplier()]));
+            }
+            if game_state.tally.best_streak > 1 {
+                ui.label(i18n::labeled(lang, "stat.best_streak", game_state.tally.best_streak));
+            }
+            for effect in game_state.player.status_effects.iter() {
+                ui.label(i18n::labeled(lang, "stat.status", trf(lang, "stat.turns", &[&effect.kind.get_name(), &effect.turns_remaining])));
+            }
+            if let Some(speed) = scheduler::describe_speed(scheduler::speed(&game_state.player.status_effects)) {
+                ui.label(i18n::labeled(lang, "stat.speed", speed));
+            }
+            ui.label(trf(lang, "stat.load", &[&game_state.player.carried_weight(), &game_state.player.carry_limit()]));
+            if game_state.player.is_encumbered() {
+                ui.colored_label(egui::Color32::from_rgb(255, 165, 0), tr(lang, "stat.encumbered"));
+            }
+            ui.label(i18n::labeled(lang, "stat.ability", ability_status(lang, &game_state.player)));
+        });
+
+        ui.add_space(10.0);
+
+        ui.group(|ui| {
+            ui.label(tr(lang, "panel.equipment"));
+            ui.separator();
+            let equipment = &game_state.player.equipment;
+            let main_hand = equipment.get(EquipSlot::MainHand).map_or(tr(lang, "equip.bare_hands"), |item| i18n::item_name(lang, &item.label));
+            let off_hand = match equipment.get(EquipSlot::OffHand) {
+                Some(item) => i18n::item_name(lang, &item.label),
+                None if equipment.main_hand.as_ref().is_some_and(|weapon| weapon.is_two_handed()) => tr(lang, "equip.two_handed_grip"),
+                None => tr(lang, "common.empty"),
+            };
+            ui.label(i18n::labeled(lang, "equip.main_hand", main_hand));
+            ui.label(i18n::labeled(lang, "equip.off_hand", off_hand));
+            ui.label(i18n::labeled(lang, "equip.neck", equipment.get(EquipSlot::Neck).map_or(tr(lang, "common.empty"), |item| i18n::item_name(lang, &item.label))));
+            ui.label(i18n::labeled(lang, "equip.finger", equipment.get(EquipSlot::Finger).map_or(tr(lang, "common.empty"), |item| i18n::item_name(lang, &item.label))));
+            if let Some(fuel) = equipment.get(EquipSlot::OffHand).and_then(|item| item.fuel) {
+                ui.label(trf(lang, "equip.torch_fuel", &[&fuel]));
+            }
         });
 
         ui.add_space(10.0);
 
         ui.group(|ui| {
-            ui.label("Inventory");
+            ui.label(trf(lang, "panel.inventory", &[&game_state.player.inventory.len(), &game_state.player.inventory_capacity()]));
             ui.separator();
             if game_state.player.inventory.is_empty() {
-                ui.label("Empty");
+                ui.label(tr(lang, "common.empty"));
             } else {
                 for item in &game_state.player.inventory {
-                    ui.label(&item.label);
+                    let label = ui.add(egui::Label::new(game_state.item_name(item)).sense(egui::Sense::click()));
+                    if label.on_hover_text(tr(lang, "panel.click_to_examine")).clicked() {
+                        interaction.examined_item = Some(item.clone());
+                    }
                 }
             }
         });
 
+        if let Some(npc) = game_state.npcs.iter().find(|npc| Some(npc.id) == self.pinned_npc) {
+            ui.add_space(10.0);
+            ui.group(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} ({})", npc.name, npc.get_display_char()));
+                    if ui.small_button("x").on_hover_text(tr(lang, "panel.unpin")).clicked() {
+                        interaction.unpin_npc = true;
+                    }
+                });
+                ui.separator();
+                ui.label(npc.npc_type.get_description(lang));
+                match game_state.npc_visibility(npc) {
+                    Visibility::Visible => {
+                        let health = npc.health(&game_state.world);
+                        ui.label(i18n::labeled(lang, "stat.health", format!("{}/{}", health.current, health.max)));
+                        let attitude = if game_state.is_hostile_to_player(npc) { "npc.hostile" } else { "npc.peaceful" };
+                        let activity = match npc.state {
+                            NPCState::Asleep => "npc.asleep",
+                            NPCState::Idle => "npc.alert",
+                            NPCState::Investigating(_) => "npc.searching",
+                        };
+                        ui.label(trf(lang, "npc.disposition", &[&tr(lang, attitude), &tr(lang, activity)]));
+                        for effect in npc.status_effects.iter() {
+                            ui.label(i18n::labeled(lang, "stat.status", trf(lang, "stat.turns", &[&effect.kind.get_name(), &effect.turns_remaining])));
+                        }
+                    }
+                    Visibility::Sensed => {
+                        ui.label(tr(lang, "npc.sensed"));
+                    }
+                    Visibility::Glimpsed | Visibility::Hidden => {
+                        ui.weak(tr(lang, "npc.out_of_sight"));
+                    }
+                }
+                ui.label(tr(lang, "stat.known_abilities"));
+                for ability in npc.npc_type.abilities(lang) {
+                    ui.label(format!("  - {}", ability));
+                }
+            });
+        }
+
         ui.add_space(10.0);
 
         ui.group(|ui| {
-            ui.label("Message Log");
+            ui.label(tr(lang, "panel.message_log"));
             ui.separator();
 
             egui::ScrollArea::vertical()
                 .max_height(200.0)
                 .stick_to_bottom(true)
                 .show(ui, |ui| {
-                    for message in &game_state.log_messages {
+                    for message in game_state.log_messages.lines() {
                         ui.label(message);
                     }
                 });
@@ -543,87 +3080,186 @@ impl RoguelikeApp {
 
         ui.add_space(10.0);
 
-        // Show hover description if mouse is over a map position
-        if self.mouse_world_pos.is_some() {
-            self.draw_hover_description(ui, game_state);
+        // Show what's at the hovered tile, or the crowded tile the player clicked on
+        if self.mouse_world_pos.or(self.pinned_location).is_some() {
+            self.draw_location_details(ui, game_state, &mut interaction);
             ui.add_space(10.0);
         }
 
         ui.group(|ui| {
-            ui.label("Controls");
+            ui.label(tr(lang, "panel.controls"));
             ui.separator();
-            ui.label("Arrow Keys / WASD: Move");
-            ui.label("P: Pick up item");
-            ui.label("U: Use item");
-            ui.label("Q: Quit");
-            ui.label("More controls coming...");
+            ui.label(tr(lang, "controls.move"));
+            let ability = game_state.player.class.ability();
+            for action in Action::ALL {
+                let name = match action {
+                    Action::Ability => ability.get_name(),
+                    _ => action.get_name(),
+                };
+                match action.pad_button() {
+                    Some(button) => ui.label(format!("{} / {}: {}", action.key().name(), button.get_name(), name)),
+                    None => ui.label(format!("{}: {}", action.key().name(), name)),
+                };
+            }
+            ui.label(tr(lang, "controls.campfire"));
+            ui.label(tr(lang, "controls.examine"));
+            ui.label(tr(lang, "controls.menus"));
+            ui.label(tr(lang, "controls.more"));
         });
+
+        interaction
     }
 
-    fn draw_hover_description(&self, ui: &mut egui::Ui, game_state: &GameState) {
-        if let Some((hover_x, hover_y)) = self.mouse_world_pos {
+    /// Everything at the hovered tile, or the tile pinned by clicking a crowded spot, with a
+    /// button to examine each creature and item there
+    fn draw_location_details(&self, ui: &mut egui::Ui, game_state: &GameState, interaction: &mut InfoPanelInteraction) {
+        let lang = self.settings.language;
+        let pinned = self.mouse_world_pos.is_none();
+        if let Some((hover_x, hover_y)) = self.mouse_world_pos.or(self.pinned_location) {
             ui.group(|ui| {
-                ui.label("Location Details");
+                ui.horizontal(|ui| {
+                    ui.label(tr(lang, "panel.location"));
+                    if pinned && ui.small_button("x").on_hover_text(tr(lang, "panel.unpin")).clicked() {
+                        interaction.unpin_location = true;
+                    }
+                });
                 ui.separator();
                 
-                // Check what's at this position
-                let mut descriptions = Vec::new();
-                
-                // Check if player is here
-                if game_state.player.position.0 == hover_x && 
-                   game_state.player.position.1 == hover_y {
-                    descriptions.push("Player (@) is here".to_string());
-                }
-                
-                // Check for NPCs
-                if let Some(npc) = game_state.npcs.iter().find(|npc| 
-                    npc.position.0 == hover_x && npc.position.1 == hover_y) {
-                    descriptions.push(format!("{} ({}) - {}", npc.name, npc.get_display_char(), 
-                        match npc.npc_type {
-                            NPCType::Goblin => "A mischievous goblin",
-                            NPCType::Orc => "A fierce orc warrior",
-                            NPCType::Skeleton => "Ancient bones animated by dark magic",
-                            NPCType::Merchant => "A traveling merchant",
-                            NPCType::Guard => "A stalwart guard",
-                        }));
+                // Check what's at this position. Each line may carry what its examine button opens.
+                let mut descriptions: Vec<(String, Option<LocationExamine>)> = Vec::new();
+
+                let visible = game_state.can_examine(hover_x, hover_y);
+                if game_state.player.status_effects.has(StatusKind::Blinded) {
+                    descriptions.push((tr(lang, "location.blind").to_string(), None));
+                } else if !visible {
+                    descriptions.push((tr(lang, "location.too_dark").to_string(), None));
                 }
-                
-                // Check for items
-                if let Some(world_item) = game_state.world.items.iter().find(|item| 
-                    item.position.0 == hover_x && item.position.1 == hover_y) {
-                    descriptions.push(format!("{} ({}) - {}", 
-                        world_item.item.label, 
-                        world_item.item.get_display_char(), 
-                        world_item.item.description));
+
+                for entity in game_state.entities_at(hover_x, hover_y) {
+                    match entity {
+                        Entity::Player => descriptions.push((tr(lang, "location.player").to_string(), None)),
+                        Entity::Npc(npc, Visibility::Sensed) => {
+                            let line = format!("{} ({}) - {}", npc.name, npc.get_display_char(), tr(lang, "npc.sensed"));
+                            descriptions.push((line, Some(LocationExamine::Npc(npc.id))));
+                        }
+                        Entity::Npc(..) if !visible => {} // Nothing to study
+                        Entity::Npc(_, Visibility::Glimpsed) => {
+                            descriptions.push((tr(lang, "location.unseen").to_string(), None));
+                        }
+                        Entity::Npc(npc, _) => {
+                            let line = format!("{} ({}) - {}{}", npc.name, npc.get_display_char(), 
+                                npc.npc_type.get_description(lang),
+                                match npc.state {
+                                    NPCState::Asleep => format!(" ({})", tr(lang, "npc.asleep")),
+                                    NPCState::Idle => String::new(),
+                                    NPCState::Investigating(_) => format!(" ({})", tr(lang, "npc.searching")),
+                                });
+                            descriptions.push((line, Some(LocationExamine::Npc(npc.id))));
+                            let health = npc.health(&game_state.world);
+                            descriptions.push((i18n::labeled(lang, "stat.health", format!("{}/{}", health.current, health.max)), None));
+                            if let Some(modifier) = npc.elite {
+                                descriptions.push((i18n::labeled(lang, "location.elite", modifier.get_description()), None));
+                            }
+                            if npc.is_hungry() {
+                                descriptions.push((tr(lang, "location.hungry").to_string(), None));
+                            }
+                            for effect in npc.status_effects.iter() {
+                                descriptions.push((i18n::labeled(lang, "stat.status", trf(lang, "stat.turns", &[&effect.kind.get_name(), &effect.turns_remaining])), None));
+                            }
+                            if let Some(speed) = scheduler::describe_speed(npc.speed()) {
+                                descriptions.push((i18n::labeled(lang, "stat.speed", speed), None));
+                            }
+                        }
+                        Entity::Cart(merchant) => {
+                            descriptions.push((trf(lang, "location.cart", &[&merchant.name, &merchant.stock.len()]), None));
+                        }
+                        Entity::Item(world_item) => {
+                            let description = if game_state.forgotten_item_types.contains(&world_item.item.item_type) {
+                                tr(lang, "location.forgotten")
+                            } else {
+                                world_item.item.description.as_str()
+                            };
+                            let line = format!("{} ({}) - {}", 
+                                game_state.item_name(&world_item.item), 
+                                world_item.item.get_display_char(), 
+                                description);
+                            descriptions.push((line, Some(LocationExamine::Item(world_item.item.clone()))));
+                            if let Some(price) = world_item.price {
+                                descriptions.push((trf(lang, "location.price", &[&price]), None));
+                            }
+                        }
+                        Entity::Trap(trap) => {
+                            descriptions.push((format!("{} (^) - {}", trap.get_name(), trap.get_description()), None));
+                        }
+                    }
                 }
                 
                 // Check tile type
-                if let Some(tile) = game_state.world.get_tile(hover_x, hover_y) {
-                    let tile_desc = match tile {
-                        TileType::Wall => "Solid stone wall",
-                        TileType::Floor => "Stone floor",
-                        TileType::Door => "Wooden door",
-                        TileType::Stairs => "Stone stairs",
-                        TileType::Empty => "Empty space",
-                    };
-                    descriptions.push(format!("Terrain: {} ({})", tile_desc, 
+                if visible && let Some(tile) = game_state.world.get_tile(hover_x, hover_y) {
+                    let tile_desc = tr(lang, match tile {
+                        TileType::Wall => "tile.wall",
+                        TileType::Floor => "tile.floor",
+                        TileType::Door => "tile.door",
+                        TileType::Stairs => "tile.stairs",
+                        TileType::Empty => "tile.empty",
+                        TileType::Grass => "tile.grass",
+                        TileType::Cauldron => "tile.cauldron",
+                        TileType::Campfire => "tile.campfire",
+                        TileType::Altar => "tile.altar",
+                        TileType::Workbench => "tile.workbench",
+                        TileType::Fungus => "tile.fungus",
+                        TileType::Water => "tile.water",
+                        TileType::Lava => "tile.lava",
+                        TileType::Rubble => "tile.rubble",
+                        TileType::Ice => "tile.ice",
+                        TileType::Checkpoint => "tile.checkpoint",
+                    });
+                    descriptions.push((trf(lang, "location.terrain", &[&tile_desc, &
                         match tile {
                             TileType::Wall => '#',
                             TileType::Floor => '.',
                             TileType::Door => '+',
                             TileType::Stairs => '>',
                             TileType::Empty => ' ',
-                        }));
+                            TileType::Grass => ',',
+                            TileType::Cauldron => '&',
+                            TileType::Campfire => 'Ω',
+                            TileType::Altar => '_',
+                            TileType::Workbench => 'π',
+                            TileType::Fungus => '"',
+                            TileType::Water | TileType::Lava => '~',
+                            TileType::Rubble => ':',
+                            TileType::Ice => '-',
+                            TileType::Checkpoint => '§',
+                        }]), None));
+                    if game_state.world.is_burning(hover_x, hover_y) {
+                        descriptions.push((tr(lang, "location.burning").to_string(), None));
+                    }
+                    if game_state.world.dark && game_state.world.is_lit(hover_x, hover_y) {
+                        descriptions.push((tr(lang, "location.firelight").to_string(), None));
+                    }
                 }
                 
-                ui.label(format!("Position: ({}, {})", hover_x, hover_y));
+                ui.label(i18n::labeled(lang, "stat.position", format!("({}, {})", hover_x, hover_y)));
                 ui.separator();
                 
                 if descriptions.is_empty() {
-                    ui.label("Nothing of interest here.");
+                    ui.label(tr(lang, "location.nothing"));
                 } else {
-                    for desc in descriptions {
-                        ui.label(desc);
+                    for (desc, examine) in descriptions {
+                        let Some(examine) = examine else {
+                            ui.label(desc);
+                            continue;
+                        };
+                        ui.horizontal(|ui| {
+                            if ui.small_button(tr(lang, "location.examine")).clicked() {
+                                match examine {
+                                    LocationExamine::Npc(id) => interaction.pinned_npc = Some(id),
+                                    LocationExamine::Item(item) => interaction.examined_item = Some(item),
+                                }
+                            }
+                            ui.label(desc);
+                        });
                     }
                 }
             });
@@ -631,7 +3267,152 @@ impl RoguelikeApp {
     }
 }
 
+/// Arrow pointing in one of the eight compass directions from the player towards an offset
+fn compass_arrow(dx: i32, dy: i32) -> char {
+    if dx == 0 && dy == 0 {
+        return '•';
+    }
+    // Screen y grows downwards, so flip it to get a conventional angle
+    let angle = geometry::angle((0, 0), (dx, dy));
+    let octant = ((angle + 360.0 + 22.5) / 45.0) as i32 % 8;
+    ['→', '↗', '↑', '↖', '←', '↙', '↓', '↘'][octant as usize]
+}
+
+/// Write the explored floor to a text file under `exports/`, returning where it went
+fn export_map(game_state: &GameState) -> std::io::Result<std::path::PathBuf> {
+    let directory = std::path::Path::new("exports");
+    std::fs::create_dir_all(directory)?;
+    let path = directory.join(format!("floor{}-turn{}.txt", game_state.world.current_floor, game_state.turn_counter));
+    std::fs::write(&path, game_state.map_ascii())?;
+    Ok(path)
+}
+
+/// Write the whole game state as pretty-printed JSON under `debug/`, for attaching to bug reports
+#[cfg(debug_assertions)]
+fn dump_game_state(game_state: &GameState) -> std::io::Result<std::path::PathBuf> {
+    let name = format!("gamestate-floor{}-turn{}.json", game_state.world.current_floor, game_state.turn_counter);
+    write_game_state(game_state, "debug", &name)
+}
+
+/// Write the game state under `saves/` after a crash, so a long run can be resumed from the game type selection
+fn write_emergency_save(game_state: &GameState) -> std::io::Result<std::path::PathBuf> {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    write_game_state(game_state, "saves", &format!("emergency-{}.json", timestamp))
+}
+
+/// Write the game state under `saves/` when a checkpoint rune saves the run, replacing the last one
+fn write_autosave(game_state: &GameState) -> std::io::Result<std::path::PathBuf> {
+    write_game_state(game_state, "saves", "checkpoint.json")
+}
+
+/// The checkpoint autosave, if an unfinished run left one behind
+fn existing_checkpoint_save() -> Option<std::path::PathBuf> {
+    Some(std::path::Path::new("saves").join("checkpoint.json")).filter(|path| path.exists())
+}
+
+/// The most recent emergency save, if a crash left one behind
+fn latest_emergency_save() -> Option<std::path::PathBuf> {
+    std::fs::read_dir("saves").ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let timestamp: u64 = name.strip_prefix("emergency-")?.strip_suffix(".json")?.parse().ok()?;
+            Some((timestamp, entry.path()))
+        })
+        .max_by_key(|(timestamp, _)| *timestamp)
+        .map(|(_, path)| path)
+}
+
+/// The mode a saved run was started in
+fn game_type_of(condition: &game_condition::SavedCondition) -> AvailableGameType {
+    use game_condition::SavedCondition;
+    match condition {
+        SavedCondition::TreasureHunt(_) => AvailableGameType::TreasureHunt,
+        SavedCondition::Survival(_) => AvailableGameType::Survival,
+        SavedCondition::Collection(_) => AvailableGameType::Collection,
+        SavedCondition::Pacifist(_) => AvailableGameType::Pacifist,
+        SavedCondition::Speedrun(_) => AvailableGameType::Speedrun,
+        SavedCondition::Arena(_) => AvailableGameType::Arena,
+        SavedCondition::Endless(_) => AvailableGameType::Endless,
+        SavedCondition::Mystery(_) => AvailableGameType::Mystery,
+    }
+}
+
+fn read_game_state(path: &std::path::Path) -> Result<GameState, String> {
+    let json = std::fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let game_state: GameState = serde_json::from_str(&json).map_err(|error| error.to_string())?;
+    game_state.reserve_npc_ids();
+    Ok(game_state)
+}
+
+fn write_game_state(game_state: &GameState, directory: &str, name: &str) -> std::io::Result<std::path::PathBuf> {
+    let directory = std::path::Path::new(directory);
+    std::fs::create_dir_all(directory)?;
+    let path = directory.join(name);
+    std::fs::write(&path, serde_json::to_string_pretty(game_state)?)?;
+    Ok(path)
+}
+
+/// Combat numbers and other properties of an item, for the examine dialog and the encyclopedia
+fn draw_item_stats(ui: &mut egui::Ui, lang: Language, item: &Item) {
+    let stats = &item.stats;
+    if let Some((min, max)) = stats.damage {
+        let grip = match stats.handedness {
+            Handedness::OneHanded => "item.one_handed",
+            Handedness::TwoHanded => "item.two_handed",
+            Handedness::Light => "item.light",
+        };
+        ui.label(trf(lang, "item.damage", &[&min, &max, &tr(lang, grip)]));
+    }
+    if stats.block_chance > 0 {
+        ui.label(trf(lang, "item.block_chance", &[&stats.block_chance]));
+    }
+    if stats.light_radius > 0 {
+        ui.label(i18n::labeled(lang, "item.light_radius", stats.light_radius));
+    }
+    if stats.telepathy {
+        ui.label(tr(lang, "item.telepathy"));
+    }
+    if let Some(passive) = stats.passive {
+        ui.label(i18n::labeled(lang, "item.passive", passive.describe()));
+    }
+    if stats.capacity > 0 {
+        ui.label(trf(lang, "item.capacity", &[&stats.capacity]));
+    }
+    if !stats.sockets.is_empty() {
+        let sockets: Vec<&str> = stats.sockets.iter()
+            .map(|socket| socket.as_ref().map_or(tr(lang, "item.empty_socket"), |element| element.gem_name()))
+            .collect();
+        ui.label(i18n::labeled(lang, "item.sockets", sockets.join(", ")));
+    }
+    if let Some(element) = stats.element {
+        ui.label(i18n::labeled(lang, "item.socketed", format!("{} ({})", element.describe(), element.get_name())));
+    }
+    if let Some(fuel) = item.fuel {
+        ui.label(trf(lang, "item.charges", &[&fuel]));
+    }
+}
+
+/// The class ability's name and whether it's ready to use
+fn ability_status(lang: Language, player: &state::Player) -> String {
+    let ability = player.class.ability();
+    if player.ability_cooldown == 0 {
+        trf(lang, "ability.ready", &[&ability.get_name()])
+    } else {
+        trf(lang, "ability.cooling_down", &[&ability.get_name(), &player.ability_cooldown])
+    }
+}
+
 fn main() -> Result<(), eframe::Error> {
+    // Record panics in the diagnostic log as well as on the console
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        tracing::error!("{}", info);
+        default_hook(info);
+    }));
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1200.0, 800.0])
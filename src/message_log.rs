@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Most messages the log holds; past this the oldest routine message makes room first
+const CAPACITY: usize = 50;
+/// Different routine messages logged in one turn past this are dropped
+const ROUTINE_PER_TURN: u32 = 3;
+
+/// How much a message matters when the log is filling up
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MessageCategory {
+    Important, // Combat, discoveries and anything else the player mustn't miss
+    Routine, // Chatter such as footsteps, bumping into walls and refused commands
+}
+
+/// A line in the log, standing for the same message repeated `count` times in a row
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub text: String,
+    pub count: u32,
+    pub category: MessageCategory,
+}
+
+impl LogEntry {
+    /// The line as shown, e.g. "Can't move there! (x5)"
+    pub fn display(&self) -> String {
+        if self.count > 1 { format!("{} (x{})", self.text, self.count) } else { self.text.clone() }
+    }
+}
+
+/// The game's message log. A message repeating the one before it is folded into it with a
+/// count, routine messages are rate-limited per turn, and important messages outlast routine
+/// ones when the log is full.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MessageLog {
+    entries: VecDeque<LogEntry>,
+    routine_turn: u32, // The turn routine messages are being counted for
+    routine_count: u32, // Routine messages added so far on that turn
+    written: u64, // Messages accepted so far, repeats included, for readers catching up with `since`
+}
+
+impl MessageLog {
+    pub fn new(messages: &[&str]) -> Self {
+        let mut log = Self::default();
+        for message in messages {
+            log.push(message.to_string(), MessageCategory::Important, 0);
+        }
+        log
+    }
+
+    /// Add a message logged on a turn, returning false if it was dropped by the rate limit
+    pub fn push(&mut self, text: String, category: MessageCategory, turn: u32) -> bool {
+        if let Some(last) = self.entries.back_mut()
+            && last.text == text
+        {
+            last.count += 1;
+            self.written += 1;
+            return true;
+        }
+
+        if category == MessageCategory::Routine {
+            if self.routine_turn != turn {
+                self.routine_turn = turn;
+                self.routine_count = 0;
+            }
+            if self.routine_count >= ROUTINE_PER_TURN {
+                return false;
+            }
+            self.routine_count += 1;
+        }
+
+        self.entries.push_back(LogEntry { text, count: 1, category });
+        self.written += 1;
+        if self.entries.len() > CAPACITY {
+            let oldest_routine = self.entries.iter().position(|entry| entry.category == MessageCategory::Routine);
+            self.entries.remove(oldest_routine.unwrap_or(0));
+        }
+        true
+    }
+
+    /// Every line in the log, oldest first
+    pub fn lines(&self) -> impl Iterator<Item = String> + '_ {
+        self.entries.iter().map(LogEntry::display)
+    }
+
+    /// How many messages have been accepted since the log was made
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// The lines holding the messages written after the first `mark`, oldest first. A message
+    /// repeated in that time shows once, with its count.
+    pub fn since(&self, mark: u64) -> Vec<String> {
+        let mut remaining = self.written.saturating_sub(mark);
+        let mut lines = Vec::new();
+        for entry in self.entries.iter().rev() {
+            if remaining == 0 {
+                break;
+            }
+            remaining = remaining.saturating_sub(entry.count as u64);
+            lines.push(entry.display());
+        }
+        lines.reverse();
+        lines
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+use std::ops::BitOr;
+
+/// The ways an actor can get around, as a set of flags. Whether a tile can be entered is
+/// decided per actor by `GameWorld::can_enter`, so a bat flies over water and a ghost drifts
+/// through walls while everyone else keeps to the floor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Movement(u8);
+
+impl Movement {
+    pub const WALK: Movement = Movement(1); // Floors, grass, doors and stairs
+    pub const SWIM: Movement = Movement(1 << 1); // Water as well
+    pub const FLY: Movement = Movement(1 << 2); // Anything open, water and lava included, without touching the ground
+    pub const PHASE: Movement = Movement(1 << 3); // Straight through walls and rubble
+
+    /// Whether every way of moving in `other` is in this set
+    pub fn contains(self, other: Movement) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Whether any way of moving in `other` is in this set
+    pub fn intersects(self, other: Movement) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    /// This set with the ways of moving in `other` taken away
+    pub fn without(self, other: Movement) -> Movement {
+        Movement(self.0 & !other.0)
+    }
+}
+
+impl BitOr for Movement {
+    type Output = Movement;
+
+    fn bitor(self, other: Movement) -> Movement {
+        Movement(self.0 | other.0)
+    }
+}
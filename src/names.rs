@@ -0,0 +1,85 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::npc::NPCType;
+
+const ORC_STARTS: [&str; 10] = ["Gr", "Th", "Ur", "Mo", "Vr", "Zu", "Kr", "Bo", "Dr", "Sk"];
+const ORC_VOWELS: [&str; 3] = ["a", "o", "u"];
+const ORC_ENDS: [&str; 8] = ["k", "g", "th", "rg", "sh", "z", "mm", "rk"];
+const ORC_EPITHETS: [&str; 10] = [
+    "the Destroyer", "the Fierce", "the Brutal", "the Savage", "the Terrible",
+    "Skullsplitter", "the Red", "Ironjaw", "the Hungry", "Bonegnawer",
+];
+
+const GOBLIN_STARTS: [&str; 8] = ["Sn", "Gr", "Sk", "N", "Fl", "Z", "Kn", "Squ"];
+const GOBLIN_VOWELS: [&str; 4] = ["i", "o", "a", "ee"];
+const GOBLIN_ENDS: [&str; 7] = ["tch", "b", "k", "zz", "p", "gg", "x"];
+
+const BONE_PREFIXES: [&str; 6] = ["Bone", "Rattle", "Grave", "Marrow", "Hollow", "Dust"];
+const SKELETON_SUFFIXES: [&str; 5] = ["crusher", "grin", "jaw", "shank", "rib"];
+const ARCHER_SUFFIXES: [&str; 5] = ["bow", "shot", "quill", "string", "fletch"];
+
+const DARK_STARTS: [&str; 7] = ["M", "V", "N", "S", "X", "Th", "Z"];
+const DARK_VOWELS: [&str; 4] = ["o", "e", "a", "y"];
+const DARK_ENDS: [&str; 7] = ["rt", "l", "k", "r", "th", "x", "n"];
+
+const STALKER_ADJECTIVES: [&str; 5] = ["Silent", "Unseen", "Shimmering", "Hollow", "Patient"];
+const STALKER_NOUNS: [&str; 4] = ["Stalker", "Shade", "Watcher", "Lurker"];
+const BAT_NAMES: [&str; 6] = ["Squeak", "Flitter", "Nip", "Leatherwing", "Screech", "Dusk"];
+
+const HUMAN_NAMES: [&str; 12] = [
+    "Aldo", "Berta", "Corwin", "Dalia", "Edric", "Fenna",
+    "Garrick", "Hilde", "Ivo", "Jorun", "Katrin", "Lothar",
+];
+const MERCHANT_TITLES: [&str; 3] = ["Trader", "Peddler", "Merchant"];
+const GUARD_RANKS: [&str; 4] = ["Captain", "Sergeant", "Warden", "Guard"];
+
+/// A fresh name for a creature of this type, with whatever title suits it,
+/// e.g. "Grokthash the Brutal" or "Sergeant Hilde"
+pub fn generate(npc_type: &NPCType, rng: &mut impl Rng) -> String {
+    let name = given_name(npc_type, rng);
+    match npc_type {
+        NPCType::Orc => format!("{} {}", name, pick(&ORC_EPITHETS, rng)),
+        NPCType::Necromancer => format!("{} the Necromancer", name),
+        NPCType::Shaman => format!("{} the Shaman", name),
+        NPCType::Merchant => format!("{} the {}", name, pick(&MERCHANT_TITLES, rng)),
+        NPCType::Guard => format!("{} {}", pick(&GUARD_RANKS, rng), name),
+        NPCType::Oracle => format!("{} the Oracle", name),
+        NPCType::Ghost => format!("The Ghost of {}", name),
+        NPCType::Goblin | NPCType::Skeleton | NPCType::SkeletonArcher | NPCType::Stalker | NPCType::Bat => name,
+    }
+}
+
+/// Just the name, for creatures given a special title of their own
+pub fn given_name(npc_type: &NPCType, rng: &mut impl Rng) -> String {
+    match npc_type {
+        NPCType::Orc | NPCType::Shaman => syllables(&ORC_STARTS, &ORC_VOWELS, &ORC_ENDS, rng.gen_range(1..=2), rng),
+        NPCType::Goblin => syllables(&GOBLIN_STARTS, &GOBLIN_VOWELS, &GOBLIN_ENDS, 1, rng),
+        NPCType::Skeleton => format!("{}{}", pick(&BONE_PREFIXES, rng), pick(&SKELETON_SUFFIXES, rng)),
+        NPCType::SkeletonArcher => format!("{}{}", pick(&BONE_PREFIXES, rng), pick(&ARCHER_SUFFIXES, rng)),
+        NPCType::Necromancer => syllables(&DARK_STARTS, &DARK_VOWELS, &DARK_ENDS, 2, rng),
+        NPCType::Stalker => format!("The {} {}", pick(&STALKER_ADJECTIVES, rng), pick(&STALKER_NOUNS, rng)),
+        NPCType::Bat => pick(&BAT_NAMES, rng).to_string(),
+        NPCType::Merchant | NPCType::Guard | NPCType::Oracle | NPCType::Ghost => pick(&HUMAN_NAMES, rng).to_string(),
+    }
+}
+
+/// Run a few consonant-vowel-consonant syllables together, e.g. "Grok" + "thash"
+fn syllables(starts: &[&str], vowels: &[&str], ends: &[&str], count: usize, rng: &mut impl Rng) -> String {
+    let mut name = String::new();
+    for index in 0..count {
+        let start = pick(starts, rng);
+        if index == 0 {
+            name.push_str(start);
+        } else {
+            name.push_str(&start.to_lowercase());
+        }
+        name.push_str(pick(vowels, rng));
+        name.push_str(pick(ends, rng));
+    }
+    name
+}
+
+fn pick<'a>(options: &[&'a str], rng: &mut impl Rng) -> &'a str {
+    options.choose(rng).copied().unwrap_or_default()
+}
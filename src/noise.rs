@@ -0,0 +1,61 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::state::GameWorld;
+use serde::{Deserialize, Serialize};
+
+/// Volume of an ordinary footstep
+pub const FOOTSTEP_VOLUME: i32 = 2;
+/// Volume of a fight breaking out
+pub const COMBAT_VOLUME: i32 = 8;
+/// Volume of something being smashed or destroyed
+pub const SMASH_VOLUME: i32 = 6;
+/// Minimum intensity needed to wake a sleeping NPC
+pub const WAKE_THRESHOLD: i32 = 3;
+
+/// A sound made somewhere in the world during a turn
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Noise {
+    pub position: (i32, i32),
+    pub volume: i32,
+}
+
+impl Noise {
+    pub fn new(x: i32, y: i32, volume: i32) -> Self {
+        Self {
+            position: (x, y),
+            volume,
+        }
+    }
+
+    /// Spread the noise outwards through walkable tiles, losing one point of
+    /// intensity per step. Walls block sound entirely.
+    /// Returns the remaining intensity at every tile the noise reaches.
+    pub fn propagate(&self, world: &GameWorld) -> HashMap<(i32, i32), i32> {
+        let mut heard = HashMap::new();
+        if self.volume <= 0 {
+            return heard;
+        }
+
+        let mut queue = VecDeque::new();
+        heard.insert(self.position, self.volume);
+        queue.push_back((self.position, self.volume));
+
+        while let Some(((x, y), intensity)) = queue.pop_front() {
+            let next_intensity = intensity - 1;
+            if next_intensity <= 0 {
+                continue;
+            }
+
+            for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
+                let next = (x + dx, y + dy);
+                if heard.contains_key(&next) || !world.is_walkable(next.0, next.1) {
+                    continue;
+                }
+                heard.insert(next, next_intensity);
+                queue.push_back((next, next_intensity));
+            }
+        }
+
+        heard
+    }
+}
@@ -1,159 +1,1188 @@
+use crate::balance;
+use crate::catalog;
+use crate::clock::TimeOfDay;
 use crate::item::{Item, ItemType};
+use crate::combat::{self, AttackOutcome, AttackProfile, DefenseProfile};
+use crate::ecs::{EntityId, Health};
+use crate::elite::{self, EliteModifier};
+use crate::faction::Faction;
+use crate::geometry;
+use crate::i18n::{Language, tr, trf};
+use crate::movement::Movement;
+use crate::names;
+use crate::noise;
+use crate::pathfinding;
+use crate::projectile;
+use crate::scheduler;
 use crate::state::{GameWorld, Player, WorldItem};
+use crate::status::{StatusEffects, StatusKind};
 use rand::Rng;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU32, Ordering};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+/// How close the player has to be before a melee hunter goes after them
+const CHASE_DISTANCE: i32 = 5;
+/// How far a skeleton archer can shoot
+const BOW_RANGE: i32 = 8;
+/// Most wares a merchant's cart can hold; past this it rolls over things without taking them
+const MAX_STOCK: usize = 12;
+/// Percent chance a wandering monster is found asleep when a floor is stocked
+const SLEEP_CHANCE: u32 = 30;
+/// Percent chance each turn that a sleeper wakes with the player right beside it, before
+/// the player's dexterity is taken into account
+const STIR_CHANCE: i32 = 50;
+/// Turns after eating before a goblin or orc goes looking for food again
+const HUNGRY_AT: u32 = 40;
+/// How far away a hungry monster can smell food on the floor
+const SMELL_RANGE: i32 = 8;
+/// How far from their post guards go after monsters that wander into town
+const TOWN_RADIUS: i32 = 6;
+/// What stepping onto a known hazard costs when planning a route, against 1 for a safe tile,
+/// so NPCs walk around fire and traps unless the detour is very long
+const HAZARD_COST: u32 = 10;
+/// How far from itself an NPC looks for a route; anything further off it just heads straight for
+const ROUTE_SEARCH_RADIUS: i32 = 20;
+/// Gold the Oracle asks for a reading
+pub const ORACLE_FEE: i32 = 15;
+
+/// Hands out a unique id to every NPC created
+static NEXT_NPC_ID: AtomicU32 = AtomicU32::new(0);
+
+/// Make sure no NPC created from now on gets this id or any below it. A loaded save keeps its
+/// NPCs' ids, but the counter starts again at 0 in a fresh process.
+pub fn reserve_ids_through(id: u32) {
+    NEXT_NPC_ID.fetch_max(id + 1, Ordering::Relaxed);
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NPC {
-    pub position: (i32, i32),
+    pub id: u32, // Stays the same however the NPC list is reordered, so the UI can keep track of it
+    pub entity: EntityId, // Where its position and health are kept, in the floor's entity store
     pub npc_type: NPCType,
     pub name: String,
+    pub state: NPCState,
+    pub ability_cooldown: u32, // Turns until a ranged attack or special ability is ready again
+    pub status_effects: StatusEffects,
+    pub just_attacked: bool, // Attacked the player this turn, briefly giving away its position
+    pub energy: i32, // Banked towards its next action by the scheduler
+    pub stock: Vec<Item>, // Wares a merchant has for sale
+    pub cart: Option<(i32, i32)>, // Where a merchant's cart stands, on the tile he last left
+    pub memory: Memory, // Grudges against the player, kept for as long as the NPC lives
+    pub haggled: i32, // Percent a merchant has been talked down (or, if negative, up) by haggling
+    pub routine: Routine, // Where a peaceful NPC goes about its day
+    pub hunger: u32, // Turns since a goblin or orc last ate
+    pub elite: Option<EliteModifier>, // Named elites are tougher and have an ability of their own
+    pub loot: Option<Item>, // Carried and left behind when it falls, such as a skeleton's key
+    #[serde(skip)]
+    pub plan: Option<StepPlan>, // The step worked out for its next action in the parallel decide phase
+}
+
+/// The first step of an NPC's route, worked out ahead of its action from a read-only view of
+/// the world. It only stands if the NPC is still where it was and heading the same way, and
+/// the tile is checked again when the step is taken, since someone may have moved there since.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StepPlan {
+    pub from: (i32, i32),
+    pub target: (i32, i32),
+    pub step: Option<(i32, i32)>, // None if there's no route at all
+}
+
+/// The stops on a peaceful NPC's daily routine. They're picked around where it first stood,
+/// the first time it needs them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Routine {
+    pub home: Option<(i32, i32)>, // A merchant's first stop, or a guard's post
+    pub waypoint: Option<(i32, i32)>, // The far end of a merchant's circuit, or where a patrolling guard is headed
+    pub outbound: bool, // Heading for the waypoint rather than back home
+}
+
+impl Routine {
+    /// Where the routine is taking the NPC right now
+    pub fn heading(&self) -> Option<(i32, i32)> {
+        if self.outbound { self.waypoint } else { self.home }
+    }
+}
+
+/// What an NPC remembers the player doing to it. Unlike faction reputation, this is personal:
+/// it doesn't fade and isn't shared with the rest of the faction.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Memory {
+    pub attacked: bool, // The player has struck it, so it fights back whenever they meet
+    pub robbed: bool, // The player has stolen from it, so it won't trade
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum NPCType {
     Goblin,
     Orc,
     Skeleton,
+    SkeletonArcher,
+    Necromancer,
+    Shaman,
+    Stalker,
+    Bat,
+    Ghost,
     Merchant,
     Guard,
+    Oracle,
+}
+
+impl NPCType {
+    pub const ALL: [NPCType; 12] = [
+        NPCType::Goblin,
+        NPCType::Orc,
+        NPCType::Skeleton,
+        NPCType::SkeletonArcher,
+        NPCType::Necromancer,
+        NPCType::Shaman,
+        NPCType::Stalker,
+        NPCType::Bat,
+        NPCType::Ghost,
+        NPCType::Merchant,
+        NPCType::Guard,
+        NPCType::Oracle,
+    ];
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            NPCType::Goblin => "Goblin",
+            NPCType::Orc => "Orc",
+            NPCType::Skeleton => "Skeleton",
+            NPCType::SkeletonArcher => "Skeleton Archer",
+            NPCType::Necromancer => "Necromancer",
+            NPCType::Shaman => "Shaman",
+            NPCType::Stalker => "Stalker",
+            NPCType::Bat => "Bat",
+            NPCType::Ghost => "Ghost",
+            NPCType::Merchant => "Merchant",
+            NPCType::Guard => "Guard",
+            NPCType::Oracle => "Oracle",
+        }
+    }
+
+    pub fn get_description(&self, language: Language) -> &'static str {
+        tr(language, match self {
+            NPCType::Goblin => "creature.goblin.description",
+            NPCType::Orc => "creature.orc.description",
+            NPCType::Skeleton => "creature.skeleton.description",
+            NPCType::SkeletonArcher => "creature.skeleton_archer.description",
+            NPCType::Necromancer => "creature.necromancer.description",
+            NPCType::Shaman => "creature.shaman.description",
+            NPCType::Stalker => "creature.stalker.description",
+            NPCType::Bat => "creature.bat.description",
+            NPCType::Ghost => "creature.ghost.description",
+            NPCType::Merchant => "creature.merchant.description",
+            NPCType::Guard => "creature.guard.description",
+            NPCType::Oracle => "creature.oracle.description",
+        })
+    }
+
+    /// What adventurers know this kind of creature can do
+    pub fn abilities(&self, language: Language) -> Vec<&'static str> {
+        let keys: &[&'static str] = match self {
+            NPCType::Goblin => &["creature.ability.dodges_nimbly"],
+            NPCType::Orc => &["creature.ability.hits_hard"],
+            NPCType::Skeleton => &["creature.ability.lies_dormant", "creature.ability.ignores_hazards"],
+            NPCType::SkeletonArcher => &["creature.ability.shoots_arrows", "creature.ability.keeps_bow_range", "creature.ability.ignores_hazards"],
+            NPCType::Necromancer => &["creature.ability.raises_skeletons", "creature.ability.curses_slowness", "creature.ability.keeps_its_distance"],
+            NPCType::Shaman => &["creature.ability.heals_wounded_allies", "creature.ability.hastes_its_allies", "creature.ability.keeps_its_distance"],
+            NPCType::Stalker => &["creature.ability.invisible"],
+            NPCType::Bat => &["creature.ability.flies", "creature.ability.dodges_nimbly"],
+            NPCType::Ghost => &["creature.ability.drifts_through_walls", "creature.ability.floats"],
+            NPCType::Merchant => &["creature.ability.trades", "creature.ability.flees_if_attacked"],
+            NPCType::Guard => &["creature.ability.tower_shield", "creature.ability.defends_town"],
+            NPCType::Oracle => &["creature.ability.reads_smoke", "creature.ability.stays_seated"],
+        };
+        keys.iter().map(|key| tr(language, key)).collect()
+    }
+
+    pub fn max_health(&self) -> i32 {
+        match self {
+            NPCType::Goblin => 10,
+            NPCType::Orc => 30,
+            NPCType::Skeleton => 15,
+            NPCType::SkeletonArcher => 12,
+            NPCType::Necromancer => 20,
+            NPCType::Shaman => 20,
+            NPCType::Stalker => 25,
+            NPCType::Bat => 8,
+            NPCType::Ghost => 18,
+            NPCType::Merchant => 25,
+            NPCType::Guard => 40,
+            NPCType::Oracle => 15,
+        }
+    }
+
+    /// Background flavour for the bestiary
+    pub fn lore(&self, language: Language) -> &'static str {
+        tr(language, match self {
+            NPCType::Goblin => "creature.goblin.lore",
+            NPCType::Orc => "creature.orc.lore",
+            NPCType::Skeleton => "creature.skeleton.lore",
+            NPCType::SkeletonArcher => "creature.skeleton_archer.lore",
+            NPCType::Necromancer => "creature.necromancer.lore",
+            NPCType::Shaman => "creature.shaman.lore",
+            NPCType::Stalker => "creature.stalker.lore",
+            NPCType::Bat => "creature.bat.lore",
+            NPCType::Ghost => "creature.ghost.lore",
+            NPCType::Merchant => "creature.merchant.lore",
+            NPCType::Guard => "creature.guard.lore",
+            NPCType::Oracle => "creature.oracle.lore",
+        })
+    }
+}
+
+/// What an NPC is currently paying attention to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum NPCState {
+    Asleep,
+    Idle,
+    Investigating((i32, i32)), // Heading towards a noise it heard
 }
 
+/// What an NPC needs to know about the rest of the game to take its turn
+pub struct TurnContext<'a> {
+    pub provoked: bool, // Attacked by the player, or of a faction turned hostile
+    pub time_of_day: TimeOfDay,
+    pub language: Language, // For the log messages the turn produces
+    pub forgotten: &'a [ItemType], // Item types the player has forgotten, left unnamed in the log
+}
+
+/// Everything that happened as a result of an NPC taking its turn
+#[derive(Debug, Default)]
+pub struct NPCActionResult {
+    pub log_messages: Vec<String>,
+    pub spawned_npcs: Vec<NPC>,
+    pub support_actions: Vec<SupportAction>,
+    pub strikes: Vec<Strike>,
+}
+
+/// An attack one NPC made on the NPC standing at `target`, already resolved
+#[derive(Debug)]
+pub struct Strike {
+    pub target: (i32, i32),
+    pub outcome: AttackOutcome,
+}
+
+/// A helpful effect one NPC casts on the NPC standing at `target`
 #[derive(Debug)]
-pub enum InteractionResult {
-    Nothing,
-    NPC(NPC),
-    Item(Item),
+pub enum SupportAction {
+    Heal { target: (i32, i32), amount: i32 },
+    ApplyStatus { target: (i32, i32), kind: StatusKind, turns: u32 },
 }
 
 impl NPC {
-    pub fn new(x: i32, y: i32, npc_type: NPCType, name: String) -> Self {
+    /// A new NPC standing at (x, y) on the world's floor, at full health
+    pub fn new(world: &mut GameWorld, x: i32, y: i32, npc_type: NPCType, name: String) -> Self {
+        // Skeletons lie dormant until something disturbs them
+        let state = match npc_type {
+            NPCType::Skeleton => NPCState::Asleep,
+            _ => NPCState::Idle,
+        };
+
+        let entity = world.entities.spawn();
+        world.entities.positions.insert(entity, (x, y));
+        world.entities.healths.insert(entity, Health::new(npc_type.max_health()));
+
+        let stock = match npc_type {
+            NPCType::Merchant => merchant_stock(),
+            _ => Vec::new(),
+        };
+        let loot = match npc_type {
+            NPCType::Skeleton => Some(catalog::bone_key()),
+            _ => None,
+        };
+
+        // Foragers don't all get peckish on the same turn
+        let hunger = match npc_type {
+            NPCType::Goblin | NPCType::Orc => rand::thread_rng().gen_range(0..HUNGRY_AT),
+            _ => 0,
+        };
+
         Self {
-            position: (x, y),
+            id: NEXT_NPC_ID.fetch_add(1, Ordering::Relaxed),
+            entity,
             npc_type,
             name,
+            state,
+            ability_cooldown: 0,
+            status_effects: StatusEffects::default(),
+            just_attacked: false,
+            energy: 0,
+            stock,
+            cart: None,
+            memory: Memory::default(),
+            haggled: 0,
+            routine: Routine::default(),
+            hunger,
+            elite: None,
+            loot,
+            plan: None,
+        }
+    }
+
+    /// Experience the player earns for slaying this NPC
+    pub fn experience_value(&self) -> i32 {
+        const ELITE_MULTIPLIER: i32 = 2;
+
+        let base = match self.npc_type {
+            NPCType::Goblin => 10,
+            NPCType::Orc => 30,
+            NPCType::Skeleton => 15,
+            NPCType::SkeletonArcher => 15,
+            NPCType::Necromancer => 50,
+            NPCType::Shaman => 25,
+            NPCType::Stalker => 40,
+            NPCType::Bat => 8,
+            NPCType::Ghost => 30,
+            NPCType::Merchant => 10,
+            NPCType::Guard => 40,
+            NPCType::Oracle => 10,
+        };
+        if self.elite.is_some() { base * ELITE_MULTIPLIER } else { base }
+    }
+
+    /// Sometimes turn a freshly spawned monster into a named elite, more often on deeper floors
+    pub fn maybe_promote(&mut self, depth: i32, world: &mut GameWorld, rng: &mut impl Rng) {
+        if self.is_hostile()
+            && let Some(modifier) = elite::roll(depth, rng)
+        {
+            self.promote(modifier, world, rng);
+        }
+    }
+
+    /// Make this NPC an elite, renaming it and raising its health to match
+    pub fn promote(&mut self, modifier: EliteModifier, world: &mut GameWorld, rng: &mut impl Rng) {
+        self.name = format!("{} {}", names::given_name(&self.npc_type, rng), modifier.title());
+        let max_health = self.health(world).max;
+        self.set_max_health(world, max_health + max_health * modifier.health_bonus() / 100);
+        self.elite = Some(modifier);
+    }
+
+    /// How quickly the NPC banks energy, from its status effects and any elite swiftness
+    pub fn speed(&self) -> i32 {
+        let speed = scheduler::speed(&self.status_effects);
+        if self.elite == Some(EliteModifier::Swift) { speed * 2 } else { speed }
+    }
+
+    /// Where the NPC stands on the floor
+    pub fn position(&self, world: &GameWorld) -> (i32, i32) {
+        world.entities.positions.get(self.entity).copied().unwrap_or_default()
+    }
+
+    pub fn move_to(&self, world: &mut GameWorld, position: (i32, i32)) {
+        world.entities.positions.insert(self.entity, position);
+    }
+
+    pub fn health(&self, world: &GameWorld) -> Health {
+        world.entities.healths.get(self.entity).copied().unwrap_or(Health { current: 0, max: 0 })
+    }
+
+    /// Change how much health the NPC can have, healing it up to the new maximum
+    pub fn set_max_health(&self, world: &mut GameWorld, max: i32) {
+        world.entities.healths.insert(self.entity, Health::new(max));
+    }
+
+    pub fn take_damage(&self, world: &mut GameWorld, damage: i32) {
+        if let Some(health) = world.entities.healths.get_mut(self.entity) {
+            health.current = (health.current - damage).max(0);
+        }
+    }
+
+    pub fn heal(&self, world: &mut GameWorld, amount: i32) {
+        if let Some(health) = world.entities.healths.get_mut(self.entity) {
+            health.current = (health.current + amount).min(health.max);
+        }
+    }
+
+    pub fn is_alive(&self, world: &GameWorld) -> bool {
+        self.health(world).is_alive()
+    }
+
+    /// Take the NPC's components off the floor once it has left it for good
+    pub fn despawn(&self, world: &mut GameWorld) {
+        world.entities.despawn(self.entity);
+    }
+
+    /// Whether this NPC fights the player and will be supported by shamans
+    pub fn is_hostile(&self) -> bool {
+        matches!(
+            self.npc_type,
+            NPCType::Orc
+                | NPCType::Skeleton
+                | NPCType::SkeletonArcher
+                | NPCType::Necromancer
+                | NPCType::Shaman
+                | NPCType::Stalker
+                | NPCType::Bat
+                | NPCType::Ghost
+        )
+    }
+
+    pub fn faction(&self) -> Faction {
+        match self.npc_type {
+            NPCType::Merchant | NPCType::Guard | NPCType::Oracle => Faction::Town,
+            NPCType::Goblin => Faction::Goblins,
+            _ => Faction::Monsters,
         }
     }
 
+    /// The ways this NPC gets around
+    pub fn movement(&self) -> Movement {
+        match self.npc_type {
+            NPCType::Bat => Movement::FLY,
+            NPCType::Ghost => Movement::FLY | Movement::PHASE,
+            _ => Movement::WALK,
+        }
+    }
+
+    /// Whether this NPC can only be seen with magical help
+    pub fn is_invisible(&self) -> bool {
+        self.npc_type == NPCType::Stalker
+    }
+
     pub fn get_display_char(&self) -> char {
         match self.npc_type {
             NPCType::Goblin => 'g',
             NPCType::Orc => 'O',
             NPCType::Skeleton => 'S',
+            NPCType::SkeletonArcher => 's',
+            NPCType::Necromancer => 'N',
+            NPCType::Shaman => 'o',
+            NPCType::Stalker => 'I',
+            NPCType::Bat => 'b',
+            NPCType::Ghost => 'W',
             NPCType::Merchant => 'M',
             NPCType::Guard => 'G',
+            NPCType::Oracle => 'V',
         }
     }
 
+    /// How a merchant's cart is drawn
+    pub fn cart_display_info() -> (char, (u8, u8, u8)) {
+        ('¤', (160, 110, 60)) // Wooden brown, unlike a treasure chest's '='
+    }
+
     pub fn display_info(&self) -> (char, (u8, u8, u8)) {
         let char = self.get_display_char();
         let color = match self.npc_type {
             NPCType::Goblin => (0, 255, 0), // Green
             NPCType::Orc => (180, 50, 50), // Dark red
             NPCType::Skeleton => (200, 200, 200), // Light gray
+            NPCType::SkeletonArcher => (230, 220, 190), // Bone white
+            NPCType::Necromancer => (148, 0, 211), // Dark violet
+            NPCType::Shaman => (255, 140, 0), // Dark orange
+            NPCType::Stalker => (120, 200, 220), // Pale cyan
+            NPCType::Bat => (150, 110, 80), // Leathery brown
+            NPCType::Ghost => (210, 225, 255), // Ghostly white
             NPCType::Merchant => (100, 150, 255), // Light blue
             NPCType::Guard => (70, 70, 150), // Dark blue
+            NPCType::Oracle => (190, 130, 255), // Lavender
         };
+        if self.elite.is_some() {
+            return (char, (255, 200, 60)); // Elites stand out in gold whatever their kind
+        }
         (char, color)
     }
 
-    /// Perform an action for this NPC during the game turn
-    pub fn perform_action(&mut self, world: &mut GameWorld, player: &mut Player, other_npcs: &[NPC]) -> Vec<String> {
+    /// Whether this NPC reacts to noises at all (merchants are too busy haggling, and the Oracle is lost in the smoke)
+    pub fn hears_noises(&self) -> bool {
+        !matches!(self.npc_type, NPCType::Merchant | NPCType::Oracle)
+    }
+
+    /// Whether the NPC needs to eat. Goblins and orcs forage; everything else gets by without.
+    fn gets_hungry(&self) -> bool {
+        matches!(self.npc_type, NPCType::Goblin | NPCType::Orc)
+    }
+
+    pub fn is_hungry(&self) -> bool {
+        self.gets_hungry() && self.hunger >= HUNGRY_AT
+    }
+
+    /// The nearest food a hungry NPC can smell, unless the player is close enough to fight.
+    /// Food dropped a little way off is enough to lure one away.
+    fn food_to_seek(&self, world: &GameWorld, player: &Player) -> Option<(i32, i32)> {
+        if !self.is_hungry() || geometry::is_adjacent(self.position(world), player.position) {
+            return None;
+        }
+        world.items.iter()
+            .filter(|world_item| world_item.item.is_food() && geometry::within_radius(self.position(world), world_item.position, SMELL_RANGE))
+            .map(|world_item| world_item.position)
+            .min_by_key(|&position| geometry::distance_squared(self.position(world), position))
+    }
+
+    /// Eat the food underfoot, or head for it
+    fn seek_food(&mut self, food: (i32, i32), world: &mut GameWorld, player: &Player, other_npcs: &[NPC], language: Language, log_messages: &mut Vec<String>) {
+        if self.position(world) != food {
+            self.step_towards(food, world, player, other_npcs);
+            return;
+        }
+        if let Some(index) = world.items.iter().position(|world_item| world_item.position == food && world_item.item.is_food()) {
+            let eaten = world.items.remove(index);
+            self.hunger = 0;
+            log_messages.push(trf(language, "log.npc_eats", &[&self.name, &eaten.item.display_name(language)]));
+        }
+    }
+
+    /// Leave a freshly placed monster sleeping some of the time, for the player to sneak past or
+    /// ambush. Invisible monsters are always on the prowl.
+    pub fn doze(&mut self, rng: &mut impl Rng) {
+        if self.is_hostile() && !self.is_invisible() && rng.gen_range(0..100) < SLEEP_CHANCE {
+            self.state = NPCState::Asleep;
+        }
+    }
+
+    /// Percent chance per turn of waking with the player beside it. Nimble players tread lighter.
+    fn stir_chance(&self, player: &Player) -> i32 {
+        const CHANCE_PER_DEXTERITY: i32 = 5;
+        (STIR_CHANCE - (player.dexterity - 10) * CHANCE_PER_DEXTERITY).max(10)
+    }
+
+    /// Called when the NPC is moved somewhere it didn't walk to. Whatever it was heading
+    /// towards is meaningless from the new spot, and the jolt wakes it up.
+    pub fn on_displaced(&mut self) {
+        self.state = NPCState::Idle;
+    }
+
+    /// React to a noise heard at the given intensity, returning a log message if it is noteworthy
+    pub fn hear_noise(&mut self, language: Language, source: (i32, i32), intensity: i32) -> Option<String> {
+        if !self.hears_noises() {
+            return None;
+        }
+
+        match self.state {
+            NPCState::Asleep => {
+                if intensity >= noise::WAKE_THRESHOLD {
+                    self.state = NPCState::Investigating(source);
+                    Some(trf(language, "log.npc_wakes", &[&self.name]))
+                } else {
+                    None
+                }
+            }
+            _ => {
+                self.state = NPCState::Investigating(source);
+                None
+            }
+        }
+    }
+
+    /// Perform an action for this NPC during the game turn. `provoked` is set when the
+    /// NPC's faction has turned on the player, so even peaceful NPCs treat them as a threat.
+    /// Townsfolk follow their daily routine by the time of day.
+    pub fn perform_action(&mut self, world: &mut GameWorld, player: &mut Player, other_npcs: &[NPC], context: &TurnContext) -> NPCActionResult {
+        let TurnContext { provoked, time_of_day, language, forgotten } = *context;
         let mut log_messages = Vec::new();
-        
-        match self.npc_type {
-            NPCType::Merchant => {
-                self.merchant_behavior(world, player, other_npcs, &mut log_messages);
+        let mut spawned_npcs = Vec::new();
+        let mut support_actions = Vec::new();
+        let mut strikes = Vec::new();
+        self.just_attacked = false;
+        let start = self.position(world);
+
+        if self.gets_hungry() {
+            self.hunger += 1;
+        }
+        if self.elite == Some(EliteModifier::Ancient) {
+            self.heal(world, 1);
+        }
+
+        // Sleeping NPCs do nothing until woken, though the player standing over them may rouse them
+        if self.state == NPCState::Asleep {
+            if geometry::is_adjacent(self.position(world), player.position)
+                && rand::thread_rng().gen_range(0..100) < self.stir_chance(player)
+            {
+                self.state = NPCState::Investigating(player.position);
+                log_messages.push(trf(language, "log.npc_stirs", &[&self.name]));
             }
-            NPCType::Orc => {
-                self.orc_behavior(world, player, other_npcs, &mut log_messages);
+            return NPCActionResult { log_messages, ..NPCActionResult::default() };
+        }
+
+        // A confused NPC flails at whatever is nearest, and rivals settle their scores before
+        // anything else gets done
+        if self.status_effects.has(StatusKind::Confused) {
+            self.confused_behavior(world, player, other_npcs, language, &mut log_messages, &mut strikes);
+        } else if let Some(rival) = self.rival_to_fight(world, player, other_npcs, provoked) {
+            self.fight_rival(rival, world, player, other_npcs, &mut strikes);
+        } else if let Some(food) = self.food_to_seek(world, player) {
+            self.seek_food(food, world, player, other_npcs, language, &mut log_messages);
+        } else {
+            match self.npc_type {
+                NPCType::Merchant if provoked => {
+                    // Keep well away from the player and their sword
+                    self.step_away_from(player.position, world, player, other_npcs);
+                }
+                NPCType::Guard if provoked => {
+                    self.hunter_behavior(world, player, other_npcs, language, &mut log_messages);
+                }
+                NPCType::Merchant => {
+                    self.merchant_routine(world, player, other_npcs, time_of_day);
+                }
+                NPCType::Guard => {
+                    self.guard_routine(world, player, other_npcs, time_of_day);
+                }
+                NPCType::Oracle => {
+                    // Sits in the smoke whatever happens
+                }
+                NPCType::Orc => {
+                    self.orc_behavior(world, player, other_npcs, language, &mut log_messages);
+                }
+                NPCType::Skeleton | NPCType::Stalker | NPCType::Bat | NPCType::Ghost => {
+                    self.hunter_behavior(world, player, other_npcs, language, &mut log_messages);
+                }
+                NPCType::SkeletonArcher => {
+                    self.archer_behavior(world, player, other_npcs, language, &mut log_messages);
+                }
+                NPCType::Necromancer => {
+                    self.necromancer_behavior(world, player, other_npcs, language, &mut log_messages, &mut spawned_npcs);
+                }
+                NPCType::Shaman => {
+                    self.shaman_behavior(world, player, other_npcs, language, &mut log_messages, &mut support_actions);
+                }
+                _ => {
+                    // Other NPCs only move to check out noises they heard
+                    self.investigate_noise(world, player, other_npcs);
+                }
             }
-            _ => {
-                // Other NPCs do nothing for now
+        }
+
+        if self.npc_type == NPCType::Merchant && self.position(world) != start {
+            self.pull_cart(start, world, forgotten, language, &mut log_messages);
+        }
+
+        NPCActionResult {
+            log_messages,
+            spawned_npcs,
+            support_actions,
+            strikes,
+        }
+    }
+
+    /// Walk towards the noise being investigated, going back to idle once there or blocked
+    fn investigate_noise(&mut self, world: &mut GameWorld, player: &Player, other_npcs: &[NPC]) {
+        if let NPCState::Investigating(target) = self.state
+            && (self.position(world) == target || !self.step_towards(target, world, player, other_npcs))
+        {
+            self.state = NPCState::Idle;
+        }
+    }
+
+    /// Check whether a position is terrain this NPC can get onto, not occupied by the player or another NPC
+    fn can_move_to(&self, new_pos: (i32, i32), world: &GameWorld, player: &Player, other_npcs: &[NPC]) -> bool {
+        world.can_enter(new_pos.0, new_pos.1, self.movement())
+            && player.position != new_pos
+            && !other_npcs.iter().any(|npc| npc.position(world) == new_pos || npc.cart == Some(new_pos))
+    }
+
+    /// Take one step along the route to the target, returning false if the way is blocked
+    fn step_towards(&mut self, target: (i32, i32), world: &mut GameWorld, player: &Player, other_npcs: &[NPC]) -> bool {
+        match self.next_step_towards(target, world, player, other_npcs) {
+            Some(new_pos) => {
+                self.move_to(world, new_pos);
+                true
             }
+            None => false,
+        }
+    }
+
+    /// Whether the NPC charges straight through fire and traps. The mindless undead don't care.
+    pub fn is_reckless(&self) -> bool {
+        matches!(self.npc_type, NPCType::Skeleton | NPCType::SkeletonArcher)
+    }
+
+    /// Whether the NPC would rather not set foot on a tile
+    fn shies_from(&self, position: (i32, i32), world: &GameWorld) -> bool {
+        !self.is_reckless() && world.is_known_hazard(position.0, position.1)
+    }
+
+    /// The route the NPC would take to a tile, walking around known hazards where it can
+    fn route_to(&self, target: (i32, i32), world: &GameWorld) -> Option<Vec<(i32, i32)>> {
+        if self.position(world) == target {
+            return None;
+        }
+        pathfinding::find_cheapest_path(self.position(world), target, ROUTE_SEARCH_RADIUS, |(x, y)| {
+            if (x, y) == target {
+                Some(1) // Whoever is standing there, it's where the NPC is headed
+            } else if !world.can_enter(x, y, self.movement()) {
+                None
+            } else if self.shies_from((x, y), world) {
+                Some(HAZARD_COST)
+            } else {
+                Some(world.moves_to_cross(x, y, self.movement()) as u32)
+            }
+        })
+    }
+
+    /// Take one step that increases the distance from the threat, returning false if cornered
+    fn step_away_from(&mut self, threat: (i32, i32), world: &mut GameWorld, player: &Player, other_npcs: &[NPC]) -> bool {
+        let distance_squared = |pos: (i32, i32)| geometry::distance_squared(pos, threat);
+
+        let current_distance = distance_squared(self.position(world));
+        let best_move = [(0, 1), (0, -1), (1, 0), (-1, 0)]
+            .iter()
+            .map(|(dx, dy)| (self.position(world).0 + dx, self.position(world).1 + dy))
+            .filter(|pos| self.can_move_to(*pos, world, player, other_npcs) && !self.shies_from(*pos, world))
+            .filter(|pos| distance_squared(*pos) > current_distance)
+            .max_by_key(|pos| distance_squared(*pos));
+
+        match best_move {
+            Some(new_pos) => {
+                self.move_to(world, new_pos);
+                true
+            }
+            None => false,
         }
-        
-        log_messages
     }
     
-    /// Merchant-specific behavior: random movement and item interaction
-    fn merchant_behavior(&mut self, world: &mut GameWorld, player: &Player, other_npcs: &[NPC], log_messages: &mut Vec<String>) {
+    /// Merchant routine: by day, push the cart back and forth between two stops;
+    /// by night, stay at the first one
+    fn merchant_routine(&mut self, world: &mut GameWorld, player: &Player, other_npcs: &[NPC], time_of_day: TimeOfDay) {
+        const CIRCUIT_RANGE: i32 = 8;
+
         let mut rng = rand::thread_rng();
-        
-        // 24% chance to move each turn
-        if rng.gen_range(0..100) < 24 {
-            self.try_random_move(world, player, other_npcs, log_messages, &mut rng);
+        let home = *self.routine.home.get_or_insert(self.position(world));
+        let waypoint = match self.routine.waypoint {
+            Some(waypoint) => waypoint,
+            None => *self.routine.waypoint.insert(Self::pick_waypoint(home, CIRCUIT_RANGE, world, &mut rng)),
+        };
+        self.routine.outbound = match time_of_day {
+            TimeOfDay::Night => false,
+            TimeOfDay::Day if self.position(world) == waypoint => false,
+            TimeOfDay::Day if self.position(world) == home => true,
+            TimeOfDay::Day => self.routine.outbound,
+        };
+
+        if rng.gen_range(0..100) >= balance::current().merchant_move_chance {
+            return; // Stops to mind the stall for a while
+        }
+        let target = if self.routine.outbound { waypoint } else { home };
+        if let Some(step) = self.next_step_towards(target, world, player, other_npcs) {
+            self.move_to(world, step);
+        }
+    }
+
+    /// Guard routine: stand at the post by day; by night, patrol from spot to spot around it.
+    /// Noises still draw a guard away to check them out.
+    fn guard_routine(&mut self, world: &mut GameWorld, player: &Player, other_npcs: &[NPC], time_of_day: TimeOfDay) {
+        const PATROL_RANGE: i32 = 6;
+
+        if let NPCState::Investigating(_) = self.state {
+            self.investigate_noise(world, player, other_npcs);
+            return;
+        }
+
+        let post = *self.routine.home.get_or_insert(self.position(world));
+        self.routine.outbound = time_of_day == TimeOfDay::Night;
+        if self.routine.outbound && self.routine.waypoint.is_none_or(|waypoint| waypoint == self.position(world)) {
+            self.routine.waypoint = Some(Self::pick_waypoint(post, PATROL_RANGE, world, &mut rand::thread_rng()));
+        }
+
+        if let Some(target) = self.routine.heading()
+            && let Some(step) = self.next_step_towards(target, world, player, other_npcs)
+        {
+            self.move_to(world, step);
+        }
+    }
+
+    /// A random walkable tile within range of a spot that can be walked to from it,
+    /// or the spot itself if none turns up
+    fn pick_waypoint(around: (i32, i32), range: i32, world: &GameWorld, rng: &mut impl Rng) -> (i32, i32) {
+        const ATTEMPTS: usize = 20;
+
+        (0..ATTEMPTS)
+            .map(|_| (around.0 + rng.gen_range(-range..=range), around.1 + rng.gen_range(-range..=range)))
+            .find(|&(x, y)| {
+                world.can_enter(x, y, Movement::WALK)
+                    && pathfinding::find_path(around, (x, y), |(x, y)| world.can_enter(x, y, Movement::WALK)).is_some()
+            })
+            .unwrap_or(around)
+    }
+
+    /// The first step of the route to a tile, if there is one and nobody is standing on it.
+    /// A step planned ahead for the same tile saves finding the route again.
+    fn next_step_towards(&self, target: (i32, i32), world: &GameWorld, player: &Player, other_npcs: &[NPC]) -> Option<(i32, i32)> {
+        let step = match self.plan {
+            Some(plan) if plan.from == self.position(world) && plan.target == target => plan.step,
+            _ if geometry::chebyshev_distance(self.position(world), target) > ROUTE_SEARCH_RADIUS => Some((
+                self.position(world).0 + (target.0 - self.position(world).0).signum(),
+                self.position(world).1 + (target.1 - self.position(world).1).signum(),
+            )),
+            _ => self.route_to(target, world)?.first().copied(),
+        };
+        step.filter(|&step| self.can_move_to(step, world, player, other_npcs))
+    }
+
+    /// Work out the first step towards whatever the NPC is after, without changing anything,
+    /// so it can be done for many NPCs at once before any of them acts
+    pub fn plan_step(&self, world: &GameWorld, player: &Player) -> Option<StepPlan> {
+        let target = self.ai_target(world, player)?;
+        let step = self.route_to(target, world).and_then(|path| path.first().copied());
+        Some(StepPlan { from: self.position(world), target, step })
+    }
+
+    /// Pull the cart onto the tile the merchant just left. Anything worth selling lying there is
+    /// loaded up as stock for the player to buy back, and now and then something falls off the back.
+    fn pull_cart(&mut self, onto: (i32, i32), world: &mut GameWorld, forgotten: &[ItemType], language: Language, log_messages: &mut Vec<String>) {
+        let behind = self.cart.replace(onto);
+
+        while self.stock.len() < MAX_STOCK
+            && let Some(item_index) = world.items.iter().position(|item| item.position == onto && item.price.is_none() && item.item.value() > 0)
+        {
+            let collected = world.items.remove(item_index);
+            log_messages.push(trf(language, "log.npc_loads_cart", &[&self.name, &collected.item.known_name(language, forgotten)]));
+            self.stock.push(collected.item);
+        }
+
+        let mut rng = rand::thread_rng();
+        if let Some(behind) = behind
+            && rng.gen_range(0..100) < balance::current().cart_drop_chance
+        {
+            self.drop_random_item(behind, world, language, log_messages, &mut rng);
         }
     }
     
-    /// Try to move the merchant randomly (up to 2 attempts)
-    fn try_random_move(&mut self, world: &mut GameWorld, player: &Player, other_npcs: &[NPC], log_messages: &mut Vec<String>, rng: &mut impl Rng) {
-        let directions = [(0, 1), (0, -1), (1, 0), (-1, 0)]; // down, up, right, left
-        
-        // Try up to 2 times to find a valid move
-        for _attempt in 0..2 {
-            let (dx, dy) = directions[rng.gen_range(0..directions.len())];
-            let new_pos = (self.position.0 + dx, self.position.1 + dy);
-            
-            // Check if the new position is valid and walkable
-            if !world.is_valid_position(new_pos.0, new_pos.1) || !world.is_walkable(new_pos.0, new_pos.1) {
-                continue; // Try another direction
+    /// Drop a random collectible item at a position
+    fn drop_random_item(&self, position: (i32, i32), world: &mut GameWorld, language: Language, log_messages: &mut Vec<String>, rng: &mut impl Rng) {
+        let item_types = [ItemType::Gem, ItemType::Scroll, ItemType::Potion];
+        let item = catalog::collectible(item_types[rng.gen_range(0..item_types.len())].clone());
+        log_messages.push(trf(language, "log.cart_drop", &[&item.display_name(language)]));
+        world.items.push(WorldItem::new(position.0, position.1, item));
+    }
+    
+    /// Skeleton archer behavior: keep the player at bow range and shoot every other turn
+    fn archer_behavior(&mut self, world: &mut GameWorld, player: &mut Player, other_npcs: &[NPC], language: Language, log_messages: &mut Vec<String>) {
+        const PREFERRED_DISTANCE: i32 = 3;
+
+        self.ability_cooldown = self.ability_cooldown.saturating_sub(1);
+
+        let blockers: Vec<(i32, i32)> = other_npcs.iter().map(|npc| npc.position(world)).collect();
+        let can_see_player = geometry::within_radius(self.position(world), player.position, BOW_RANGE)
+            && projectile::has_clear_shot(world, self.position(world), player.position, BOW_RANGE, &blockers);
+
+        if !can_see_player {
+            // Nothing to shoot at - check out any noises instead
+            self.investigate_noise(world, player, other_npcs);
+            return;
+        }
+
+        // Too close for comfort - back off rather than shoot
+        if geometry::closer_than(self.position(world), player.position, PREFERRED_DISTANCE)
+            && self.step_away_from(player.position, world, player, other_npcs)
+        {
+            return;
+        }
+
+        if self.ability_cooldown == 0 {
+            self.attack_player((3, 8), "attack.verb_bone_shard", world, player, language, log_messages);
+            self.ability_cooldown = 2;
+        }
+    }
+
+    /// Necromancer behavior: keep well away from the player and raise skeletons to fight instead
+    fn necromancer_behavior(&mut self, world: &mut GameWorld, player: &mut Player, other_npcs: &[NPC], language: Language, log_messages: &mut Vec<String>, spawned_npcs: &mut Vec<NPC>) {
+        const SAFE_DISTANCE: i32 = 5;
+        const SUMMON_INTERVAL: u32 = 5;
+        const MAX_SKELETONS: usize = 4;
+        const CURSE_RANGE: i32 = 7;
+        const CURSE_TURNS: u32 = 4;
+
+        self.ability_cooldown = self.ability_cooldown.saturating_sub(1);
+
+        // Self-preservation comes first
+        if geometry::closer_than(self.position(world), player.position, SAFE_DISTANCE)
+            && self.step_away_from(player.position, world, player, other_npcs)
+        {
+            return;
+        }
+
+        if self.ability_cooldown > 0 {
+            return;
+        }
+
+        // With its army at full strength, the necromancer curses the player with slowness instead
+        let skeleton_count = other_npcs.iter().filter(|npc| npc.npc_type == NPCType::Skeleton).count();
+        if skeleton_count >= MAX_SKELETONS {
+            if geometry::within_radius(self.position(world), player.position, CURSE_RANGE) && !player.status_effects.has(StatusKind::Slowed) {
+                player.status_effects.add(StatusKind::Slowed, CURSE_TURNS);
+                log_messages.push(trf(language, "log.npc_curses", &[&self.name]));
+                self.ability_cooldown = SUMMON_INTERVAL;
             }
-            
-            // Check if player is at the new position
-            if player.position == new_pos {
-                continue; // Try another direction
+            return;
+        }
+
+        // Raise the skeleton on a free tile next to the necromancer
+        let summon_pos = [(0, 1), (0, -1), (1, 0), (-1, 0)]
+            .iter()
+            .map(|(dx, dy)| (self.position(world).0 + dx, self.position(world).1 + dy))
+            .find(|pos| self.can_move_to(*pos, world, player, other_npcs));
+
+        if let Some(pos) = summon_pos {
+            let mut skeleton = NPC::new(world, pos.0, pos.1, NPCType::Skeleton, names::generate(&NPCType::Skeleton, &mut rand::thread_rng()));
+            skeleton.state = NPCState::Investigating(player.position);
+            spawned_npcs.push(skeleton);
+            log_messages.push(trf(language, "log.npc_raises_skeleton", &[&self.name]));
+            self.ability_cooldown = SUMMON_INTERVAL;
+        }
+    }
+
+    /// Shaman behavior: hang back and heal or haste nearby allies instead of fighting
+    fn shaman_behavior(&mut self, world: &mut GameWorld, player: &Player, other_npcs: &[NPC], language: Language, log_messages: &mut Vec<String>, support_actions: &mut Vec<SupportAction>) {
+        const SAFE_DISTANCE: i32 = 3;
+        const SUPPORT_RADIUS: i32 = 4;
+        const HEAL_AMOUNT: i32 = 8;
+        const HASTE_TURNS: u32 = 5;
+        const HASTE_INTERVAL: u32 = 4;
+
+        self.ability_cooldown = self.ability_cooldown.saturating_sub(1);
+
+        if geometry::closer_than(self.position(world), player.position, SAFE_DISTANCE)
+            && self.step_away_from(player.position, world, player, other_npcs)
+        {
+            return;
+        }
+
+        let allies_in_range: Vec<&NPC> = other_npcs
+            .iter()
+            .filter(|npc| npc.is_hostile())
+            .filter(|npc| geometry::chebyshev_distance(npc.position(world), self.position(world)) <= SUPPORT_RADIUS)
+            .collect();
+
+        // Healing the most wounded ally takes priority over hasting
+        if let Some(wounded) = allies_in_range
+            .iter()
+            .filter(|npc| npc.health(world).current < npc.health(world).max)
+            .min_by_key(|npc| npc.health(world).current)
+        {
+            support_actions.push(SupportAction::Heal { target: wounded.position(world), amount: HEAL_AMOUNT });
+            log_messages.push(trf(language, "log.npc_heals", &[&self.name, &wounded.name]));
+            return;
+        }
+
+        if self.ability_cooldown == 0
+            && let Some(ally) = allies_in_range.iter().find(|npc| !npc.status_effects.has(StatusKind::Hasted))
+        {
+            support_actions.push(SupportAction::ApplyStatus {
+                target: ally.position(world),
+                kind: StatusKind::Hasted,
+                turns: HASTE_TURNS,
+            });
+            log_messages.push(trf(language, "log.npc_hastes", &[&self.name, &ally.name]));
+            self.ability_cooldown = HASTE_INTERVAL;
+        }
+    }
+
+    /// Skeleton and stalker behavior: once awake, attack the player if nearby, otherwise chase noises
+    fn hunter_behavior(&mut self, world: &mut GameWorld, player: &mut Player, other_npcs: &[NPC], language: Language, log_messages: &mut Vec<String>) {
+        if geometry::within_radius(self.position(world), player.position, CHASE_DISTANCE) {
+            self.move_towards_player_or_attack(world, player, other_npcs, language, log_messages);
+        } else {
+            self.investigate_noise(world, player, other_npcs);
+        }
+    }
+
+    /// A rival NPC this one means to fight instead of going about its business. Guards go after
+    /// monsters that wander into town; anyone else only turns on a rival standing right next to
+    /// it, and not while it has the player to deal with.
+    fn rival_to_fight(&self, world: &GameWorld, player: &Player, other_npcs: &[NPC], provoked: bool) -> Option<(i32, i32)> {
+        if matches!(self.npc_type, NPCType::Merchant | NPCType::Oracle) {
+            return None; // Leaves the fighting to the guards
+        }
+        let mut rivals = other_npcs.iter()
+            .filter(|other| self.faction().is_rival(other.faction()) && !other.is_invisible());
+        if self.npc_type == NPCType::Guard {
+            let post = self.routine.home.unwrap_or(self.position(world));
+            return rivals
+                .filter(|other| !provoked && geometry::within_radius(post, other.position(world), TOWN_RADIUS))
+                .min_by_key(|other| geometry::distance_squared(self.position(world), other.position(world)))
+                .map(|other| other.position(world));
+        }
+        let busy_with_player = (provoked || self.is_hostile())
+            && geometry::within_radius(self.position(world), player.position, CHASE_DISTANCE);
+        if busy_with_player {
+            return None;
+        }
+        rivals.find(|other| geometry::is_adjacent(self.position(world), other.position(world))).map(|other| other.position(world))
+    }
+
+    /// Close in on a rival and strike it once in reach
+    fn fight_rival(&mut self, target: (i32, i32), world: &mut GameWorld, player: &Player, other_npcs: &[NPC], strikes: &mut Vec<Strike>) {
+        match other_npcs.iter().find(|npc| npc.position(world) == target) {
+            Some(rival) if geometry::is_adjacent(self.position(world), target) => strikes.push(self.strike(rival, world)),
+            _ => {
+                self.step_towards(target, world, player, other_npcs);
             }
-            
-            // Check if another NPC is at the new position
-            if other_npcs.iter().any(|npc| npc.position == new_pos) {
-                continue; // Try another direction
+        }
+    }
+
+    /// Attack another NPC through the shared combat resolution. The damage is dealt once
+    /// the NPC's turn is over, since the others can't be changed while it acts.
+    fn strike(&mut self, rival: &NPC, world: &mut GameWorld) -> Strike {
+        let attack = AttackProfile { damage: self.melee_damage(), crit_chance: 5, life_steal: 0 };
+        let outcome = combat::resolve_attack(&attack, &rival.defense_profile(), &mut rand::thread_rng());
+        self.just_attacked = true;
+        world.emit_noise(self.position(world), noise::COMBAT_VOLUME);
+        Strike { target: rival.position(world), outcome }
+    }
+
+    /// Confused behavior: swing at whoever is next to it, player or NPC alike, or stagger
+    /// about if there's nobody there
+    fn confused_behavior(&mut self, world: &mut GameWorld, player: &mut Player, other_npcs: &[NPC], language: Language, log_messages: &mut Vec<String>, strikes: &mut Vec<Strike>) {
+        let mut rng = rand::thread_rng();
+        let neighbours: Vec<Option<&NPC>> = other_npcs.iter()
+            .filter(|npc| geometry::is_adjacent(self.position(world), npc.position(world)))
+            .map(Some)
+            .chain(geometry::is_adjacent(self.position(world), player.position).then_some(None))
+            .collect();
+        if !neighbours.is_empty() {
+            match neighbours[rng.gen_range(0..neighbours.len())] {
+                Some(npc) => strikes.push(self.strike(npc, world)),
+                None => self.attack_player(self.melee_damage(), "attack.verb_lashes_out", world, player, language, log_messages),
             }
-            
-            // Valid move found! Check if there's an item at the new position - if so, destroy it
-            if let Some(item_index) = world.items.iter().position(|item| item.position == new_pos) {
-                let destroyed_item = world.items.remove(item_index);
-                log_messages.push(format!("The merchant and his cart destroyed the {} on the ground!", destroyed_item.item.label));
+            return;
+        }
+        let steps: Vec<(i32, i32)> = geometry::circle(self.position(world), 1)
+            .filter(|&pos| pos != self.position(world) && self.can_move_to(pos, world, player, other_npcs))
+            .collect();
+        if !steps.is_empty() {
+            self.move_to(world, steps[rng.gen_range(0..steps.len())]);
+        }
+    }
+
+    /// Where the NPC means to go, for the debug overlay. Mirrors the checks its behavior makes.
+    pub fn ai_target(&self, world: &GameWorld, player: &Player) -> Option<(i32, i32)> {
+        let chases_player = matches!(self.npc_type, NPCType::Orc | NPCType::Skeleton | NPCType::Stalker | NPCType::Bat | NPCType::Ghost)
+            && geometry::within_radius(self.position(world), player.position, CHASE_DISTANCE);
+        match self.state {
+            NPCState::Asleep => None,
+            _ if chases_player => Some(player.position),
+            NPCState::Investigating(target) => Some(target),
+            NPCState::Idle => self.routine.heading().filter(|&heading| heading != self.position(world)),
+        }
+    }
+
+    /// The tiles the NPC would step through to reach the target if nothing got in its way
+    pub fn planned_path(&self, target: (i32, i32), world: &GameWorld) -> Vec<(i32, i32)> {
+        const MAX_STEPS: usize = 30;
+
+        let mut path = self.route_to(target, world).unwrap_or_default();
+        path.truncate(MAX_STEPS);
+        path
+    }
+
+    /// How far away this NPC can hurt the player from: the next tile for brawlers, further for
+    /// archers, or None for those who leave the fighting to others
+    pub fn attack_reach(&self) -> Option<i32> {
+        match self.npc_type {
+            NPCType::SkeletonArcher => Some(BOW_RANGE),
+            NPCType::Orc | NPCType::Skeleton | NPCType::Stalker | NPCType::Bat | NPCType::Ghost | NPCType::Guard => Some(1),
+            NPCType::Goblin | NPCType::Necromancer | NPCType::Shaman | NPCType::Merchant | NPCType::Oracle => None,
+        }
+    }
+
+    /// The tiles this NPC could move onto or attack with a number of actions: it can step as
+    /// far as it has actions, and attack from anywhere it can reach with one to spare
+    pub fn threat_area(&self, world: &GameWorld, actions: u32) -> HashSet<(i32, i32)> {
+        let mut area = HashSet::new();
+        let mut seen = HashSet::from([self.position(world)]);
+        let mut frontier = vec![self.position(world)];
+        let mut attack_from = vec![self.position(world)];
+        for step in 1..=actions {
+            let next: Vec<(i32, i32)> = frontier.iter()
+                .flat_map(|&(x, y)| [(x, y + 1), (x, y - 1), (x + 1, y), (x - 1, y)])
+                .filter(|&(x, y)| world.can_enter(x, y, self.movement()))
+                .filter(|&position| seen.insert(position))
+                .collect();
+            area.extend(next.iter().copied());
+            if step < actions {
+                attack_from.extend(next.iter().copied());
             }
-            
-            // Move the merchant
-            self.position = new_pos;
-            
-            // 15% chance to drop an item after moving
-            if rng.gen_range(0..100) < 15 {
-                self.drop_random_item(world, log_messages, rng);
+            frontier = next;
+        }
+
+        if actions == 0 {
+            return area;
+        }
+        match self.attack_reach() {
+            Some(1) => {
+                // Blows land diagonally too
+                for &from in &attack_from {
+                    area.extend((-1..=1).flat_map(|dx| (-1..=1).map(move |dy| (from.0 + dx, from.1 + dy))));
+                }
             }
-            
-            return; // Successfully moved, exit the function
+            Some(range) => {
+                for &from in &attack_from {
+                    area.extend(geometry::circle(from, range).filter(|&target| projectile::has_clear_shot(world, from, target, range, &[])));
+                }
+            }
+            None => {}
         }
-        
-        // If we get here, no valid move was found after 2 attempts
+        area.retain(|&position| position != self.position(world));
+        area
     }
-    
-    /// Drop a random collectible item
-    fn drop_random_item(&self, world: &mut GameWorld, log_messages: &mut Vec<String>, rng: &mut impl Rng) {
-        let item_types = [ItemType::Gem, ItemType::Scroll, ItemType::Potion];
-        let item_type = item_types[rng.gen_range(0..item_types.len())].clone();
-        
-        let (name, description) = match item_type {
-            ItemType::Gem => ("Precious Gem", "A sparkling gem that catches the light"),
-            ItemType::Scroll => ("Ancient Scroll", "A scroll covered in mysterious writing"),
-            ItemType::Potion => ("Magic Potion", "A bubbling potion with unknown effects"),
-            _ => ("Unknown Item", "A mysterious object"),
+
+    /// Damage range of this NPC's melee attack
+    fn melee_damage(&self) -> (i32, i32) {
+        let (min, max) = match self.npc_type {
+            NPCType::Orc => balance::current().orc_damage,
+            NPCType::Skeleton => (3, 10),
+            NPCType::Stalker => (4, 12),
+            NPCType::Ghost => (2, 8),
+            _ => (1, 5),
         };
-        
-        let item = Item::new(item_type, name.to_string(), description.to_string());
-        world.items.push(WorldItem::new(self.position.0, self.position.1, item));
-        
-        log_messages.push(format!("The merchant dropped a {} from his cart!", name));
+        let bonus = self.elite.map_or(0, |modifier| modifier.damage_bonus());
+        (min + bonus, max + bonus)
     }
-    
+
+    /// How hard this NPC is to hit
+    pub fn defense_profile(&self) -> DefenseProfile {
+        let dodge_chance = match self.npc_type {
+            NPCType::Goblin | NPCType::Bat => 20,
+            NPCType::Stalker => 15,
+            NPCType::SkeletonArcher | NPCType::Necromancer | NPCType::Shaman => 10,
+            _ => 5,
+        };
+        let block_chance = match self.npc_type {
+            NPCType::Guard => 30, // Carries a tower shield
+            _ => 0,
+        };
+        DefenseProfile { dodge_chance, block_chance }
+    }
+
+    /// Attack the player through the shared combat resolution, logging and showing the outcome
+    fn attack_player(&mut self, damage: (i32, i32), verb: &'static str, world: &mut GameWorld, player: &mut Player, language: Language, log_messages: &mut Vec<String>) {
+        const VAMPIRIC_LIFE_STEAL: u32 = 50;
+        const BRUTAL_STUN_CHANCE: u32 = 20;
+
+        let life_steal = if self.elite == Some(EliteModifier::Vampiric) { VAMPIRIC_LIFE_STEAL } else { 0 };
+        let attack = AttackProfile { damage, crit_chance: 5, life_steal };
+        let mut rng = rand::thread_rng();
+        let outcome = combat::resolve_attack(&attack, &player.defense_profile(), &mut rng);
+        player.take_damage(outcome.damage());
+        self.heal(world, outcome.damage() * attack.life_steal as i32 / 100);
+        self.just_attacked = true;
+        if self.elite == Some(EliteModifier::Brutal) && outcome.damage() > 0 && rng.gen_range(0..100) < BRUTAL_STUN_CHANCE {
+            player.status_effects.add(StatusKind::Stunned, 1);
+            log_messages.push(trf(language, "log.npc_stuns", &[&self.name]));
+        }
+
+        let attacker = if self.is_invisible() && !player.status_effects.has(StatusKind::SeeInvisible) {
+            tr(language, "log.something_unseen")
+        } else {
+            &self.name
+        };
+        log_messages.push(combat::describe_attack_on_player(language, &outcome, attacker, tr(language, verb)));
+        world.show_attack_outcome(player.position, &outcome);
+        world.emit_noise(self.position(world), noise::COMBAT_VOLUME);
+    }
+
     /// Orc-specific behavior: aggressive movement towards player
-    fn orc_behavior(&mut self, world: &mut GameWorld, player: &mut Player, other_npcs: &[NPC], log_messages: &mut Vec<String>) {
-        let player_distance = self.distance_to_player(player);
-        
-        if player_distance <= 5.0 {
+    fn orc_behavior(&mut self, world: &mut GameWorld, player: &mut Player, other_npcs: &[NPC], language: Language, log_messages: &mut Vec<String>) {
+        if geometry::within_radius(self.position(world), player.position, CHASE_DISTANCE) {
             // Close to player - move towards them or attack
-            self.move_towards_player_or_attack(world, player, other_npcs, log_messages);
+            self.move_towards_player_or_attack(world, player, other_npcs, language, log_messages);
+        } else if let NPCState::Investigating(_) = self.state {
+            // Heard something - go and check it out
+            self.investigate_noise(world, player, other_npcs);
         } else {
             // Far from player - move randomly
             let mut rng = rand::thread_rng();
@@ -161,46 +1190,20 @@ impl NPC {
         }
     }
     
-    /// Calculate distance to player
-    fn distance_to_player(&self, player: &Player) -> f32 {
-        let dx = (self.position.0 - player.position.0) as f32;
-        let dy = (self.position.1 - player.position.1) as f32;
-        (dx * dx + dy * dy).sqrt()
-    }
-    
     /// Move towards player or attack if adjacent
-    fn move_towards_player_or_attack(&mut self, world: &mut GameWorld, player: &mut Player, other_npcs: &[NPC], log_messages: &mut Vec<String>) {
-        let dx = player.position.0 - self.position.0;
-        let dy = player.position.1 - self.position.1;
-        
-        // Calculate the direction to move (one step towards player)
-        let move_x = if dx > 0 { 1 } else if dx < 0 { -1 } else { 0 };
-        let move_y = if dy > 0 { 1 } else if dy < 0 { -1 } else { 0 };
-        
-        let new_pos = (self.position.0 + move_x, self.position.1 + move_y);
+    fn move_towards_player_or_attack(&mut self, world: &mut GameWorld, player: &mut Player, other_npcs: &[NPC], language: Language, log_messages: &mut Vec<String>) {
+        let dx = player.position.0 - self.position(world).0;
+        let dy = player.position.1 - self.position(world).1;
         
         // Check if we would move onto the player - if so, attack instead
+        let new_pos = (self.position(world).0 + dx.signum(), self.position(world).1 + dy.signum());
         if new_pos == player.position {
-            // Attack the player
-            let mut rng = rand::thread_rng();
-            let damage = rng.gen_range(5..=20);
-            player.take_damage(damage);
-            log_messages.push(format!("The orc {} attacks you for {} damage!", self.name, damage));
+            self.attack_player(self.melee_damage(), "attack.verb_attacks", world, player, language, log_messages);
             return;
         }
         
-        // Check if the new position is valid and walkable
-        if !world.is_valid_position(new_pos.0, new_pos.1) || !world.is_walkable(new_pos.0, new_pos.1) {
-            return; // Can't move there
-        }
-        
-        // Check if another NPC is at the new position
-        if other_npcs.iter().any(|npc| npc.position == new_pos) {
-            return; // Can't move into another NPC
-        }
-        
-        // Move the orc
-        self.position = new_pos;
+        // Otherwise close the distance
+        self.step_towards(player.position, world, player, other_npcs);
     }
     
     /// Try to move the orc randomly (for when far from player)
@@ -210,10 +1213,10 @@ impl NPC {
         // Try up to 2 times to find a valid move
         for _attempt in 0..2 {
             let (dx, dy) = directions[rng.gen_range(0..directions.len())];
-            let new_pos = (self.position.0 + dx, self.position.1 + dy);
+            let new_pos = (self.position(world).0 + dx, self.position(world).1 + dy);
             
             // Check if the new position is valid and walkable
-            if !world.is_valid_position(new_pos.0, new_pos.1) || !world.is_walkable(new_pos.0, new_pos.1) {
+            if !world.can_enter(new_pos.0, new_pos.1, self.movement()) {
                 continue; // Try another direction
             }
             
@@ -223,15 +1226,30 @@ impl NPC {
             }
             
             // Check if another NPC is at the new position
-            if other_npcs.iter().any(|npc| npc.position == new_pos) {
+            if other_npcs.iter().any(|npc| npc.position(world) == new_pos) {
+                continue; // Try another direction
+            }
+
+            // Don't wander into fire or traps
+            if self.shies_from(new_pos, world) {
                 continue; // Try another direction
             }
             
             // Valid move found - move the orc
-            self.position = new_pos;
+            self.move_to(world, new_pos);
             return; // Successfully moved, exit the function
         }
         
         // If we get here, no valid move was found after 2 attempts
     }
-}
\ No newline at end of file
+}
+
+/// What a merchant carries to sell
+fn merchant_stock() -> Vec<Item> {
+    vec![
+        catalog::healing_potion(),
+        catalog::trail_rations(),
+        catalog::torch(100),
+        catalog::leather_backpack(),
+    ]
+}
@@ -0,0 +1,86 @@
+use crate::geometry;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+/// Shortest route from `start` to `goal` moving in the four cardinal directions, only
+/// stepping onto tiles `passable` allows. The route leaves out `start` and ends on `goal`.
+/// Returns None if the goal can't be reached.
+pub fn find_path(start: (i32, i32), goal: (i32, i32), passable: impl Fn((i32, i32)) -> bool) -> Option<Vec<(i32, i32)>> {
+    // Every step costs the same, so a breadth-first search finds the shortest route
+    let mut came_from = HashMap::new();
+    let mut queue = VecDeque::new();
+    came_from.insert(start, start);
+    queue.push_back(start);
+
+    while let Some((x, y)) = queue.pop_front() {
+        if (x, y) == goal {
+            let mut path = vec![goal];
+            let mut current = goal;
+            while let Some(&previous) = came_from.get(&current).filter(|&&previous| previous != start) {
+                path.push(previous);
+                current = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
+            let next = (x + dx, y + dy);
+            if came_from.contains_key(&next) || !passable(next) {
+                continue;
+            }
+            came_from.insert(next, (x, y));
+            queue.push_back(next);
+        }
+    }
+
+    None
+}
+
+/// Cheapest route from `start` to `goal` moving in all eight directions, where `cost` prices
+/// stepping onto each tile, or returns None for tiles that can't be entered at all. The search
+/// stays within `max_distance` steps of `start`, so a far-off or walled-in goal gives up quickly.
+/// Like `find_path`, the route leaves out `start` and ends on `goal`.
+pub fn find_cheapest_path(start: (i32, i32), goal: (i32, i32), max_distance: i32, cost: impl Fn((i32, i32)) -> Option<u32>) -> Option<Vec<(i32, i32)>> {
+    // Steps cost different amounts, so this is Dijkstra's algorithm rather than a plain search
+    let mut came_from = HashMap::new();
+    let mut best_cost = HashMap::new();
+    let mut frontier = BinaryHeap::new();
+    best_cost.insert(start, 0);
+    frontier.push(Reverse((0, start)));
+
+    while let Some(Reverse((spent, (x, y)))) = frontier.pop() {
+        if (x, y) == goal {
+            let mut path = vec![goal];
+            let mut current = goal;
+            while let Some(&previous) = came_from.get(&current).filter(|&&previous| previous != start) {
+                path.push(previous);
+                current = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        if best_cost.get(&(x, y)).is_some_and(|&best| spent > best) {
+            continue; // Already reached more cheaply
+        }
+
+        for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0), (1, 1), (1, -1), (-1, 1), (-1, -1)] {
+            let next = (x + dx, y + dy);
+            if geometry::chebyshev_distance(start, next) > max_distance {
+                continue;
+            }
+            let Some(step_cost) = cost(next) else {
+                continue;
+            };
+            let total = spent + step_cost;
+            if best_cost.get(&next).is_some_and(|&best| best <= total) {
+                continue;
+            }
+            best_cost.insert(next, total);
+            came_from.insert(next, (x, y));
+            frontier.push(Reverse((total, next)));
+        }
+    }
+
+    None
+}
@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::command::GameEvent;
+use crate::item::Item;
+use crate::npc::NPCType;
+
+/// File the player's profile is kept in
+const PROFILE_PATH: &str = "profile.json";
+/// How many items the stash holds
+pub const STASH_CAPACITY: usize = 3;
+
+/// Everything that carries over from one run to the next, kept on disk between games
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Profile {
+    #[serde(default)]
+    pub stash: Vec<Item>, // Banked at the end of a won run, to be withdrawn at the start of the next
+    #[serde(default)]
+    pub stats: LifetimeStats,
+    #[serde(default)]
+    pub encyclopedia: Encyclopedia,
+}
+
+/// Creatures and items the player has come across, unlocked for good once met
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Encyclopedia {
+    pub creatures: Vec<NPCType>, // In the order they were first seen
+    pub items: BTreeMap<String, Item>, // By name, as first identified
+}
+
+impl Encyclopedia {
+    /// Unlock a creature's entry. Returns true if it wasn't known before.
+    pub fn discover_creature(&mut self, npc_type: &NPCType) -> bool {
+        if self.creatures.contains(npc_type) {
+            return false;
+        }
+        self.creatures.push(npc_type.clone());
+        true
+    }
+
+    /// Unlock an item's entry. Returns true if it wasn't known before.
+    pub fn discover_item(&mut self, item: &Item) -> bool {
+        if self.items.contains_key(&item.label) {
+            return false;
+        }
+        self.items.insert(item.label.clone(), item.clone());
+        true
+    }
+}
+
+/// What happened during the current run, gathered from the events of each command
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunStats {
+    pub kills: BTreeMap<String, u32>, // Creatures slain, by type
+    pub items_used: BTreeMap<String, u32>, // Times each item was used, by name
+}
+
+impl RunStats {
+    pub fn observe(&mut self, events: &[GameEvent]) {
+        for event in events {
+            match event {
+                GameEvent::Slain { npc_type, .. } => *self.kills.entry(npc_type.get_name().to_string()).or_default() += 1,
+                GameEvent::ItemUsed(label) => *self.items_used.entry(label.clone()).or_default() += 1,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Totals across every finished run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LifetimeStats {
+    pub runs: u32,
+    pub wins: BTreeMap<String, u32>, // By game mode
+    pub kills: BTreeMap<String, u32>, // By creature type
+    pub items_used: BTreeMap<String, u32>, // By item name
+    pub turns: u32,
+}
+
+impl LifetimeStats {
+    /// Fold a finished run into the totals
+    pub fn record_run(&mut self, mode: &str, won: bool, turns: u32, run: &RunStats) {
+        self.runs += 1;
+        if won {
+            *self.wins.entry(mode.to_string()).or_default() += 1;
+        }
+        self.turns += turns;
+        for (kind, count) in &run.kills {
+            *self.kills.entry(kind.clone()).or_default() += count;
+        }
+        for (label, count) in &run.items_used {
+            *self.items_used.entry(label.clone()).or_default() += count;
+        }
+    }
+
+    pub fn total_wins(&self) -> u32 {
+        self.wins.values().sum()
+    }
+
+    pub fn total_kills(&self) -> u32 {
+        self.kills.values().sum()
+    }
+
+    /// The item used most often, and how many times
+    pub fn favorite_item(&self) -> Option<(&str, u32)> {
+        self.items_used.iter().max_by_key(|(_, count)| **count).map(|(label, count)| (label.as_str(), *count))
+    }
+}
+
+impl Profile {
+    /// Read the profile from disk. A missing or unreadable file starts a fresh one.
+    pub fn load() -> Self {
+        std::fs::read_to_string(PROFILE_PATH)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        std::fs::write(PROFILE_PATH, serde_json::to_string_pretty(self)?)
+    }
+
+    pub fn stash_is_full(&self) -> bool {
+        self.stash.len() >= STASH_CAPACITY
+    }
+}
@@ -0,0 +1,22 @@
+use crate::geometry;
+use crate::state::GameWorld;
+
+/// Trace the tiles a projectile passes through when thrown or fired from `from` towards `to`.
+/// The path follows a straight line, stops at the first tile that isn't walkable and never
+/// travels further than `range` tiles. The starting tile is not included.
+pub fn trace(world: &GameWorld, from: (i32, i32), to: (i32, i32), range: i32) -> Vec<(i32, i32)> {
+    geometry::line(from, to)
+        .take(range.max(0) as usize)
+        .take_while(|&(x, y)| world.is_walkable(x, y)) // Projectiles shatter against walls
+        .collect()
+}
+
+/// Check whether a projectile from `from` would reach `to` without hitting a wall or any of the
+/// given blockers first
+pub fn has_clear_shot(world: &GameWorld, from: (i32, i32), to: (i32, i32), range: i32, blockers: &[(i32, i32)]) -> bool {
+    let path = trace(world, from, to, range);
+    if path.last() != Some(&to) {
+        return false;
+    }
+    !path[..path.len() - 1].iter().any(|pos| blockers.contains(pos))
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+
+/// The only colours glyphs take in the high-contrast theme: bright, far apart, and a grey for
+/// what's remembered rather than seen
+const HIGH_CONTRAST_GLYPHS: [(u8, u8, u8); 10] = [
+    (255, 255, 255), // White
+    (255, 255, 0), // Yellow
+    (0, 255, 255), // Cyan
+    (0, 255, 0), // Green
+    (255, 60, 60), // Red
+    (255, 0, 255), // Magenta
+    (255, 160, 0), // Orange
+    (110, 160, 255), // Blue
+    (150, 150, 150), // Grey
+    (0, 0, 0), // Black, for empty space
+];
+/// The only colours behind glyphs in the high-contrast theme, dark enough for any glyph to stand out
+const HIGH_CONTRAST_BACKGROUNDS: [(u8, u8, u8); 5] = [
+    (130, 0, 0), // Danger, fire and invalid targets
+    (0, 0, 150), // Routes and the helpless
+    (0, 110, 0), // Valid targets
+    (110, 0, 110), // Debug paths
+    (110, 70, 0), // Shops, blasts and debug targets
+];
+
+/// What a glyph on the map belongs to, from the bottom up. A tile shows the glyph of its highest
+/// layer; effects such as fire sit above everything and colour the tile's background rather
+/// than hiding what's on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RenderLayer {
+    Terrain, // The floor, walls and any trap the player knows about
+    Item, // Items lying on the ground, shown as a pile when there's more than one
+    Actor, // The player, creatures and merchants' carts
+    Effect, // Fire, blasts and the like
+}
+
+/// One thing that could be drawn on a tile
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glyph {
+    pub layer: RenderLayer,
+    pub symbol: char,
+    pub color: (u8, u8, u8),
+}
+
+impl Glyph {
+    pub fn new(layer: RenderLayer, symbol: char, color: (u8, u8, u8)) -> Self {
+        Self { layer, symbol, color }
+    }
+}
+
+/// How a tile is drawn: the glyph on top, and the colour of any effect behind it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileAppearance {
+    pub glyph: Glyph,
+    pub effect: Option<(u8, u8, u8)>,
+}
+
+/// How the map is drawn: the standard palette, or large glyphs in a few strong colours with
+/// room between the tiles, for players with low vision
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    Standard,
+    HighContrast,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 2] = [Theme::Standard, Theme::HighContrast];
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            Theme::Standard => "Standard",
+            Theme::HighContrast => "High contrast, large glyphs",
+        }
+    }
+
+    /// Point size of the map's glyphs
+    pub fn glyph_size(&self) -> f32 {
+        match self {
+            Theme::Standard => 12.0,
+            Theme::HighContrast => 20.0,
+        }
+    }
+
+    /// Points of space left between neighbouring tiles
+    pub fn tile_spacing(&self) -> f32 {
+        match self {
+            Theme::Standard => 0.0,
+            Theme::HighContrast => 2.0,
+        }
+    }
+
+    /// Whether glyphs are drawn strong
+    pub fn bold(&self) -> bool {
+        *self == Theme::HighContrast
+    }
+
+    /// The colour a glyph is drawn in
+    pub fn glyph_color(&self, color: (u8, u8, u8)) -> (u8, u8, u8) {
+        match self {
+            Theme::Standard => color,
+            Theme::HighContrast => nearest(&HIGH_CONTRAST_GLYPHS, color),
+        }
+    }
+
+    /// The colour drawn behind a glyph, for effects and highlights
+    pub fn background_color(&self, color: (u8, u8, u8)) -> (u8, u8, u8) {
+        match self {
+            Theme::Standard => color,
+            Theme::HighContrast => nearest(&HIGH_CONTRAST_BACKGROUNDS, color),
+        }
+    }
+}
+
+/// The colour in a palette closest to the one given
+fn nearest(palette: &[(u8, u8, u8)], color: (u8, u8, u8)) -> (u8, u8, u8) {
+    let distance = |other: &(u8, u8, u8)| {
+        let channel = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        channel(color.0, other.0) + channel(color.1, other.1) + channel(color.2, other.2)
+    };
+    palette.iter().copied().min_by_key(distance).unwrap_or(color)
+}
+
+/// Pick what shows on a tile from everything stacked on it. The highest layer wins, and
+/// within a layer whatever comes first in the stack, so the same tile always draws the same way.
+pub fn compose(stack: &[Glyph]) -> TileAppearance {
+    let effect = stack.iter().find(|glyph| glyph.layer == RenderLayer::Effect).map(|glyph| glyph.color);
+    let glyph = stack.iter()
+        .filter(|glyph| glyph.layer != RenderLayer::Effect)
+        .rev()
+        .max_by_key(|glyph| glyph.layer)
+        .copied()
+        .unwrap_or(Glyph::new(RenderLayer::Terrain, ' ', (0, 0, 0)));
+    TileAppearance { glyph, effect }
+}
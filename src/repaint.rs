@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use eframe::egui;
+
+/// Slowest a countdown is redrawn in power-saving mode, so the time left still ticks down
+const POWER_SAVING_COUNTDOWN: Duration = Duration::from_secs(1);
+
+/// Why a frame wants another one after it, which decides whether power saving lets it through
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RepaintReason {
+    Input, // Presses from the on-screen controls, waiting to be fed in
+    Walk, // The next step along a tapped route
+    Countdown, // A time limit ticking down
+    Clock, // The run's clock and play time
+    Animation, // Shakes and flashes still playing
+    Toast, // Taking a toast down once its time is up
+}
+
+impl RepaintReason {
+    /// When a request for a frame `after` from now still gets one in power-saving mode, or
+    /// None if it has to wait for input
+    fn power_saving(&self, after: Duration) -> Option<Duration> {
+        match self {
+            RepaintReason::Input | RepaintReason::Walk | RepaintReason::Toast => Some(after),
+            RepaintReason::Countdown => Some(after.max(POWER_SAVING_COUNTDOWN)),
+            RepaintReason::Clock | RepaintReason::Animation => None,
+        }
+    }
+}
+
+/// The app's single say over when the next frame is drawn. Anything that needs a frame later
+/// asks here while the frame runs, and `finish_frame` passes only the soonest on to egui, so the
+/// app sits idle until input when nobody asked. Controller polling and online replies wake the
+/// UI themselves, since they happen outside a frame.
+#[derive(Debug, Default)]
+pub struct RepaintScheduler {
+    requests: Vec<(RepaintReason, Duration)>, // Asked for during this frame
+}
+
+impl RepaintScheduler {
+    /// Ask for a frame `after` from now
+    pub fn request(&mut self, reason: RepaintReason, after: Duration) {
+        self.requests.push((reason, after));
+    }
+
+    /// Request the soonest frame asked for, dropping what power saving holds back
+    pub fn finish_frame(&mut self, ctx: &egui::Context, power_saving: bool) {
+        let soonest = self.requests.drain(..)
+            .filter_map(|(reason, after)| if power_saving { reason.power_saving(after) } else { Some(after) })
+            .min();
+        if let Some(after) = soonest {
+            ctx.request_repaint_after(after);
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use rand::Rng;
+
+use crate::ecs::{self, Entities};
+use crate::status::{StatusEffects, StatusKind};
+
+/// Energy an actor spends to take one ordinary action, such as a step or reading a scroll
+pub const ACTION_COST: i32 = 100;
+/// Energy for something done in a moment, like grabbing an item or downing a potion
+pub const QUICK_ACTION_COST: i32 = 50;
+/// Energy for a swing of a two-handed weapon
+pub const HEAVY_ATTACK_COST: i32 = 150;
+/// Energy for a step while carrying more than the player can manage
+pub const ENCUMBERED_MOVE_COST: i32 = 150;
+pub const NORMAL_SPEED: i32 = 10;
+
+/// Run every entity system once, at the end of a turn
+pub fn run_systems(entities: &mut Entities) {
+    for system in ecs::SYSTEMS {
+        system(entities);
+    }
+}
+
+/// How quickly an actor banks energy, from its status effects
+pub fn speed(status_effects: &StatusEffects) -> i32 {
+    match (status_effects.has(StatusKind::Hasted), status_effects.has(StatusKind::Slowed)) {
+        (true, false) => NORMAL_SPEED * 2,
+        (false, true) => NORMAL_SPEED / 2,
+        _ => NORMAL_SPEED, // Haste and slow cancel out
+    }
+}
+
+/// Time that passes in the world while an actor at this speed takes an action costing this much
+pub fn time_for_action(cost: i32, speed: i32) -> i32 {
+    cost / speed
+}
+
+/// Turns that go by while an actor at this speed takes a number of actions of the same cost
+pub fn turns_for_actions(actions: i32, cost: i32, speed: i32) -> i32 {
+    let turn = time_for_action(ACTION_COST, NORMAL_SPEED);
+    (actions * time_for_action(cost, speed) + turn - 1) / turn
+}
+
+/// Whether an actor at this speed gets more or fewer actions than normal, for the UI
+pub fn describe_speed(speed: i32) -> Option<&'static str> {
+    match speed.cmp(&NORMAL_SPEED) {
+        std::cmp::Ordering::Greater => Some("fast (2 actions per turn)"),
+        std::cmp::Ordering::Less => Some("slow (1 action every 2 turns)"),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+/// Roll for an actor's place in the order NPCs act in this round. Faster actors tend to go
+/// first, but a lucky roll lets a slower one get in ahead.
+pub fn roll_initiative(speed: i32, rng: &mut impl Rng) -> i32 {
+    speed + rng.gen_range(0..NORMAL_SPEED)
+}
+
+/// Bank energy for the time that has passed and work out how many actions the actor can afford.
+/// Leftover energy carries over to the next turn.
+pub fn take_actions(energy: &mut i32, speed: i32, elapsed: i32) -> u32 {
+    *energy += speed * elapsed;
+    let actions = (*energy / ACTION_COST).max(0) as u32;
+    *energy -= actions as i32 * ACTION_COST;
+    actions
+}
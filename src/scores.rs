@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+
+use crate::command::GameEvent;
+use crate::item::ItemType;
+use crate::state::{GameState, Player};
+
+/// File the local high-score table is kept in
+pub const SCORES_PATH: &str = "high_scores.json";
+/// File the endless descent table is kept in, separate since its runs are scored differently
+pub const ENDLESS_SCORES_PATH: &str = "endless_scores.json";
+/// How many runs the table keeps
+const MAX_ENTRIES: usize = 20;
+/// Bonus for winning a run rather than dying
+const VICTORY_BONUS: i32 = 1000;
+/// Score for each floor cleared in an endless descent
+const FLOOR_SCORE: i32 = 100;
+/// Score for a kill, multiplied by the streak it's part of
+const KILL_SCORE: i32 = 10;
+/// Highest multiplier a kill streak can reach
+const MAX_STREAK_MULTIPLIER: u32 = 5;
+/// Winning faster than this earns a bonus for every turn saved
+const PAR_TURNS: u32 = 500;
+const SPEED_BONUS_PER_TURN: i32 = 2;
+
+/// A finished run on the high-score table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    pub mode: String,
+    pub class: String,
+    pub won: bool,
+    pub score: i32,
+    pub turns: u32,
+    pub elapsed_secs: f64, // Real time played, not counting pauses
+    #[serde(default)]
+    pub depth: i32, // Deepest floor reached
+}
+
+impl ScoreEntry {
+    /// Sum up a finished run. Winning is worth the most; gold and experience break ties.
+    pub fn from_run(game_state: &GameState, mode: &str, won: bool) -> Self {
+        let player = &game_state.player;
+        let mut score = player.gold + (player.level - 1) * 50 + player.experience + game_state.tally.streak_points;
+        if won {
+            score += VICTORY_BONUS;
+            // Modes won by lasting a set number of turns can't be won any faster
+            if game_state.game_condition.turns_remaining(game_state).is_none() {
+                score += speed_bonus(game_state.turn_counter);
+            }
+        }
+        Self {
+            mode: mode.to_string(),
+            class: player.class.get_name().to_string(),
+            won,
+            score,
+            turns: game_state.turn_counter,
+            elapsed_secs: game_state.play_time,
+            depth: game_state.world.current_floor,
+        }
+    }
+
+    /// Sum up an endless descent: floors cleared plus the treasure carried out of them
+    pub fn from_descent(game_state: &GameState, mode: &str) -> Self {
+        let floors_cleared = game_state.world.current_floor - 1;
+        Self {
+            score: floors_cleared * FLOOR_SCORE + treasure_value(&game_state.player),
+            ..Self::from_run(game_state, mode, false)
+        }
+    }
+}
+
+/// Bonus score built up from the events of a run as it is played, so game conditions don't need to
+/// know anything about scoring. Kills landed without being hurt in between form a streak, and each
+/// kill in a streak is worth more than the last.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScoreTally {
+    pub streak: u32, // Kills since the player was last hurt
+    pub best_streak: u32,
+    pub streak_points: i32,
+}
+
+impl ScoreTally {
+    pub fn observe(&mut self, events: &[GameEvent]) {
+        for event in events {
+            match event {
+                GameEvent::Slain { .. } => {
+                    self.streak += 1;
+                    self.best_streak = self.best_streak.max(self.streak);
+                    self.streak_points += KILL_SCORE * self.multiplier() as i32;
+                }
+                GameEvent::Hurt(_) => self.streak = 0,
+                _ => {}
+            }
+        }
+    }
+
+    /// What kills in the current streak are multiplied by
+    pub fn multiplier(&self) -> u32 {
+        self.streak.clamp(1, MAX_STREAK_MULTIPLIER)
+    }
+}
+
+/// Bonus for winning in fewer turns than par
+fn speed_bonus(turns: u32) -> i32 {
+    PAR_TURNS.saturating_sub(turns) as i32 * SPEED_BONUS_PER_TURN
+}
+
+/// What the player's gold, gems and treasure are worth
+pub fn treasure_value(player: &Player) -> i32 {
+    let carried: i32 = player.inventory.iter()
+        .filter(|item| matches!(item.item_type, ItemType::Gem | ItemType::Treasure))
+        .map(|item| item.value())
+        .sum();
+    player.gold + carried
+}
+
+/// The best local runs, highest score first, kept on disk between games
+#[derive(Debug)]
+pub struct HighScores {
+    path: &'static str,
+    entries: Vec<ScoreEntry>,
+}
+
+impl HighScores {
+    /// Read the table from disk. A missing or unreadable file just starts a fresh table.
+    pub fn load(path: &'static str) -> Self {
+        let entries = std::fs::read_to_string(path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        Self { path, entries }
+    }
+
+    pub fn entries(&self) -> &[ScoreEntry] {
+        &self.entries
+    }
+
+    /// Add a run to the table and save it. Returns the run's place in the table, if it made the cut.
+    pub fn record(&mut self, entry: ScoreEntry) -> std::io::Result<Option<usize>> {
+        let place = self.entries.iter().position(|existing| existing.score < entry.score).unwrap_or(self.entries.len());
+        self.entries.insert(place, entry);
+        self.entries.truncate(MAX_ENTRIES);
+        std::fs::write(self.path, serde_json::to_string_pretty(&self.entries)?)?;
+        Ok((place < MAX_ENTRIES).then_some(place))
+    }
+}
+
+/// Seconds as minutes and seconds, e.g. "3:07"
+pub fn format_time(seconds: f64) -> String {
+    let whole = seconds.max(0.0) as u64;
+    format!("{}:{:02}", whole / 60, whole % 60)
+}
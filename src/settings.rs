@@ -0,0 +1,60 @@
+use crate::animation::VisualEffect;
+use crate::diagnostics::Verbosity;
+use crate::i18n::Language;
+use crate::item::{Item, ItemType};
+use crate::render::Theme;
+use serde::{Deserialize, Serialize};
+
+/// Player preferences that carry over from one game to the next
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub auto_pickup: Vec<ItemType>, // Item types picked up just by walking over them
+    pub log_verbosity: Verbosity, // Detail written to the diagnostic log file
+    pub language: Language, // Language the interface is shown in
+    pub online_scores: bool, // Send finished runs to the online leaderboard and show its top entries
+    pub touch_controls: bool, // On-screen buttons, tap to move and long-press to examine; turned on by the first touch
+    pub screen_reader: bool, // Announce what happens on the map as text for screen readers
+    pub reduced_motion: bool, // No screen shake, transitions or animated walking
+    pub no_flashes: bool, // No damage flashes or blast glare
+    pub theme: Theme, // How the map is drawn
+    pub power_saving: bool, // Only redraw on input, apart from a countdown ticking once a second
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            auto_pickup: vec![ItemType::Gem, ItemType::Treasure, ItemType::Gold],
+            log_verbosity: Verbosity::default(),
+            language: Language::default(),
+            online_scores: false,
+            touch_controls: false,
+            screen_reader: false,
+            reduced_motion: false,
+            no_flashes: false,
+            theme: Theme::default(),
+            power_saving: false,
+        }
+    }
+}
+
+impl Settings {
+    pub fn auto_picks_up(&self, item: &Item) -> bool {
+        item.item_type.allows_auto_pickup() && self.auto_pickup.contains(&item.item_type)
+    }
+
+    /// Whether a kind of visual effect may play. Every effect checks here rather than
+    /// keeping a switch of its own.
+    pub fn allows(&self, effect: VisualEffect) -> bool {
+        match effect {
+            VisualEffect::Shake | VisualEffect::Motion => !self.reduced_motion,
+            VisualEffect::Flash => !self.no_flashes,
+        }
+    }
+
+    pub fn set_auto_pickup(&mut self, item_type: ItemType, enabled: bool) {
+        self.auto_pickup.retain(|existing| *existing != item_type);
+        if enabled {
+            self.auto_pickup.push(item_type);
+        }
+    }
+}
@@ -1,15 +1,201 @@
-use crate::game_condition::{GameCondition, GameStatus, TreasureHuntCondition};
-use crate::item::{Item, ItemType, ItemUseResult};
-use crate::npc::{NPC, NPCType, InteractionResult};
+use crate::ability::Ability;
+use crate::alchemy;
+use crate::animation::VisualEffect;
+use crate::balance;
+use crate::catalog;
+use crate::elite;
+use crate::clock::TimeOfDay;
+use crate::collapse::{self, Collapse};
+use crate::faction::{self, Attitude, Faction, Reputation};
+use crate::ecs::{Entities, Light};
+use crate::geometry::{self, within_radius};
+use crate::grid::Grid;
+use crate::i18n::{Language, tr, trf};
+use crate::combat::{self, AttackOutcome, AttackProfile, DefenseProfile};
+use crate::command::{Command, GameEvent};
+use crate::game_condition::{GameCondition, GameStatus, SavedCondition};
+use crate::item::{Element, EquipSlot, ItemEffect, ItemUseResult, PassiveEffect, WorldMutation};
+use crate::movement::Movement;
+use crate::message_log::{MessageCategory, MessageLog};
+use crate::names;
+use crate::noise::{self, Noise};
+use crate::pathfinding;
+use crate::projectile;
+use crate::scheduler;
+use crate::profile::{Encyclopedia, RunStats};
+use crate::render::{self, Glyph, RenderLayer, TileAppearance};
+use crate::scores::ScoreTally;
+use crate::settings::Settings;
+use crate::npc::{self, NPCState, ORACLE_FEE, StepPlan, Strike, SupportAction, TurnContext};
+use crate::status::{StatusEffects, StatusKind};
+use crate::trap::{Trap, TrapType};
+use crate::weather::Weather;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+// The canonical item and NPC types live in item.rs and npc.rs; they are re-exported here
+// because nearly everything that handles game state needs them too
+pub use crate::item::{Item, ItemType};
+pub use crate::npc::{NPC, NPCType};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Player {
     pub position: (i32, i32),
     pub health: i32,
     pub max_health: i32,
     pub level: i32,
     pub experience: i32,
+    pub dexterity: i32,
+    pub strength: i32, // Every point above 10 adds a point of melee damage
+    pub charisma: i32, // Better prices and haggling with merchants
+    pub skills: Vec<Skill>,
+    pub ability_cooldown: u32, // Turns until the class ability can be used again
+    pub nutrition: i32, // Counts down every turn; the player starves at zero
+    pub gold: i32,
+    pub class: PlayerClass,
     pub inventory: Vec<Item>,
+    pub equipment: Equipment,
+    pub status_effects: StatusEffects,
+    pub invincible: bool, // Takes no damage at all, for wizard mode
+}
+
+/// Archetype chosen at the start of a game
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum PlayerClass {
+    #[default]
+    Warrior,
+    Rogue,
+    Mage,
+}
+
+impl PlayerClass {
+    pub const ALL: [PlayerClass; 3] = [PlayerClass::Warrior, PlayerClass::Rogue, PlayerClass::Mage];
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            PlayerClass::Warrior => "Warrior",
+            PlayerClass::Rogue => "Rogue",
+            PlayerClass::Mage => "Mage",
+        }
+    }
+
+    pub fn get_description(&self) -> &str {
+        match self {
+            PlayerClass::Warrior => "Tough and steady. Starts with extra health, a sword and a shield.",
+            PlayerClass::Rogue => "Quick on their feet. Higher dexterity and starts dual-wielding daggers.",
+            PlayerClass::Mage => "Frail but dangerous from afar. Starts with a staff and a healing potion.",
+        }
+    }
+
+    pub fn ability(&self) -> Ability {
+        match self {
+            PlayerClass::Warrior => Ability::ShieldBash,
+            PlayerClass::Rogue => Ability::SmokeBomb,
+            PlayerClass::Mage => Ability::Firebolt,
+        }
+    }
+
+    /// Signature gear the class starts the game with equipped
+    fn starting_kit(&self) -> Vec<(Item, EquipSlot)> {
+        match self {
+            PlayerClass::Warrior => vec![
+                (catalog::short_sword(), EquipSlot::MainHand),
+                (catalog::wooden_shield(), EquipSlot::OffHand),
+            ],
+            PlayerClass::Rogue => vec![(catalog::dagger(), EquipSlot::MainHand), (catalog::dagger(), EquipSlot::OffHand)],
+            PlayerClass::Mage => vec![(catalog::oak_staff(), EquipSlot::MainHand)],
+        }
+    }
+}
+
+/// Passive talents the player can learn when they level up
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Skill {
+    TrapSense,
+    IronStomach,
+}
+
+impl Skill {
+    pub const ALL: [Skill; 2] = [Skill::TrapSense, Skill::IronStomach];
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            Skill::TrapSense => "Trap Sense",
+            Skill::IronStomach => "Iron Stomach",
+        }
+    }
+
+    pub fn get_description(&self) -> &str {
+        match self {
+            Skill::TrapSense => "Spot hidden traps on the tiles next to you.",
+            Skill::IronStomach => "Get hungry half as quickly.",
+        }
+    }
+}
+
+/// A reward offered to the player when they level up
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LevelUpChoice {
+    MaxHealth(i32),
+    Strength(i32),
+    LearnSkill(Skill),
+}
+
+impl LevelUpChoice {
+    pub fn get_name(&self) -> String {
+        match self {
+            LevelUpChoice::MaxHealth(amount) => format!("+{} Max Health", amount),
+            LevelUpChoice::Strength(amount) => format!("+{} Strength", amount),
+            LevelUpChoice::LearnSkill(skill) => format!("Learn {}", skill.get_name()),
+        }
+    }
+
+    pub fn get_description(&self) -> &str {
+        match self {
+            LevelUpChoice::MaxHealth(_) => "Toughen up, and heal by the same amount.",
+            LevelUpChoice::Strength(_) => "Hit harder with every melee attack.",
+            LevelUpChoice::LearnSkill(skill) => skill.get_description(),
+        }
+    }
+}
+
+/// Items the player is holding ready for combat
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Equipment {
+    pub main_hand: Option<Item>,
+    pub off_hand: Option<Item>,
+    pub neck: Option<Item>,
+    pub finger: Option<Item>,
+}
+
+impl Equipment {
+    pub fn get(&self, slot: EquipSlot) -> Option<&Item> {
+        match slot {
+            EquipSlot::MainHand => self.main_hand.as_ref(),
+            EquipSlot::OffHand => self.off_hand.as_ref(),
+            EquipSlot::Neck => self.neck.as_ref(),
+            EquipSlot::Finger => self.finger.as_ref(),
+        }
+    }
+
+    /// Everything currently equipped, in any slot
+    pub fn items(&self) -> impl Iterator<Item = &Item> {
+        [&self.main_hand, &self.off_hand, &self.neck, &self.finger].into_iter().flatten()
+    }
+
+    /// Take whatever is in a slot out of it
+    pub fn take(&mut self, slot: EquipSlot) -> Option<Item> {
+        self.slot_mut(slot).take()
+    }
+
+    fn slot_mut(&mut self, slot: EquipSlot) -> &mut Option<Item> {
+        match slot {
+            EquipSlot::MainHand => &mut self.main_hand,
+            EquipSlot::OffHand => &mut self.off_hand,
+            EquipSlot::Neck => &mut self.neck,
+            EquipSlot::Finger => &mut self.finger,
+        }
+    }
 }
 
 impl Default for Player {
@@ -20,24 +206,38 @@ impl Default for Player {
             max_health: 100,
             level: 1,
             experience: 0,
+            dexterity: 10,
+            strength: 10,
+            charisma: 10,
+            skills: Vec::new(),
+            ability_cooldown: 0,
+            nutrition: STARTING_NUTRITION,
+            gold: 25,
+            class: PlayerClass::default(),
             inventory: Vec::new(),
+            equipment: Equipment::default(),
+            status_effects: StatusEffects::default(),
+            invincible: false,
         }
     }
 }
 
-impl Player {
-    pub fn new(x: i32, y: i32) -> Self {
-        Self {
-            position: (x, y),
-            ..Default::default()
-        }
-    }
+/// Items the player can carry without a backpack
+const BASE_INVENTORY_SIZE: usize = 10;
+
+const MAX_NUTRITION: i32 = 1000;
+const STARTING_NUTRITION: i32 = 600;
+const HUNGRY_NUTRITION: i32 = 150;
 
+impl Player {
     pub fn move_to(&mut self, new_pos: (i32, i32)) {
         self.position = new_pos;
     }
 
     pub fn take_damage(&mut self, damage: i32) {
+        if self.invincible {
+            return;
+        }
         self.health = (self.health - damage).max(0);
     }
 
@@ -48,23 +248,262 @@ impl Player {
     pub fn is_alive(&self) -> bool {
         self.health > 0
     }
+
+    pub fn eat(&mut self, nutrition: i32) {
+        self.nutrition = (self.nutrition + nutrition).min(MAX_NUTRITION);
+    }
+
+    /// How many items the player can carry, including any backpacks
+    pub fn inventory_capacity(&self) -> usize {
+        BASE_INVENTORY_SIZE + self.inventory.iter().map(|item| item.stats.capacity).sum::<usize>()
+    }
+
+    /// Whether everything else still fits in the pack once this inventory item is gone,
+    /// which only a backpack can stand in the way of
+    pub fn can_part_with(&self, index: usize) -> bool {
+        let capacity = self.inventory[index].stats.capacity;
+        capacity == 0 || self.inventory.len() - 1 <= self.inventory_capacity() - capacity
+    }
+
+    pub fn is_inventory_full(&self) -> bool {
+        self.inventory.len() >= self.inventory_capacity()
+    }
+
+    /// Put an item in the pack if there's room, handing it back if not. Everything that adds
+    /// to the pack goes through here, so the capacity always holds.
+    pub fn take_into_pack(&mut self, item: Item) -> Result<(), Box<Item>> {
+        if self.is_inventory_full() {
+            return Err(Box::new(item));
+        }
+        self.inventory.push(item);
+        Ok(())
+    }
+
+    /// Pounds of gear carried, in the pack and equipped
+    pub fn carried_weight(&self) -> u32 {
+        self.inventory.iter().chain(self.equipment.items()).map(|item| item.weight()).sum()
+    }
+
+    /// Pounds the player can carry before every step starts to cost extra
+    pub fn carry_limit(&self) -> u32 {
+        const POUNDS_PER_STRENGTH: u32 = 3;
+        self.strength.max(0) as u32 * POUNDS_PER_STRENGTH
+    }
+
+    pub fn is_encumbered(&self) -> bool {
+        self.carried_weight() > self.carry_limit()
+    }
+
+    /// Experience needed to reach the next level
+    pub fn experience_to_level(&self) -> i32 {
+        self.level * balance::current().experience_per_level
+    }
+
+    pub fn has_skill(&self, skill: Skill) -> bool {
+        self.skills.contains(&skill)
+    }
+
+    /// How hungry the player is, as the key of its name in the string catalog
+    pub fn hunger_status(&self) -> &'static str {
+        self.hunger_status_after(0)
+    }
+
+    /// How hungry the player would be after eating something this nourishing
+    pub fn hunger_status_after(&self, nutrition: i32) -> &'static str {
+        match (self.nutrition + nutrition).min(MAX_NUTRITION) {
+            0 => "hunger.starving",
+            n if n <= HUNGRY_NUTRITION => "hunger.hungry",
+            n if n >= MAX_NUTRITION * 3 / 4 => "hunger.satiated",
+            _ => "hunger.fed",
+        }
+    }
+
+    pub fn with_class(class: PlayerClass) -> Self {
+        let mut player = Self { class, ..Self::default() };
+        match class {
+            PlayerClass::Warrior => {
+                player.max_health = 120;
+                player.health = 120;
+            }
+            PlayerClass::Rogue => {
+                player.dexterity = 14;
+                player.charisma = 12;
+            }
+            PlayerClass::Mage => {
+                player.max_health = 80;
+                player.health = 80;
+                player.inventory.push(catalog::healing_potion());
+            }
+        }
+        for (item, slot) in class.starting_kit() {
+            player.equip(item, slot);
+        }
+        player
+    }
+
+    /// Check whether an item can go into the given slot right now
+    pub fn check_equip(&self, language: Language, item: &Item, slot: EquipSlot) -> Result<(), String> {
+        if item.equip_slot().is_none() {
+            return Err(trf(language, "refuse.cant_equip", &[&item.display_name(language)]));
+        }
+        if !item.possible_slots().contains(&slot) {
+            return Err(trf(language, "refuse.wrong_slot", &[&item.display_name(language)]));
+        }
+        match slot {
+            EquipSlot::MainHand | EquipSlot::Neck | EquipSlot::Finger => {}
+            EquipSlot::OffHand => {
+                if item.item_type == ItemType::Weapon {
+                    if self.class != PlayerClass::Rogue {
+                        return Err(tr(language, "refuse.dual_wield_rogue").to_string());
+                    }
+                    if !item.is_light_weapon() {
+                        return Err(trf(language, "refuse.too_heavy_off_hand", &[&item.display_name(language)]));
+                    }
+                    if !self.equipment.main_hand.as_ref().is_some_and(|weapon| weapon.is_light_weapon()) {
+                        return Err(tr(language, "refuse.dual_wield_dagger").to_string());
+                    }
+                }
+                if let Some(weapon) = self.equipment.main_hand.as_ref().filter(|weapon| weapon.is_two_handed()) {
+                    return Err(trf(language, "refuse.needs_both_hands", &[&weapon.display_name(language)]));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Put an item into a slot that `check_equip` has allowed, returning everything it displaced.
+    /// A two-handed weapon frees the off hand, and dropping the main-hand dagger ends dual wielding.
+    pub fn equip(&mut self, item: Item, slot: EquipSlot) -> Vec<Item> {
+        let mut displaced = Vec::new();
+        if slot == EquipSlot::MainHand {
+            let off_hand_conflict = item.is_two_handed()
+                || (!item.is_light_weapon()
+                    && self.equipment.off_hand.as_ref().is_some_and(|off_hand| off_hand.item_type == ItemType::Weapon));
+            if off_hand_conflict {
+                displaced.extend(self.equipment.off_hand.take());
+            }
+        }
+        displaced.extend(self.equipment.slot_mut(slot).replace(item));
+        displaced
+    }
+
+    /// Take an item out of a slot. Unequipping the main-hand dagger also ends dual wielding.
+    pub fn unequip(&mut self, slot: EquipSlot) -> Vec<Item> {
+        let mut removed: Vec<Item> = self.equipment.slot_mut(slot).take().into_iter().collect();
+        if slot == EquipSlot::MainHand
+            && self.equipment.off_hand.as_ref().is_some_and(|off_hand| off_hand.item_type == ItemType::Weapon)
+        {
+            removed.extend(self.equipment.off_hand.take());
+        }
+        removed
+    }
+
+    /// One attack roll per weapon hand: a dual-wielding rogue swings twice
+    pub fn attack_profiles(&self) -> Vec<AttackProfile> {
+        let crit_chance = 5 + ((self.dexterity - 10).max(0) / 2) as u32
+            + self.socketed_count(Element::Lightning) as u32 * Element::LIGHTNING_CRIT;
+        let bonus = (self.strength - 10).max(0) + self.socketed_count(Element::Fire) as i32 * Element::FIRE_DAMAGE;
+        let main_hand = self.equipment.main_hand.as_ref();
+        let (min, max) = main_hand
+            .and_then(|weapon| weapon.stats.damage)
+            .unwrap_or((5, 15)); // Bare-handed
+        let mut profiles = vec![AttackProfile { damage: (min + bonus, max + bonus), crit_chance, life_steal: life_steal(main_hand) }];
+        let off_hand = self.equipment.off_hand.as_ref().filter(|item| item.item_type == ItemType::Weapon);
+        if let Some((min, max)) = off_hand.and_then(|weapon| weapon.stats.damage) {
+            profiles.push(AttackProfile { damage: (min + bonus, max + bonus), crit_chance, life_steal: life_steal(off_hand) });
+        }
+        profiles
+    }
+
+    /// How many gems of this element are set into the player's equipped gear
+    pub fn socketed_count(&self, element: Element) -> usize {
+        self.equipment.items().flat_map(|item| item.socketed()).filter(|&socketed| socketed == element).count()
+    }
+
+    /// The ways the player can get around: on foot, plus whatever their gear grants and
+    /// flight while levitating
+    pub fn movement(&self) -> Movement {
+        let granted = self.equipment.items().fold(Movement::WALK, |movement, item| movement | item.stats.movement);
+        if self.status_effects.has(StatusKind::Levitating) { granted | Movement::FLY } else { granted }
+    }
+
+    /// Passive powers of everything the player has equipped
+    pub fn passive_effects(&self) -> Vec<PassiveEffect> {
+        self.equipment.items().filter_map(|item| item.stats.passive).collect()
+    }
+
+    pub fn has_telepathy(&self) -> bool {
+        self.equipment.neck.as_ref().is_some_and(|amulet| amulet.stats.telepathy)
+    }
+
+    /// How far the player can see on a dark floor
+    pub fn light_radius(&self) -> i32 {
+        const DARKVISION_RADIUS: i32 = 1; // Enough to make out adjacent tiles
+        DARKVISION_RADIUS + self.equipment.off_hand.as_ref().map_or(0, |item| item.stats.light_radius)
+    }
+
+    pub fn defense_profile(&self) -> DefenseProfile {
+        DefenseProfile {
+            dodge_chance: self.dexterity.max(0) as u32,
+            block_chance: self.equipment.off_hand.as_ref().map_or(0, |off_hand| off_hand.stats.block_chance)
+                + self.socketed_count(Element::Frost) as u32 * Element::FROST_BLOCK,
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameWorld {
     pub size: (usize, usize), // width, height
     pub current_floor: i32,
-    pub tiles: Vec<Vec<TileType>>, // 2D grid of tiles
+    pub tiles: Grid<TileType>, // 2D grid of tiles, shared chunk by chunk with any copies of the world
+    pub tile_effects: Grid<Option<TileEffect>>, // Temporary effects layered over the tiles
     pub items: Vec<WorldItem>, // Items placed in the world
+    pub noises: Vec<Noise>, // Noises made since they were last resolved
+    pub traps: Vec<Trap>,
+    pub floating_texts: Vec<FloatingText>, // Combat feedback shown until the player's next turn
+    pub blast_tiles: Vec<(i32, i32)>, // Tiles caught in an explosion, flashed until the player's next turn
+    pub dark: bool, // Dark floors are only visible where something lights them
+    pub entities: Entities, // Things placed on the floor as components, such as lights
+    pub shops: Vec<Shop>,
+    pub collapse: Option<Collapse>, // Set on floors that cave in as time goes on
+    pub weather: Option<Weather>, // Only outdoor maps have weather; it changes with the world clock
+}
+
+/// Short-lived text drawn over a tile, such as damage numbers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FloatingText {
+    pub position: (i32, i32),
+    pub text: String,
+    pub color: (u8, u8, u8),
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Percent of damage a weapon heals back on each hit
+fn life_steal(weapon: Option<&Item>) -> u32 {
+    match weapon.and_then(|weapon| weapon.stats.passive) {
+        Some(PassiveEffect::Vampiric { percent }) => percent,
+        _ => 0,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TileType {
     Floor,
     Wall,
+    #[allow(dead_code)] // Not placed by the current room generator
     Door,
-    Stairs,
+    Stairs, // Leads down to a new floor
     Empty,
+    Grass,
+    Cauldron, // Potions can be mixed while standing next to one
+    Campfire, // Bump into it to cook food or rest safely
+    Altar, // Bump into it to make an offering and mend the town's opinion
+    Workbench, // Bump into it to set gems into socketed gear
+    Fungus, // Bursts into blinding spores when stepped on
+    Water, // Only crossable while levitating
+    Lava,
+    Rubble, // Broken rock, slow going underfoot
+    Ice, // Slippery; whoever steps onto it slides on a tile further
+    Checkpoint, // A glowing rune; touching it saves the run for the player to respawn at
 }
 
 impl TileType {
@@ -75,17 +514,100 @@ impl TileType {
             TileType::Door => ('+', (139, 69, 19)), // Brown
             TileType::Stairs => ('>', (128, 128, 128)), // Gray
             TileType::Empty => (' ', (0, 0, 0)), // Black
+            TileType::Grass => (',', (60, 140, 40)), // Green
+            TileType::Cauldron => ('&', (184, 115, 51)), // Copper
+            TileType::Campfire => ('Ω', (255, 140, 0)), // Firelight orange
+            TileType::Altar => ('_', (230, 220, 170)), // Pale marble
+            TileType::Workbench => ('π', (150, 100, 50)), // Worn timber
+            TileType::Fungus => ('"', (190, 150, 210)), // Pale violet
+            TileType::Water => ('~', (30, 90, 200)), // Deep blue
+            TileType::Lava => ('~', (230, 80, 20)), // Molten orange
+            TileType::Rubble => (':', (120, 105, 90)), // Broken stone
+            TileType::Ice => ('-', (170, 220, 255)), // Pale frost blue
+            TileType::Checkpoint => ('§', (90, 240, 200)), // Glowing teal, unlike a merchant's cart '¤'
+        }
+    }
+
+    /// A short name for the tile, as read out by the accessibility announcements
+    pub fn get_name(&self) -> &str {
+        match self {
+            TileType::Wall => "wall",
+            TileType::Floor => "floor",
+            TileType::Door => "door",
+            TileType::Stairs => "stairs",
+            TileType::Empty => "nothing",
+            TileType::Grass => "grass",
+            TileType::Cauldron => "cauldron",
+            TileType::Campfire => "campfire",
+            TileType::Altar => "altar",
+            TileType::Workbench => "workbench",
+            TileType::Fungus => "fungus",
+            TileType::Water => "water",
+            TileType::Lava => "lava",
+            TileType::Rubble => "rubble",
+            TileType::Ice => "ice",
+            TileType::Checkpoint => "checkpoint rune",
+        }
+    }
+
+    /// Tiles that can't be walked on but can be floated over
+    pub fn is_hazard(&self) -> bool {
+        matches!(self, TileType::Water | TileType::Lava)
+    }
+
+    /// Whether fire can catch on this tile
+    pub fn is_flammable(&self) -> bool {
+        matches!(self, TileType::Door | TileType::Grass)
+    }
+
+    /// How many moves it takes to cross this tile on foot
+    pub fn moves_to_cross(&self) -> i32 {
+        match self {
+            TileType::Rubble => 2,
+            _ => 1,
         }
     }
 }
 
+/// Temporary effects layered on top of a tile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TileEffect {
+    Burning(u32), // Turns of fire remaining
+}
+
+impl TileEffect {
+    /// Background color used to draw the effect over whatever occupies the tile
+    pub fn overlay_color(&self) -> (u8, u8, u8) {
+        match self {
+            TileEffect::Burning(_) => (190, 60, 0), // Flame orange
+        }
+    }
+}
 
+/// Something on a tile the player can perceive, as listed by `GameState::entities_at`
+#[derive(Debug, Clone, Copy)]
+pub enum Entity<'a> {
+    Player,
+    Npc(&'a NPC, Visibility),
+    Cart(&'a NPC), // A merchant's cart, with the merchant it belongs to
+    Item(&'a WorldItem),
+    Trap(&'a Trap), // Only once it has been revealed
+}
 
+/// How much the player can perceive of an entity in the world
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Visibility {
+    Visible,
+    Glimpsed, // Only a hint that something is there
+    Sensed, // Felt through telepathy without being seen
+    Hidden,
+}
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorldItem {
     pub position: (i32, i32),
     pub item: Item,
+    pub price: Option<i32>, // Gold owed to the shop's keeper for taking it
 }
 
 impl WorldItem {
@@ -93,37 +615,49 @@ impl WorldItem {
         Self {
             position: (x, y),
             item,
+            price: None,
         }
     }
+
+    /// Wares on display in a shop, to be paid for once picked up
+    pub fn for_sale(x: i32, y: i32, item: Item, price: i32) -> Self {
+        Self { price: Some(price), ..Self::new(x, y, item) }
+    }
+}
+
+/// A stretch of floor a shopkeeper watches over. Priced items picked up inside it must be paid for.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Shop {
+    pub area: ((i32, i32), (i32, i32)), // Top-left and bottom-right corners, inclusive
+    pub keeper: u32, // Id of the NPC who runs it
+}
+
+impl Shop {
+    pub fn contains(&self, (x, y): (i32, i32)) -> bool {
+        let ((left, top), (right, bottom)) = self.area;
+        (left..=right).contains(&x) && (top..=bottom).contains(&y)
+    }
 }
 
+/// Respawns a run starts with, used up one per death; hardcore runs get none
+pub const CHECKPOINT_RESPAWNS: u32 = 3;
 
+/// The last checkpoint rune the player touched, to respawn at
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub position: (i32, i32), // The rune touched
+}
 
+/// Payment a shopkeeper wants for goods the player picked up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShopDemand {
+    pub keeper: u32, // Id of the shopkeeper owed
+    pub amount: i32, // Gold owed for everything taken so far
+}
 
 impl Default for GameWorld {
     fn default() -> Self {
-        let size = (50, 30);
-        let mut tiles = vec![vec![TileType::Empty; size.1]; size.0];
-
-        // Create a simple room with walls
-        for x in 0..size.0 {
-            for y in 0..size.1 {
-                if x == 0 || x == size.0 - 1 || y == 0 || y == size.1 - 1 {
-                    tiles[x][y] = TileType::Wall;
-                } else if (x + y) % 7 == 0 {
-                    tiles[x][y] = TileType::Floor;
-                } else {
-                    tiles[x][y] = TileType::Empty;
-                }
-            }
-        }
-
-        Self {
-            size,
-            current_floor: 1,
-            tiles,
-            items: Vec::new(),
-        }
+        Self::new(50, 30)
     }
 }
 
@@ -132,8 +666,18 @@ impl GameWorld {
         let mut world = Self {
             size: (width, height),
             current_floor: 1,
-            tiles: vec![vec![TileType::Empty; height]; width],
+            tiles: Grid::new(width, height, TileType::Empty),
+            tile_effects: Grid::new(width, height, None),
             items: Vec::new(),
+            noises: Vec::new(),
+            traps: Vec::new(),
+            floating_texts: Vec::new(),
+            blast_tiles: Vec::new(),
+            dark: false,
+            entities: Entities::default(),
+            shops: Vec::new(),
+            collapse: None,
+            weather: None,
         };
         world.generate_simple_room();
         world
@@ -141,34 +685,144 @@ impl GameWorld {
 
     pub fn generate_simple_room(&mut self) {
         // Generate a simple room layout
-        for x in 0..self.size.0 {
-            for y in 0..self.size.1 {
-                if x == 0 || x == self.size.0 - 1 || y == 0 || y == self.size.1 - 1 {
-                    self.tiles[x][y] = TileType::Wall;
+        let (width, height) = self.size;
+        for x in 0..width {
+            for y in 0..height {
+                self.tiles[(x, y)] = if x == 0 || x == width - 1 || y == 0 || y == height - 1 {
+                    TileType::Wall
                 } else if (x + y) % 7 == 0 {
-                    self.tiles[x][y] = TileType::Floor;
+                    TileType::Floor
                 } else {
-                    self.tiles[x][y] = TileType::Empty;
+                    TileType::Empty
+                };
+            }
+        }
+    }
+
+    /// Generate winding natural caves in place of rooms: random rock smoothed over a few passes
+    /// of cellular automata, with any pocket that can't be reached from the largest cave filled
+    /// in so everything placed on the floor can be got to
+    pub fn generate_caves(&mut self) {
+        use rand::Rng;
+        const ROCK_CHANCE: f64 = 0.45;
+        const SMOOTHING_PASSES: usize = 5;
+        const OPENING_PASSES: usize = 2; // Early passes also break up wide open spaces with pillars
+        const MIN_OPEN_SHARE: f64 = 0.4; // Caves smaller than this share of the map are dug again
+
+        let mut rng = rand::thread_rng();
+        let (width, height) = self.size;
+        for _ in 0..10 {
+            let mut rock = Grid::new(width, height, true);
+            for x in 1..width - 1 {
+                for y in 1..height - 1 {
+                    rock[(x, y)] = rng.gen_bool(ROCK_CHANCE);
+                }
+            }
+            for pass in 0..SMOOTHING_PASSES {
+                let mut next = rock.clone();
+                for x in 1..width - 1 {
+                    for y in 1..height - 1 {
+                        let neighbours = (x - 1..=x + 1)
+                            .flat_map(|nx| (y - 1..=y + 1).map(move |ny| (nx, ny)))
+                            .filter(|&cell| cell != (x, y) && rock[cell])
+                            .count();
+                        next[(x, y)] = neighbours >= 5 || (pass < OPENING_PASSES && neighbours == 0);
+                    }
+                }
+                rock = next;
+            }
+
+            let cave = Self::largest_cave(&rock, width, height);
+            if (cave.len() as f64) < (width * height) as f64 * MIN_OPEN_SHARE {
+                continue;
+            }
+            for x in 0..width {
+                for y in 0..height {
+                    self.tiles[(x, y)] = TileType::Wall;
+                }
+            }
+            for cell in cave {
+                self.tiles[cell] = TileType::Empty;
+            }
+            return;
+        }
+        // Never dug a cave big enough, so fall back on an open floor
+        self.generate_simple_room();
+    }
+
+    /// The cells of the biggest connected stretch of open ground
+    fn largest_cave(rock: &Grid<bool>, width: usize, height: usize) -> Vec<(usize, usize)> {
+        let mut seen = Grid::new(width, height, false);
+        let mut largest = Vec::new();
+        for x in 0..width {
+            for y in 0..height {
+                if rock[(x, y)] || seen[(x, y)] {
+                    continue;
+                }
+                seen[(x, y)] = true;
+                let mut cave = Vec::new();
+                let mut frontier = vec![(x, y)];
+                while let Some((cx, cy)) = frontier.pop() {
+                    cave.push((cx, cy));
+                    for (nx, ny) in [(cx + 1, cy), (cx - 1, cy), (cx, cy + 1), (cx, cy - 1)] {
+                        if !rock[(nx, ny)] && !seen[(nx, ny)] {
+                            seen[(nx, ny)] = true;
+                            frontier.push((nx, ny));
+                        }
+                    }
+                }
+                if cave.len() > largest.len() {
+                    largest = cave;
                 }
             }
         }
+        largest
     }
 
     pub fn get_tile(&self, x: i32, y: i32) -> Option<&TileType> {
         if x >= 0 && y >= 0 && (x as usize) < self.size.0 && (y as usize) < self.size.1 {
-            Some(&self.tiles[x as usize][y as usize])
+            Some(&self.tiles[(x as usize, y as usize)])
         } else {
             None
         }
     }
 
+    /// Whether the floor has stairs leading further down
+    pub fn has_stairs(&self) -> bool {
+        (0..self.size.0 as i32).any(|x| (0..self.size.1 as i32).any(|y| self.get_tile(x, y) == Some(&TileType::Stairs)))
+    }
+
+    /// Whether a tile is open ground. Actors moving about should ask `can_enter` instead;
+    /// this is for what only travels along the floor, such as sound and projectiles.
     pub fn is_walkable(&self, x: i32, y: i32) -> bool {
+        matches!(self.get_tile(x, y), Some(TileType::Floor) | Some(TileType::Door) | Some(TileType::Empty) | Some(TileType::Grass) | Some(TileType::Fungus) | Some(TileType::Stairs) | Some(TileType::Rubble) | Some(TileType::Ice) | Some(TileType::Checkpoint))
+    }
+
+    /// Whether an actor that gets around in the given ways can move onto a tile
+    pub fn can_enter(&self, x: i32, y: i32, movement: Movement) -> bool {
         match self.get_tile(x, y) {
-            Some(TileType::Floor) | Some(TileType::Door) | Some(TileType::Empty) => true,
-            _ => false,
+            Some(TileType::Water) => movement.intersects(Movement::SWIM | Movement::FLY),
+            Some(TileType::Lava) => movement.contains(Movement::FLY),
+            Some(TileType::Wall) => movement.contains(Movement::PHASE),
+            Some(_) => self.is_walkable(x, y) && movement.intersects(Movement::WALK | Movement::FLY | Movement::PHASE),
+            None => false,
+        }
+    }
+
+    /// Moves it takes an actor that gets around in the given ways to cross a tile. Fliers pass
+    /// over rough ground as if it were floor.
+    pub fn moves_to_cross(&self, x: i32, y: i32, movement: Movement) -> i32 {
+        match self.get_tile(x, y) {
+            Some(tile) if !movement.contains(Movement::FLY) => tile.moves_to_cross(),
+            _ => 1,
         }
     }
 
+    /// Whether an actor that gets around in the given ways would slide on a tile
+    pub fn is_slippery(&self, x: i32, y: i32, movement: Movement) -> bool {
+        self.get_tile(x, y) == Some(&TileType::Ice) && !movement.contains(Movement::FLY)
+    }
+
     pub fn is_valid_position(&self, x: i32, y: i32) -> bool {
         x >= 0 && y >= 0 && (x as usize) < self.size.0 && (y as usize) < self.size.1
     }
@@ -184,204 +838,2674 @@ impl GameWorld {
             let y = rng.gen_range(2..self.size.1 - 2);
             
             // Only place obstacle if the position is currently empty
-            if self.tiles[x][y] == TileType::Empty {
-                self.tiles[x][y] = TileType::Wall;
+            if self.tiles[(x, y)] == TileType::Empty {
+                self.tiles[(x, y)] = TileType::Wall;
             }
         }
     }
-}
 
-pub struct GameState {
-    pub player: Player,
-    pub world: GameWorld,
-    pub npcs: Vec<NPC>,
-    pub log_messages: Vec<String>,
-    pub game_condition: Box<dyn GameCondition>,
-    pub turn_counter: u32,
-}
+    /// Inscribe a checkpoint rune, which glows enough to be found on a dark floor
+    pub fn add_checkpoint(&mut self, position: (i32, i32)) {
+        const RUNE_GLOW: i32 = 2;
 
-impl GameState {
-    pub fn new() -> Self {
-        Self::with_condition(Box::new(TreasureHuntCondition))
+        self.tiles[(position.0 as usize, position.1 as usize)] = TileType::Checkpoint;
+        self.add_light(position, RUNE_GLOW, None);
     }
 
-    pub fn with_condition(game_condition: Box<dyn GameCondition>) -> Self {
-        let mut npcs = Vec::new();
-        let mut world = GameWorld::new(50, 30);
-        let mut player = Player::default();
-        
-        // Let the game condition set up the world, NPCs, and player position
-        game_condition.setup_world(&mut world, &mut npcs, &mut player);
+    /// Scatter patches of dry grass across the empty parts of the map
+    pub fn add_grass_patches(&mut self, patch_count: usize) {
+        self.add_terrain_patches(TileType::Grass, patch_count);
+    }
 
-        Self {
-            player,
-            world,
-            npcs,
-            log_messages: vec![
-                "Welcome to the dungeon!".to_string(),
-                "Press arrow keys to move.".to_string(),
-                "Explore carefully...".to_string(),
-            ],
-            game_condition,
-            turn_counter: 0,
+    /// Scatter small patches of a kind of terrain over the open floor
+    pub fn add_terrain_patches(&mut self, terrain: TileType, patch_count: usize) {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..patch_count {
+            // Grow each patch with a short random walk from a random interior position
+            let mut x = rng.gen_range(2..self.size.0 - 2);
+            let mut y = rng.gen_range(2..self.size.1 - 2);
+            for _ in 0..rng.gen_range(6..14) {
+                if self.tiles[(x, y)] == TileType::Empty {
+                    self.tiles[(x, y)] = terrain.clone();
+                }
+                x = (x as i32 + rng.gen_range(-1..=1)).clamp(1, self.size.0 as i32 - 2) as usize;
+                y = (y as i32 + rng.gen_range(-1..=1)).clamp(1, self.size.1 as i32 - 2) as usize;
+            }
         }
     }
 
-    pub fn check_game_status(&self) -> GameStatus {
-        self.game_condition.check_status(self)
+    pub fn get_tile_effect(&self, x: i32, y: i32) -> Option<&TileEffect> {
+        if self.is_valid_position(x, y) {
+            self.tile_effects[(x as usize, y as usize)].as_ref()
+        } else {
+            None
+        }
     }
 
-    pub fn get_win_description(&self) -> String {
-        self.game_condition.win_description()
+    pub fn is_burning(&self, x: i32, y: i32) -> bool {
+        matches!(self.get_tile_effect(x, y), Some(TileEffect::Burning(_)))
     }
 
-    pub fn get_victory_message(&self) -> &str {
-        self.game_condition.victory_message()
+    /// Whether a tile holds a danger anyone can see: fire, a revealed trap, or lava and deep water
+    pub fn is_known_hazard(&self, x: i32, y: i32) -> bool {
+        self.is_burning(x, y)
+            || self.trap_at((x, y)).is_some_and(|trap| trap.revealed)
+            || self.get_tile(x, y).is_some_and(|tile| tile.is_hazard())
     }
 
-    pub fn get_loss_description(&self) -> &str {
-        self.game_condition.loss_description()
-    }
-    
-    pub fn increment_turn(&mut self) {
-        self.turn_counter += 1;
+    /// Whether a light source on the floor illuminates this tile
+    pub fn is_lit(&self, x: i32, y: i32) -> bool {
+        self.entities.lights.iter().any(|(entity, light)| {
+            self.entities.positions.get(entity).is_some_and(|&position| within_radius(position, (x, y), light.radius))
+        })
     }
-    
-    pub fn get_turn_info(&self) -> String {
-        format!("Turn: {}", self.turn_counter)
+
+    /// Place a light on the floor, burning for a number of turns or, given None, for good
+    pub fn add_light(&mut self, position: (i32, i32), radius: i32, turns_remaining: Option<u32>) {
+        let entity = self.entities.spawn();
+        self.entities.positions.insert(entity, position);
+        self.entities.lights.insert(entity, Light { radius, turns_remaining });
     }
 
-    pub fn add_log_message(&mut self, message: String) {
-        self.log_messages.push(message);
+    /// Whether fire can spread here, either from the terrain or from items lying on it
+    pub fn is_flammable(&self, x: i32, y: i32) -> bool {
+        self.get_tile(x, y).is_some_and(|tile| tile.is_flammable())
+            || self.items.iter().any(|world_item| world_item.position == (x, y) && world_item.item.is_flammable())
+    }
 
-        // Keep only the last 50 messages
-        if self.log_messages.len() > 50 {
-            self.log_messages.remove(0);
+    /// Set a tile on fire for a number of turns
+    pub fn ignite(&mut self, position: (i32, i32), turns: u32) {
+        if self.is_valid_position(position.0, position.1) && self.is_walkable(position.0, position.1) {
+            self.tile_effects[(position.0 as usize, position.1 as usize)] = Some(TileEffect::Burning(turns));
         }
     }
 
-    pub fn try_move_player(&mut self, dx: i32, dy: i32) -> bool {
+    /// Advance every fire by one turn: burn up flammable items, spread to flammable
+    /// neighbours and burn out. Returns the items that burned up.
+    pub fn update_fires(&mut self) -> Vec<Item> {
+        use rand::Rng;
+        const SPREAD_CHANCE: f64 = 0.5;
+        const FIRE_DURATION: u32 = 4;
+
+        let mut rng = rand::thread_rng();
+        let mut burned = Vec::new();
+        let spread_chance = SPREAD_CHANCE * self.weather.map_or(1.0, |weather| weather.fire_spread_factor());
+
+        let burning: Vec<(i32, i32)> = (0..self.size.0 as i32)
+            .flat_map(|x| (0..self.size.1 as i32).map(move |y| (x, y)))
+            .filter(|(x, y)| self.is_burning(*x, *y))
+            .collect();
+
+        for &(x, y) in &burning {
+            // Spread before burning items up, so a burning scroll can pass the fire along
+            for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
+                let next = (x + dx, y + dy);
+                if !self.is_burning(next.0, next.1) && self.is_flammable(next.0, next.1) && rng.gen_bool(spread_chance) {
+                    self.ignite(next, FIRE_DURATION);
+                }
+            }
+
+            let (burning_items, rest) = std::mem::take(&mut self.items).into_iter()
+                .partition(|world_item| world_item.position == (x, y) && world_item.item.is_flammable());
+            self.items = rest;
+            burned.extend(burning_items.into_iter().map(|world_item: WorldItem| world_item.item));
+
+            // Count down the fire, leaving scorched floor where grass burned away
+            let effect = &mut self.tile_effects[(x as usize, y as usize)];
+            if let Some(TileEffect::Burning(turns)) = effect {
+                *turns -= 1;
+                if *turns == 0 {
+                    *effect = None;
+                    if self.tiles[(x as usize, y as usize)] == TileType::Grass {
+                        self.tiles[(x as usize, y as usize)] = TileType::Floor;
+                    }
+                }
+            }
+        }
+
+        burned
+    }
+
+    pub fn trap_at(&self, position: (i32, i32)) -> Option<&Trap> {
+        self.traps.iter().find(|trap| trap.position == position)
+    }
+
+    /// Float the result of an attack over the defender's tile
+    pub fn show_attack_outcome(&mut self, position: (i32, i32), outcome: &AttackOutcome) {
+        let (text, color) = outcome.floating_text();
+        self.add_floating_text(position, text, color);
+    }
+
+    pub fn add_floating_text(&mut self, position: (i32, i32), text: String, color: (u8, u8, u8)) {
+        self.floating_texts.push(FloatingText { position, text, color });
+    }
+
+    /// Make a noise at the given position, to be heard by NPCs when noises are resolved
+    pub fn emit_noise(&mut self, position: (i32, i32), volume: i32) {
+        self.noises.push(Noise::new(position.0, position.1, volume));
+    }
+}
+
+/// Floating text marking an actor that is gaining or losing turns to its speed
+fn speed_change_text(speed: i32) -> Option<(&'static str, (u8, u8, u8))> {
+    match speed.cmp(&scheduler::NORMAL_SPEED) {
+        std::cmp::Ordering::Greater => Some(("+turn", (120, 220, 255))),
+        std::cmp::Ordering::Less => Some(("-turn", (160, 160, 200))),
+        std::cmp::Ordering::Equal => None,
+    }
+}
+
+/// Game conditions are written out by mode, with the settings each was started with
+#[allow(clippy::borrowed_box)] // serde passes the field as it's declared
+fn serialize_condition<S: serde::Serializer>(condition: &Box<dyn GameCondition>, serializer: S) -> Result<S::Ok, S::Error> {
+    condition.saved().serialize(serializer)
+}
+
+fn deserialize_condition<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Box<dyn GameCondition>, D::Error> {
+    Ok(SavedCondition::deserialize(deserializer)?.restore())
+}
+
+/// Damage multiplier for a blow against a sleeping creature
+const AMBUSH_MULTIPLIER: i32 = 2;
+
+/// Chance per turn of rest that a wandering monster finds the player
+const AMBUSH_CHANCE: f64 = 0.02;
+const CAMPFIRE_AMBUSH_CHANCE: f64 = 0.005;
+
+#[derive(Serialize, Deserialize)]
+pub struct GameState {
+    pub player: Player,
+    pub world: GameWorld,
+    pub npcs: Vec<NPC>,
+    pub log_messages: MessageLog,
+    #[serde(serialize_with = "serialize_condition", deserialize_with = "deserialize_condition")]
+    pub game_condition: Box<dyn GameCondition>,
+    pub turn_counter: u32,
+    pub explored: Vec<Vec<bool>>, // Tiles the player has seen and remembers
+    pub forgotten_item_types: Vec<ItemType>, // Item types the player no longer recognizes
+    pub pending_level_up: Option<Vec<LevelUpChoice>>, // Rewards waiting for the player to pick one
+    pub shop_demand: Option<ShopDemand>, // A shopkeeper waiting to be paid for goods the player took
+    pub collapsing_floors: bool, // Run modifier: every floor starts caving in after a while
+    pub checkpoint: Option<Checkpoint>, // The last rune touched on this floor, to respawn at after a death
+    pub respawns_left: u32, // Deaths the run can still come back from
+    pub checkpoint_reached: bool, // A rune saved the run and the autosave hasn't been written yet
+    pub action_cost: i32, // Energy the player's current action takes, which sets how long NPCs get to act
+    pub reputation: Reputation,
+    pub settings: Settings,
+    pub encyclopedia: Encyclopedia, // Copied from the profile when the run starts
+    pub encyclopedia_changed: bool, // New entries were unlocked that the profile hasn't saved yet
+    pub stash: Vec<Item>, // Banked items, copied from the profile when the run starts
+    pub stash_changed: bool, // Items were taken out that the profile hasn't saved yet
+    pub wizard_mode: bool, // Cheats for testing: invincibility and a fully revealed map
+    pub wizard_used: bool, // Wizard mode was on at some point, so the run doesn't count for high scores
+    pub damage_dealt: i32, // Total damage the player has done to other creatures
+    pub play_time: f64, // Seconds of real time played, not counting pauses
+    pub goal_revealed: bool, // A friendly NPC has told the player what a mystery run is really after
+    pub tally: ScoreTally, // Streak bonuses, worked out from the events of each command
+    pub run_stats: RunStats, // Kills and item use for the lifetime statistics, from the same events
+    #[serde(skip)]
+    events: Vec<GameEvent>, // What has happened during the command being applied
+    #[serde(skip)]
+    milestones: Vec<String>, // Milestones reached that the UI hasn't celebrated yet
+}
+
+impl GameState {
+    pub fn with_condition(game_condition: Box<dyn GameCondition>, player_class: PlayerClass, settings: Settings) -> Self {
+        let mut npcs = Vec::new();
+        let mut world = GameWorld::new(50, 30);
+        let mut player = Player::with_class(player_class);
+        
+        // Let the game condition set up the world, NPCs, and player position
+        game_condition.setup_world(&mut world, &mut npcs, &mut player);
+
+        let explored = vec![vec![false; world.size.1]; world.size.0];
+        let mut game_state = Self {
+            player,
+            world,
+            npcs,
+            log_messages: MessageLog::new(&[
+                tr(settings.language, "log.welcome"),
+                tr(settings.language, "log.welcome_controls"),
+                tr(settings.language, "log.welcome_explore"),
+            ]),
+            game_condition,
+            turn_counter: 0,
+            explored,
+            forgotten_item_types: Vec::new(),
+            pending_level_up: None,
+            shop_demand: None,
+            collapsing_floors: false,
+            checkpoint: None,
+            respawns_left: CHECKPOINT_RESPAWNS,
+            checkpoint_reached: false,
+            action_cost: scheduler::ACTION_COST,
+            reputation: Reputation::default(),
+            settings,
+            encyclopedia: Encyclopedia::default(),
+            encyclopedia_changed: false,
+            stash: Vec::new(),
+            stash_changed: false,
+            wizard_mode: false,
+            wizard_used: false,
+            damage_dealt: 0,
+            play_time: 0.0,
+            goal_revealed: false,
+            tally: ScoreTally::default(),
+            run_stats: RunStats::default(),
+            events: Vec::new(),
+            milestones: Vec::new(),
+        };
+        game_state.update_exploration();
+        game_state
+    }
+
+    pub fn check_game_status(&self) -> GameStatus {
+        self.game_condition.check_status(self)
+    }
+
+    pub fn get_win_description(&self) -> String {
+        self.game_condition.win_description(self.settings.language)
+    }
+
+    pub fn time_remaining(&self) -> Option<f64> {
+        self.game_condition.time_remaining(self)
+    }
+
+    pub fn turns_remaining(&self) -> Option<u32> {
+        self.game_condition.turns_remaining(self)
+    }
+
+    pub fn get_condition_warning(&self) -> Option<String> {
+        self.game_condition.warning(self)
+    }
+
+    pub fn get_progress(&self) -> String {
+        self.game_condition.progress(self)
+    }
+
+    pub fn objective_position(&self) -> Option<(i32, i32)> {
+        self.game_condition.objective_position(self)
+    }
+
+    pub fn get_victory_message(&self) -> &str {
+        self.game_condition.victory_message(self.settings.language)
+    }
+
+    pub fn get_loss_description(&self) -> &str {
+        self.game_condition.loss_description(self.settings.language)
+    }
+    
+    pub fn increment_turn(&mut self) {
+        self.turn_counter += 1;
+    }
+
+    /// Finish the round after the player has acted: advance the turn counter,
+    /// let the NPCs act and update fires
+    pub fn end_turn(&mut self) {
+        self.increment_turn();
+        self.process_npc_actions();
+        for message in self.game_condition.on_turn_end(self.settings.language, &mut self.world, &mut self.npcs, &mut self.player, self.turn_counter) {
+            self.add_log_message(message);
+        }
+        self.update_weather();
+        self.update_fires();
+        self.update_collapse();
+        if self.turn_counter.is_multiple_of(faction::DECAY_INTERVAL) {
+            self.reputation.decay();
+        }
+        self.update_hunger();
+        self.update_passive_effects();
+        self.sense_traps();
+        self.player.ability_cooldown = self.player.ability_cooldown.saturating_sub(1);
+        self.update_torch();
+        scheduler::run_systems(&mut self.world.entities);
+        self.update_exploration();
+        tracing::debug!(
+            turn = self.turn_counter,
+            position = ?self.player.position,
+            health = self.player.health,
+            nutrition = self.player.nutrition,
+            npcs = self.npcs.len(),
+            "turn ended"
+        );
+    }
+
+    /// Turn wizard mode on or off. Once used, the run is marked as a wizard run for good.
+    pub fn toggle_wizard_mode(&mut self) {
+        self.wizard_mode = !self.wizard_mode;
+        self.wizard_used |= self.wizard_mode;
+        self.player.invincible = self.wizard_mode;
+        if self.wizard_mode {
+            self.reveal_map();
+            self.add_log_message(tr(self.settings.language, "log.wizard_on").to_string());
+        } else {
+            self.add_log_message(tr(self.settings.language, "log.wizard_off").to_string());
+        }
+    }
+
+    /// Wizard mode's free stair travel: go straight down to the next floor from anywhere
+    pub fn wizard_descend(&mut self) {
+        if !self.wizard_mode {
+            return;
+        }
+        if self.world.has_stairs() {
+            self.descend();
+        } else {
+            self.add_log_message(tr(self.settings.language, "log.no_floor_below").to_string());
+        }
+    }
+
+    /// Remember the whole floor as if it had all been explored
+    pub fn reveal_map(&mut self) {
+        for column in self.explored.iter_mut() {
+            column.fill(true);
+        }
+    }
+
+    /// Remember every tile the player can currently see
+    fn update_exploration(&mut self) {
+        for x in 0..self.world.size.0 {
+            for y in 0..self.world.size.1 {
+                if self.is_tile_visible(x as i32, y as i32) {
+                    self.explored[x][y] = true;
+                }
+            }
+        }
+        self.record_discoveries();
+    }
+
+    /// Unlock encyclopedia entries for the creatures in sight and the identified items the player holds
+    fn record_discoveries(&mut self) {
+        let seen: Vec<NPCType> = self.npcs.iter()
+            .filter(|npc| self.npc_visibility(npc) == Visibility::Visible)
+            .map(|npc| npc.npc_type.clone())
+            .collect();
+        for npc_type in seen {
+            if self.encyclopedia.discover_creature(&npc_type) {
+                self.encyclopedia_changed = true;
+                self.add_log_message(trf(self.settings.language, "log.bestiary_entry", &[&npc_type.get_name()]));
+            }
+        }
+        let identified: Vec<Item> = self.player.inventory.iter()
+            .chain(self.player.equipment.items())
+            .filter(|item| !self.forgotten_item_types.contains(&item.item_type) && !self.encyclopedia.items.contains_key(&item.label))
+            .cloned()
+            .collect();
+        for item in identified {
+            self.encyclopedia_changed |= self.encyclopedia.discover_item(&item);
+        }
+    }
+
+    pub fn is_explored(&self, x: i32, y: i32) -> bool {
+        self.world.is_valid_position(x, y) && self.explored[x as usize][y as usize]
+    }
+
+    /// The route the player would walk to reach a tile, through explored ground only
+    pub fn player_path_to(&self, target: (i32, i32)) -> Option<Vec<(i32, i32)>> {
+        if target == self.player.position {
+            return None;
+        }
+        pathfinding::find_path(self.player.position, target, |(x, y)| {
+            self.is_explored(x, y) && self.world.can_enter(x, y, self.player.movement())
+        })
+    }
+
+    /// Wipe the player's memory of the map and make them forget what one kind of item does
+    fn suffer_amnesia(&mut self) {
+        use rand::seq::SliceRandom;
+
+        for column in self.explored.iter_mut() {
+            column.fill(false);
+        }
+        self.update_exploration();
+        self.add_log_message(tr(self.settings.language, "log.amnesia").to_string());
+
+        let remembered: Vec<ItemType> = [ItemType::Potion, ItemType::Scroll].into_iter()
+            .filter(|item_type| !self.forgotten_item_types.contains(item_type))
+            .collect();
+        if let Some(forgotten) = remembered.choose(&mut rand::thread_rng()) {
+            self.add_log_message(trf(self.settings.language, "log.forget_item_type", &[&forgotten.get_name().to_lowercase()]));
+            self.forgotten_item_types.push(forgotten.clone());
+        }
+    }
+
+    /// The name the player knows an item by. Forgotten item types show up unidentified.
+    pub fn item_name(&self, item: &Item) -> String {
+        item.known_name(self.settings.language, &self.forgotten_item_types)
+    }
+
+    /// Using an item of a forgotten type jogs the player's memory
+    fn identify_item_type(&mut self, item_type: &ItemType) {
+        if let Some(index) = self.forgotten_item_types.iter().position(|forgotten| forgotten == item_type) {
+            self.forgotten_item_types.remove(index);
+            self.add_log_message(trf(self.settings.language, "log.remember_item_type", &[&item_type.get_name().to_lowercase()]));
+        }
+    }
+
+    /// Burn down the fuel of an equipped torch, warning when it runs low and removing it once spent
+    fn update_torch(&mut self) {
+        const LOW_FUEL_WARNING: u32 = 10;
+
+        let Some(torch) = self.player.equipment.off_hand.as_mut().filter(|item| item.item_type == ItemType::Torch) else {
+            return;
+        };
+        let Some(fuel) = torch.fuel.as_mut() else {
+            return;
+        };
+        *fuel = fuel.saturating_sub(1);
+        match *fuel {
+            0 => {
+                let name = torch.display_name(self.settings.language);
+                self.player.equipment.off_hand = None;
+                self.add_log_message(trf(self.settings.language, "log.torch_out", &[&name]));
+            }
+            LOW_FUEL_WARNING => self.add_log_message(tr(self.settings.language, "log.torch_low").to_string()),
+            _ => {}
+        }
+    }
+
+    /// Drop the equipped torch, leaving it burning on the floor as a temporary light
+    fn drop_lit_torch(&mut self) {
+        let Some(torch) = self.player.equipment.off_hand.take_if(|item| item.item_type == ItemType::Torch) else {
+            return;
+        };
+        self.world.add_light(self.player.position, torch.stats.light_radius, Some(torch.fuel.unwrap_or(0)));
+        self.add_log_message(trf(self.settings.language, "log.drop_lit_torch", &[&torch.display_name(self.settings.language)]));
+    }
+
+    /// With Trap Sense, reveal any hidden traps next to the player
+    fn sense_traps(&mut self) {
+        if !self.player.has_skill(Skill::TrapSense) {
+            return;
+        }
+        let position = self.player.position;
+        let mut spotted = Vec::new();
+        for trap in self.world.traps.iter_mut() {
+            if !trap.revealed && within_radius(position, trap.position, 1) {
+                trap.revealed = true;
+                spotted.push(trf(self.settings.language, "log.spot_trap", &[&trap.get_name()]));
+            }
+        }
+        for message in spotted {
+            self.add_log_message(message);
+        }
+    }
+
+    /// Search a tile next to the player, revealing any trap hidden there
+    fn search(&mut self, position: (i32, i32)) {
+        let message = match self.world.traps.iter_mut().find(|trap| trap.position == position && !trap.revealed) {
+            Some(trap) => {
+                trap.revealed = true;
+                trf(self.settings.language, "log.search_found", &[&trap.get_name()])
+            }
+            None => tr(self.settings.language, "log.search_nothing").to_string(),
+        };
+        self.add_log_message(message);
+    }
+
+    /// Award experience, offering a level-up choice once the player has earned enough
+    fn gain_experience(&mut self, amount: i32) {
+        self.player.experience += amount;
+        self.offer_level_up();
+    }
+
+    fn offer_level_up(&mut self) {
+        use rand::seq::SliceRandom;
+
+        if self.pending_level_up.is_some() || self.player.experience < self.player.experience_to_level() {
+            return;
+        }
+        let mut choices = vec![LevelUpChoice::MaxHealth(10), LevelUpChoice::Strength(1)];
+        let unlearned: Vec<Skill> = Skill::ALL.into_iter().filter(|skill| !self.player.has_skill(*skill)).collect();
+        if let Some(skill) = unlearned.choose(&mut rand::thread_rng()) {
+            choices.push(LevelUpChoice::LearnSkill(*skill));
+        }
+        self.pending_level_up = Some(choices);
+        self.add_log_message(tr(self.settings.language, "log.level_up_ready").to_string());
+    }
+
+    /// Apply the reward the player picked and move on to the next level
+    fn choose_level_up(&mut self, choice: LevelUpChoice) {
+        self.pending_level_up = None;
+        self.player.experience -= self.player.experience_to_level();
+        self.player.level += 1;
+        match &choice {
+            LevelUpChoice::MaxHealth(amount) => {
+                self.player.max_health += amount;
+                self.player.heal(*amount);
+            }
+            LevelUpChoice::Strength(amount) => self.player.strength += amount,
+            LevelUpChoice::LearnSkill(skill) => self.player.skills.push(*skill),
+        }
+        self.add_log_message(trf(self.settings.language, "log.level_up", &[&self.player.level, &choice.get_name()]));
+        // Enough experience may have banked up for another level
+        self.offer_level_up();
+    }
+
+    /// Evaluate the per-turn passive effects of the player's equipment
+    fn update_passive_effects(&mut self) {
+        for effect in self.player.passive_effects() {
+            match effect {
+                PassiveEffect::Regeneration { interval, hunger } => {
+                    self.player.nutrition = (self.player.nutrition - hunger).max(0);
+                    // A starving body has nothing left to heal with
+                    if self.turn_counter.is_multiple_of(interval) && self.player.nutrition > 0 && self.player.health < self.player.max_health {
+                        self.player.heal(1);
+                    }
+                }
+                PassiveEffect::Vampiric { .. } => {} // Only triggers when landing a hit
+            }
+        }
+    }
+
+    /// Burn a turn's worth of nutrition, warning as the player gets hungry and starving them at zero
+    fn update_hunger(&mut self) {
+        if self.player.has_skill(Skill::IronStomach) && !self.turn_counter.is_multiple_of(2) {
+            return;
+        }
+        let previous = self.player.nutrition;
+        self.player.nutrition = (previous - balance::current().hunger_per_turn).max(0);
+        if previous > HUNGRY_NUTRITION && self.player.nutrition <= HUNGRY_NUTRITION {
+            self.add_log_message(tr(self.settings.language, "log.getting_hungry").to_string());
+        }
+        if self.player.nutrition == 0 {
+            if previous > 0 {
+                self.add_log_message(tr(self.settings.language, "log.starving").to_string());
+            }
+            self.player.take_damage(1);
+        }
+    }
+
+    /// Move outdoor weather along with the world clock, announcing any change
+    fn update_weather(&mut self) {
+        let Some(weather) = self.world.weather else { return };
+        let next = Weather::at(self.turn_counter);
+        if next != weather {
+            self.world.weather = Some(next);
+            self.add_log_message(next.arrival_message().to_string());
+        }
+    }
+
+    /// Spread and burn out fires, then burn anyone standing in the flames
+    fn update_fires(&mut self) {
+        const FIRE_DAMAGE: i32 = 5;
+
+        for item in self.world.update_fires() {
+            self.add_log_message(trf(self.settings.language, "log.item_burns", &[&self.item_name(&item)]));
+        }
+
+        if self.world.is_burning(self.player.position.0, self.player.position.1) {
+            self.player.take_damage(FIRE_DAMAGE);
+            self.add_log_message(trf(self.settings.language, "log.burned", &[&FIRE_DAMAGE]));
+        }
+
+        let mut messages = Vec::new();
+        for npc in &self.npcs {
+            let (x, y) = npc.position(&self.world);
+            if self.world.is_burning(x, y) {
+                npc.take_damage(&mut self.world, FIRE_DAMAGE);
+                if !npc.is_alive(&self.world) {
+                    messages.push(trf(self.settings.language, "log.npc_burns_to_death", &[&npc.name]));
+                }
+            }
+        }
+        for npc in self.take_fallen_npcs() {
+            npc.despawn(&mut self.world);
+        }
+
+        for message in messages {
+            self.add_log_message(message);
+        }
+    }
+
+    /// Carry out a player command and report what happened. Everything that changes the game on
+    /// the player's behalf comes through here; anything but a free command takes a turn.
+    pub fn apply_command(&mut self, command: Command) -> Vec<GameEvent> {
+        tracing::debug!(turn = self.turn_counter, ?command, "player command");
+        self.events.clear();
+
+        if let Err(reason) = self.check_command(&command) {
+            self.add_routine_message(reason);
+            self.events.push(GameEvent::Rejected);
+            return std::mem::take(&mut self.events);
+        }
+
+        let health_before = self.player.health;
+        self.action_cost = self.cost_of(&command);
+        match command {
+            Command::Move { dx, dy } => self.take_turn(|state| { state.try_move_player(dx, dy); true }),
+            Command::PickUp => self.take_turn(|state| { state.try_pickup_item(); true }),
+            Command::PickUpItems(indices) => self.take_turn(|state| { state.pick_up_items(indices); true }),
+            Command::Wait => self.take_turn(|state| { state.rest(); true }),
+            Command::UseItem(index) => self.take_turn(|state| { state.use_inventory_item(index, None); true }),
+            Command::ReadBlinkScroll { item_index, target } => self.take_turn(|state| state.read_blink_scroll(item_index, target)),
+            Command::UseAbility(target) => self.take_turn(|state| state.use_ability(target)),
+            Command::Equip { item_index, slot } => self.take_turn(|state| { state.equip_from_inventory(item_index, slot); true }),
+            Command::Unequip(slot) => self.take_turn(|state| { state.unequip_item(slot); true }),
+            Command::DropLitTorch => self.take_turn(|state| { state.drop_lit_torch(); true }),
+            Command::DropItem(index) => self.take_turn(|state| { state.drop_item(index); true }),
+            Command::MixPotions(first, second) => self.take_turn(|state| { state.mix_potions(first, second); true }),
+            Command::Cook(index) => self.take_turn(|state| { state.cook_item(index); true }),
+            Command::SocketGem { item_index, gem_index } => self.take_turn(|state| { state.socket_gem(item_index, gem_index); true }),
+            Command::RestByCampfire => self.rest_by_campfire(),
+            Command::Talk(position) => self.take_turn(|state| { state.talk_to_npc(position); true }),
+            Command::Search(position) => self.take_turn(|state| { state.search(position); true }),
+            Command::AttackFriendly(position) => self.take_turn(|state| { state.attack_friendly_npc(position); true }),
+            Command::Buy { merchant, stock_index } => self.buy_item(merchant, stock_index),
+            Command::Sell { merchant, item_index } => self.sell_item(merchant, item_index),
+            Command::Haggle(merchant) => self.take_turn(|state| state.haggle(merchant)),
+            Command::PayFine(guard) => self.take_turn(|state| { state.pay_fine(guard); true }),
+            Command::Donate(amount) => self.take_turn(|state| state.donate(amount)),
+            Command::ConsultOracle(oracle) => self.take_turn(|state| { state.consult_oracle(oracle); true }),
+            Command::ChooseLevelUp(choice) => self.choose_level_up(choice),
+            Command::PayShopkeeper => self.pay_shopkeeper(),
+            Command::ReturnGoods => self.return_goods(),
+            Command::RefuseToPay => self.refuse_to_pay(),
+            Command::TakeFromStash(index) => self.take_from_stash(index),
+        }
+
+        // Broken invariants are reported rather than panicking, so a bug doesn't end the run
+        if cfg!(debug_assertions)
+            && let Err(problems) = self.validate()
+        {
+            for problem in problems {
+                tracing::error!(turn = self.turn_counter, "invariant broken: {}", problem);
+                self.add_log_message(format!("[debug] {}", problem));
+            }
+        }
+        if self.player.health < health_before {
+            self.events.push(GameEvent::Hurt(health_before - self.player.health));
+        }
+        let events = std::mem::take(&mut self.events);
+        self.tally.observe(&events);
+        self.run_stats.observe(&events);
+        events
+    }
+
+    /// Check the invariants every part of the game relies on, listing each one that doesn't hold.
+    /// Run after every command in debug builds to catch logic bugs close to where they happen.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+        let in_bounds = |(x, y): (i32, i32)| self.world.is_valid_position(x, y);
+
+        // Actors
+        if !in_bounds(self.player.position) {
+            problems.push(format!("Player is out of bounds at {:?}", self.player.position));
+        }
+        for (index, npc) in self.npcs.iter().enumerate() {
+            if !in_bounds(npc.position(&self.world)) {
+                problems.push(format!("{} is out of bounds at {:?}", npc.name, npc.position(&self.world)));
+            }
+            if npc.position(&self.world) == self.player.position {
+                problems.push(format!("{} shares the player's tile {:?}", npc.name, npc.position(&self.world)));
+            }
+            if let Some(other) = self.npcs[index + 1..].iter().find(|other| other.position(&self.world) == npc.position(&self.world)) {
+                problems.push(format!("{} and {} share the tile {:?}", npc.name, other.name, npc.position(&self.world)));
+            }
+        }
+
+        // Things lying around the world
+        for world_item in &self.world.items {
+            if !in_bounds(world_item.position) {
+                problems.push(format!("{} is out of bounds at {:?}", world_item.item.label, world_item.position));
+            }
+        }
+        for trap in &self.world.traps {
+            if !in_bounds(trap.position) {
+                problems.push(format!("A {} is out of bounds at {:?}", trap.get_name(), trap.position));
+            }
+        }
+
+        // The player's stats and belongings
+        let player = &self.player;
+        if player.health > player.max_health {
+            problems.push(format!("Health {} is above the maximum of {}", player.health, player.max_health));
+        }
+        if player.gold < 0 {
+            problems.push(format!("Gold is negative ({})", player.gold));
+        }
+        if player.nutrition < 0 {
+            problems.push(format!("Nutrition is negative ({})", player.nutrition));
+        }
+        for slot in EquipSlot::ALL {
+            if let Some(item) = player.equipment.get(slot)
+                && !item.possible_slots().contains(&slot)
+            {
+                problems.push(format!("The {} is equipped in the {} slot it can't go in", item.label, slot.get_name()));
+            }
+        }
+        if let (Some(weapon), Some(off_hand)) = (&player.equipment.main_hand, &player.equipment.off_hand)
+            && weapon.is_two_handed()
+        {
+            problems.push(format!("The two-handed {} is held alongside the {}", weapon.label, off_hand.label));
+        }
+        if player.inventory.len() > player.inventory_capacity() {
+            problems.push(format!("{} items are carried in a pack that holds {}", player.inventory.len(), player.inventory_capacity()));
+        }
+        if player.inventory.iter().any(|item| matches!(item.effect, Some(ItemEffect::Coins(_)))) {
+            problems.push("Gold is in the inventory instead of the purse".to_string());
+        }
+        if let Some(choices) = &self.pending_level_up
+            && choices.is_empty()
+        {
+            problems.push("A level-up is pending with nothing to choose from".to_string());
+        }
+
+        // Progress towards the goal, which the conditions work out from these
+        if self.damage_dealt < 0 {
+            problems.push(format!("Damage dealt is negative ({})", self.damage_dealt));
+        }
+        if self.play_time < 0.0 || self.play_time.is_nan() {
+            problems.push(format!("Play time is negative or not a number ({})", self.play_time));
+        }
+        if let Some(seconds) = self.game_condition.time_remaining(self)
+            && (seconds < 0.0 || seconds.is_nan())
+        {
+            problems.push(format!("Time remaining is negative or not a number ({})", seconds));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// Energy a command takes, which decides how much the world gets to do in the meantime.
+    /// Free commands cost nothing since no turn passes.
+    fn cost_of(&self, command: &Command) -> i32 {
+        match command {
+            Command::Move { dx, dy } => {
+                let target = (self.player.position.0 + dx, self.player.position.1 + dy);
+                if self.npcs.iter().any(|npc| npc.position(&self.world) == target) { self.attack_cost() } else { self.step_cost(target) }
+            }
+            Command::AttackFriendly(_) => self.attack_cost(),
+            Command::PickUp | Command::DropItem(_) | Command::DropLitTorch => scheduler::QUICK_ACTION_COST,
+            Command::PickUpItems(indices) if indices.len() == 1 => scheduler::QUICK_ACTION_COST,
+            Command::UseItem(index) => match self.player.inventory.get(*index).map(|item| &item.item_type) {
+                Some(ItemType::Potion) => scheduler::QUICK_ACTION_COST,
+                _ => scheduler::ACTION_COST,
+            },
+            Command::ChooseLevelUp(_) | Command::PayShopkeeper | Command::ReturnGoods | Command::RefuseToPay | Command::TakeFromStash(_) => 0,
+            _ => scheduler::ACTION_COST,
+        }
+    }
+
+    /// Energy a step takes, more if the player is weighed down or wading through snow
+    pub fn move_cost(&self) -> i32 {
+        let base = if self.player.is_encumbered() { scheduler::ENCUMBERED_MOVE_COST } else { scheduler::ACTION_COST };
+        base + self.world.weather.map_or(0, |weather| weather.extra_move_cost())
+    }
+
+    /// Energy a step onto a tile takes, twice as much over rubble
+    pub fn step_cost(&self, to: (i32, i32)) -> i32 {
+        self.move_cost() * self.world.moves_to_cross(to.0, to.1, self.player.movement())
+    }
+
+    /// Where an actor stepping or shoved onto a tile in a direction ends up after sliding on ice,
+    /// or None if it keeps its footing. It skids on one more tile unless something is in the way.
+    fn slide_destination(&self, onto: (i32, i32), direction: (i32, i32), movement: Movement) -> Option<(i32, i32)> {
+        let next = (onto.0 + direction.0, onto.1 + direction.1);
+        (self.world.is_slippery(onto.0, onto.1, movement)
+            && self.world.can_enter(next.0, next.1, movement)
+            && !self.is_occupied(next))
+            .then_some(next)
+    }
+
+    /// Energy a melee attack takes, more with a two-handed weapon
+    fn attack_cost(&self) -> i32 {
+        match &self.player.equipment.main_hand {
+            Some(weapon) if weapon.is_two_handed() => scheduler::HEAVY_ATTACK_COST,
+            _ => scheduler::ACTION_COST,
+        }
+    }
+
+    /// Why a command can't be carried out right now, before any time is spent on it
+    fn check_command(&self, command: &Command) -> Result<(), String> {
+        let lang = self.settings.language;
+        let inventory_len = self.player.inventory.len();
+        let in_inventory = |index: &usize| *index < inventory_len;
+        match command {
+            Command::UseItem(index) | Command::DropItem(index) | Command::Cook(index) if !in_inventory(index) => {
+                Err(tr(lang, "refuse.not_carried").to_string())
+            }
+            Command::Equip { item_index, .. } | Command::Sell { item_index, .. } if !in_inventory(item_index) => {
+                Err(tr(lang, "refuse.not_carried").to_string())
+            }
+            Command::DropItem(index) | Command::Sell { item_index: index, .. } if !self.player.can_part_with(*index) => {
+                Err(trf(lang, "refuse.pack_overflow", &[&self.item_name(&self.player.inventory[*index])]))
+            }
+            Command::MixPotions(first, second) if first == second || !in_inventory(first) || !in_inventory(second) => {
+                Err(tr(lang, "refuse.mix_two_potions").to_string())
+            }
+            Command::MixPotions(..) if !self.is_next_to_cauldron() => Err(tr(lang, "refuse.no_cauldron").to_string()),
+            Command::Cook(_) if !self.is_next_to_campfire() => Err(tr(lang, "refuse.no_campfire_to_cook").to_string()),
+            Command::RestByCampfire if !self.is_next_to_campfire() => Err(tr(lang, "refuse.no_campfire_to_rest").to_string()),
+            Command::TakeFromStash(index) if *index >= self.stash.len() => Err(tr(lang, "refuse.not_in_stash").to_string()),
+            Command::TakeFromStash(_) if self.player.is_inventory_full() => Err(tr(lang, "refuse.pack_full").to_string()),
+            Command::SocketGem { item_index, gem_index } => {
+                let gear = self.player.inventory.get(*item_index);
+                let element = self.player.inventory.get(*gem_index).and_then(|gem| gem.stats.element);
+                if !self.is_next_to_workbench() {
+                    Err(tr(lang, "refuse.no_workbench").to_string())
+                } else if !gear.is_some_and(|gear| gear.has_open_socket()) {
+                    Err(tr(lang, "refuse.no_open_socket").to_string())
+                } else if element.is_none() {
+                    Err(tr(lang, "refuse.not_a_gem").to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            Command::Move { dx, dy } => {
+                let (px, py) = self.player.position;
+                match self.npcs.iter().find(|npc| npc.position(&self.world) == (px + dx, py + dy)) {
+                    // Walking never starts a fight with someone friendly; that goes through interacting
+                    Some(npc) if self.needs_bump_menu(npc) => Err(trf(lang, "refuse.npc_in_the_way", &[&npc.name])),
+                    _ => match self.npcs.iter().find(|npc| npc.cart == Some((px + dx, py + dy))) {
+                        Some(merchant) => Err(trf(lang, "refuse.cart_in_the_way", &[&merchant.name])),
+                        None => Ok(()),
+                    },
+                }
+            }
+            Command::PickUpItems(indices) if indices.iter().any(|&index| index >= self.world.items.len()) => {
+                Err(tr(lang, "refuse.item_gone").to_string())
+            }
+            Command::ReadBlinkScroll { item_index, target } => {
+                match self.player.inventory.get(*item_index).and_then(|item| item.effect.as_ref()) {
+                    Some(ItemEffect::Blink(range)) if self.is_valid_blink_target(*target, *range) => Ok(()),
+                    Some(ItemEffect::Blink(_)) => Err(tr(lang, "refuse.cant_blink_there").to_string()),
+                    _ => Err(tr(lang, "refuse.not_carried").to_string()),
+                }
+            }
+            Command::UseAbility(target) => {
+                self.check_ability()?;
+                let ability = self.player.class.ability();
+                match target {
+                    Some(target) if !self.is_valid_ability_target(ability, *target) => {
+                        Err(trf(lang, "refuse.cant_use_ability_there", &[&ability.get_name()]))
+                    }
+                    _ => Ok(()),
+                }
+            }
+            Command::Talk(target) | Command::Search(target) | Command::AttackFriendly(target) | Command::Haggle(target)
+            | Command::PayFine(target) | Command::ConsultOracle(target)
+            | Command::Buy { merchant: target, .. } | Command::Sell { merchant: target, .. }
+                if !geometry::is_adjacent(self.player.position, *target) =>
+            {
+                Err(tr(lang, "refuse.too_far").to_string())
+            }
+            Command::Buy { merchant, .. } | Command::Sell { merchant, .. } | Command::Haggle(merchant) => {
+                match self.npcs.iter().find(|npc| npc.position(&self.world) == *merchant) {
+                    Some(npc) if npc.memory.robbed => Err(trf(lang, "refuse.wont_deal_with_thief", &[&npc.name])),
+                    _ => Ok(()),
+                }
+            }
+            Command::PayFine(guard) => {
+                let fine = self.reputation.fine(Faction::Town).unwrap_or_default();
+                match self.npcs.iter().find(|npc| npc.position(&self.world) == *guard) {
+                    Some(npc) if !self.can_pay_fine(npc) => Err(trf(lang, "refuse.no_fine", &[&npc.name])),
+                    Some(_) if self.player.gold < fine => Err(trf(lang, "refuse.cant_afford_fine", &[&fine])),
+                    Some(_) => Ok(()),
+                    None => Err(tr(lang, "refuse.nobody_there").to_string()),
+                }
+            }
+            Command::ConsultOracle(oracle) => {
+                match self.npcs.iter().find(|npc| npc.position(&self.world) == *oracle) {
+                    Some(npc) if npc.npc_type != NPCType::Oracle => Err(trf(lang, "refuse.no_visions", &[&npc.name])),
+                    Some(npc) if self.is_hostile_to_player(npc) => Err(trf(lang, "refuse.oracle_hostile", &[&npc.name])),
+                    Some(_) if self.player.gold < ORACLE_FEE => Err(trf(lang, "refuse.cant_afford_gold", &[&ORACLE_FEE])),
+                    Some(_) => Ok(()),
+                    None => Err(tr(lang, "refuse.nobody_there").to_string()),
+                }
+            }
+            Command::PayShopkeeper | Command::ReturnGoods | Command::RefuseToPay if self.shop_demand.is_none() => {
+                Err(tr(lang, "refuse.no_payment_due").to_string())
+            }
+            Command::PayShopkeeper => {
+                let amount = self.shop_demand.as_ref().map_or(0, |demand| demand.amount);
+                if self.player.gold < amount {
+                    Err(trf(lang, "refuse.cant_afford_gold", &[&amount]))
+                } else {
+                    Ok(())
+                }
+            }
+            Command::Donate(amount) => {
+                if !self.is_next_to_altar() {
+                    Err(tr(lang, "refuse.no_altar").to_string())
+                } else if *amount <= 0 || self.player.gold < *amount {
+                    Err(tr(lang, "refuse.cant_afford_offering").to_string())
+                } else if self.reputation.get(Faction::Town) >= 0 {
+                    Err(tr(lang, "refuse.nothing_to_atone").to_string())
+                } else {
+                    Ok(())
+                }
+            }
+            Command::ChooseLevelUp(choice) if !self.pending_level_up.iter().flatten().any(|offered| offered == choice) => {
+                Err(tr(lang, "refuse.reward_not_offered").to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Spend the player's turn on an action and let the world move on. A stunned or paralysed
+    /// player loses the turn instead.
+    fn take_turn(&mut self, action: impl FnOnce(&mut Self) -> bool) {
+        if !self.begin_player_turn() {
+            self.action_cost = scheduler::ACTION_COST; // A lost turn is a whole turn
+            self.end_turn();
+            self.record_event(GameEvent::TurnLost);
+            return;
+        }
+        // Actions that couldn't go ahead after all (e.g. nowhere to escape to) take no time
+        if !action(self) {
+            self.events.push(GameEvent::Rejected);
+            return;
+        }
+        self.end_turn();
+        self.record_event(GameEvent::TurnPassed);
+    }
+
+    /// Start the player's turn by counting down their status effects.
+    /// Returns false if an effect such as a stun makes them lose this turn.
+    pub fn begin_player_turn(&mut self) -> bool {
+        let can_act = !self.player.status_effects.is_incapacitated();
+        self.world.floating_texts.clear();
+        self.world.blast_tiles.clear();
+
+        let expired = self.player.status_effects.tick();
+        for kind in expired.iter() {
+            self.add_log_message(trf(self.settings.language, "log.status_expired", &[&kind.get_name()]));
+        }
+        self.check_levitation(expired.contains(&StatusKind::Levitating));
+
+        if !can_act {
+            self.add_log_message(tr(self.settings.language, "log.unable_to_act").to_string());
+        }
+        can_act
+    }
+
+    /// Warn the player as levitation runs out, and drop them into whatever they're floating over when it ends
+    fn check_levitation(&mut self, just_expired: bool) {
+        const LEVITATION_WARNING: u32 = 2;
+
+        // Only what the player couldn't get onto without floating is a danger, so swimmers
+        // come down safely into water
+        let (x, y) = self.player.position;
+        let grounded = self.player.movement().without(Movement::FLY);
+        let hazard = self.world.get_tile(x, y)
+            .filter(|tile| tile.is_hazard() && !self.world.can_enter(x, y, grounded))
+            .cloned();
+        if self.player.status_effects.turns_remaining(StatusKind::Levitating) == Some(LEVITATION_WARNING) {
+            if hazard.is_some() {
+                self.add_log_message(tr(self.settings.language, "log.levitation_sinking").to_string());
+            } else {
+                self.add_log_message(tr(self.settings.language, "log.levitation_fading").to_string());
+            }
+        }
+
+        if !just_expired {
+            return;
+        }
+        match hazard {
+            Some(TileType::Water) => {
+                self.player.take_damage(3);
+                if self.player.equipment.off_hand.as_ref().is_some_and(|item| item.item_type == ItemType::Torch) {
+                    self.player.equipment.off_hand = None;
+                    self.add_log_message(tr(self.settings.language, "log.torch_doused").to_string());
+                }
+                self.add_log_message(tr(self.settings.language, "log.fall_into_water").to_string());
+            }
+            Some(TileType::Lava) => {
+                self.player.take_damage(25);
+                self.add_log_message(tr(self.settings.language, "log.fall_into_lava").to_string());
+            }
+            _ => {
+                // Coming down onto solid ground is as good as stepping on it
+                self.trigger_trap((x, y));
+                self.burst_fungus((x, y));
+                return;
+            }
+        }
+        if let Some(shore) = self.nearest_free_position(self.player.position) {
+            self.player.move_to(shore);
+        }
+    }
+
+    /// Find the closest walkable tile nobody is standing on, searching outwards ring by ring
+    fn nearest_free_position(&self, from: (i32, i32)) -> Option<(i32, i32)> {
+        (1..10).find_map(|radius| {
+            (-radius..=radius)
+                .flat_map(|dx| (-radius..=radius).map(move |dy| (from.0 + dx, from.1 + dy)))
+                .filter(|&(x, y)| (x - from.0).abs().max((y - from.1).abs()) == radius)
+                .find(|&(x, y)| self.world.is_walkable(x, y) && !self.is_occupied((x, y)))
+        })
+    }
+
+    /// Whether the player can currently see a tile. All rendering and inspection
+    /// of the map should go through this check.
+    pub fn is_tile_visible(&self, x: i32, y: i32) -> bool {
+        if self.player.status_effects.has(StatusKind::Blinded) {
+            // A blind player can only feel their way around the tiles next to them
+            return geometry::chebyshev_distance(self.player.position, (x, y)) <= 1;
+        }
+        if let Some(sight_radius) = self.world.weather.and_then(|weather| weather.sight_radius())
+            && !within_radius(self.player.position, (x, y), sight_radius)
+        {
+            return false;
+        }
+        !self.world.dark
+            || within_radius(self.player.position, (x, y), self.player.light_radius())
+            || self.world.is_lit(x, y)
+    }
+
+    /// Whether the player can make out the details of a tile when looking at it.
+    /// Blind players can feel their surroundings but can't study them.
+    pub fn can_examine(&self, x: i32, y: i32) -> bool {
+        self.is_tile_visible(x, y) && !self.player.status_effects.has(StatusKind::Blinded)
+    }
+
+    /// Whether the player can perceive an item lying in the world. Treasure detection
+    /// shows valuables wherever they are, like telepathy does for creatures.
+    pub fn is_item_perceived(&self, world_item: &WorldItem) -> bool {
+        self.is_tile_visible(world_item.position.0, world_item.position.1)
+            || (self.player.status_effects.has(StatusKind::DetectingTreasure) && world_item.item.is_treasure())
+    }
+
+    /// Decide how much the player can perceive of an NPC. All rendering and
+    /// inspection of NPCs should go through this check.
+    pub fn npc_visibility(&self, npc: &NPC) -> Visibility {
+        let (x, y) = npc.position(&self.world);
+        let in_view = self.is_tile_visible(x, y);
+        if in_view && (!npc.is_invisible() || self.player.status_effects.has(StatusKind::SeeInvisible)) {
+            Visibility::Visible
+        } else if self.player.has_telepathy() {
+            // Telepathy overrides sight for creatures, but never reveals the terrain around them
+            Visibility::Sensed
+        } else if in_view && npc.just_attacked {
+            Visibility::Glimpsed
+        } else {
+            Visibility::Hidden
+        }
+    }
+
+    /// Whether the player or a perceived NPC at this position is stunned or paralyzed
+    pub fn is_incapacitated_at(&self, x: i32, y: i32) -> bool {
+        if self.player.position == (x, y) {
+            return self.player.status_effects.is_incapacitated();
+        }
+        self.perceived_npc_at(x, y)
+            .is_some_and(|(npc, _)| npc.status_effects.is_incapacitated())
+    }
+
+    /// Find the NPC at a position the player can perceive, along with how well they perceive it.
+    /// If several share the tile, the longest-lived one is found.
+    pub fn perceived_npc_at(&self, x: i32, y: i32) -> Option<(&NPC, Visibility)> {
+        self.perceived_npcs_at(x, y).into_iter().next()
+    }
+
+    /// Every NPC at a position the player can perceive, oldest first
+    pub fn perceived_npcs_at(&self, x: i32, y: i32) -> Vec<(&NPC, Visibility)> {
+        let mut perceived: Vec<(&NPC, Visibility)> = self.npcs
+            .iter()
+            .filter(|npc| npc.position(&self.world) == (x, y))
+            .map(|npc| (npc, self.npc_visibility(npc)))
+            .filter(|(_, visibility)| *visibility != Visibility::Hidden)
+            .collect();
+        perceived.sort_by_key(|(npc, _)| npc.id);
+        perceived
+    }
+
+    /// Everything on a tile the player can perceive, from the top of the render order down:
+    /// the player, creatures, carts, items (the top of a pile first) and any known trap
+    pub fn entities_at(&self, x: i32, y: i32) -> Vec<Entity<'_>> {
+        let mut entities = Vec::new();
+        if self.player.position == (x, y) {
+            entities.push(Entity::Player);
+        }
+        entities.extend(self.perceived_npcs_at(x, y).into_iter().map(|(npc, visibility)| Entity::Npc(npc, visibility)));
+        entities.extend(self.visible_cart_at(x, y).map(Entity::Cart));
+        entities.extend(self.world.items.iter().rev()
+            .filter(|item| item.position == (x, y) && self.is_item_perceived(item))
+            .map(Entity::Item));
+        if self.is_tile_visible(x, y) {
+            entities.extend(self.world.trap_at((x, y)).filter(|trap| trap.revealed).map(Entity::Trap));
+        }
+        entities
+    }
+
+    /// Whether a tile holds more than one thing besides the player, so examining it should
+    /// list them all rather than pick one
+    pub fn is_crowded(&self, x: i32, y: i32) -> bool {
+        self.entities_at(x, y).iter().filter(|entity| !matches!(entity, Entity::Player)).count() > 1
+    }
+
+    /// Everything the player can perceive on a tile, as glyphs in render layers from the
+    /// terrain up. Within a layer the player comes before creatures, and creatures before carts.
+    pub fn tile_stack(&self, x: i32, y: i32) -> Vec<Glyph> {
+        let visible = self.is_tile_visible(x, y);
+        let mut stack = Vec::new();
+
+        // Terrain, dimmed where it's only remembered and left out where it's never been seen
+        let terrain = match self.world.trap_at((x, y)).filter(|trap| trap.revealed) {
+            Some(trap) => Some(trap.display_info()),
+            None => self.world.get_tile(x, y).map(|tile| tile.display_info()),
+        };
+        match terrain {
+            Some((symbol, color)) if visible => stack.push(Glyph::new(RenderLayer::Terrain, symbol, color)),
+            Some((symbol, (r, g, b))) if self.is_explored(x, y) => {
+                stack.push(Glyph::new(RenderLayer::Terrain, symbol, (r / 3, g / 3, b / 3)));
+            }
+            _ => {}
+        }
+
+        let items: Vec<&WorldItem> = self.world.items.iter()
+            .filter(|item| item.position == (x, y) && self.is_item_perceived(item))
+            .collect();
+        match items.as_slice() {
+            [] => {}
+            [item] => {
+                let (symbol, color) = item.item.display_info();
+                stack.push(Glyph::new(RenderLayer::Item, symbol, color));
+            }
+            _ => stack.push(Glyph::new(RenderLayer::Item, '&', (230, 230, 230))), // Several items piled on one tile
+        }
+
+        if self.player.position == (x, y) {
+            stack.push(Glyph::new(RenderLayer::Actor, '@', (255, 255, 0))); // Player - bright yellow
+        }
+        // Creatures are checked regardless of sight so telepathy can show them in the dark
+        for (npc, visibility) in self.perceived_npcs_at(x, y) {
+            let (symbol, color) = match visibility {
+                Visibility::Glimpsed => ('?', (200, 200, 200)), // Something unseen
+                Visibility::Sensed => (npc.get_display_char(), (190, 140, 255)), // Psychic violet
+                _ => npc.display_info(),
+            };
+            stack.push(Glyph::new(RenderLayer::Actor, symbol, color));
+        }
+        if self.visible_cart_at(x, y).is_some() {
+            let (symbol, color) = NPC::cart_display_info();
+            stack.push(Glyph::new(RenderLayer::Actor, symbol, color));
+        }
+
+        if visible {
+            if self.settings.allows(VisualEffect::Flash) && self.world.blast_tiles.contains(&(x, y)) {
+                stack.push(Glyph::new(RenderLayer::Effect, ' ', (200, 110, 20))); // Blast flash
+            }
+            if let Some(effect) = self.world.get_tile_effect(x, y) {
+                stack.push(Glyph::new(RenderLayer::Effect, ' ', effect.overlay_color()));
+            }
+        }
+        stack
+    }
+
+    /// How a tile looks to the player: the top glyph of its stack, tinted by the weather
+    /// where it's in sight, over the colour of any effect on it
+    pub fn tile_appearance(&self, x: i32, y: i32) -> TileAppearance {
+        let mut appearance = render::compose(&self.tile_stack(x, y));
+        if let Some(weather) = self.world.weather
+            && self.is_tile_visible(x, y)
+        {
+            appearance.glyph.color = weather.tint(appearance.glyph.color);
+        }
+        appearance
+    }
+    
+    /// The floor as the player knows it, one line of text per row. Unexplored tiles are blank.
+    pub fn map_ascii(&self) -> String {
+        let (width, height) = self.world.size;
+        let mut map = String::new();
+        for y in 0..height as i32 {
+            let row: String = (0..width as i32).map(|x| render::compose(&self.tile_stack(x, y)).glyph.symbol).collect();
+            map.push_str(row.trim_end());
+            map.push('\n');
+        }
+        map
+    }
+
+    pub fn get_turn_info(&self) -> String {
+        let time_of_day = TimeOfDay::at(self.turn_counter);
+        match self.world.weather {
+            Some(weather) => trf(self.settings.language, "stat.turn_weather", &[&self.turn_counter, &time_of_day.get_name(), &weather.get_name()]),
+            None => trf(self.settings.language, "stat.turn_time", &[&self.turn_counter, &time_of_day.get_name()]),
+        }
+    }
+
+    pub fn add_log_message(&mut self, message: String) {
+        self.log(message, MessageCategory::Important);
+    }
+
+    /// Log chatter the player doesn't need to see every time, such as footsteps or a refused
+    /// command. Too many in one turn are dropped rather than push more important news away.
+    pub fn add_routine_message(&mut self, message: String) {
+        self.log(message, MessageCategory::Routine);
+    }
+
+    /// Note something that happened during the command, along with any milestone towards the
+    /// goal it reached. The condition sees the game as it stands right as the event happens.
+    fn record_event(&mut self, event: GameEvent) {
+        let milestone = self.game_condition.on_event(&event, self);
+        self.events.push(event);
+        if let Some(milestone) = milestone {
+            self.add_log_message(milestone.clone());
+            self.milestones.push(milestone.clone());
+            self.events.push(GameEvent::Milestone(milestone));
+        }
+    }
+
+    /// The milestones reached since this was last asked, for the UI to celebrate
+    pub fn take_milestones(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.milestones)
+    }
+
+    fn log(&mut self, message: String, category: MessageCategory) {
+        tracing::info!(turn = self.turn_counter, "{}", message);
+        if self.log_messages.push(message.clone(), category, self.turn_counter) {
+            self.events.push(GameEvent::Message(message));
+        }
+    }
+
+    fn try_move_player(&mut self, dx: i32, dy: i32) -> bool {
         let new_pos = (self.player.position.0 + dx, self.player.position.1 + dy);
+        let levitating = self.player.status_effects.has(StatusKind::Levitating);
+
+        // Creatures can be fought wherever they are, even a ghost halfway through a wall
+        let occupied = self.npcs.iter().any(|npc| npc.position(&self.world) == new_pos);
+        if !occupied && !self.world.can_enter(new_pos.0, new_pos.1, self.player.movement()) {
+            self.add_routine_message(tr(self.settings.language, "log.cant_move").to_string());
+            return false;
+        }
+
+        // Check for NPC collision
+        if let Some(npc_index) = self.npcs.iter().position(|npc| npc.position(&self.world) == new_pos) {
+            // Remove NPC temporarily to avoid borrow checker issues
+            let npc = self.npcs.remove(npc_index);
+            
+            // Interact with NPC instead of moving, and put it back unless it fell
+            if let Some(npc) = self.interact_with_npc(npc) {
+                self.npcs.push(npc);
+            }
+            false
+        } else {
+            // Move player, skidding on across ice
+            self.player.move_to(new_pos);
+            let new_pos = match self.slide_destination(new_pos, (dx, dy), self.player.movement()) {
+                Some(slid_to) => {
+                    self.player.move_to(slid_to);
+                    self.add_log_message(tr(self.settings.language, "log.slide").to_string());
+                    slid_to
+                }
+                None => new_pos,
+            };
+            self.world.emit_noise(new_pos, noise::FOOTSTEP_VOLUME);
+            // Levitating players drift over whatever is on the ground
+            if !levitating {
+                self.trigger_trap(new_pos);
+                self.burst_fungus(new_pos);
+            }
+            self.add_routine_message(trf(self.settings.language, "log.moved", &[&new_pos.0, &new_pos.1]));
+            if !levitating {
+                self.auto_pickup();
+            }
+            self.touch_checkpoint(new_pos);
+            if self.world.get_tile(new_pos.0, new_pos.1) == Some(&TileType::Stairs) {
+                self.descend();
+            }
+            true
+        }
+    }
+
+    /// After loading a save, keep newly created NPCs from reusing the id of one already in the
+    /// run, including shopkeepers remembered by their shops after they're gone
+    pub fn reserve_npc_ids(&self) {
+        let ids = self.npcs.iter().map(|npc| npc.id)
+            .chain(self.world.shops.iter().map(|shop| shop.keeper))
+            .chain(self.shop_demand.iter().map(|demand| demand.keeper));
+        if let Some(largest) = ids.max() {
+            npc::reserve_ids_through(largest);
+        }
+    }
+
+    /// Turn on the hardcore modifier: death ends the run, checkpoints or not
+    pub fn enable_hardcore(&mut self) {
+        self.respawns_left = 0;
+    }
+
+    /// Touching a checkpoint rune marks where to respawn after a death and saves the run
+    fn touch_checkpoint(&mut self, position: (i32, i32)) {
+        if self.world.get_tile(position.0, position.1) != Some(&TileType::Checkpoint) {
+            return;
+        }
+        self.checkpoint = Some(Checkpoint { position });
+        self.checkpoint_reached = true;
+        self.add_log_message(tr(self.settings.language, "log.checkpoint").to_string());
+    }
+
+    /// Whether the player has died with a checkpoint and a respawn left to come back with
+    pub fn can_respawn(&self) -> bool {
+        !self.player.is_alive() && self.respawns_left > 0 && self.checkpoint.is_some()
+    }
+
+    /// Bring the player back at their checkpoint, fully healed. They keep everything they had
+    /// when they died, and the floor carries on as it is, so whatever killed them is still about.
+    pub fn respawn(&mut self) {
+        let Some(checkpoint) = self.checkpoint.clone() else {
+            return;
+        };
+        self.respawns_left = self.respawns_left.saturating_sub(1);
+        self.player.health = self.player.max_health;
+        // Rise beside the rune if something is standing on it
+        let position = geometry::circle(checkpoint.position, 2)
+            .filter(|&(x, y)| self.world.can_enter(x, y, self.player.movement()) && !self.npcs.iter().any(|npc| npc.position(&self.world) == (x, y)))
+            .min_by_key(|&position| geometry::chebyshev_distance(position, checkpoint.position))
+            .unwrap_or(checkpoint.position);
+        self.player.move_to(position);
+        // Whatever was owed or being chosen went with the old body
+        self.pending_level_up = None;
+        self.shop_demand = None;
+        self.add_log_message(trf(self.settings.language, "log.respawn", &[&self.respawns_left]));
+        self.update_exploration();
+    }
+
+    /// Turn on the collapsing floors modifier, starting with the floor the player is on
+    pub fn enable_collapsing_floors(&mut self) {
+        self.collapsing_floors = true;
+        self.world.collapse.get_or_insert_with(|| Collapse::new(collapse::DEFAULT_DELAY));
+    }
+
+    /// Bring down the next ring of a collapsing floor when it's due. Whatever is lying there is
+    /// buried, NPCs caught under it are crushed, and the player is hurt and scrambles clear.
+    fn update_collapse(&mut self) {
+        const CRUSH_DAMAGE: i32 = 10;
+
+        let Some(mut floor_collapse) = self.world.collapse.take() else {
+            return;
+        };
+        if floor_collapse.turns_until() == Some(collapse::WARNING_TURNS) {
+            self.add_log_message(tr(self.settings.language, "log.collapse_warning").to_string());
+        }
+        let fallen = floor_collapse.advance(&mut self.world);
+        self.world.collapse = Some(floor_collapse);
+        if fallen.is_empty() {
+            return;
+        }
+
+        self.add_log_message(tr(self.settings.language, "log.collapse").to_string());
+        self.world.traps.retain(|trap| !fallen.contains(&trap.position));
+        let standing = |state: &GameState, (x, y): (i32, i32)| state.world.is_walkable(x, y) && !fallen.contains(&(x, y));
+
+        // Most things are buried for good, but quest items and whatever crushed NPCs carried
+        // tumble onto the nearest ground still standing, so a quest can't be lost under the rubble
+        let (buried, kept): (Vec<WorldItem>, Vec<WorldItem>) = std::mem::take(&mut self.world.items).into_iter()
+            .partition(|world_item| fallen.contains(&world_item.position));
+        self.world.items = kept;
+        let mut rescued: Vec<WorldItem> = buried.into_iter().filter(|world_item| world_item.item.is_quest_item()).collect();
+
+        let (crushed, survivors): (Vec<NPC>, Vec<NPC>) = std::mem::take(&mut self.npcs).into_iter()
+            .partition(|npc| fallen.contains(&npc.position(&self.world)));
+        self.npcs = survivors;
+        for npc in crushed {
+            if self.npc_visibility(&npc) == Visibility::Visible {
+                self.add_log_message(trf(self.settings.language, "log.npc_crushed", &[&npc.name]));
+            }
+            let (x, y) = npc.position(&self.world);
+            npc.despawn(&mut self.world);
+            if let Some(loot) = npc.loot {
+                rescued.push(WorldItem::new(x, y, loot));
+            }
+        }
+
+        for mut world_item in rescued {
+            if let Some(spot) = self.nearest_tile(world_item.position, |spot| standing(self, spot)) {
+                world_item.position = spot;
+                self.world.items.push(world_item);
+            }
+        }
+
+        if fallen.contains(&self.player.position) {
+            self.player.take_damage(CRUSH_DAMAGE);
+            self.add_log_message(trf(self.settings.language, "log.crushed", &[&CRUSH_DAMAGE]));
+            let refuge = self.nearest_tile(self.player.position, |spot| standing(self, spot) && !self.is_occupied(spot));
+            if let Some(refuge) = refuge {
+                self.player.move_to(refuge);
+            }
+        }
+    }
+
+    /// The closest tile to `origin` that `usable` accepts, searching outwards ring by ring
+    fn nearest_tile(&self, origin: (i32, i32), usable: impl Fn((i32, i32)) -> bool) -> Option<(i32, i32)> {
+        (1..self.world.size.0.max(self.world.size.1) as i32)
+            .find_map(|radius| geometry::circle(origin, radius)
+                .filter(|&spot| usable(spot))
+                .min_by_key(|&spot| geometry::distance_squared(spot, origin)))
+    }
+
+    /// Go down the stairs to a freshly generated floor. The player keeps everything they carry,
+    /// but the floor left behind is gone for good.
+    fn descend(&mut self) {
+        let depth = self.world.current_floor + 1;
+        let mut world = GameWorld::new(self.world.size.0, self.world.size.1);
+        world.current_floor = depth;
+        let mut npcs = Vec::new();
+        self.game_condition.setup_floor(depth, &mut world, &mut npcs, &mut self.player);
+        if self.collapsing_floors && world.collapse.is_none() {
+            world.collapse = Some(Collapse::new(collapse::DEFAULT_DELAY));
+        }
+
+        self.explored = vec![vec![false; world.size.1]; world.size.0];
+        self.world = world;
+        self.npcs = npcs;
+        // The rune touched is left behind on the floor above
+        self.checkpoint = None;
+        self.add_log_message(trf(self.settings.language, "log.descend", &[&depth]));
+        if self.wizard_mode {
+            self.reveal_map();
+        }
+        self.update_exploration();
+    }
+
+    /// Spring the trap at the player's position, if there is one
+    fn trigger_trap(&mut self, position: (i32, i32)) {
+        use rand::Rng;
+
+        let Some(trap) = self.world.traps.iter_mut().find(|trap| trap.position == position) else {
+            return;
+        };
+        trap.revealed = true;
+
+        match trap.trap_type {
+            TrapType::Paralysis => {
+                self.player.status_effects.add(StatusKind::Paralyzed, 3);
+                self.add_log_message(tr(self.settings.language, "log.trap_paralysis").to_string());
+            }
+            TrapType::Fire => {
+                self.add_log_message(tr(self.settings.language, "log.trap_fire").to_string());
+                let damage = rand::thread_rng().gen_range(2..=5);
+                self.explode(position, 1, damage, "blast.flames");
+                self.world.ignite(position, 4);
+            }
+            TrapType::BlindingDart => {
+                self.player.status_effects.add(StatusKind::Blinded, 8);
+                self.add_log_message(tr(self.settings.language, "log.trap_blinding_dart").to_string());
+            }
+            TrapType::Amnesia => {
+                self.add_log_message(tr(self.settings.language, "log.trap_amnesia").to_string());
+                self.suffer_amnesia();
+            }
+            TrapType::Teleport => {
+                if let Some(destination) = self.random_free_position() {
+                    self.teleport_player(destination);
+                    self.add_log_message(tr(self.settings.language, "log.trap_teleport").to_string());
+                }
+            }
+        }
+    }
+
+    /// Whether the player or any NPC is standing on a tile
+    pub fn is_occupied(&self, position: (i32, i32)) -> bool {
+        self.player.position == position || self.npcs.iter().any(|npc| npc.position(&self.world) == position || npc.cart == Some(position))
+    }
+
+    /// The merchant whose cart stands on a tile the player can see
+    pub fn visible_cart_at(&self, x: i32, y: i32) -> Option<&NPC> {
+        self.npcs.iter().find(|npc| npc.cart == Some((x, y)) && self.is_tile_visible(x, y))
+    }
+
+    /// Pick a random walkable tile that nobody is standing on
+    pub fn random_free_position(&self) -> Option<(i32, i32)> {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let x = rng.gen_range(1..self.world.size.0 as i32 - 1);
+            let y = rng.gen_range(1..self.world.size.1 as i32 - 1);
+            if self.world.is_walkable(x, y) && !self.is_occupied((x, y)) {
+                return Some((x, y));
+            }
+        }
+        None
+    }
+
+    /// Move the player instantly. Teleporting makes no footsteps and doesn't set off traps on arrival.
+    pub fn teleport_player(&mut self, destination: (i32, i32)) {
+        self.player.move_to(destination);
+    }
+
+    /// Make an NPC that just moved pay for the ground it moved onto: it slides on across ice,
+    /// and rubble takes a second move's worth of energy to clamber over
+    fn cross_terrain(&mut self, npc: &mut NPC, start: (i32, i32)) {
+        let position = npc.position(&self.world);
+        if position == start {
+            return;
+        }
+        let movement = npc.movement();
+        let direction = ((position.0 - start.0).signum(), (position.1 - start.1).signum());
+        if let Some(slid_to) = self.slide_destination(position, direction, movement) {
+            npc.move_to(&mut self.world, slid_to);
+            if self.npc_visibility(npc) == Visibility::Visible {
+                self.add_log_message(trf(self.settings.language, "log.npc_slides", &[&npc.name]));
+            }
+        }
+        let (x, y) = npc.position(&self.world);
+        let extra_moves = self.world.moves_to_cross(x, y, movement) - 1;
+        npc.energy -= extra_moves * scheduler::ACTION_COST;
+    }
+
+    /// Teleport an NPC that stepped on a teleport trap
+    fn teleport_npc_from_trap(&mut self, npc: &mut NPC) {
+        // Fliers pass over the rune without touching it
+        let on_teleport_trap = !npc.movement().contains(Movement::FLY) && self.world.trap_at(npc.position(&self.world))
+            .is_some_and(|trap| trap.trap_type == TrapType::Teleport);
+        if !on_teleport_trap {
+            return;
+        }
+        let Some(destination) = self.random_free_position() else {
+            return;
+        };
+
+        // The player only learns about the trap if they saw it go off
+        if self.npc_visibility(npc) == Visibility::Visible {
+            let position = npc.position(&self.world);
+            if let Some(trap) = self.world.traps.iter_mut().find(|trap| trap.position == position) {
+                trap.revealed = true;
+            }
+            self.add_log_message(trf(self.settings.language, "log.npc_teleported", &[&npc.name]));
+        }
+        npc.move_to(&mut self.world, destination);
+        npc.on_displaced();
+    }
+
+    /// Whether a Blink scroll with the given range could take the player to a tile
+    pub fn is_valid_blink_target(&self, target: (i32, i32), range: i32) -> bool {
+        self.is_tile_visible(target.0, target.1)
+            && within_radius(self.player.position, target, range)
+            && self.world.is_walkable(target.0, target.1)
+            && !self.is_occupied(target)
+    }
+
+    /// Read a Blink scroll from the inventory, teleporting to the chosen tile.
+    /// Returns false and keeps the scroll if the target isn't valid.
+    fn read_blink_scroll(&mut self, item_index: usize, target: (i32, i32)) -> bool {
+        let Some(Some(ItemEffect::Blink(range))) = self.player.inventory.get(item_index).map(|item| item.effect.clone()) else {
+            return false;
+        };
+        if !self.is_valid_blink_target(target, range) {
+            self.add_log_message(tr(self.settings.language, "refuse.cant_blink_there").to_string());
+            return false;
+        }
+
+        self.use_inventory_item(item_index, Some(target));
+        true
+    }
+
+    /// Whether the class ability could be aimed at a tile
+    pub fn is_valid_ability_target(&self, ability: Ability, target: (i32, i32)) -> bool {
+        let Some(range) = ability.range() else {
+            return false;
+        };
+        target != self.player.position
+            && within_radius(self.player.position, target, range)
+            && self.is_tile_visible(target.0, target.1)
+            && self.npcs.iter().any(|npc| npc.position(&self.world) == target && self.npc_visibility(npc) == Visibility::Visible)
+    }
+
+    /// Whether the class ability can be used right now, or why not
+    pub fn check_ability(&self) -> Result<(), String> {
+        let ability = self.player.class.ability();
+        if self.player.ability_cooldown > 0 {
+            return Err(trf(self.settings.language, "refuse.ability_not_ready", &[&ability.get_name(), &self.player.ability_cooldown]));
+        }
+        let has_shield = self.player.equipment.off_hand.as_ref().is_some_and(|item| item.item_type == ItemType::Shield);
+        if ability == Ability::ShieldBash && !has_shield {
+            return Err(tr(self.settings.language, "refuse.bash_needs_shield").to_string());
+        }
+        Ok(())
+    }
+
+    /// Use the player's class ability, aimed at `target` if the ability needs one.
+    /// Returns false without spending the turn if it can't be used right now.
+    fn use_ability(&mut self, target: Option<(i32, i32)>) -> bool {
+        let ability = self.player.class.ability();
+        if let Err(reason) = self.check_ability() {
+            self.add_log_message(reason);
+            return false;
+        }
+        if let Some(target) = target
+            && !self.is_valid_ability_target(ability, target)
+        {
+            self.add_log_message(trf(self.settings.language, "refuse.cant_use_ability_there", &[&ability.get_name()]));
+            return false;
+        }
+
+        let used = match (ability, target) {
+            (Ability::ShieldBash, Some(target)) => self.shield_bash(target),
+            (Ability::SmokeBomb, _) => self.smoke_bomb(),
+            (Ability::Firebolt, Some(target)) => self.firebolt(target),
+            _ => false,
+        };
+        if used {
+            self.player.ability_cooldown = ability.cooldown();
+        }
+        used
+    }
+
+    fn shield_bash(&mut self, target: (i32, i32)) -> bool {
+        use rand::Rng;
+        const STUN_TURNS: u32 = 2;
+
+        let Some(index) = self.npcs.iter().position(|npc| npc.position(&self.world) == target) else {
+            return false;
+        };
+        let damage = rand::thread_rng().gen_range(3..=8);
+        let npc = &mut self.npcs[index];
+        npc.status_effects.add(StatusKind::Stunned, STUN_TURNS);
+        let name = npc.name.clone();
+        self.add_log_message(trf(self.settings.language, "log.shield_bash", &[&name]));
+        self.world.emit_noise(target, noise::COMBAT_VOLUME);
+        let direction = ((target.0 - self.player.position.0).signum(), (target.1 - self.player.position.1).signum());
+        self.knock_back(index, direction);
+        self.damage_npc(index, damage);
+        true
+    }
+
+    /// Shove an NPC a tile away in a direction, if there's room. It skids on across ice, and
+    /// goes sprawling if it lands in rubble.
+    fn knock_back(&mut self, index: usize, direction: (i32, i32)) {
+        const SPRAWL_TURNS: u32 = 1;
+
+        let movement = self.npcs[index].movement();
+        let from = self.npcs[index].position(&self.world);
+        let landing = (from.0 + direction.0, from.1 + direction.1);
+        if !self.world.can_enter(landing.0, landing.1, movement) || self.is_occupied(landing) {
+            return;
+        }
+        let destination = self.slide_destination(landing, direction, movement).unwrap_or(landing);
+        let sprawls = self.world.moves_to_cross(destination.0, destination.1, movement) > 1;
+
+        let npc = &mut self.npcs[index];
+        npc.move_to(&mut self.world, destination);
+        npc.on_displaced();
+        if sprawls {
+            let stunned = npc.status_effects.turns_remaining(StatusKind::Stunned).unwrap_or(0);
+            npc.status_effects.add(StatusKind::Stunned, stunned + SPRAWL_TURNS);
+        }
+        let name = npc.name.clone();
+        if destination != landing {
+            self.add_log_message(trf(self.settings.language, "log.knocked_onto_ice", &[&name]));
+        } else if sprawls {
+            self.add_log_message(trf(self.settings.language, "log.knocked_into_rubble", &[&name]));
+        } else {
+            self.add_log_message(trf(self.settings.language, "log.knocked_back", &[&name]));
+        }
+    }
+
+    /// Vanish to the free tile nearby that's furthest from any hostile, leaving adjacent enemies stunned
+    fn smoke_bomb(&mut self) -> bool {
+        const ESCAPE_RANGE: i32 = 5;
+        const CHOKE_TURNS: u32 = 2;
+        const CONFUSE_TURNS: u32 = 5; // Reeling once the choking stops
+
+        let origin = self.player.position;
+        let hostiles: Vec<(i32, i32)> = self.npcs.iter().filter(|npc| npc.is_hostile()).map(|npc| npc.position(&self.world)).collect();
+        let distance_to_hostiles = |pos: (i32, i32)| {
+            hostiles.iter().map(|&hostile| geometry::chebyshev_distance(hostile, pos)).min().unwrap_or(i32::MAX)
+        };
+        let mut candidates = Vec::new();
+        for x in origin.0 - ESCAPE_RANGE..=origin.0 + ESCAPE_RANGE {
+            for y in origin.1 - ESCAPE_RANGE..=origin.1 + ESCAPE_RANGE {
+                if self.is_valid_blink_target((x, y), ESCAPE_RANGE) && !self.world.trap_at((x, y)).is_some_and(|trap| trap.revealed) {
+                    candidates.push((x, y));
+                }
+            }
+        }
+        let Some(destination) = candidates.into_iter().max_by_key(|pos| distance_to_hostiles(*pos)) else {
+            self.add_log_message(tr(self.settings.language, "log.nowhere_to_escape").to_string());
+            return false;
+        };
+
+        for npc in self.npcs.iter_mut().filter(|npc| npc.is_hostile() && within_radius(origin, npc.position(&self.world), 1)) {
+            npc.status_effects.add(StatusKind::Stunned, CHOKE_TURNS);
+            npc.status_effects.add(StatusKind::Confused, CONFUSE_TURNS);
+            npc.on_displaced();
+        }
+        self.world.add_floating_text(origin, "smoke".to_string(), (170, 170, 170));
+        self.teleport_player(destination);
+        self.add_log_message(tr(self.settings.language, "log.smoke_bomb").to_string());
+        true
+    }
+
+    /// Fire a bolt along a straight line, burning the first creature in its way
+    fn firebolt(&mut self, target: (i32, i32)) -> bool {
+        use rand::Rng;
+        const FIREBOLT_BURN_TURNS: u32 = 3;
+
+        let range = Ability::Firebolt.range().unwrap_or(0);
+        let path = projectile::trace(&self.world, self.player.position, target, range);
+        let impact = path.iter()
+            .find(|pos| self.npcs.iter().any(|npc| npc.position(&self.world) == **pos))
+            .or(path.last())
+            .copied();
+        let Some(impact) = impact else {
+            return false;
+        };
+
+        self.add_log_message(tr(self.settings.language, "log.firebolt").to_string());
+        if let Some(index) = self.npcs.iter().position(|npc| npc.position(&self.world) == impact) {
+            let damage = rand::thread_rng().gen_range(10..=18);
+            self.add_log_message(trf(self.settings.language, "log.firebolt_hit", &[&self.npcs[index].name]));
+            self.damage_npc(index, damage);
+        }
+        self.world.ignite(impact, FIREBOLT_BURN_TURNS);
+        self.world.emit_noise(impact, noise::COMBAT_VOLUME);
+        true
+    }
+
+    /// Set off a blast of `radius` around `center`. Everyone it reaches without a wall in the way
+    /// takes the damage, fragile items lying there shatter, and the tiles it covers flash until
+    /// the player's next turn. NPCs it kills count as the player's kills. `blast` is the key of
+    /// its name in the string catalog.
+    fn explode(&mut self, center: (i32, i32), radius: i32, damage: i32, blast: &'static str) {
+        let blast = tr(self.settings.language, blast);
+        let caught: Vec<(i32, i32)> = geometry::circle(center, radius)
+            .filter(|&position| self.world.is_walkable(position.0, position.1))
+            .filter(|&position| geometry::line(center, position).all(|(x, y)| self.world.is_walkable(x, y)))
+            .collect();
+
+        if caught.contains(&self.player.position) {
+            self.player.take_damage(damage);
+            self.world.show_attack_outcome(self.player.position, &AttackOutcome::Hit(damage));
+            self.add_log_message(trf(self.settings.language, "log.caught_in_blast", &[&blast, &damage]));
+        }
+
+        // From the back, so removing the dead keeps the other indices valid
+        let hit: Vec<usize> = (0..self.npcs.len()).rev().filter(|&index| caught.contains(&self.npcs[index].position(&self.world))).collect();
+        for index in hit {
+            self.add_log_message(trf(self.settings.language, "log.blast_hits", &[&blast, &self.npcs[index].name]));
+            self.damage_npc(index, damage);
+        }
+
+        let (shattered, spared): (Vec<WorldItem>, Vec<WorldItem>) = std::mem::take(&mut self.world.items).into_iter()
+            .partition(|world_item| world_item.item.is_fragile() && caught.contains(&world_item.position));
+        self.world.items = spared;
+        for world_item in shattered {
+            self.add_log_message(trf(self.settings.language, "log.item_shatters", &[&self.item_name(&world_item.item)]));
+        }
+
+        self.world.emit_noise(center, noise::SMASH_VOLUME);
+        self.world.blast_tiles.extend(caught);
+    }
+
+    /// Hurt an NPC with one of the player's abilities, awarding experience if it dies
+    fn damage_npc(&mut self, index: usize, damage: i32) {
+        let npc = &mut self.npcs[index];
+        npc.take_damage(&mut self.world, damage);
+        npc.memory.attacked = true;
+        self.damage_dealt += damage;
+        let position = npc.position(&self.world);
+        self.world.show_attack_outcome(position, &AttackOutcome::Hit(damage));
+        if !self.npcs[index].is_alive(&self.world) {
+            let npc = self.npcs.remove(index);
+            self.slay(&npc);
+        }
+    }
+
+    /// Stepping on fungus bursts it into a cloud of blinding spores, leaving bare floor behind
+    fn burst_fungus(&mut self, position: (i32, i32)) {
+        if self.world.get_tile(position.0, position.1) != Some(&TileType::Fungus) {
+            return;
+        }
+        self.world.tiles[(position.0 as usize, position.1 as usize)] = TileType::Floor;
+        self.player.status_effects.add(StatusKind::Blinded, 5);
+        self.add_log_message(tr(self.settings.language, "log.fungus").to_string());
+    }
+
+    /// Bump into an NPC, returning it unless that killed it
+    pub fn interact_with_npc(&mut self, npc: NPC) -> Option<NPC> {
+        match npc.npc_type {
+            NPCType::Skeleton | NPCType::SkeletonArcher => {
+                // Brittle old bones fall apart at the first touch, leaving whatever they carried
+                self.damage_dealt += npc.health(&self.world).current;
+                self.world.emit_noise(npc.position(&self.world), noise::SMASH_VOLUME);
+                self.slay(&npc);
+                None
+            }
+            NPCType::Orc | NPCType::Necromancer | NPCType::Shaman | NPCType::Stalker | NPCType::Bat | NPCType::Ghost => self.player_attack(npc),
+            NPCType::Goblin => {
+                self.add_log_message(tr(self.settings.language, "log.goblin_tweaks_nose").to_string());
+                Some(npc)
+            }
+            // Friendly townsfolk can't be walked into, so reaching here means they have
+            // already turned on the player
+            NPCType::Guard | NPCType::Merchant | NPCType::Oracle => self.player_attack(npc),
+        }
+    }
+
+    /// Every tile a hostile the player can see could move onto or attack next turn, for the
+    /// threat overlay. Each gets the actions its banked energy would pay for over a normal turn.
+    pub fn threatened_tiles(&self) -> std::collections::HashSet<(i32, i32)> {
+        let elapsed = scheduler::time_for_action(scheduler::ACTION_COST, scheduler::NORMAL_SPEED);
+        self.npcs.iter()
+            .filter(|npc| npc.state != NPCState::Asleep && !npc.status_effects.is_incapacitated())
+            .filter(|npc| self.is_hostile_to_player(npc) && self.npc_visibility(npc) == Visibility::Visible)
+            .flat_map(|npc| {
+                let mut energy = npc.energy;
+                let actions = scheduler::take_actions(&mut energy, npc.speed(), elapsed);
+                npc.threat_area(&self.world, actions)
+            })
+            .collect()
+    }
+
+    /// Whether an NPC will fight the player, either by nature, because the player attacked it,
+    /// or because its faction has turned on them
+    pub fn is_hostile_to_player(&self, npc: &NPC) -> bool {
+        npc.is_hostile() || npc.memory.attacked || self.reputation.is_hostile(npc.faction())
+    }
+
+    /// Whether walking into this NPC should ask what to do rather than just bumping it.
+    /// A guard will hear the player out about a fine even once the town has turned on them.
+    pub fn needs_bump_menu(&self, npc: &NPC) -> bool {
+        npc.faction() == Faction::Town && (!self.is_hostile_to_player(npc) || self.can_pay_fine(npc))
+    }
+
+    /// Whether this NPC will take gold to clear the player's bad standing. Guards the player
+    /// has struck themselves won't hear of it.
+    pub fn can_pay_fine(&self, npc: &NPC) -> bool {
+        npc.npc_type == NPCType::Guard && !npc.memory.attacked && self.reputation.fine(npc.faction()).is_some()
+    }
+
+    /// Pay a guard to wipe the slate clean with the town
+    fn pay_fine(&mut self, guard_position: (i32, i32)) {
+        let Some(npc) = self.npcs.iter().find(|npc| npc.position(&self.world) == guard_position) else {
+            return;
+        };
+        let faction = npc.faction();
+        let Some(fine) = self.reputation.fine(faction) else {
+            return;
+        };
+        let message = trf(self.settings.language, "log.pay_fine", &[&npc.name, &fine]);
+        self.player.gold -= fine;
+        self.reputation.adjust(faction, -self.reputation.get(faction));
+        self.add_log_message(message);
+    }
+
+    /// Pay the Oracle for a glimpse of what the run needs next
+    fn consult_oracle(&mut self, oracle_position: (i32, i32)) {
+        let Some(npc) = self.npcs.iter().find(|npc| npc.position(&self.world) == oracle_position) else {
+            return;
+        };
+        let message = trf(self.settings.language, "log.oracle", &[&npc.name, &ORACLE_FEE, &self.game_condition.hint(self)]);
+        self.player.gold -= ORACLE_FEE;
+        self.add_log_message(message);
+    }
+
+    pub fn is_next_to_workbench(&self) -> bool {
+        let (px, py) = self.player.position;
+        (-1..=1).any(|dx| (-1..=1).any(|dy| self.world.get_tile(px + dx, py + dy) == Some(&TileType::Workbench)))
+    }
+
+    /// Set a gem from the inventory into the first open socket of another item. It's there for good.
+    fn socket_gem(&mut self, item_index: usize, gem_index: usize) {
+        let gem = self.player.inventory.remove(gem_index);
+        let item_index = if gem_index < item_index { item_index - 1 } else { item_index };
+        let (Some(element), Some(gear)) = (gem.stats.element, self.player.inventory.get_mut(item_index)) else {
+            return;
+        };
+        if let Some(socket) = gear.stats.sockets.iter_mut().find(|socket| socket.is_none()) {
+            *socket = Some(element);
+        }
+        let message = trf(self.settings.language, "log.socket_gem", &[&gem.display_name(self.settings.language), &gear.display_name(self.settings.language), &element.describe()]);
+        self.add_log_message(message);
+    }
+
+    pub fn is_next_to_campfire(&self) -> bool {
+        let (px, py) = self.player.position;
+        (-1..=1).any(|dx| (-1..=1).any(|dy| self.world.get_tile(px + dx, py + dy) == Some(&TileType::Campfire)))
+    }
 
-        if !self.world.is_valid_position(new_pos.0, new_pos.1) ||
-            !self.world.is_walkable(new_pos.0, new_pos.1) {
-            self.add_log_message("Can't move there!".to_string());
+    pub fn is_next_to_altar(&self) -> bool {
+        let (px, py) = self.player.position;
+        (-1..=1).any(|dx| (-1..=1).any(|dy| self.world.get_tile(px + dx, py + dy) == Some(&TileType::Altar)))
+    }
+
+    /// Leave gold at an altar. The town hears of it and thinks better of the player, though
+    /// no offering raises their standing past neutral, so only what that takes is left behind.
+    /// Returns whether anything was offered.
+    fn donate(&mut self, amount: i32) -> bool {
+        let owed = -self.reputation.get(Faction::Town);
+        if owed <= 0 {
+            self.add_log_message(tr(self.settings.language, "log.donation_unneeded").to_string());
+            return false;
+        }
+        let restored = (amount / faction::DONATION_PER_POINT).min(owed);
+        if restored == 0 {
+            self.add_log_message(trf(self.settings.language, "log.donation_too_small", &[&amount]));
             return false;
         }
+        let paid = restored * faction::DONATION_PER_POINT;
+        self.player.gold -= paid;
+        self.reputation.adjust(Faction::Town, restored);
+        let mut message = trf(self.settings.language, "log.donation", &[&paid]);
+        if paid < amount {
+            message.push(' ');
+            message.push_str(&trf(self.settings.language, "log.donation_change", &[&(amount - paid)]));
+        }
+        self.add_log_message(message);
+        true
+    }
 
-        // Check for NPC collision
-        if let Some(npc_index) = self.npcs.iter().position(|npc| npc.position == new_pos) {
-            // Remove NPC temporarily to avoid borrow checker issues
-            let npc = self.npcs.remove(npc_index);
-            
-            // Interact with NPC instead of moving
-            let result = self.interact_with_npc(npc);
-            
-            // Handle interaction result
-            match result {
-                InteractionResult::Nothing => {
-                    // Do nothing
-                }
-                InteractionResult::NPC(npc) => {
-                    // Add NPC back to the vector
-                    self.npcs.push(npc);
-                }
-                InteractionResult::Item(item) => {
-                    // Add item to world at NPC's position
-                    self.world.items.push(WorldItem::new(new_pos.0, new_pos.1, item));
-                }
+    /// Chat with a friendly NPC
+    fn talk_to_npc(&mut self, position: (i32, i32)) {
+        let Some(npc) = self.npcs.iter().find(|npc| npc.position(&self.world) == position) else {
+            return;
+        };
+        if let Some(hint) = self.game_condition.npc_hint(self.settings.language, npc) {
+            let message = trf(self.settings.language, "log.npc_hint", &[&npc.name, &hint]);
+            if !self.goal_revealed {
+                self.goal_revealed = true;
+                self.add_log_message(tr(self.settings.language, "log.goal_revealed").to_string());
             }
-            false
-        } else {
-            // Move player
-            self.player.move_to(new_pos);
-            self.add_log_message(format!("Moved to ({}, {})", new_pos.0, new_pos.1));
-            true
+            self.add_log_message(message);
+            return;
+        }
+        let line: &'static str = match (&npc.npc_type, self.reputation.attitude(npc.faction())) {
+            (NPCType::Merchant, _) if npc.memory.robbed => "talk.merchant_robbed",
+            (_, _) if npc.memory.attacked => "talk.attacked",
+            (NPCType::Merchant, Attitude::Wary) => "talk.merchant_wary",
+            (NPCType::Merchant, _) => "talk.merchant",
+            (NPCType::Guard, Attitude::Wary) => "talk.guard_wary",
+            (NPCType::Guard, _) => "talk.guard",
+            (NPCType::Oracle, _) => "talk.oracle",
+            _ => "talk.nothing",
+        };
+        let message = trf(self.settings.language, "log.npc_says", &[&npc.name, &tr(self.settings.language, line)]);
+        self.add_log_message(message);
+    }
+
+    /// Deliberately attack a friendly NPC, turning their whole faction against the player
+    fn attack_friendly_npc(&mut self, position: (i32, i32)) {
+        let Some(index) = self.npcs.iter().position(|npc| npc.position(&self.world) == position) else {
+            return;
+        };
+        let npc = self.npcs.remove(index);
+        let faction = npc.faction();
+        self.reputation.adjust(faction, -faction::ATTACK_PENALTY);
+        if self.reputation.is_hostile(faction) {
+            self.add_log_message(trf(self.settings.language, "log.faction_turns_hostile", &[&faction.get_name().to_lowercase()]));
+        }
+        if let Some(npc) = self.player_attack(npc) {
+            self.npcs.push(npc);
         }
     }
 
-    pub fn interact_with_npc(&mut self, npc: NPC) -> InteractionResult {
-        match npc.npc_type {
-            NPCType::Skeleton => {
-                self.add_log_message("The skeleton collapses to a pile of bones".to_string());
-                let key = Item::new(
-                    ItemType::Key,
-                    "Bone Key".to_string(),
-                    "A key carved from ancient bone.".to_string(),
-                );
-                InteractionResult::Item(key)
-            }
-            NPCType::Orc => {
-                use rand::Rng;
-                let damage = rand::thread_rng().gen_range(5..=20);
-                self.player.take_damage(damage);
-                self.add_log_message(format!("{} attacks you for {} damage!", npc.name, damage));
-                InteractionResult::NPC(npc)
-            }
-            NPCType::Goblin => {
-                self.add_log_message("Goblin cackles and tweaks your nose".to_string());
-                InteractionResult::NPC(npc)
+    /// Buy an item from a merchant's stock
+    fn buy_item(&mut self, merchant_position: (i32, i32), stock_index: usize) {
+        let Some(price) = self.npcs.iter()
+            .find(|npc| npc.position(&self.world) == merchant_position)
+            .and_then(|merchant| merchant.stock.get(stock_index).map(|item| self.buy_price(merchant, item)))
+        else {
+            return;
+        };
+        let Some(merchant) = self.npcs.iter_mut().find(|npc| npc.position(&self.world) == merchant_position) else {
+            return;
+        };
+        if self.player.gold < price {
+            self.add_log_message(tr(self.settings.language, "log.cant_afford").to_string());
+            return;
+        }
+        let item = merchant.stock.remove(stock_index);
+        let message = trf(self.settings.language, "log.buy", &[&item.known_name(self.settings.language, &self.forgotten_item_types), &price]);
+        if let Err(item) = self.player.take_into_pack(item) {
+            merchant.stock.insert(stock_index, *item);
+            self.add_log_message(tr(self.settings.language, "refuse.pack_full").to_string());
+            return;
+        }
+        self.player.gold -= price;
+        self.add_log_message(message);
+    }
+
+    /// Sell an item from the inventory to a merchant for around half its value
+    fn sell_item(&mut self, merchant_position: (i32, i32), inventory_index: usize) {
+        let Some(price) = self.npcs.iter()
+            .find(|npc| npc.position(&self.world) == merchant_position)
+            .and_then(|merchant| self.player.inventory.get(inventory_index).map(|item| self.sell_price(merchant, item)))
+        else {
+            return;
+        };
+        let message = trf(self.settings.language, "log.sell", &[&self.item_name(&self.player.inventory[inventory_index]), &price]);
+        let Some(merchant) = self.npcs.iter_mut().find(|npc| npc.position(&self.world) == merchant_position) else {
+            return;
+        };
+        if price == 0 {
+            self.add_log_message(tr(self.settings.language, "log.merchant_not_interested").to_string());
+            return;
+        }
+        let item = self.player.inventory.remove(inventory_index);
+        merchant.stock.push(item);
+        self.player.gold += price;
+        self.add_log_message(message);
+    }
+
+    /// Percent off a merchant's prices, from the player's charisma, the town's opinion of them
+    /// and any haggling so far
+    fn price_modifier(&self, merchant: &NPC) -> i32 {
+        const MAX_MODIFIER: i32 = 40;
+
+        let charisma = (self.player.charisma - 10) * 3;
+        let modifier = charisma + self.reputation.price_modifier(merchant.faction()) + merchant.haggled;
+        modifier.clamp(-MAX_MODIFIER, MAX_MODIFIER)
+    }
+
+    /// What a merchant asks for an item
+    pub fn buy_price(&self, merchant: &NPC, item: &Item) -> i32 {
+        (item.value() * (100 - self.price_modifier(merchant)) / 100).max(1)
+    }
+
+    /// What a merchant offers for an item; nothing for worthless junk. The modifier counts for
+    /// half here, so nothing can be bought and sold back at a profit.
+    pub fn sell_price(&self, merchant: &NPC, item: &Item) -> i32 {
+        item.value() / 2 * (100 + self.price_modifier(merchant) / 2) / 100
+    }
+
+    /// Try to talk a merchant's prices down, which takes a turn. Charisma and the town's goodwill
+    /// help; failing pushes prices up and sours the town's opinion of the player.
+    fn haggle(&mut self, merchant_position: (i32, i32)) -> bool {
+        use rand::Rng;
+
+        const HAGGLE_STEP: i32 = 10;
+        const MAX_HAGGLED: i32 = 30;
+
+        let Some(index) = self.npcs.iter().position(|npc| npc.position(&self.world) == merchant_position) else {
+            return false;
+        };
+        let faction = self.npcs[index].faction();
+        if self.npcs[index].haggled >= MAX_HAGGLED {
+            let message = trf(self.settings.language, "log.haggle_limit", &[&self.npcs[index].name]);
+            self.add_log_message(message);
+            return false;
+        }
+        let chance = (40 + (self.player.charisma - 10) * 5 + self.reputation.price_modifier(faction)).clamp(5, 95);
+        let lang = self.settings.language;
+        let merchant = &mut self.npcs[index];
+        let message = if rand::thread_rng().gen_range(0..100) < chance {
+            merchant.haggled += HAGGLE_STEP;
+            trf(lang, "log.haggle_success", &[&merchant.name, &HAGGLE_STEP])
+        } else {
+            merchant.haggled -= HAGGLE_STEP;
+            self.reputation.adjust(faction, -faction::HAGGLE_PENALTY);
+            trf(lang, "log.haggle_failure", &[&merchant.name])
+        };
+        self.add_log_message(message);
+        true
+    }
+
+    /// Strike an NPC in melee, returning it unless the blow was fatal
+    fn player_attack(&mut self, mut npc: NPC) -> Option<NPC> {
+        npc.memory.attacked = true;
+        for mut attack in self.player.attack_profiles() {
+            // A sleeper can't dodge or block, and the first blow lands with extra force
+            let defense = if npc.state == NPCState::Asleep {
+                self.add_log_message(trf(self.settings.language, "log.ambush", &[&npc.name]));
+                attack.damage = (attack.damage.0 * AMBUSH_MULTIPLIER, attack.damage.1 * AMBUSH_MULTIPLIER);
+                npc.state = NPCState::Investigating(self.player.position);
+                DefenseProfile { dodge_chance: 0, block_chance: 0 }
+            } else {
+                npc.defense_profile()
+            };
+            let outcome = combat::resolve_attack(&attack, &defense, &mut rand::thread_rng());
+            npc.take_damage(&mut self.world, outcome.damage());
+            self.damage_dealt += outcome.damage();
+            self.world.show_attack_outcome(npc.position(&self.world), &outcome);
+            self.add_log_message(combat::describe_player_attack(self.settings.language, &outcome, &npc.name));
+            let drained = outcome.damage() * attack.life_steal as i32 / 100;
+            if drained > 0 && self.player.health < self.player.max_health {
+                self.player.heal(drained);
+                self.world.add_floating_text(self.player.position, format!("+{}", drained), (220, 20, 60));
+                self.add_log_message(trf(self.settings.language, "log.drain", &[&drained, &npc.name]));
             }
-            _ => {
-                self.add_log_message(format!("You interact with {}.", npc.name));
-                InteractionResult::NPC(npc)
+            if !npc.is_alive(&self.world) {
+                break;
             }
         }
+        self.world.emit_noise(npc.position(&self.world), noise::COMBAT_VOLUME);
+
+        if npc.is_alive(&self.world) {
+            Some(npc)
+        } else {
+            self.slay(&npc);
+            None
+        }
+    }
+
+    /// Credit the player with a kill
+    fn slay(&mut self, npc: &NPC) {
+        let (x, y) = npc.position(&self.world);
+        npc.despawn(&mut self.world);
+        self.add_log_message(trf(self.settings.language, "log.slay", &[&npc.name]));
+        if npc.elite.is_some() {
+            let loot = elite::loot(&mut rand::thread_rng());
+            self.add_log_message(trf(self.settings.language, "log.elite_drops", &[&npc.name, &self.item_name(&loot)]));
+            self.world.items.push(WorldItem::new(x, y, loot));
+        }
+        if let Some(ref loot) = npc.loot {
+            self.add_log_message(trf(self.settings.language, "log.npc_drops", &[&npc.name, &self.item_name(loot)]));
+            self.world.items.push(WorldItem::new(x, y, loot.clone()));
+        }
+        self.record_event(GameEvent::Slain { name: npc.name.clone(), npc_type: npc.npc_type.clone() });
+        self.gain_experience(npc.experience_value());
+    }
+
+    /// Equip an item from the inventory into a slot, putting anything displaced back in the inventory
+    fn equip_item(&self, item: Item, slot: EquipSlot) -> ItemUseResult {
+        if let Err(reason) = self.player.check_equip(self.settings.language, &item, slot) {
+            return ItemUseResult::unused(reason, item); // Slot conflict, nothing changes
+        }
+        let message = trf(self.settings.language, "log.equip", &[&self.item_name(&item)]);
+        ItemUseResult::consumed(message, vec![WorldMutation::Equip(item, slot)])
+    }
+
+    /// Take an item out of the inventory and equip it; if it can't go in that slot it stays put
+    fn equip_from_inventory(&mut self, item_index: usize, slot: EquipSlot) {
+        let item = self.player.inventory.remove(item_index);
+        let result = self.equip_item(item, slot);
+        self.apply_item_use(result);
+    }
+
+    fn unequip_item(&mut self, slot: EquipSlot) {
+        for item in self.player.unequip(slot) {
+            self.add_log_message(trf(self.settings.language, "log.unequip", &[&self.item_name(&item)]));
+            self.stow(item);
+        }
     }
 
-    pub fn try_pickup_item(&mut self) {
+    fn try_pickup_item(&mut self) {
         let player_pos = self.player.position;
         
         // Check if there's an item at the player's position
         if let Some(item_index) = self.world.items.iter().position(|world_item| world_item.position == player_pos) {
-            // Remove item from world
-            let world_item = self.world.items.remove(item_index);
-            
-            // Add item to player inventory
-            self.player.inventory.push(world_item.item.clone());
-            
-            // Log pickup message
-            self.add_log_message(format!("You picked up {}.", world_item.item.label));
+            self.pick_up(item_index);
         } else {
-            self.add_log_message("There is nothing here to pick up.".to_string());
+            self.add_routine_message(tr(self.settings.language, "log.nothing_to_pick_up").to_string());
+        }
+    }
+
+    /// Indices into `world.items` of everything lying at a position
+    pub fn items_at(&self, position: (i32, i32)) -> Vec<usize> {
+        self.world.items.iter().enumerate()
+            .filter(|(_, world_item)| world_item.position == position)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Put an item from the inventory down on the player's tile
+    fn drop_item(&mut self, inventory_index: usize) {
+        let item = self.player.inventory.remove(inventory_index);
+        self.add_log_message(trf(self.settings.language, "log.drop", &[&self.item_name(&item)]));
+        let (x, y) = self.player.position;
+        self.world.items.push(WorldItem::new(x, y, item));
+    }
+
+    /// Put an item in the pack, or set it down underfoot if there's no room for it
+    fn stow(&mut self, item: Item) {
+        if let Err(item) = self.player.take_into_pack(item) {
+            self.add_log_message(trf(self.settings.language, "log.stow_full", &[&self.item_name(&item)]));
+            let (x, y) = self.player.position;
+            self.world.items.push(WorldItem::new(x, y, *item));
+        }
+    }
+
+    /// Take a banked item out of the stash before setting off, which takes no time
+    fn take_from_stash(&mut self, index: usize) {
+        let item = self.stash.remove(index);
+        let name = self.item_name(&item);
+        match self.player.take_into_pack(item) {
+            Ok(()) => {
+                self.stash_changed = true;
+                self.add_log_message(trf(self.settings.language, "log.take_from_stash", &[&name]));
+            }
+            Err(item) => self.stash.insert(index, *item),
+        }
+    }
+
+    /// Pick up several items from a pile at once
+    fn pick_up_items(&mut self, mut item_indices: Vec<usize>) {
+        // Remove from the back so the remaining indices stay valid
+        item_indices.sort_unstable_by(|a, b| b.cmp(a));
+        for item_index in item_indices {
+            self.pick_up(item_index);
+        }
+    }
+
+    /// Pick up everything underfoot that the auto-pickup settings allow
+    fn auto_pickup(&mut self) {
+        let wanted = self.items_at(self.player.position).into_iter()
+            .filter(|&index| self.settings.auto_picks_up(&self.world.items[index].item))
+            .collect();
+        self.pick_up_items(wanted);
+    }
+
+    /// Move an item from the world into the inventory, or into the purse if it's gold.
+    /// Items are left where they are if the inventory is full.
+    fn pick_up(&mut self, item_index: usize) {
+        if let Some(ItemEffect::Coins(amount)) = self.world.items[item_index].item.effect {
+            self.world.items.remove(item_index);
+            self.player.gold += amount;
+            self.add_log_message(trf(self.settings.language, "log.pick_up_gold", &[&amount]));
+            return;
+        }
+        let world_item = self.world.items.remove(item_index);
+        let keeper = world_item.price.and_then(|_| self.shopkeeper_at(world_item.position)).map(|keeper| keeper.id);
+        let mut item = world_item.item.clone();
+        item.stolen_from = keeper;
+        let name = self.item_name(&item);
+        let item_type = item.item_type.clone();
+        if self.player.take_into_pack(item).is_err() {
+            let name = self.item_name(&world_item.item);
+            self.world.items.insert(item_index, world_item);
+            self.add_log_message(trf(self.settings.language, "log.pick_up_full", &[&name]));
+            return;
+        }
+        self.add_log_message(trf(self.settings.language, "log.pick_up", &[&name]));
+        if let (Some(keeper), Some(price)) = (keeper, world_item.price) {
+            self.take_unpaid(keeper, price);
+        }
+        self.record_event(GameEvent::PickedUp(item_type));
+    }
+
+    /// The living shopkeeper watching over a tile, if it's inside a shop
+    fn shopkeeper_at(&self, position: (i32, i32)) -> Option<&NPC> {
+        let shop = self.world.shops.iter().find(|shop| shop.contains(position))?;
+        self.npcs.iter().find(|npc| npc.id == shop.keeper)
+    }
+
+    /// The player has walked off with a shopkeeper's wares. A keeper they've robbed before doesn't
+    /// bother asking; otherwise the bill is added up until the player pays, puts it back or refuses.
+    fn take_unpaid(&mut self, keeper: u32, price: i32) {
+        let Some(npc) = self.npcs.iter().find(|npc| npc.id == keeper) else {
+            return;
+        };
+        if npc.memory.robbed {
+            self.report_theft(keeper);
+            return;
+        }
+        let demand = self.shop_demand.get_or_insert(ShopDemand { keeper, amount: 0 });
+        demand.amount += price;
+        let message = trf(self.settings.language, "log.shop_demand", &[&npc.name, &demand.amount]);
+        self.add_log_message(message);
+    }
+
+    /// Settle the shopkeeper's bill. The goods are the player's to keep.
+    fn pay_shopkeeper(&mut self) {
+        let Some(demand) = self.shop_demand.take() else {
+            return;
+        };
+        self.player.gold -= demand.amount;
+        for item in self.player.inventory.iter_mut().filter(|item| item.stolen_from == Some(demand.keeper)) {
+            item.stolen_from = None;
+        }
+        if let Some(npc) = self.npcs.iter().find(|npc| npc.id == demand.keeper) {
+            let message = trf(self.settings.language, "log.pay_shopkeeper", &[&npc.name, &demand.amount]);
+            self.add_log_message(message);
+        }
+    }
+
+    /// Set the unpaid goods back down where the player stands, still priced for sale
+    fn return_goods(&mut self) {
+        let Some(demand) = self.shop_demand.take() else {
+            return;
+        };
+        let (x, y) = self.player.position;
+        let (returned, kept) = std::mem::take(&mut self.player.inventory).into_iter()
+            .partition(|item| item.stolen_from == Some(demand.keeper));
+        self.player.inventory = kept;
+        for mut item in returned {
+            item.stolen_from = None;
+            let price = item.value();
+            self.world.items.push(WorldItem::for_sale(x, y, item, price));
+        }
+        self.add_log_message(tr(self.settings.language, "log.return_goods").to_string());
+    }
+
+    /// Keep the goods without paying, which brings the guards running
+    fn refuse_to_pay(&mut self) {
+        if let Some(demand) = self.shop_demand.take() {
+            self.add_log_message(tr(self.settings.language, "log.refuse_to_pay").to_string());
+            self.report_theft(demand.keeper);
+        }
+    }
+
+    /// A shopkeeper cries thief: they'll never trade again, the town thinks less of the player,
+    /// and a guard turns up nearby to deal with them
+    fn report_theft(&mut self, keeper: u32) {
+        const GUARD_RANGE: i32 = 4;
+
+        let Some(npc) = self.npcs.iter_mut().find(|npc| npc.id == keeper) else {
+            return;
+        };
+        npc.memory.robbed = true;
+        let (name, position) = (npc.name.clone(), npc.position(&self.world));
+        self.reputation.adjust(npc.faction(), -faction::THEFT_PENALTY);
+        self.add_log_message(trf(self.settings.language, "log.thief", &[&name]));
+
+        let spot = geometry::circle(position, GUARD_RANGE)
+            .filter(|&(x, y)| self.world.is_walkable(x, y) && !self.is_occupied((x, y)))
+            .max_by_key(|&spot| geometry::distance_squared(spot, position));
+        if let Some((x, y)) = spot {
+            let guard = NPC::new(&mut self.world, x, y, NPCType::Guard, names::generate(&NPCType::Guard, &mut rand::thread_rng()));
+            self.add_log_message(trf(self.settings.language, "log.guard_arrives", &[&guard.name]));
+            self.npcs.push(guard);
+        }
+    }
+
+    /// Use an item from the inventory. Targeted items such as Blink scrolls need a tile.
+    fn use_inventory_item(&mut self, item_index: usize, target: Option<(i32, i32)>) {
+        let item = self.player.inventory.remove(item_index);
+        self.record_event(GameEvent::ItemUsed(item.label.clone()));
+        if matches!(item.item_type, ItemType::Potion | ItemType::Scroll) {
+            self.identify_item_type(&item.item_type);
+        }
+        let result = self.use_item(item, target);
+        self.apply_item_use(result);
+    }
+
+    /// Keep whatever an item's use hands back, drop the rest underfoot, tell the player what
+    /// happened and apply the changes it calls for
+    fn apply_item_use(&mut self, result: ItemUseResult) {
+        if let Some(item) = result.returned_to_inventory {
+            self.stow(item);
+        }
+        let (x, y) = self.player.position;
+        for dropped_item in result.dropped_on_ground {
+            self.world.items.push(WorldItem::new(x, y, dropped_item));
+        }
+        for message in result.messages {
+            self.add_log_message(message);
+        }
+        for mutation in result.mutations {
+            self.apply_mutation(mutation);
+        }
+    }
+
+    /// Carry out one change an item's use called for
+    fn apply_mutation(&mut self, mutation: WorldMutation) {
+        match mutation {
+            WorldMutation::Heal(amount) => self.player.heal(amount),
+            WorldMutation::Nourish(nutrition) => self.player.eat(nutrition),
+            WorldMutation::ApplyStatus(kind, turns) => self.player.status_effects.add(kind, turns),
+            WorldMutation::Teleport(destination) => self.teleport_player(destination),
+            WorldMutation::ForgetMap => self.suffer_amnesia(),
+            WorldMutation::OpenChest(chest_index) => {
+                self.player.inventory.remove(chest_index);
+            }
+            WorldMutation::Equip(item, slot) => {
+                for previous in self.player.equip(item, slot) {
+                    self.add_log_message(trf(self.settings.language, "log.unequip", &[&self.item_name(&previous)]));
+                    self.stow(previous);
+                }
+            }
         }
     }
 
-    pub fn use_item(&mut self, item: Item) -> ItemUseResult {
-        match item.item_type {
-            ItemType::Key => {
+    /// Work out what using an item does. Every item effect goes through here; what the player
+    /// is told and the changes to the player and world come back in the result rather than
+    /// being made directly.
+    fn use_item(&self, item: Item, target: Option<(i32, i32)>) -> ItemUseResult {
+        let lang = self.settings.language;
+        let name = self.item_name(&item);
+        match (item.item_type.clone(), item.effect.clone()) {
+            (ItemType::Key, _) => {
                 // Check if player has a treasure chest
-                if let Some(chest_index) = self.player.inventory.iter().position(|inv_item| inv_item.item_type == ItemType::TreasureChest) {
-                    // Remove treasure chest from inventory
-                    let _chest = self.player.inventory.remove(chest_index);
-                    
-                    // Log the opening message
-                    self.add_log_message("When the key clicks in the lock the treasure chest spills open, dropping a pile of treasure on the ground".to_string());
-                    
-                    // Create treasure item to be dropped
-                    let treasure = Item::new(
-                        ItemType::Treasure,
-                        "Pile of Treasure".to_string(),
-                        "Glittering coins and gems scattered on the ground.".to_string(),
-                    );
-                    
-                    ItemUseResult {
-                        returned_to_inventory: None, // Key was consumed
-                        dropped_on_ground: vec![treasure],
-                    }
-                } else {
-                    self.add_log_message(format!("You need a treasure chest to use {}.", item.label));
-                    ItemUseResult {
-                        returned_to_inventory: Some(item), // Return the key since it wasn't used
-                        dropped_on_ground: vec![],
+                match self.player.inventory.iter().position(|inv_item| inv_item.item_type == ItemType::TreasureChest) {
+                    Some(chest_index) => {
+                        // Create treasure item to be dropped
+                        let treasure = Item::new(
+                            ItemType::Treasure,
+                            "Pile of Treasure".to_string(),
+                            "Glittering coins and gems scattered on the ground.".to_string(),
+                        );
+                        ItemUseResult {
+                            dropped_on_ground: vec![treasure], // Key was consumed
+                            ..ItemUseResult::consumed(
+                                tr(lang, "log.open_chest").to_string(),
+                                vec![WorldMutation::OpenChest(chest_index)],
+                            )
+                        }
                     }
+                    None => ItemUseResult::unused(trf(lang, "log.key_needs_chest", &[&name]), item), // Return the key since it wasn't used
                 }
             }
-            _ => {
-                self.add_log_message(format!("You don't know how to use {}.", item.label));
-                ItemUseResult {
-                    returned_to_inventory: Some(item), // Return the item since it wasn't used
-                    dropped_on_ground: vec![],
+            (ItemType::Weapon | ItemType::Shield | ItemType::Torch | ItemType::Amulet | ItemType::Ring, _) => {
+                match item.equip_slot() {
+                    Some(slot) => self.equip_item(item, slot),
+                    None => ItemUseResult::unused(trf(lang, "refuse.cant_equip", &[&name]), item),
+                }
+            }
+            (ItemType::Potion, Some(ItemEffect::ApplyStatus(kind, turns))) => ItemUseResult::consumed(
+                trf(lang, "log.drink_status", &[&name, &kind.get_name()]),
+                vec![WorldMutation::ApplyStatus(kind, turns)],
+            ),
+            (ItemType::Potion, Some(ItemEffect::Heal(amount))) => ItemUseResult::consumed(
+                trf(lang, "log.drink_heal", &[&name]),
+                vec![WorldMutation::Heal(amount)],
+            ),
+            (ItemType::Scroll, Some(ItemEffect::Amnesia)) => ItemUseResult::consumed(
+                trf(lang, "log.read", &[&name]),
+                vec![WorldMutation::ForgetMap], // Scroll crumbles to dust once read
+            ),
+            (ItemType::Scroll, Some(ItemEffect::ApplyStatus(kind, turns))) => ItemUseResult::consumed(
+                trf(lang, "log.read_status", &[&name, &kind.get_name()]),
+                vec![WorldMutation::ApplyStatus(kind, turns)],
+            ),
+            (ItemType::Scroll, Some(ItemEffect::Blink(_))) => match target {
+                Some(destination) => ItemUseResult::consumed(
+                    trf(lang, "log.read_blink", &[&name]),
+                    vec![WorldMutation::Teleport(destination)],
+                ),
+                None => ItemUseResult::unused(trf(lang, "log.blink_needs_target", &[&name]), item),
+            },
+            (ItemType::RawFood | ItemType::Food, effect) => {
+                let nutrition = match effect {
+                    Some(ItemEffect::Nourish(nutrition)) => nutrition,
+                    _ => 0,
+                };
+                let hunger = self.player.hunger_status_after(nutrition);
+                ItemUseResult::consumed(
+                    trf(lang, "log.eat", &[&name, &tr(lang, hunger).to_lowercase()]),
+                    vec![WorldMutation::Nourish(nutrition)], // Food was eaten
+                )
+            }
+            _ => ItemUseResult::unused(trf(lang, "log.cant_use", &[&name]), item), // Return the item since it wasn't used
+        }
+    }
+
+    /// Whether the player is standing next to a cauldron they can brew in
+    pub fn is_next_to_cauldron(&self) -> bool {
+        let (px, py) = self.player.position;
+        (-1..=1).any(|dx| (-1..=1).any(|dy| self.world.get_tile(px + dx, py + dy) == Some(&TileType::Cauldron)))
+    }
+
+    /// Pour two potions from the inventory into the cauldron. A known recipe brews a new
+    /// potion; anything else explodes, burning the player and setting the floor alight.
+    fn mix_potions(&mut self, first_index: usize, second_index: usize) {
+        // Remove the later index first so the earlier one stays valid
+        let (low, high) = (first_index.min(second_index), first_index.max(second_index));
+        let second = self.player.inventory.remove(high);
+        let first = self.player.inventory.remove(low);
+
+        match alchemy::mix(&first, &second, &mut rand::thread_rng()) {
+            alchemy::MixOutcome::Brewed(potion) => {
+                let message = trf(self.settings.language, "log.brew", &[&self.item_name(&first), &self.item_name(&second), &self.item_name(&potion)]);
+                self.add_log_message(message);
+                self.stow(potion);
+            }
+            alchemy::MixOutcome::Exploded { damage } => {
+                let position = self.player.position;
+                let message = trf(self.settings.language, "log.brew_explodes", &[&self.item_name(&first), &self.item_name(&second)]);
+                self.add_log_message(message);
+                self.explode(position, 1, damage, "blast.explosion");
+                self.world.ignite(position, 3);
+            }
+        }
+    }
+
+    /// Spend the turn resting quietly to recover a little health
+    fn rest(&mut self) {
+        self.player.heal(1);
+        self.add_routine_message(tr(self.settings.language, "log.rest").to_string());
+        self.check_for_ambush(AMBUSH_CHANCE);
+    }
+
+    /// Cook a raw food item from the inventory over a campfire
+    fn cook_item(&mut self, index: usize) {
+        if let Some(meal) = self.player.inventory.get(index).and_then(|item| item.cooked()) {
+            let raw = self.player.inventory.remove(index);
+            let message = trf(self.settings.language, "log.cook", &[&self.item_name(&raw), &self.item_name(&meal)]);
+            self.add_log_message(message);
+            self.stow(meal);
+        }
+    }
+
+    /// Rest by a campfire for several turns. The fire keeps most wanderers away, but the
+    /// rest is cut short by an ambush, by getting hurt or by a hostile coming close.
+    fn rest_by_campfire(&mut self) {
+        const CAMPFIRE_REST_TURNS: u32 = 10;
+        const CAMPFIRE_HEALING: i32 = 3;
+
+        self.add_log_message(tr(self.settings.language, "log.campfire_rest").to_string());
+        for _ in 0..CAMPFIRE_REST_TURNS {
+            if !self.begin_player_turn() {
+                self.end_turn();
+                self.record_event(GameEvent::TurnLost);
+                break;
+            }
+
+            let health_before = self.player.health;
+            self.player.heal(CAMPFIRE_HEALING);
+            let ambushed = self.check_for_ambush(CAMPFIRE_AMBUSH_CHANCE);
+            self.end_turn();
+            self.record_event(GameEvent::TurnPassed);
+
+            if ambushed || self.player.health < health_before || self.hostile_nearby(5) {
+                self.add_log_message(tr(self.settings.language, "log.rest_interrupted").to_string());
+                break;
+            }
+        }
+    }
+
+    fn hostile_nearby(&self, radius: i32) -> bool {
+        let (px, py) = self.player.position;
+        self.npcs.iter().any(|npc| {
+            npc.is_hostile()
+                && npc.state != NPCState::Asleep
+                && geometry::chebyshev_distance(npc.position(&self.world), (px, py)) <= radius
+        })
+    }
+
+    /// Roll for a wandering monster finding the player while they rest.
+    /// Returns true if the player was ambushed.
+    fn check_for_ambush(&mut self, chance: f64) -> bool {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        if !rng.gen_bool(chance) {
+            return false;
+        }
+
+        // The ambusher appears a short distance away, somewhere it could have crept up from
+        let (px, py) = self.player.position;
+        for _ in 0..20 {
+            let x = px + rng.gen_range(-6..=6);
+            let y = py + rng.gen_range(-6..=6);
+            let distance = (x - px).abs().max((y - py).abs());
+            if distance >= 3
+                && self.world.is_walkable(x, y)
+                && !self.npcs.iter().any(|npc| npc.position(&self.world) == (x, y))
+            {
+                let name = names::generate(&NPCType::Orc, &mut rng);
+                self.npcs.push(NPC::new(&mut self.world, x, y, NPCType::Orc, name));
+                self.add_log_message(tr(self.settings.language, "log.ambushed").to_string());
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Let every NPC within earshot react to the noises made since the last resolution
+    fn resolve_noises(&mut self) {
+        let noises = std::mem::take(&mut self.world.noises);
+        let mut messages = Vec::new();
+
+        for noise in noises {
+            let heard = noise.propagate(&self.world);
+            for npc in self.npcs.iter_mut() {
+                if let Some(&intensity) = heard.get(&npc.position(&self.world))
+                    && let Some(message) = npc.hear_noise(self.settings.language, noise.position, intensity)
+                {
+                    messages.push(message);
+                }
+            }
+        }
+
+        for message in messages {
+            self.add_log_message(message);
+        }
+    }
+
+    /// Land the blows one NPC struck against others, reporting the fight if the player can see it
+    fn apply_strikes(&mut self, attacker_index: usize, strikes: Vec<Strike>) {
+        for strike in strikes {
+            let Some(index) = self.npcs.iter().position(|npc| npc.position(&self.world) == strike.target && npc.is_alive(&self.world)) else {
+                continue;
+            };
+            self.npcs[index].take_damage(&mut self.world, strike.outcome.damage());
+            self.world.show_attack_outcome(strike.target, &strike.outcome);
+
+            let (attacker, defender) = (&self.npcs[attacker_index], &self.npcs[index]);
+            let seen = self.npc_visibility(attacker) == Visibility::Visible || self.npc_visibility(defender) == Visibility::Visible;
+            let mut messages = vec![combat::describe_npc_attack(self.settings.language, &strike.outcome, &attacker.name, &defender.name)];
+            if !defender.is_alive(&self.world) {
+                messages.push(trf(self.settings.language, "log.npc_falls", &[&defender.name, &attacker.name]));
+            }
+            if seen {
+                for message in messages {
+                    self.add_log_message(message);
+                }
+            }
+        }
+    }
+
+    /// Take away NPCs killed by other NPCs. They still leave what they carried behind, so a quest
+    /// can't be lost to a fight the player had no part in.
+    fn clear_fallen_npcs(&mut self) {
+        for npc in self.take_fallen_npcs() {
+            let (x, y) = npc.position(&self.world);
+            npc.despawn(&mut self.world);
+            if let Some(loot) = npc.loot {
+                self.world.items.push(WorldItem::new(x, y, loot));
+            }
+        }
+    }
+
+    /// Take the NPCs with no health left out of the NPC list. Their components stay on the
+    /// floor until they are despawned, so where they fell can still be looked up.
+    fn take_fallen_npcs(&mut self) -> Vec<NPC> {
+        let (fallen, standing) = std::mem::take(&mut self.npcs).into_iter()
+            .partition(|npc| !npc.is_alive(&self.world));
+        self.npcs = standing;
+        fallen
+    }
+
+    /// Apply heals and buffs cast by one NPC on the NPCs they targeted
+    fn apply_support_actions(&mut self, support_actions: Vec<SupportAction>) {
+        for action in support_actions {
+            match action {
+                SupportAction::Heal { target, amount } => {
+                    if let Some(npc) = self.npcs.iter_mut().find(|npc| npc.position(&self.world) == target) {
+                        npc.heal(&mut self.world, amount);
+                    }
+                }
+                SupportAction::ApplyStatus { target, kind, turns } => {
+                    if let Some(npc) = self.npcs.iter_mut().find(|npc| npc.position(&self.world) == target) {
+                        npc.status_effects.add(kind, turns);
+                    }
                 }
             }
         }
@@ -389,23 +3513,245 @@ impl GameState {
 
     /// Process NPC actions for this turn
     pub fn process_npc_actions(&mut self) {
-        // Process each NPC by temporarily removing it from the vector
-        let mut i = 0;
-        while i < self.npcs.len() {
-            let mut npc = self.npcs.remove(i);
-            
-            // Let the NPC perform its action, passing the remaining NPCs as a slice
-            let log_messages = npc.perform_action(&mut self.world, &mut self.player, self.npcs.as_slice());
-            
-            // Add any log messages from the NPC action
-            for message in log_messages {
-                self.add_log_message(message);
+        // NPCs react to anything they heard before deciding what to do
+        self.resolve_noises();
+
+        // A quick or hasted action takes less world time, giving NPCs less energy, and a slow one more
+        let player_speed = scheduler::speed(&self.player.status_effects);
+        let elapsed = scheduler::time_for_action(self.action_cost, player_speed);
+        if let Some(text) = speed_change_text(player_speed) {
+            self.world.add_floating_text(self.player.position, text.0.to_string(), text.1);
+        }
+
+        // Every NPC banks energy for the time that passed and rolls initiative for the round.
+        // Stunned NPCs lose their turn, otherwise the scheduler decides how many actions
+        // the NPC's banked energy pays for. Effects count down at the start of the turn
+        // once they have been applied.
+        let mut rng = rand::thread_rng();
+        let mut initiative = Vec::new();
+        for index in 0..self.npcs.len() {
+            let npc = &mut self.npcs[index];
+            let npc_speed = npc.speed();
+            let action_count = if npc.status_effects.is_incapacitated() {
+                npc.energy = 0;
+                0
+            } else {
+                scheduler::take_actions(&mut npc.energy, npc_speed, elapsed)
+            };
+            let expired = npc.status_effects.tick();
+
+            // Make extra and lost turns visible for NPCs whose own speed is altered
+            let npc = &self.npcs[index];
+            let speed_text = speed_change_text(npc_speed)
+                .filter(|_| action_count != 1 && self.npc_visibility(npc) == Visibility::Visible);
+            let (name, position) = (npc.name.clone(), npc.position(&self.world));
+            if let Some((text, color)) = speed_text {
+                self.world.add_floating_text(position, text.to_string(), color);
             }
-            
-            // Put the NPC back in the vector
-            self.npcs.insert(i, npc);
-            
-            i += 1;
+            for kind in expired {
+                self.add_log_message(trf(self.settings.language, "log.npc_status_expired", &[&name, &kind.get_name()]));
+            }
+            initiative.push((index, action_count, scheduler::roll_initiative(npc_speed, &mut rng)));
+        }
+        // Highest initiative first; ties keep their usual order
+        initiative.sort_by_key(|&(_, _, roll)| std::cmp::Reverse(roll));
+
+        // Actions are taken in rounds, so a hasted NPC's second action comes after everyone's first
+        let time_of_day = TimeOfDay::at(self.turn_counter);
+        let rounds = initiative.iter().map(|&(_, actions, _)| actions).max().unwrap_or(0);
+        let mut spawned_npcs = Vec::new();
+        for round in 0..rounds {
+            // Decide: everyone acting this round finds their route at once, from the world as it
+            // stands. Routes are the costly part of a crowded floor's turn.
+            let acting: Vec<usize> = initiative.iter()
+                .filter(|&&(_, actions, _)| actions > round)
+                .map(|&(index, _, _)| index)
+                .collect();
+            let plans: Vec<Option<StepPlan>> = acting.par_iter()
+                .map(|&index| self.npcs[index].plan_step(&self.world, &self.player))
+                .collect();
+            for (&index, plan) in acting.iter().zip(plans) {
+                self.npcs[index].plan = plan;
+            }
+
+            // Apply: one at a time in initiative order. A planned step onto a tile someone else
+            // took in the meantime is refused, as if the NPC had only looked when its turn came.
+            for index in acting {
+                // NPCs cut down by another this turn are cleared away after everyone has acted
+                if !self.npcs[index].is_alive(&self.world) {
+                    continue;
+                }
+                // Take the NPC out while it acts so it can see everyone else as a slice
+                let mut npc = self.npcs.remove(index);
+                let start = npc.position(&self.world);
+                tracing::trace!(turn = self.turn_counter, npc = %npc.name, position = ?npc.position(&self.world), state = ?npc.state, "npc acts");
+                let provoked = npc.memory.attacked || self.reputation.is_hostile(npc.faction());
+                let context = TurnContext { provoked, time_of_day, language: self.settings.language, forgotten: &self.forgotten_item_types };
+                let result = npc.perform_action(&mut self.world, &mut self.player, self.npcs.as_slice(), &context);
+                npc.plan = None;
+
+                for message in result.log_messages {
+                    self.add_log_message(message);
+                }
+                spawned_npcs.extend(result.spawned_npcs);
+                self.cross_terrain(&mut npc, start);
+                self.teleport_npc_from_trap(&mut npc);
+
+                // Put the NPC back where it was so the other indices stay valid
+                self.npcs.insert(index, npc);
+                self.apply_support_actions(result.support_actions);
+                self.apply_strikes(index, result.strikes);
+            }
+        }
+        self.clear_fallen_npcs();
+
+        // Summoned NPCs join once everyone has acted, as long as their tile is still free
+        for spawned in spawned_npcs {
+            let occupied = self.player.position == spawned.position(&self.world)
+                || self.npcs.iter().any(|npc| npc.position(&self.world) == spawned.position(&self.world));
+            if !occupied {
+                self.npcs.push(spawned);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elite::EliteModifier;
+    use crate::game_condition::ArenaCondition;
+
+    fn game_state() -> GameState {
+        let mut state = GameState::with_condition(Box::new(ArenaCondition), PlayerClass::Warrior, Settings::default());
+        state.player.inventory.clear();
+        state
+    }
+
+    fn item(item_type: ItemType) -> Item {
+        Item::new(item_type, "Test Item".to_string(), String::new())
+    }
+
+    #[test]
+    fn potions_and_scrolls_call_for_their_effect() {
+        let state = game_state();
+        let cases = [
+            (ItemType::Potion, ItemEffect::Heal(5), WorldMutation::Heal(5)),
+            (ItemType::Potion, ItemEffect::ApplyStatus(StatusKind::Hasted, 10), WorldMutation::ApplyStatus(StatusKind::Hasted, 10)),
+            (ItemType::Scroll, ItemEffect::ApplyStatus(StatusKind::SeeInvisible, 20), WorldMutation::ApplyStatus(StatusKind::SeeInvisible, 20)),
+            (ItemType::Scroll, ItemEffect::Amnesia, WorldMutation::ForgetMap),
+        ];
+        for (item_type, effect, mutation) in cases {
+            let result = state.use_item(item(item_type).with_effect(effect), None);
+            assert_eq!(result.mutations, vec![mutation]);
+            assert!(result.returned_to_inventory.is_none());
+            assert_eq!(result.messages.len(), 1);
+        }
+    }
+
+    #[test]
+    fn blink_needs_a_destination() {
+        let state = game_state();
+        let scroll = item(ItemType::Scroll).with_effect(ItemEffect::Blink(5));
+
+        let result = state.use_item(scroll.clone(), Some((3, 4)));
+        assert_eq!(result.mutations, vec![WorldMutation::Teleport((3, 4))]);
+
+        let result = state.use_item(scroll.clone(), None);
+        assert!(result.mutations.is_empty());
+        assert_eq!(result.returned_to_inventory, Some(scroll));
+    }
+
+    #[test]
+    fn food_nourishes_even_without_an_effect() {
+        let state = game_state();
+        let result = state.use_item(item(ItemType::Food).with_effect(ItemEffect::Nourish(300)), None);
+        assert_eq!(result.mutations, vec![WorldMutation::Nourish(300)]);
+        let result = state.use_item(item(ItemType::RawFood), None);
+        assert_eq!(result.mutations, vec![WorldMutation::Nourish(0)]);
+    }
+
+    #[test]
+    fn key_opens_a_carried_chest() {
+        let mut state = game_state();
+        let result = state.use_item(item(ItemType::Key), None);
+        assert!(result.mutations.is_empty());
+        assert!(result.returned_to_inventory.is_some());
+
+        state.player.inventory.push(item(ItemType::Potion));
+        state.player.inventory.push(item(ItemType::TreasureChest));
+        let result = state.use_item(item(ItemType::Key), None);
+        assert_eq!(result.mutations, vec![WorldMutation::OpenChest(1)]);
+        assert!(result.returned_to_inventory.is_none());
+        assert_eq!(result.dropped_on_ground.len(), 1);
+        assert_eq!(result.dropped_on_ground[0].item_type, ItemType::Treasure);
+    }
+
+    #[test]
+    fn equipment_goes_into_its_slot() {
+        let state = game_state();
+        let cases = [
+            (ItemType::Weapon, EquipSlot::MainHand),
+            (ItemType::Shield, EquipSlot::OffHand),
+            (ItemType::Torch, EquipSlot::OffHand),
+            (ItemType::Amulet, EquipSlot::Neck),
+            (ItemType::Ring, EquipSlot::Finger),
+        ];
+        for (item_type, slot) in cases {
+            let gear = item(item_type);
+            let result = state.use_item(gear.clone(), None);
+            assert_eq!(result.mutations, vec![WorldMutation::Equip(gear, slot)]);
+        }
+    }
+
+    #[test]
+    fn a_backpack_holding_overflow_stays_put() {
+        let mut state = game_state();
+        state.player.inventory.push(crate::catalog::leather_backpack());
+        while state.player.inventory.len() <= BASE_INVENTORY_SIZE {
+            state.player.inventory.push(item(ItemType::Gem));
+        }
+        assert!(state.check_command(&Command::DropItem(0)).is_ok());
+
+        state.player.inventory.push(item(ItemType::Gem));
+        assert!(state.check_command(&Command::DropItem(0)).is_err());
+        assert!(state.check_command(&Command::DropItem(1)).is_ok());
+    }
+
+    #[test]
+    fn commands_on_other_tiles_need_the_player_beside_them() {
+        let state = game_state();
+        let (x, y) = state.player.position;
+        assert!(state.check_command(&Command::AttackFriendly((x + 5, y))).is_err());
+        assert!(state.check_command(&Command::Talk((x + 1, y - 1))).is_ok());
+        assert!(state.check_command(&Command::Search((x, y))).is_err());
+        assert!(state.check_command(&Command::RestByCampfire).is_err());
+    }
+
+    #[test]
+    fn walking_into_a_skeleton_counts_as_a_kill() {
+        let mut state = game_state();
+        state.world.items.clear();
+        let (x, y) = state.player.position;
+        let mut skeleton = NPC::new(&mut state.world, x + 1, y, NPCType::Skeleton, "Rattles".to_string());
+        skeleton.promote(EliteModifier::Brutal, &mut state.world, &mut rand::thread_rng());
+        skeleton.loot = Some(item(ItemType::Key));
+        let experience = skeleton.experience_value();
+
+        assert!(state.interact_with_npc(skeleton).is_none());
+        assert!(state.events.iter().any(|event| matches!(event, GameEvent::Slain { npc_type: NPCType::Skeleton, .. })));
+        assert_eq!(state.player.experience, experience);
+        // The key it carried and an elite's loot both fall where it stood
+        assert_eq!(state.world.items.iter().filter(|world_item| world_item.position == (x + 1, y)).count(), 2);
+    }
+
+    #[test]
+    fn other_items_are_handed_back() {
+        let state = game_state();
+        for item_type in [ItemType::TreasureChest, ItemType::Treasure, ItemType::Gem, ItemType::Gold, ItemType::Backpack] {
+            let result = state.use_item(item(item_type.clone()), None);
+            assert!(result.mutations.is_empty());
+            assert_eq!(result.returned_to_inventory.map(|item| item.item_type), Some(item_type));
         }
     }
-}
\ No newline at end of file
+}
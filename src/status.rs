@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+/// Kinds of temporary status effects that can be applied to the player or NPCs
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StatusKind {
+    Hasted,
+    SeeInvisible,
+    Stunned,
+    Paralyzed,
+    Blinded, // Can only make out adjacent tiles
+    Levitating, // Floats over water, lava and traps
+    DetectingTreasure, // Senses valuables anywhere on the floor
+    Slowed,
+    Confused, // Lashes out at whatever is next to it, friend or foe
+}
+
+impl StatusKind {
+    pub fn get_name(&self) -> &str {
+        match self {
+            StatusKind::Hasted => "hasted",
+            StatusKind::SeeInvisible => "seeing invisible",
+            StatusKind::Stunned => "stunned",
+            StatusKind::Paralyzed => "paralyzed",
+            StatusKind::Blinded => "blinded",
+            StatusKind::Levitating => "levitating",
+            StatusKind::DetectingTreasure => "sensing treasure",
+            StatusKind::Slowed => "slowed",
+            StatusKind::Confused => "confused",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub turns_remaining: u32,
+}
+
+/// The set of status effects currently active on an actor
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StatusEffects {
+    effects: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    /// Apply an effect for a number of turns. Reapplying an active effect
+    /// extends it rather than stacking a second copy.
+    pub fn add(&mut self, kind: StatusKind, turns: u32) {
+        if let Some(effect) = self.effects.iter_mut().find(|effect| effect.kind == kind) {
+            effect.turns_remaining = effect.turns_remaining.max(turns);
+        } else {
+            self.effects.push(StatusEffect { kind, turns_remaining: turns });
+        }
+    }
+
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.effects.iter().any(|effect| effect.kind == kind)
+    }
+
+    pub fn turns_remaining(&self, kind: StatusKind) -> Option<u32> {
+        self.effects.iter().find(|effect| effect.kind == kind).map(|effect| effect.turns_remaining)
+    }
+
+    /// Whether an effect prevents the actor from doing anything this turn
+    pub fn is_incapacitated(&self) -> bool {
+        self.has(StatusKind::Stunned) || self.has(StatusKind::Paralyzed)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StatusEffect> {
+        self.effects.iter()
+    }
+
+    /// Count down every effect by one turn, returning the kinds that just wore off.
+    /// Called at the start of the actor's turn, after checking what the effects do to it.
+    pub fn tick(&mut self) -> Vec<StatusKind> {
+        let mut expired = Vec::new();
+        for effect in self.effects.iter_mut() {
+            effect.turns_remaining = effect.turns_remaining.saturating_sub(1);
+            if effect.turns_remaining == 0 {
+                expired.push(effect.kind);
+            }
+        }
+        self.effects.retain(|effect| effect.turns_remaining > 0);
+        expired
+    }
+}
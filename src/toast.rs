@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use eframe::egui;
+
+/// Seconds a toast stays up
+const TOAST_SECONDS: f64 = 3.0;
+/// Most toasts up at once; the oldest makes way for a new one
+const MAX_TOASTS: usize = 3;
+
+/// Short celebratory banners across the top of the screen for milestones towards the goal
+#[derive(Debug, Default)]
+pub struct Toasts {
+    showing: Vec<(String, f64)>, // Each toast and when it comes down, in egui time
+}
+
+impl Toasts {
+    pub fn push(&mut self, text: String, now: f64) {
+        if self.showing.len() == MAX_TOASTS {
+            self.showing.remove(0);
+        }
+        self.showing.push((text, now + TOAST_SECONDS));
+    }
+
+    /// Draw the toasts still up, returning how long until the next one comes down
+    pub fn show(&mut self, ctx: &egui::Context, now: f64) -> Option<Duration> {
+        self.showing.retain(|(_, until)| *until > now);
+        if self.showing.is_empty() {
+            return None;
+        }
+        egui::Area::new(egui::Id::new("toasts"))
+            .order(egui::Order::Foreground)
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 40.0))
+            .interactable(false)
+            .show(ctx, |ui| {
+                for (text, _) in &self.showing {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.label(egui::RichText::new(text).strong().size(18.0).color(egui::Color32::from_rgb(255, 215, 0)));
+                    });
+                    ui.add_space(4.0);
+                }
+            });
+        let soonest = self.showing.iter().map(|(_, until)| until - now).fold(f64::INFINITY, f64::min);
+        Some(Duration::from_secs_f64(soonest))
+    }
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TrapType {
+    Paralysis,
+    Fire,
+    BlindingDart,
+    Teleport,
+    Amnesia,
+}
+
+/// A trap hidden on a tile until something steps on it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trap {
+    pub position: (i32, i32),
+    pub trap_type: TrapType,
+    pub revealed: bool,
+}
+
+impl Trap {
+    pub fn new(x: i32, y: i32, trap_type: TrapType) -> Self {
+        Self {
+            position: (x, y),
+            trap_type,
+            revealed: false,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self.trap_type {
+            TrapType::Paralysis => "Paralysis Trap",
+            TrapType::Fire => "Fire Trap",
+            TrapType::BlindingDart => "Dart Trap",
+            TrapType::Teleport => "Teleport Trap",
+            TrapType::Amnesia => "Amnesia Trap",
+        }
+    }
+
+    pub fn get_description(&self) -> &str {
+        match self.trap_type {
+            TrapType::Paralysis => "A pressure plate wired to a venomous needle",
+            TrapType::Fire => "A scorched vent that spews flame when stepped on",
+            TrapType::BlindingDart => "A hole in the wall that spits darts tipped with blinding venom",
+            TrapType::Teleport => "A shimmering rune that hurls whoever steps on it elsewhere on the floor",
+            TrapType::Amnesia => "A faded glyph that clouds the mind of anyone who treads on it",
+        }
+    }
+
+    pub fn display_info(&self) -> (char, (u8, u8, u8)) {
+        let color = match self.trap_type {
+            TrapType::Paralysis => (0, 200, 150), // Sickly teal
+            TrapType::Fire => (255, 69, 0), // Red orange
+            TrapType::BlindingDart => (120, 120, 160), // Dull steel
+            TrapType::Teleport => (100, 149, 237), // Cornflower blue
+            TrapType::Amnesia => (180, 180, 180), // Foggy gray
+        };
+        ('^', color)
+    }
+}
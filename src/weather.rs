@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+/// Turns each spell of weather lasts before the next rolls in
+const SPELL_LENGTH: u32 = 50;
+/// How far the player can see through rain
+const RAIN_SIGHT_RADIUS: i32 = 12;
+/// How far the player can see through fog
+const FOG_SIGHT_RADIUS: i32 = 8;
+/// The order spells of weather come in, repeating as the days go by
+const FORECAST: [Weather; 8] = [
+    Weather::Clear,
+    Weather::Rain,
+    Weather::Clear,
+    Weather::Fog,
+    Weather::Clear,
+    Weather::Clear,
+    Weather::Snow,
+    Weather::Rain,
+];
+
+/// The weather over an outdoor map, changing with the world clock. Indoor floors have none.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Weather {
+    Clear,
+    Rain, // Dampens fires and shortens sight
+    Fog, // Shortens sight even more than rain
+    Snow, // Makes every step heavier
+}
+
+impl Weather {
+    /// The weather a number of turns into the game
+    pub fn at(turn: u32) -> Self {
+        FORECAST[(turn / SPELL_LENGTH) as usize % FORECAST.len()]
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            Weather::Clear => "Clear",
+            Weather::Rain => "Rain",
+            Weather::Fog => "Fog",
+            Weather::Snow => "Snow",
+        }
+    }
+
+    /// Logged when this weather sets in
+    pub fn arrival_message(&self) -> &str {
+        match self {
+            Weather::Clear => "The sky clears.",
+            Weather::Rain => "It starts to rain.",
+            Weather::Fog => "A thick fog rolls in.",
+            Weather::Snow => "Snow begins to fall.",
+        }
+    }
+
+    /// How far the player can see in this weather, if it limits sight at all
+    pub fn sight_radius(&self) -> Option<i32> {
+        match self {
+            Weather::Rain => Some(RAIN_SIGHT_RADIUS),
+            Weather::Fog => Some(FOG_SIGHT_RADIUS),
+            Weather::Clear | Weather::Snow => None,
+        }
+    }
+
+    /// Scales the chance of a fire spreading to its neighbours
+    pub fn fire_spread_factor(&self) -> f64 {
+        match self {
+            Weather::Rain => 0.3,
+            Weather::Snow => 0.6,
+            Weather::Clear | Weather::Fog => 1.0,
+        }
+    }
+
+    /// Extra energy each step takes in this weather
+    pub fn extra_move_cost(&self) -> i32 {
+        match self {
+            Weather::Snow => 50,
+            _ => 0,
+        }
+    }
+
+    /// Blend a tile's colour a little towards the weather's own, so the map shows what it's like out
+    pub fn tint(&self, color: (u8, u8, u8)) -> (u8, u8, u8) {
+        let (tint, strength) = match self {
+            Weather::Clear => return color,
+            Weather::Rain => ((70, 90, 140), 0.25),
+            Weather::Fog => ((160, 160, 160), 0.35),
+            Weather::Snow => ((230, 235, 255), 0.25),
+        };
+        let blend = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * strength) as u8;
+        (blend(color.0, tint.0), blend(color.1, tint.1), blend(color.2, tint.2))
+    }
+}